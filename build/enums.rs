@@ -119,6 +119,11 @@ pub fn main() -> anyhow::Result<()> {
         libinput::LIBINPUT_CONFIG_ACCEL_PROFILE,
         "libinput_config_accel_profile",
     )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_ACCEL_TYPE,
+        "libinput_config_accel_type",
+    )?;
     write_ty(
         &mut f,
         libinput::LIBINPUT_CONFIG_TAP_STATE,
@@ -134,6 +139,31 @@ pub fn main() -> anyhow::Result<()> {
         libinput::LIBINPUT_CONFIG_DRAG_LOCK_STATE,
         "libinput_config_drag_lock_state",
     )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_CLICK_METHOD,
+        "libinput_config_click_method",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_MIDDLE_EMULATION_STATE,
+        "libinput_config_middle_emulation_state",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_SCROLL_METHOD,
+        "libinput_config_scroll_method",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_DWT_STATE,
+        "libinput_config_dwt_state",
+    )?;
+    write_ty(
+        &mut f,
+        libinput::LIBINPUT_CONFIG_TAP_BUTTON_MAP,
+        "libinput_config_tap_button_map",
+    )?;
 
     let mut f = open("pango_tys.rs")?;
     write_ty(&mut f, pango::CAIRO_FORMATS, "cairo_format_t")?;
@@ -156,6 +186,7 @@ pub fn main() -> anyhow::Result<()> {
         "xkb_state_component",
     )?;
     write_ty(&mut f, xkbcommon::XKB_KEY_DIRECTION, "xkb_key_direction")?;
+    write_ty(&mut f, xkbcommon::XKB_KEYSYM_FLAGS, "xkb_keysym_flags")?;
 
     Ok(())
 }
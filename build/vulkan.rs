@@ -10,15 +10,47 @@ const ROOT: &str = "src/gfx_apis/vulkan/shaders";
 pub fn main() -> anyhow::Result<()> {
     println!("cargo:rerun-if-changed={}", ROOT);
     compile_simple("fill.frag")?;
+    compile_fill_frag("fill.frag.rounded.spv", true)?;
     compile_simple("fill.vert")?;
     compile_simple("tex.vert")?;
-    compile_tex_frag("tex.frag.spv", false, false)?;
-    compile_tex_frag("tex.frag.mult+opaque.spv", false, true)?;
-    compile_tex_frag("tex.frag.mult+alpha.spv", true, true)?;
+    compile_tex_frag("tex.frag.spv", false, false, false)?;
+    compile_tex_frag("tex.frag.mult+opaque.spv", false, true, false)?;
+    compile_tex_frag("tex.frag.mult+alpha.spv", true, true, false)?;
+    compile_tex_frag("tex.frag.rounded+opaque.spv", false, true, true)?;
+    compile_tex_frag("tex.frag.rounded+alpha.spv", true, true, true)?;
+    compile_blur_frag("blur.frag.down.spv", true, false)?;
+    compile_blur_frag("blur.frag.up.spv", false, false)?;
+    compile_blur_frag("blur.frag.up+rounded.spv", false, true)?;
     Ok(())
 }
 
-fn compile_tex_frag(out: &str, alpha: bool, alpha_multiplier: bool) -> anyhow::Result<()> {
+fn compile_blur_frag(out: &str, down: bool, rounded: bool) -> anyhow::Result<()> {
+    let mut opts = CompileOptions::new().unwrap();
+    if down {
+        opts.add_macro_definition("DOWN", None);
+    }
+    if rounded {
+        opts.add_macro_definition("ROUNDED", None);
+    }
+    compile_shader("blur.frag", out, Some(&opts)).with_context(|| out.to_string())?;
+    Ok(())
+}
+
+fn compile_fill_frag(out: &str, rounded: bool) -> anyhow::Result<()> {
+    let mut opts = CompileOptions::new().unwrap();
+    if rounded {
+        opts.add_macro_definition("ROUNDED", None);
+    }
+    compile_shader("fill.frag", out, Some(&opts)).with_context(|| out.to_string())?;
+    Ok(())
+}
+
+fn compile_tex_frag(
+    out: &str,
+    alpha: bool,
+    alpha_multiplier: bool,
+    rounded: bool,
+) -> anyhow::Result<()> {
     let mut opts = CompileOptions::new().unwrap();
     if alpha {
         opts.add_macro_definition("ALPHA", None);
@@ -26,6 +58,9 @@ fn compile_tex_frag(out: &str, alpha: bool, alpha_multiplier: bool) -> anyhow::R
     if alpha_multiplier {
         opts.add_macro_definition("ALPHA_MULTIPLIER", None);
     }
+    if rounded {
+        opts.add_macro_definition("ROUNDED", None);
+    }
     compile_shader("tex.frag", out, Some(&opts)).with_context(|| out.to_string())?;
     Ok(())
 }
@@ -5,29 +5,37 @@ use {
         _private::{
             bincode_ops,
             ipc::{
-                ClientMessage, InitMessage, Response, ServerFeature, ServerMessage, WorkspaceSource,
+                ClientMessage, InitMessage, Response, ServerFeature, ServerMessage, StatusBlock,
+                StatusClick, WorkspaceSource,
             },
             logging, Config, ConfigEntry, ConfigEntryGen, PollableId, WireMode, VERSION,
         },
+        animation::{Animation, AnimationCurve, WorkspaceAnimationStyle},
         exec::Command,
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::{AccelProfile, AccelType},
+            capability::Capability,
+            click_method::ClickMethod,
+            scroll_method::ScrollMethod,
+            send_events::SendEventsMode,
+            tap_button_map::TapButtonMap,
+            FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent, TabletPadControl,
         },
         keyboard::{
             mods::{Modifiers, RELEASE},
             syms::KeySym,
-            Keymap,
+            KeyboardLayout, Keymap, KeymapNames,
         },
         logging::LogLevel,
         tasks::{JoinHandle, JoinSlot},
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{colors::Colorable, sized::Resizable, Color, ColorScheme},
         timer::Timer,
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
             Connector, DrmDevice, GfxApi, Mode, Transform,
         },
-        Axis, Direction, ModifiedKeySym, PciId, Workspace,
+        Axis, ContainerLayout, Direction, IdleStage, ModifiedKeySym, PciId, Workspace,
+        WorkspaceLayout,
     },
     bincode::Options,
     futures_util::task::ArcWake,
@@ -97,7 +105,13 @@ pub(crate) struct Client {
     on_new_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_del_drm_device: RefCell<Option<Callback<DrmDevice>>>,
     on_idle: RefCell<Option<Callback>>,
+    on_idle_stage: RefCell<Option<Callback<String>>>,
+    on_idle_stage_resumed: RefCell<Option<Callback<String>>>,
+    on_before_sleep: RefCell<Option<Callback>>,
     on_switch_event: RefCell<HashMap<InputDevice, Callback<SwitchEvent>>>,
+    on_tablet_pad_event: RefCell<HashMap<(InputDevice, TabletPadControl, u32), Callback<f64>>>,
+    on_status_click: RefCell<Option<Callback<StatusClick>>>,
+    on_keyboard_layout_changed: RefCell<HashMap<Seat, Callback<KeyboardLayout>>>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
     read_interests: RefCell<HashMap<PollableId, Interest>>,
@@ -227,7 +241,13 @@ pub unsafe extern "C" fn init(
         on_new_drm_device: Default::default(),
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
+        on_idle_stage: Default::default(),
+        on_idle_stage_resumed: Default::default(),
+        on_before_sleep: Default::default(),
         on_switch_event: Default::default(),
+        on_tablet_pad_event: Default::default(),
+        on_status_click: Default::default(),
+        on_keyboard_layout_changed: Default::default(),
         bufs: Default::default(),
         reload: Cell::new(false),
         read_interests: Default::default(),
@@ -488,14 +508,143 @@ impl Client {
         self.send(&ClientMessage::SetFloating { seat, floating });
     }
 
+    pub fn set_window_opacity(&self, seat: Seat, opacity: f64) {
+        self.send(&ClientMessage::SetWindowOpacity { seat, opacity });
+    }
+
     pub fn toggle_floating(&self, seat: Seat) {
         self.set_floating(seat, !self.get_floating(seat));
     }
 
+    pub fn get_sticky(&self, seat: Seat) -> bool {
+        let res = self.send_with_response(&ClientMessage::GetSticky { seat });
+        get_response!(res, false, GetSticky { sticky });
+        sticky
+    }
+
+    pub fn set_sticky(&self, seat: Seat, sticky: bool) {
+        self.send(&ClientMessage::SetSticky { seat, sticky });
+    }
+
+    pub fn toggle_sticky(&self, seat: Seat) {
+        self.set_sticky(seat, !self.get_sticky(seat));
+    }
+
+    pub fn mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::Mark {
+            seat,
+            mark: mark.to_string(),
+        });
+    }
+
+    pub fn unmark(&self, seat: Seat, mark: Option<&str>) {
+        self.send(&ClientMessage::Unmark {
+            seat,
+            mark: mark.map(|m| m.to_string()),
+        });
+    }
+
+    pub fn focus_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::FocusMark {
+            seat,
+            mark: mark.to_string(),
+        });
+    }
+
+    pub fn swap_with_mark(&self, seat: Seat, mark: &str) {
+        self.send(&ClientMessage::SwapWithMark {
+            seat,
+            mark: mark.to_string(),
+        });
+    }
+
+    pub fn container_layout(&self, seat: Seat) -> ContainerLayout {
+        let res = self.send_with_response(&ClientMessage::GetContainerLayout { seat });
+        get_response!(
+            res,
+            ContainerLayout::SplitHorizontal,
+            GetContainerLayout { layout }
+        );
+        layout
+    }
+
+    pub fn set_container_layout(&self, seat: Seat, layout: ContainerLayout) {
+        self.send(&ClientMessage::SetContainerLayout { seat, layout });
+    }
+
+    pub fn get_workspace_layout(&self, workspace: WorkspaceSource) -> WorkspaceLayout {
+        let res = self.send_with_response(&ClientMessage::GetWorkspaceLayout { workspace });
+        get_response!(res, WorkspaceLayout::Manual, GetWorkspaceLayout { layout });
+        layout
+    }
+
+    pub fn set_workspace_layout(&self, workspace: WorkspaceSource, layout: WorkspaceLayout) {
+        self.send(&ClientMessage::SetWorkspaceLayout { workspace, layout });
+    }
+
+    pub fn set_default_workspace_layout(&self, layout: WorkspaceLayout) {
+        self.send(&ClientMessage::SetDefaultWorkspaceLayout { layout });
+    }
+
+    pub fn move_to_scratchpad(&self, seat: Seat) {
+        self.send(&ClientMessage::MoveToScratchpad { seat });
+    }
+
+    pub fn toggle_scratchpad(&self, seat: Seat) {
+        self.send(&ClientMessage::ToggleScratchpad { seat });
+    }
+
     pub fn reset_colors(&self) {
         self.send(&ClientMessage::ResetColors);
     }
 
+    pub fn set_color_scheme(&self, scheme: ColorScheme) {
+        self.send(&ClientMessage::SetColorScheme { scheme });
+    }
+
+    pub fn set_high_contrast(&self, enabled: bool) {
+        self.send(&ClientMessage::SetHighContrast { enabled });
+    }
+
+    pub fn set_smart_gaps(&self, enabled: bool) {
+        self.send(&ClientMessage::SetSmartGaps { enabled });
+    }
+
+    pub fn set_blurred_app_ids(&self, app_ids: Vec<String>) {
+        self.send(&ClientMessage::SetBlurredAppIds { app_ids });
+    }
+
+    pub fn set_blurred_layer_namespaces(&self, namespaces: Vec<String>) {
+        self.send(&ClientMessage::SetBlurredLayerNamespaces { namespaces });
+    }
+
+    pub fn set_window_opacities(&self, opacities: Vec<(String, f64)>) {
+        self.send(&ClientMessage::SetWindowOpacities { opacities });
+    }
+
+    pub fn set_animations_enabled(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAnimationsEnabled { enabled });
+    }
+
+    pub fn set_animation_duration(&self, animation: Animation, duration: Duration) {
+        self.send(&ClientMessage::SetAnimationDuration {
+            animation,
+            duration,
+        });
+    }
+
+    pub fn set_animation_curve(&self, animation: Animation, curve: AnimationCurve) {
+        self.send(&ClientMessage::SetAnimationCurve { animation, curve });
+    }
+
+    pub fn set_workspace_animation_style(&self, style: WorkspaceAnimationStyle) {
+        self.send(&ClientMessage::SetWorkspaceAnimationStyle { style });
+    }
+
+    pub fn reset_animations(&self) {
+        self.send(&ClientMessage::ResetAnimations);
+    }
+
     pub fn reset_sizes(&self) {
         self.send(&ClientMessage::ResetSizes);
     }
@@ -551,12 +700,21 @@ impl Client {
         self.send(&ClientMessage::SetStatus { status });
     }
 
+    pub fn set_status_blocks(&self, status: &str, blocks: Vec<StatusBlock>) {
+        self.send(&ClientMessage::SetStatusBlocks { status, blocks });
+    }
+
     pub fn set_status_tasks(&self, tasks: Vec<JoinHandle<()>>) {
+        self.on_status_click.take();
         for old in self.status_task.replace(tasks) {
             old.abort();
         }
     }
 
+    pub fn on_status_click<F: FnMut(StatusClick) + 'static>(&self, f: F) {
+        *self.on_status_click.borrow_mut() = Some(cb(f));
+    }
+
     pub fn set_i3bar_separator(&self, separator: &str) {
         *self.i3bar_separator.borrow_mut() = Some(Rc::new(separator.to_string()));
     }
@@ -623,6 +781,47 @@ impl Client {
             .insert(input_device, cb(f));
     }
 
+    pub fn on_tablet_pad_event<F: FnMut(f64) + 'static>(
+        &self,
+        device: InputDevice,
+        control: TabletPadControl,
+        index: u32,
+        f: F,
+    ) {
+        self.on_tablet_pad_event
+            .borrow_mut()
+            .insert((device, control, index), cb(f));
+        self.send(&ClientMessage::SetTabletPadBinding {
+            device,
+            control,
+            index,
+            bound: true,
+        });
+    }
+
+    pub fn remove_tablet_pad_binding(
+        &self,
+        device: InputDevice,
+        control: TabletPadControl,
+        index: u32,
+    ) {
+        self.on_tablet_pad_event
+            .borrow_mut()
+            .remove(&(device, control, index));
+        self.send(&ClientMessage::SetTabletPadBinding {
+            device,
+            control,
+            index,
+            bound: false,
+        });
+    }
+
+    pub fn on_keyboard_layout_changed<F: FnMut(KeyboardLayout) + 'static>(&self, seat: Seat, f: F) {
+        self.on_keyboard_layout_changed
+            .borrow_mut()
+            .insert(seat, cb(f));
+    }
+
     pub fn set_double_click_interval(&self, usec: u64) {
         self.send(&ClientMessage::SetDoubleClickIntervalUsec { usec });
     }
@@ -822,6 +1021,23 @@ impl Client {
         *self.on_idle.borrow_mut() = Some(cb(move |_| f()));
     }
 
+    pub fn on_before_sleep<F: FnMut() + 'static>(&self, mut f: F) {
+        *self.on_before_sleep.borrow_mut() = Some(cb(move |_| f()));
+        self.send(&ClientMessage::EnableBeforeSleep);
+    }
+
+    pub fn lock(&self) {
+        self.send(&ClientMessage::Lock);
+    }
+
+    pub fn on_idle_stage<F: FnMut(&str) + 'static>(&self, mut f: F) {
+        *self.on_idle_stage.borrow_mut() = Some(cb(move |name: String| f(&name)));
+    }
+
+    pub fn on_idle_stage_resumed<F: FnMut(&str) + 'static>(&self, mut f: F) {
+        *self.on_idle_stage_resumed.borrow_mut() = Some(cb(move |name: String| f(&name)));
+    }
+
     pub fn on_connector_connected<F: FnMut(Connector) + 'static>(&self, f: F) {
         *self.on_connector_connected.borrow_mut() = Some(cb(f));
     }
@@ -854,6 +1070,14 @@ impl Client {
         self.send(&ClientMessage::SetIdle { timeout })
     }
 
+    pub fn set_idle_stages(&self, stages: Vec<IdleStage>) {
+        self.send(&ClientMessage::SetIdleStages { stages })
+    }
+
+    pub fn set_fullscreen_inhibits_idle(&self, enabled: bool) {
+        self.send(&ClientMessage::SetFullscreenInhibitsIdle { enabled })
+    }
+
     pub fn set_explicit_sync_enabled(&self, enabled: bool) {
         self.send(&ClientMessage::SetExplicitSyncEnabled { enabled })
     }
@@ -881,6 +1105,21 @@ impl Client {
         self.send(&ClientMessage::SetAccelSpeed { device, speed })
     }
 
+    pub fn set_custom_accel_function(
+        &self,
+        device: InputDevice,
+        ty: AccelType,
+        step: f64,
+        points: &[f64],
+    ) {
+        self.send(&ClientMessage::SetCustomAccelFunction {
+            device,
+            ty,
+            step,
+            points: points.to_vec(),
+        })
+    }
+
     pub fn set_transform_matrix(&self, device: InputDevice, matrix: [[f64; 2]; 2]) {
         self.send(&ClientMessage::SetTransformMatrix { device, matrix })
     }
@@ -897,6 +1136,70 @@ impl Client {
         self.send(&ClientMessage::SetNaturalScrollingEnabled { device, enabled })
     }
 
+    pub fn set_input_click_method(&self, device: InputDevice, method: ClickMethod) {
+        self.send(&ClientMessage::SetClickMethod { device, method })
+    }
+
+    pub fn set_input_middle_button_emulation_enabled(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetMiddleButtonEmulationEnabled { device, enabled })
+    }
+
+    pub fn set_input_scroll_method(&self, device: InputDevice, method: ScrollMethod) {
+        self.send(&ClientMessage::SetScrollMethod { device, method })
+    }
+
+    pub fn set_input_scroll_button(&self, device: InputDevice, button: u32) {
+        self.send(&ClientMessage::SetScrollButton { device, button })
+    }
+
+    pub fn set_input_disable_while_typing_enabled(&self, device: InputDevice, enabled: bool) {
+        self.send(&ClientMessage::SetDisableWhileTypingEnabled { device, enabled })
+    }
+
+    pub fn set_input_tap_button_map(&self, device: InputDevice, map: TapButtonMap) {
+        self.send(&ClientMessage::SetTapButtonMap { device, map })
+    }
+
+    pub fn set_input_send_events_mode(&self, device: InputDevice, mode: SendEventsMode) {
+        self.send(&ClientMessage::SetSendEventsMode { device, mode })
+    }
+
+    pub fn set_input_rotation(&self, device: InputDevice, degrees: u32) {
+        self.send(&ClientMessage::SetRotation { device, degrees })
+    }
+
+    pub fn set_input_calibration_matrix(&self, device: InputDevice, matrix: [[f32; 3]; 2]) {
+        self.send(&ClientMessage::SetCalibrationMatrix { device, matrix })
+    }
+
+    pub fn set_input_region(&self, device: InputDevice, x: i32, y: i32, width: i32, height: i32) {
+        self.send(&ClientMessage::SetInputRegion {
+            device,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    pub fn set_tablet_area(&self, device: InputDevice, x: f64, y: f64, width: f64, height: f64) {
+        self.send(&ClientMessage::SetTabletArea {
+            device,
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+
+    pub fn set_tablet_keep_aspect_ratio(&self, device: InputDevice, keep: bool) {
+        self.send(&ClientMessage::SetTabletKeepAspectRatio { device, keep })
+    }
+
+    pub fn set_tablet_pressure_curve(&self, device: InputDevice, curve: Option<[f64; 4]>) {
+        self.send(&ClientMessage::SetTabletPressureCurve { device, curve })
+    }
+
     pub fn set_input_drag_enabled(&self, device: InputDevice, enabled: bool) {
         self.send(&ClientMessage::SetDragEnabled { device, enabled })
     }
@@ -955,6 +1258,10 @@ impl Client {
         self.send(&ClientMessage::SetFocusFollowsMouseMode { seat, mode })
     }
 
+    pub fn set_remember_keyboard_layout(&self, seat: Seat, remember: bool) {
+        self.send(&ClientMessage::SetRememberKeyboardLayout { seat, remember })
+    }
+
     pub fn set_input_device_connector(&self, input_device: InputDevice, connector: Connector) {
         self.send(&ClientMessage::SetInputDeviceConnector {
             input_device,
@@ -972,6 +1279,40 @@ impl Client {
         keymap
     }
 
+    pub fn keymap_from_names(&self, names: &KeymapNames) -> Keymap {
+        let res = self.send_with_response(&ClientMessage::KeymapFromNames {
+            names: names.clone(),
+        });
+        get_response!(res, Keymap(0), ParseKeymap { keymap });
+        keymap
+    }
+
+    pub fn seat_next_keyboard_layout(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatNextKeyboardLayout { seat })
+    }
+
+    pub fn seat_set_keyboard_layout(&self, seat: Seat, layout: u32) {
+        self.send(&ClientMessage::SeatSetKeyboardLayout { seat, layout })
+    }
+
+    pub fn seat_get_keyboard_layout(&self, seat: Seat) -> KeyboardLayout {
+        let res = self.send_with_response(&ClientMessage::SeatGetKeyboardLayout { seat });
+        get_response!(
+            res,
+            KeyboardLayout::default(),
+            SeatGetKeyboardLayout { layout }
+        );
+        layout
+    }
+
+    pub fn device_next_keyboard_layout(&self, device: InputDevice) {
+        self.send(&ClientMessage::DeviceNextKeyboardLayout { device })
+    }
+
+    pub fn device_set_keyboard_layout(&self, device: InputDevice, layout: u32) {
+        self.send(&ClientMessage::DeviceSetKeyboardLayout { device, layout })
+    }
+
     pub fn latch<F: FnOnce() + 'static>(&self, seat: Seat, f: F) {
         if !self.feat_mod_mask.get() {
             log::error!("compositor does not support latching");
@@ -1295,6 +1636,9 @@ impl Client {
             }
             ServerMessage::DelInputDevice { device } => {
                 self.on_switch_event.borrow_mut().remove(&device);
+                self.on_tablet_pad_event
+                    .borrow_mut()
+                    .retain(|(d, _, _), _| *d != device);
                 let handler = self.on_input_device_removed.borrow_mut().clone();
                 if let Some(handler) = handler {
                     run_cb("input device removed", &handler, device);
@@ -1389,6 +1733,51 @@ impl Client {
                     run_cb("switch event", &cb, event);
                 }
             }
+            ServerMessage::StatusClicked { click } => {
+                let cb = self.on_status_click.borrow().clone();
+                if let Some(cb) = cb {
+                    run_cb("status click", &cb, click);
+                }
+            }
+            ServerMessage::KeyboardLayoutChanged { seat, layout } => {
+                let cb = self.on_keyboard_layout_changed.borrow().get(&seat).cloned();
+                if let Some(cb) = cb {
+                    run_cb("keyboard layout changed", &cb, layout);
+                }
+            }
+            ServerMessage::IdleStageEntered { name } => {
+                let cb = self.on_idle_stage.borrow().clone();
+                if let Some(cb) = cb {
+                    run_cb("idle stage", &cb, name);
+                }
+            }
+            ServerMessage::BeforeSleep => {
+                let cb = self.on_before_sleep.borrow().clone();
+                if let Some(cb) = cb {
+                    run_cb("before sleep", &cb, ());
+                }
+            }
+            ServerMessage::TabletPadEvent {
+                input_device,
+                control,
+                index,
+                delta,
+            } => {
+                let cb = self
+                    .on_tablet_pad_event
+                    .borrow()
+                    .get(&(input_device, control, index))
+                    .cloned();
+                if let Some(cb) = cb {
+                    run_cb("tablet pad event", &cb, delta);
+                }
+            }
+            ServerMessage::IdleStageResumed { name } => {
+                let cb = self.on_idle_stage_resumed.borrow().clone();
+                if let Some(cb) = cb {
+                    run_cb("idle stage resumed", &cb, name);
+                }
+            }
         }
     }
 
@@ -1,15 +1,21 @@
 use {
     crate::{
+        animation::{Animation, AnimationCurve, WorkspaceAnimationStyle},
         input::{
-            acceleration::AccelProfile, capability::Capability, FocusFollowsMouseMode, InputDevice,
-            Seat, SwitchEvent,
+            acceleration::{AccelProfile, AccelType},
+            capability::Capability,
+            click_method::ClickMethod,
+            scroll_method::ScrollMethod,
+            send_events::SendEventsMode,
+            tap_button_map::TapButtonMap,
+            FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent, TabletPadControl,
         },
-        keyboard::{mods::Modifiers, syms::KeySym, Keymap},
+        keyboard::{mods::Modifiers, syms::KeySym, KeyboardLayout, Keymap, KeymapNames},
         logging::LogLevel,
-        theme::{colors::Colorable, sized::Resizable, Color},
+        theme::{colors::Colorable, sized::Resizable, Color, ColorScheme},
         timer::Timer,
         video::{connector_type::ConnectorType, Connector, DrmDevice, GfxApi, Transform},
-        Axis, Direction, PciId, Workspace,
+        Axis, ContainerLayout, Direction, IdleStage, PciId, Workspace, WorkspaceLayout,
         _private::{PollableId, WireMode},
     },
     serde::{Deserialize, Serialize},
@@ -88,6 +94,47 @@ pub enum ServerMessage {
         input_device: InputDevice,
         event: SwitchEvent,
     },
+    StatusClicked {
+        click: StatusClick,
+    },
+    KeyboardLayoutChanged {
+        seat: Seat,
+        layout: KeyboardLayout,
+    },
+    IdleStageEntered {
+        name: String,
+    },
+    IdleStageResumed {
+        name: String,
+    },
+    BeforeSleep,
+    TabletPadEvent {
+        input_device: InputDevice,
+        control: TabletPadControl,
+        index: u32,
+        delta: f64,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StatusBlock {
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct StatusClick {
+    pub block: usize,
+    pub button: u32,
+    pub mods: Modifiers,
+    pub x: i32,
+    pub y: i32,
+    pub relative_x: i32,
+    pub relative_y: i32,
+    pub output_x: i32,
+    pub output_y: i32,
+    pub width: i32,
+    pub height: i32,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -483,6 +530,195 @@ pub enum ClientMessage<'a> {
     RemoveInputMapping {
         input_device: InputDevice,
     },
+    SetColorScheme {
+        scheme: ColorScheme,
+    },
+    SetHighContrast {
+        enabled: bool,
+    },
+    SetSmartGaps {
+        enabled: bool,
+    },
+    SetWindowOpacity {
+        seat: Seat,
+        opacity: f64,
+    },
+    SetBlurredAppIds {
+        app_ids: Vec<String>,
+    },
+    SetBlurredLayerNamespaces {
+        namespaces: Vec<String>,
+    },
+    SetAnimationsEnabled {
+        enabled: bool,
+    },
+    SetAnimationDuration {
+        animation: Animation,
+        duration: Duration,
+    },
+    SetAnimationCurve {
+        animation: Animation,
+        curve: AnimationCurve,
+    },
+    SetWorkspaceAnimationStyle {
+        style: WorkspaceAnimationStyle,
+    },
+    ResetAnimations,
+    MoveToScratchpad {
+        seat: Seat,
+    },
+    ToggleScratchpad {
+        seat: Seat,
+    },
+    GetSticky {
+        seat: Seat,
+    },
+    SetSticky {
+        seat: Seat,
+        sticky: bool,
+    },
+    Mark {
+        seat: Seat,
+        mark: String,
+    },
+    Unmark {
+        seat: Seat,
+        mark: Option<String>,
+    },
+    FocusMark {
+        seat: Seat,
+        mark: String,
+    },
+    SwapWithMark {
+        seat: Seat,
+        mark: String,
+    },
+    GetContainerLayout {
+        seat: Seat,
+    },
+    SetContainerLayout {
+        seat: Seat,
+        layout: ContainerLayout,
+    },
+    GetWorkspaceLayout {
+        workspace: WorkspaceSource,
+    },
+    SetWorkspaceLayout {
+        workspace: WorkspaceSource,
+        layout: WorkspaceLayout,
+    },
+    SetDefaultWorkspaceLayout {
+        layout: WorkspaceLayout,
+    },
+    SetStatusBlocks {
+        status: &'a str,
+        blocks: Vec<StatusBlock>,
+    },
+    KeymapFromNames {
+        names: KeymapNames,
+    },
+    SeatNextKeyboardLayout {
+        seat: Seat,
+    },
+    SeatSetKeyboardLayout {
+        seat: Seat,
+        layout: u32,
+    },
+    SeatGetKeyboardLayout {
+        seat: Seat,
+    },
+    DeviceNextKeyboardLayout {
+        device: InputDevice,
+    },
+    DeviceSetKeyboardLayout {
+        device: InputDevice,
+        layout: u32,
+    },
+    SetRememberKeyboardLayout {
+        seat: Seat,
+        remember: bool,
+    },
+    SetClickMethod {
+        device: InputDevice,
+        method: ClickMethod,
+    },
+    SetMiddleButtonEmulationEnabled {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SetScrollMethod {
+        device: InputDevice,
+        method: ScrollMethod,
+    },
+    SetScrollButton {
+        device: InputDevice,
+        button: u32,
+    },
+    SetDisableWhileTypingEnabled {
+        device: InputDevice,
+        enabled: bool,
+    },
+    SetTapButtonMap {
+        device: InputDevice,
+        map: TapButtonMap,
+    },
+    SetSendEventsMode {
+        device: InputDevice,
+        mode: SendEventsMode,
+    },
+    SetRotation {
+        device: InputDevice,
+        degrees: u32,
+    },
+    SetCalibrationMatrix {
+        device: InputDevice,
+        matrix: [[f32; 3]; 2],
+    },
+    SetCustomAccelFunction {
+        device: InputDevice,
+        ty: AccelType,
+        step: f64,
+        points: Vec<f64>,
+    },
+    SetIdleStages {
+        stages: Vec<IdleStage>,
+    },
+    SetFullscreenInhibitsIdle {
+        enabled: bool,
+    },
+    EnableBeforeSleep,
+    Lock,
+    SetInputRegion {
+        device: InputDevice,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    },
+    SetTabletArea {
+        device: InputDevice,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
+    SetTabletKeepAspectRatio {
+        device: InputDevice,
+        keep: bool,
+    },
+    SetTabletPressureCurve {
+        device: InputDevice,
+        curve: Option<[f64; 4]>,
+    },
+    SetTabletPadBinding {
+        device: InputDevice,
+        control: TabletPadControl,
+        index: u32,
+        bound: bool,
+    },
+    SetWindowOpacities {
+        opacities: Vec<(String, f64)>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -631,6 +867,18 @@ pub enum Response {
     GetSocketPath {
         path: String,
     },
+    GetSticky {
+        sticky: bool,
+    },
+    GetContainerLayout {
+        layout: ContainerLayout,
+    },
+    GetWorkspaceLayout {
+        layout: WorkspaceLayout,
+    },
+    SeatGetKeyboardLayout {
+        layout: KeyboardLayout,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
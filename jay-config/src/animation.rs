@@ -0,0 +1,92 @@
+//! Tools for configuring animations.
+
+use {
+    serde::{Deserialize, Serialize},
+    std::time::Duration,
+};
+
+/// An easing curve.
+///
+/// The curve maps the elapsed fraction of an animation to the fraction of the
+/// distance that has been covered.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum AnimationCurve {
+    /// A constant speed.
+    Linear,
+    /// Starts slowly and ends at full speed.
+    EaseIn,
+    /// Starts at full speed and ends slowly.
+    EaseOut,
+    /// Starts and ends slowly.
+    EaseInOut,
+    /// A cubic bézier curve with the control points `(x1, y1)` and `(x2, y2)`.
+    ///
+    /// This behaves like the CSS `cubic-bezier(x1, y1, x2, y2)` function. The x values
+    /// are clamped to `[0, 1]`.
+    CubicBezier { x1: f64, y1: f64, x2: f64, y2: f64 },
+}
+
+/// An animated transition.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub struct Animation(pub u32);
+
+impl Animation {
+    /// Sets the duration of the animation.
+    ///
+    /// A duration of zero disables this animation.
+    pub fn set_duration(self, duration: Duration) {
+        get!().set_animation_duration(self, duration)
+    }
+
+    /// Sets the easing curve of the animation.
+    pub fn set_curve(self, curve: AnimationCurve) {
+        get!().set_animation_curve(self, curve)
+    }
+}
+
+/// The transition between two workspaces on the same output.
+///
+/// The default duration is 200 ms. The default curve is [`AnimationCurve::EaseOut`].
+pub const WORKSPACE_SWITCH: Animation = Animation(0);
+/// The appearance of a newly mapped window.
+///
+/// The default duration is 150 ms. The default curve is [`AnimationCurve::EaseOut`].
+pub const WINDOW_OPEN: Animation = Animation(1);
+/// The disappearance of a window that is being closed.
+///
+/// Only the main surface of the window is shown while it disappears.
+///
+/// The default duration is 150 ms. The default curve is [`AnimationCurve::EaseIn`].
+pub const WINDOW_CLOSE: Animation = Animation(2);
+/// The movement of tiled windows when their container changes its layout.
+///
+/// The default duration is 150 ms. The default curve is [`AnimationCurve::EaseOut`].
+pub const LAYOUT: Animation = Animation(3);
+
+/// The style of workspace transitions.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Hash, Eq, PartialEq)]
+pub enum WorkspaceAnimationStyle {
+    /// The new workspace slides in from the side of its position in the workspace list.
+    Slide,
+    /// The old workspace fades out while the new workspace fades in.
+    Fade,
+}
+
+/// Enables or disables all animations.
+///
+/// The default is `false`.
+pub fn set_animations_enabled(enabled: bool) {
+    get!().set_animations_enabled(enabled)
+}
+
+/// Sets the style of workspace transitions.
+///
+/// The default is [`WorkspaceAnimationStyle::Slide`].
+pub fn set_workspace_animation_style(style: WorkspaceAnimationStyle) {
+    get!().set_workspace_animation_style(style)
+}
+
+/// Resets all animation settings to their defaults.
+pub fn reset_animations() {
+    get!().reset_animations()
+}
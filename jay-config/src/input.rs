@@ -2,12 +2,23 @@
 
 pub mod acceleration;
 pub mod capability;
+pub mod click_method;
+pub mod scroll_method;
+pub mod send_events;
+pub mod tap_button_map;
 
 use {
     crate::{
-        input::{acceleration::AccelProfile, capability::Capability},
-        keyboard::{mods::Modifiers, Keymap},
-        Axis, Direction, ModifiedKeySym, Workspace,
+        input::{
+            acceleration::{AccelProfile, AccelType},
+            capability::Capability,
+            click_method::ClickMethod,
+            scroll_method::ScrollMethod,
+            send_events::SendEventsMode,
+            tap_button_map::TapButtonMap,
+        },
+        keyboard::{mods::Modifiers, KeyboardLayout, Keymap},
+        Axis, ContainerLayout, Direction, ModifiedKeySym, Workspace, WorkspaceLayout,
         _private::{ipc::WorkspaceSource, DEFAULT_SEAT_NAME},
         video::Connector,
     },
@@ -35,6 +46,22 @@ impl InputDevice {
         get!().set_device_keymap(self, keymap)
     }
 
+    /// Switches the effective keymap of the device to its next layout.
+    ///
+    /// If the device does not have its own keymap, this switches the layout of the seat
+    /// keymap.
+    pub fn next_keyboard_layout(self) {
+        get!().device_next_keyboard_layout(self)
+    }
+
+    /// Switches the effective keymap of the device to the layout with the given index.
+    ///
+    /// If the device does not have its own keymap, this switches the layout of the seat
+    /// keymap.
+    pub fn set_keyboard_layout(self, layout: u32) {
+        get!().device_set_keyboard_layout(self, layout)
+    }
+
     /// Returns whether the device has the specified capability.
     pub fn has_capability(self, cap: Capability) -> bool {
         get!(false).has_capability(self, cap)
@@ -42,8 +69,9 @@ impl InputDevice {
 
     /// Sets the device to be left handed.
     ///
-    /// This has the effect of swapping the left and right mouse button. See the libinput
-    /// documentation for more details.
+    /// This has the effect of swapping the left and right mouse button. Graphics tablets
+    /// are instead rotated by 180 degrees. See the libinput documentation for more
+    /// details.
     pub fn set_left_handed(self, left_handed: bool) {
         get!().set_left_handed(self, left_handed);
     }
@@ -62,6 +90,21 @@ impl InputDevice {
         get!().set_accel_speed(self, speed);
     }
 
+    /// Sets a function of the custom acceleration profile of the device.
+    ///
+    /// The function maps the speed of the device to the speed of the pointer. `points`
+    /// contains the values of the function at `0`, `step`, `2 * step`, etc. and the
+    /// function is interpolated linearly between these points. libinput requires between
+    /// 2 and 64 points.
+    ///
+    /// The function only has an effect while the acceleration profile of the device is
+    /// [`ACCEL_PROFILE_CUSTOM`](acceleration::ACCEL_PROFILE_CUSTOM).
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/pointer-acceleration.html#the-custom-acceleration-profile>
+    pub fn set_custom_accel_function(self, ty: AccelType, step: f64, points: &[f64]) {
+        get!().set_custom_accel_function(self, ty, step, points);
+    }
+
     /// Sets the transformation matrix of the device.
     ///
     /// This is not a libinput setting but a setting of the compositor. It currently affects
@@ -125,6 +168,73 @@ impl InputDevice {
         get!().set_input_natural_scrolling_enabled(self, enabled);
     }
 
+    /// Sets the click method of this device.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/clickpad-softbuttons.html>
+    pub fn set_click_method(self, method: ClickMethod) {
+        get!().set_input_click_method(self, method);
+    }
+
+    /// Sets whether pressing the left and right button at the same time generates a
+    /// middle button click.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/middle-button-emulation.html>
+    pub fn set_middle_button_emulation_enabled(self, enabled: bool) {
+        get!().set_input_middle_button_emulation_enabled(self, enabled);
+    }
+
+    /// Sets the scroll method of this device.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/scrolling.html>
+    pub fn set_scroll_method(self, method: ScrollMethod) {
+        get!().set_input_scroll_method(self, method);
+    }
+
+    /// Sets the button used by the [`SCROLL_METHOD_ON_BUTTON_DOWN`] scroll method.
+    ///
+    /// The button is identified by its evdev code, e.g. `0x112` for the middle button.
+    ///
+    /// [`SCROLL_METHOD_ON_BUTTON_DOWN`]: scroll_method::SCROLL_METHOD_ON_BUTTON_DOWN
+    pub fn set_scroll_button(self, button: u32) {
+        get!().set_input_scroll_button(self, button);
+    }
+
+    /// Sets whether the device is disabled while typing.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/palm-detection.html#disable-while-typing>
+    pub fn set_disable_while_typing_enabled(self, enabled: bool) {
+        get!().set_input_disable_while_typing_enabled(self, enabled);
+    }
+
+    /// Sets which buttons are generated by two- and three-finger taps.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/tapping.html>
+    pub fn set_tap_button_map(self, map: TapButtonMap) {
+        get!().set_input_tap_button_map(self, map);
+    }
+
+    /// Sets whether the device sends events.
+    ///
+    /// This can be used to disable a touchpad while an external mouse is connected.
+    pub fn set_send_events_mode(self, mode: SendEventsMode) {
+        get!().set_input_send_events_mode(self, mode);
+    }
+
+    /// Sets the clockwise rotation of the device in degrees.
+    ///
+    /// See <https://wayland.freedesktop.org/libinput/doc/latest/configuration.html#rotation>
+    pub fn set_rotation(self, degrees: u32) {
+        get!().set_input_rotation(self, degrees);
+    }
+
+    /// Sets the calibration matrix of this device.
+    ///
+    /// This only applies to absolute devices such as touchscreens and tablets. See
+    /// <https://wayland.freedesktop.org/libinput/doc/latest/absolute-axes.html#calibration-of-absolute-devices>
+    pub fn set_calibration_matrix(self, matrix: [[f32; 3]; 2]) {
+        get!().set_input_calibration_matrix(self, matrix);
+    }
+
     /// Returns the syspath of this device.
     ///
     /// E.g. `/sys/devices/pci0000:00/0000:00:08.1/0000:14:00.4/usb5/5-1/5-1.1/5-1.1.3/5-1.1.3:1.0`.
@@ -153,10 +263,82 @@ impl InputDevice {
         get!().set_input_device_connector(self, connector);
     }
 
-    /// Removes the mapping of this device to a connector.
+    /// Maps this input device to a region of the compositor space.
+    ///
+    /// The region is specified in logical pixels and replaces any mapping to a connector.
+    ///
+    /// This should be used for touch screens and graphics tablets.
+    pub fn set_region(self, x: i32, y: i32, width: i32, height: i32) {
+        get!().set_input_region(self, x, y, width, height);
+    }
+
+    /// Removes the mapping of this device to a connector or region.
     pub fn remove_mapping(self) {
         get!().remove_input_mapping(self);
     }
+
+    /// Sets the active area of a graphics tablet.
+    ///
+    /// Only this part of the tablet is mapped to the output. All values are fractions of
+    /// the tablet size, i.e. `(0.0, 0.0, 1.0, 1.0)` is the whole tablet.
+    pub fn set_tablet_area(self, x: f64, y: f64, width: f64, height: f64) {
+        get!().set_tablet_area(self, x, y, width, height);
+    }
+
+    /// Sets whether the active area of a graphics tablet is shrunk to match the aspect
+    /// ratio of the mapped output or region.
+    ///
+    /// The default is `false`.
+    pub fn set_tablet_keep_aspect_ratio(self, keep: bool) {
+        get!().set_tablet_keep_aspect_ratio(self, keep);
+    }
+
+    /// Sets the pressure curve of a graphics tablet.
+    ///
+    /// The curve is a cubic bezier curve from `(0, 0)` to `(1, 1)` with the control
+    /// points `(x1, y1)` and `(x2, y2)`. All values must be in the range `[0, 1]`.
+    ///
+    /// The default is the linear curve `(0.0, 0.0, 1.0, 1.0)`.
+    pub fn set_tablet_pressure_curve(self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        get!().set_tablet_pressure_curve(self, Some([x1, y1, x2, y2]));
+    }
+
+    /// Resets the pressure curve of a graphics tablet to the linear curve.
+    pub fn reset_tablet_pressure_curve(self) {
+        get!().set_tablet_pressure_curve(self, None);
+    }
+
+    /// Sets a callback that will be run if a button on this tablet pad is pressed.
+    ///
+    /// While the callback is set, the button is no longer forwarded to applications.
+    pub fn on_tablet_pad_button<F: FnMut() + 'static>(self, button: u32, mut f: F) {
+        get!().on_tablet_pad_event(self, TabletPadControl::Button, button, move |_| f())
+    }
+
+    /// Sets a callback that will be run if a ring on this tablet pad is rotated.
+    ///
+    /// The callback receives the rotation in degrees. Clockwise rotation is positive.
+    ///
+    /// While the callback is set, the ring is no longer forwarded to applications.
+    pub fn on_tablet_pad_ring<F: FnMut(f64) + 'static>(self, ring: u32, f: F) {
+        get!().on_tablet_pad_event(self, TabletPadControl::Ring, ring, f)
+    }
+
+    /// Sets a callback that will be run if a finger moves along a strip on this tablet
+    /// pad.
+    ///
+    /// The callback receives the distance moved as a fraction of the strip length.
+    /// Moving down or right is positive.
+    ///
+    /// While the callback is set, the strip is no longer forwarded to applications.
+    pub fn on_tablet_pad_strip<F: FnMut(f64) + 'static>(self, strip: u32, f: F) {
+        get!().on_tablet_pad_event(self, TabletPadControl::Strip, strip, f)
+    }
+
+    /// Removes a callback set with one of the `on_tablet_pad_*` functions.
+    pub fn remove_tablet_pad_binding(self, control: TabletPadControl, index: u32) {
+        get!().remove_tablet_pad_binding(self, control, index)
+    }
 }
 
 /// A seat.
@@ -265,6 +447,30 @@ impl Seat {
         get!().seat_set_keymap(self, keymap)
     }
 
+    /// Switches the seat keymap to its next layout.
+    ///
+    /// After the last layout, the first layout is used again.
+    pub fn next_keyboard_layout(self) {
+        get!().seat_next_keyboard_layout(self)
+    }
+
+    /// Switches the seat keymap to the layout with the given index.
+    pub fn set_keyboard_layout(self, layout: u32) {
+        get!().seat_set_keyboard_layout(self, layout)
+    }
+
+    /// Returns the active layout of the seat keymap.
+    pub fn keyboard_layout(self) -> KeyboardLayout {
+        get!(KeyboardLayout::default()).seat_get_keyboard_layout(self)
+    }
+
+    /// Sets a callback that will be run when the active keyboard layout of the seat changes.
+    ///
+    /// This can be used to show the layout in the status bar.
+    pub fn on_keyboard_layout_changed<F: FnMut(KeyboardLayout) + 'static>(self, f: F) {
+        get!().on_keyboard_layout_changed(self, f)
+    }
+
     /// Returns the repeat rate of the seat.
     ///
     /// The returned tuple is `(rate, delay)` where `rate` is the number of times keys repeat per second
@@ -308,6 +514,23 @@ impl Seat {
         self.set_split(self.split().other());
     }
 
+    /// Sets the automatic tiling layout of the workspace shown on the output of the seat.
+    pub fn set_workspace_layout(self, layout: WorkspaceLayout) {
+        get!().set_workspace_layout(WorkspaceSource::Seat(self), layout)
+    }
+
+    /// Returns the layout of the parent-container of the currently focused window.
+    pub fn container_layout(self) -> ContainerLayout {
+        get!(ContainerLayout::SplitHorizontal).container_layout(self)
+    }
+
+    /// Sets the layout of the parent-container of the currently focused window.
+    ///
+    /// Clicking on a title of a tabbed or stacked container switches to that child.
+    pub fn set_container_layout(self, layout: ContainerLayout) {
+        get!().set_container_layout(self, layout)
+    }
+
     /// Returns the input devices assigned to this seat.
     pub fn input_devices(self) -> Vec<InputDevice> {
         get!().get_input_devices(Some(self))
@@ -337,6 +560,17 @@ impl Seat {
         get!().set_floating(self, floating);
     }
 
+    /// Sets the opacity of the currently focused window.
+    ///
+    /// The opacity is multiplied with the opacity of the window's contents and must be
+    /// between `0.0` and `1.0`. It overrides the opacity configured with
+    /// [`set_window_opacities`](crate::theme::set_window_opacities).
+    ///
+    /// Default: `1.0`.
+    pub fn set_window_opacity(self, opacity: f64) {
+        get!().set_window_opacity(self, opacity);
+    }
+
     /// Toggles whether the currently focused window is floating.
     ///
     /// You can do the same by double-clicking on the header.
@@ -344,6 +578,73 @@ impl Seat {
         get!().toggle_floating(self);
     }
 
+    /// Returns whether the currently focused window is sticky.
+    pub fn get_sticky(self) -> bool {
+        get!().get_sticky(self)
+    }
+
+    /// Sets whether the currently focused window is sticky.
+    ///
+    /// A sticky floating window stays visible on whichever workspace is shown on its
+    /// output. Tiled windows are not affected by this setting until they are made
+    /// floating.
+    pub fn set_sticky(self, sticky: bool) {
+        get!().set_sticky(self, sticky);
+    }
+
+    /// Toggles whether the currently focused window is sticky.
+    pub fn toggle_sticky(self) {
+        get!().toggle_sticky(self);
+    }
+
+    /// Attaches a mark to the currently focused window.
+    ///
+    /// Marks are shown in the title of the window. A mark can be attached to at most one
+    /// window. If another window already has this mark, the mark is moved.
+    pub fn mark(self, mark: &str) {
+        get!().mark(self, mark);
+    }
+
+    /// Removes a mark from the window that has it.
+    ///
+    /// If `mark` is `None`, all marks are removed from the currently focused window.
+    pub fn unmark(self, mark: Option<&str>) {
+        get!().unmark(self, mark);
+    }
+
+    /// Focuses the window that has the mark.
+    ///
+    /// If the window is on a workspace that is not currently visible, the workspace is
+    /// shown first.
+    pub fn focus_mark(self, mark: &str) {
+        get!().focus_mark(self, mark);
+    }
+
+    /// Swaps the currently focused window with the window that has the mark.
+    pub fn swap_with_mark(self, mark: &str) {
+        get!().swap_with_mark(self, mark);
+    }
+
+    /// Moves the currently focused window to the scratchpad.
+    ///
+    /// The scratchpad is a hidden holding area for windows. Windows in the scratchpad can
+    /// be shown with [`Seat::toggle_scratchpad`].
+    pub fn move_to_scratchpad(self) {
+        get!().move_to_scratchpad(self);
+    }
+
+    /// Shows or hides scratchpad windows.
+    ///
+    /// If the currently focused window is a scratchpad window, it is hidden. Otherwise the
+    /// next hidden scratchpad window is shown floating and centered on the output that
+    /// contains the seat's cursor. Calling this function repeatedly cycles through all
+    /// scratchpad windows.
+    ///
+    /// A window stops being a scratchpad window when it is made tiled.
+    pub fn toggle_scratchpad(self) {
+        get!().toggle_scratchpad(self);
+    }
+
     /// Returns the workspace that is currently active on the output that contains the seat's
     /// cursor.
     ///
@@ -414,6 +715,17 @@ impl Seat {
     pub fn set_focus_follows_mouse_mode(self, mode: FocusFollowsMouseMode) {
         get!().set_focus_follows_mouse_mode(self, mode);
     }
+
+    /// Sets whether the keyboard layout is remembered per window.
+    ///
+    /// If this is enabled, the active layout of the seat keymap is stored when a window
+    /// loses the keyboard focus and restored when it regains the focus. Windows that
+    /// have never had the focus start with the first layout.
+    ///
+    /// The default is `false`.
+    pub fn set_remember_keyboard_layout(self, remember: bool) {
+        get!().set_remember_keyboard_layout(self, remember);
+    }
 }
 
 /// A focus-follows-mouse mode.
@@ -504,6 +816,17 @@ pub fn disable_default_seat() {
     get!().disable_default_seat();
 }
 
+/// A control on a tablet pad.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum TabletPadControl {
+    /// A button.
+    Button,
+    /// A ring.
+    Ring,
+    /// A strip.
+    Strip,
+}
+
 /// An event generated by a switch.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum SwitchEvent {
@@ -12,3 +12,21 @@ pub struct AccelProfile(pub u32);
 pub const ACCEL_PROFILE_FLAT: AccelProfile = AccelProfile(1 << 0);
 /// An adaptive acceleration profile.
 pub const ACCEL_PROFILE_ADAPTIVE: AccelProfile = AccelProfile(1 << 1);
+/// A custom acceleration profile.
+///
+/// The acceleration functions of this profile can be set with
+/// [`InputDevice::set_custom_accel_function`](crate::input::InputDevice::set_custom_accel_function).
+/// This profile requires libinput 1.23 or later. With older versions of libinput,
+/// setting this profile has no effect.
+pub const ACCEL_PROFILE_CUSTOM: AccelProfile = AccelProfile(1 << 2);
+
+/// The type of motion that a custom acceleration function applies to.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct AccelType(pub u32);
+
+/// The function used for all types of motion without a more specific function.
+pub const ACCEL_TYPE_FALLBACK: AccelType = AccelType(0);
+/// The function used for pointer motion.
+pub const ACCEL_TYPE_MOTION: AccelType = AccelType(1);
+/// The function used for scrolling.
+pub const ACCEL_TYPE_SCROLL: AccelType = AccelType(2);
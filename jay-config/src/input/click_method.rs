@@ -0,0 +1,16 @@
+//! Constants determining the click method of a touchpad.
+//!
+//! See the libinput documentation for details.
+
+use serde::{Deserialize, Serialize};
+
+/// The click method of a touchpad.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct ClickMethod(pub u32);
+
+/// No software click emulation.
+pub const CLICK_METHOD_NONE: ClickMethod = ClickMethod(0);
+/// The location of the click determines the button.
+pub const CLICK_METHOD_BUTTON_AREAS: ClickMethod = ClickMethod(1 << 0);
+/// The number of fingers on the touchpad determines the button.
+pub const CLICK_METHOD_CLICKFINGER: ClickMethod = ClickMethod(1 << 1);
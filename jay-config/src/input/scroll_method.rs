@@ -0,0 +1,18 @@
+//! Constants determining the scroll method of a device.
+//!
+//! See the libinput documentation for details.
+
+use serde::{Deserialize, Serialize};
+
+/// The scroll method of a device.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct ScrollMethod(pub u32);
+
+/// Scrolling is disabled.
+pub const SCROLL_METHOD_NONE: ScrollMethod = ScrollMethod(0);
+/// Scrolling with two fingers on a touchpad.
+pub const SCROLL_METHOD_TWO_FINGER: ScrollMethod = ScrollMethod(1 << 0);
+/// Scrolling by moving a finger along the edge of a touchpad.
+pub const SCROLL_METHOD_EDGE: ScrollMethod = ScrollMethod(1 << 1);
+/// Scrolling by moving the device while the scroll button is pressed.
+pub const SCROLL_METHOD_ON_BUTTON_DOWN: ScrollMethod = ScrollMethod(1 << 2);
@@ -0,0 +1,16 @@
+//! Constants determining whether a device sends events.
+//!
+//! See the libinput documentation for details.
+
+use serde::{Deserialize, Serialize};
+
+/// The send-events mode of a device.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct SendEventsMode(pub u32);
+
+/// The device sends events.
+pub const SEND_EVENTS_ENABLED: SendEventsMode = SendEventsMode(0);
+/// The device does not send events.
+pub const SEND_EVENTS_DISABLED: SendEventsMode = SendEventsMode(1 << 0);
+/// The device does not send events while an external pointer device is connected.
+pub const SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE: SendEventsMode = SendEventsMode(1 << 1);
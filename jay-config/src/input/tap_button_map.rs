@@ -0,0 +1,14 @@
+//! Constants determining the buttons generated by multi-finger taps.
+//!
+//! See the libinput documentation for details.
+
+use serde::{Deserialize, Serialize};
+
+/// The mapping from the number of fingers in a tap to buttons.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct TapButtonMap(pub u32);
+
+/// 1, 2, and 3 finger taps generate left, right, and middle clicks.
+pub const TAP_BUTTON_MAP_LRM: TapButtonMap = TapButtonMap(0);
+/// 1, 2, and 3 finger taps generate left, middle, and right clicks.
+pub const TAP_BUTTON_MAP_LMR: TapButtonMap = TapButtonMap(1);
@@ -109,3 +109,49 @@ impl Keymap {
 pub fn parse_keymap(keymap: &str) -> Keymap {
     get!(Keymap::INVALID).parse_keymap(keymap)
 }
+
+/// The RMLVO names of a keymap.
+///
+/// These are the same names that are used by `setxkbmap` and in the `XKB_DEFAULT_*`
+/// environment variables. Names that are not set use the defaults of libxkbcommon.
+///
+/// Multiple layouts can be specified by separating them with commas, e.g. `us,de`.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct KeymapNames {
+    /// The rules file, e.g. `evdev`.
+    pub rules: Option<String>,
+    /// The keyboard model, e.g. `pc105`.
+    pub model: Option<String>,
+    /// The layouts, e.g. `us,de`.
+    pub layout: Option<String>,
+    /// The variants of the layouts, e.g. `dvp,`.
+    pub variant: Option<String>,
+    /// The options, e.g. `grp:alt_shift_toggle,caps:escape`.
+    pub options: Option<String>,
+}
+
+/// Creates a keymap from RMLVO names.
+///
+/// If the keymap cannot be compiled, returns an invalid keymap.
+///
+/// Example:
+///
+/// ```rust,ignore
+/// let keymap = keymap_from_names(&KeymapNames {
+///     layout: Some("us,de".to_string()),
+///     options: Some("grp:alt_shift_toggle".to_string()),
+///     ..Default::default()
+/// });
+/// ```
+pub fn keymap_from_names(names: &KeymapNames) -> Keymap {
+    get!(Keymap::INVALID).keymap_from_names(names)
+}
+
+/// A layout of a keymap.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct KeyboardLayout {
+    /// The index of the layout in the keymap.
+    pub index: u32,
+    /// The name of the layout, e.g. `English (US)`.
+    pub name: String,
+}
@@ -55,6 +55,7 @@ use {
 mod macros;
 #[doc(hidden)]
 pub mod _private;
+pub mod animation;
 pub mod embedded;
 pub mod exec;
 pub mod input;
@@ -93,6 +94,45 @@ impl Axis {
     }
 }
 
+/// The layout of a container.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ContainerLayout {
+    /// The children are placed side by side.
+    SplitHorizontal,
+    /// The children are placed on top of each other.
+    SplitVertical,
+    /// Only one child is visible and the titles of all children are shown in a single row.
+    Tabbed,
+    /// Only one child is visible and the title of each child is shown in a row of its own.
+    Stacked,
+}
+
+/// The automatic tiling layout of a workspace.
+///
+/// The layout decides where new tiled windows are placed. Workspaces with a layout other than
+/// `Manual` are restructured automatically when windows are removed.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum WorkspaceLayout {
+    /// New windows are placed next to the last active window.
+    Manual,
+    /// The first window occupies the left half and all other windows are stacked on the right.
+    MasterStack,
+    /// Each new window splits the space of the previous window, alternating the axis.
+    Dwindle,
+    /// All windows are placed in equally sized columns.
+    Columns,
+    /// All windows are placed in columns that are half as wide as the workspace. The
+    /// workspace scrolls horizontally to show the focused column.
+    ScrollingColumns,
+}
+
+/// Sets the layout of new workspaces.
+///
+/// The default is `Manual`.
+pub fn set_default_workspace_layout(layout: WorkspaceLayout) {
+    get!().set_default_workspace_layout(layout)
+}
+
 /// Exits the compositor.
 pub fn quit() {
     get!().quit()
@@ -169,6 +209,18 @@ impl Workspace {
     pub fn move_to_output(self, output: Connector) {
         get!().move_to_output(WorkspaceSource::Explicit(self), output);
     }
+
+    /// Sets the automatic tiling layout of the workspace.
+    ///
+    /// This has no effect if the workspace does not exist.
+    pub fn set_layout(self, layout: WorkspaceLayout) {
+        get!().set_workspace_layout(WorkspaceSource::Explicit(self), layout)
+    }
+
+    /// Returns the automatic tiling layout of the workspace.
+    pub fn layout(self) -> WorkspaceLayout {
+        get!(WorkspaceLayout::Manual).get_workspace_layout(WorkspaceSource::Explicit(self))
+    }
 }
 
 /// Returns the workspace with the given name.
@@ -201,6 +253,27 @@ pub fn on_idle<F: FnMut() + 'static>(f: F) {
     get!().on_idle(f)
 }
 
+/// Sets the callback to be called before the system goes to sleep.
+///
+/// This is a good place to lock the screen. The compositor delays sleep for up to 3
+/// seconds until the screen is locked and the lock surfaces are displayed on all outputs.
+///
+/// This requires logind.
+pub fn on_before_sleep<F: FnMut() + 'static>(f: F) {
+    get!().on_before_sleep(f)
+}
+
+/// Locks the screen with the built-in lock screen.
+///
+/// The built-in lock screen asks for the password of the current user and verifies it
+/// with the `jay` PAM service. It is also used automatically if a lock client exits
+/// without unlocking the screen.
+///
+/// This has no effect if the screen is already locked.
+pub fn lock() {
+    get!().lock()
+}
+
 /// Sets the callback to be called when all devices have been enumerated.
 ///
 /// This callback is only invoked once during the lifetime of the compositor. This is a
@@ -226,6 +299,55 @@ pub fn set_idle(timeout: Option<Duration>) {
     get!().set_idle(timeout.unwrap_or_default())
 }
 
+/// A stage of idle handling.
+///
+/// A stage is entered once there has been no input for `timeout`. All stages that have
+/// been entered are left when the next input event arrives.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct IdleStage {
+    /// The name of the stage. This name is passed to the [`on_idle_stage`] and
+    /// [`on_idle_stage_resumed`] callbacks.
+    pub name: String,
+    /// The time without input after which the stage is entered.
+    pub timeout: Duration,
+    /// Whether the outputs are dimmed while the stage is active.
+    pub dim_outputs: bool,
+    /// Whether the outputs are powered off while the stage is active.
+    pub power_off_outputs: bool,
+    /// Whether the system is suspended via logind when the stage is entered.
+    pub suspend: bool,
+}
+
+/// Configures the idle stages.
+///
+/// This replaces all previously configured stages. The stages are independent of the
+/// timeout configured with [`set_idle`]. Stages are not entered while an idle inhibitor
+/// is active.
+pub fn set_idle_stages(stages: Vec<IdleStage>) {
+    get!().set_idle_stages(stages)
+}
+
+/// Configures whether a visible fullscreen window inhibits idling.
+///
+/// The default is `false`.
+pub fn set_fullscreen_inhibits_idle(enabled: bool) {
+    get!().set_fullscreen_inhibits_idle(enabled)
+}
+
+/// Sets the callback to be called when an idle stage is entered.
+///
+/// The callback receives the name of the stage.
+pub fn on_idle_stage<F: FnMut(&str) + 'static>(f: F) {
+    get!().on_idle_stage(f)
+}
+
+/// Sets the callback to be called when an idle stage is left due to user input.
+///
+/// The callback receives the name of the stage.
+pub fn on_idle_stage_resumed<F: FnMut(&str) + 'static>(f: F) {
+    get!().on_idle_stage_resumed(f)
+}
+
 /// Enables or disables explicit sync.
 ///
 /// Calling this after the compositor has started has no effect.
@@ -1,15 +1,28 @@
 //! Knobs for changing the status text.
 
 use {
-    crate::{exec::Command, io::Async, tasks::spawn},
+    crate::{
+        _private::ipc::{StatusBlock, StatusClick},
+        exec::Command,
+        io::Async,
+        keyboard::mods::{Modifiers, CTRL, LOCK, MOD1, MOD2, MOD3, MOD4, MOD5, SHIFT},
+        tasks::spawn,
+    },
     bstr::ByteSlice,
     error_reporter::Report,
     futures_util::{io::BufReader, AsyncBufReadExt},
-    serde::Deserialize,
-    std::borrow::BorrowMut,
+    serde::{Deserialize, Serialize},
+    std::{
+        borrow::BorrowMut,
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
     uapi::{c, OwnedFd},
 };
 
+#[cfg(test)]
+mod tests;
+
 /// Sets the status text.
 ///
 /// The status text is displayed at the right end of the bar.
@@ -40,6 +53,9 @@ pub enum MessageFormat {
     ///
     /// The separator between individual components can be set using [`set_i3bar_separator`].
     ///
+    /// If the command enables `click_events` in its header, clicks on the components are
+    /// written to its stdin.
+    ///
     /// [i3bar]: https://github.com/i3/i3/blob/next/docs/i3bar-protocol
     I3Bar,
 }
@@ -74,6 +90,27 @@ pub fn set_status_command(format: MessageFormat, mut command: impl BorrowMut<Com
     let (mut read, write) = pipe!();
     let (mut stderr_read, stderr_write) = pipe!();
     let command = command.borrow_mut();
+    let mut i3bar = None;
+    if format == MessageFormat::I3Bar {
+        let (stdin_read, stdin_write) = match uapi::pipe2(c::O_CLOEXEC) {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Could not create a pipe: {}", Report::new(e));
+                return;
+            }
+        };
+        if let Ok(fl) = uapi::fcntl_getfl(stdin_write.raw()) {
+            let _ = uapi::fcntl_setfl(stdin_write.raw(), fl | c::O_NONBLOCK);
+        }
+        command.stdin(stdin_read);
+        i3bar = Some(Rc::new(I3Bar {
+            name: command.prog.clone(),
+            stdin: stdin_write,
+            click_events: Cell::new(false),
+            header_written: Cell::new(false),
+            components: Default::default(),
+        }));
+    }
     command.stdout(write).stderr(stderr_write).spawn();
     let name = command.prog.clone();
     let name2 = command.prog.clone();
@@ -94,9 +131,10 @@ pub fn set_status_command(format: MessageFormat, mut command: impl BorrowMut<Com
             );
         }
     });
+    let i3bar2 = i3bar.clone();
     let handle = spawn(async move {
-        if format == MessageFormat::I3Bar {
-            handle_i3bar(name, read).await;
+        if let Some(i3bar) = i3bar2 {
+            handle_i3bar(name, read, i3bar).await;
             return;
         }
         let mut line = String::new();
@@ -121,6 +159,9 @@ pub fn set_status_command(format: MessageFormat, mut command: impl BorrowMut<Com
         }
     });
     get!().set_status_tasks(vec![handle, stderr_handle]);
+    if let Some(i3bar) = i3bar {
+        get!().on_status_click(move |click| i3bar.send_click(click));
+    }
 }
 
 /// Unsets the previously set status command.
@@ -137,12 +178,106 @@ pub fn set_i3bar_separator(separator: &str) {
     get!().set_i3bar_separator(separator);
 }
 
-async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
+struct I3Bar {
+    name: String,
+    stdin: OwnedFd,
+    click_events: Cell<bool>,
+    header_written: Cell<bool>,
+    components: RefCell<Vec<(Option<String>, Option<String>)>>,
+}
+
+impl I3Bar {
+    fn send_click(&self, click: StatusClick) {
+        #[derive(Serialize)]
+        struct ClickEvent<'a> {
+            #[serde(skip_serializing_if = "Option::is_none")]
+            name: Option<&'a str>,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            instance: Option<&'a str>,
+            button: u32,
+            modifiers: Vec<&'static str>,
+            x: i32,
+            y: i32,
+            relative_x: i32,
+            relative_y: i32,
+            output_x: i32,
+            output_y: i32,
+            width: i32,
+            height: i32,
+        }
+        if !self.click_events.get() {
+            return;
+        }
+        let components = self.components.borrow();
+        let Some((name, instance)) = components.get(click.block) else {
+            return;
+        };
+        let event = ClickEvent {
+            name: name.as_deref(),
+            instance: instance.as_deref(),
+            button: click.button,
+            modifiers: modifier_names(click.mods),
+            x: click.x,
+            y: click.y,
+            relative_x: click.relative_x,
+            relative_y: click.relative_y,
+            output_x: click.output_x,
+            output_y: click.output_y,
+            width: click.width,
+            height: click.height,
+        };
+        let mut msg = String::new();
+        if !self.header_written.get() {
+            msg.push_str("[\n");
+        }
+        match serde_json::to_string(&event) {
+            Ok(s) => msg.push_str(&s),
+            Err(e) => {
+                log::error!("Could not serialize click event: {}", Report::new(e));
+                return;
+            }
+        }
+        msg.push_str(",\n");
+        // Writes of at most PIPE_BUF bytes are atomic. If the command does not read its
+        // stdin, the event is dropped instead of blocking the config.
+        if let Err(e) = uapi::write(self.stdin.raw(), msg.as_bytes()) {
+            log::warn!(
+                "Could not write click event to `{}`: {}",
+                self.name,
+                Report::new(e)
+            );
+            return;
+        }
+        self.header_written.set(true);
+    }
+}
+
+fn modifier_names(mods: Modifiers) -> Vec<&'static str> {
+    let names = [
+        (SHIFT, "Shift"),
+        (LOCK, "Lock"),
+        (CTRL, "Control"),
+        (MOD1, "Mod1"),
+        (MOD2, "Mod2"),
+        (MOD3, "Mod3"),
+        (MOD4, "Mod4"),
+        (MOD5, "Mod5"),
+    ];
+    names
+        .into_iter()
+        .filter(|(m, _)| mods.0 & m.0 != 0)
+        .map(|(_, n)| n)
+        .collect()
+}
+
+async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>, i3bar: Rc<I3Bar>) {
     use std::fmt::Write;
 
     #[derive(Deserialize)]
     struct Version {
         version: i32,
+        #[serde(default)]
+        click_events: bool,
     }
     #[derive(Deserialize)]
     struct Component {
@@ -150,6 +285,8 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
         full_text: String,
         color: Option<String>,
         background: Option<String>,
+        name: Option<String>,
+        instance: Option<String>,
     }
     let mut line = String::new();
     macro_rules! read_line {
@@ -167,8 +304,12 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
     }
     read_line!();
     match serde_json::from_str::<Version>(&line) {
-        Ok(v) if v.version == 1 => {}
-        Ok(v) => log::warn!("Unexpected i3bar format version: {}", v.version),
+        Ok(v) => {
+            if v.version != 1 {
+                log::warn!("Unexpected i3bar format version: {}", v.version);
+            }
+            i3bar.click_events.set(v.click_events);
+        }
         Err(e) => {
             log::warn!(
                 "Could not deserialize i3bar version message: {}",
@@ -201,6 +342,8 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
             _ => r##" <span color="#333333">|</span> "##,
         };
         status.clear();
+        let mut blocks = vec![];
+        let mut names = vec![];
         let mut first = true;
         for component in &components {
             if component.full_text.is_empty() {
@@ -210,6 +353,7 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
                 status.push_str(separator);
             }
             first = false;
+            let start = status.len();
             let have_span = component.color.is_some() || component.background.is_some();
             if have_span {
                 status.push_str("<span");
@@ -229,8 +373,14 @@ async fn handle_i3bar(name: String, mut read: BufReader<Async<OwnedFd>>) {
             if have_span {
                 status.push_str("</span>");
             }
+            blocks.push(StatusBlock {
+                start,
+                end: status.len(),
+            });
+            names.push((component.name.clone(), component.instance.clone()));
         }
-        set_status(&status);
+        *i3bar.components.borrow_mut() = names;
+        get!().set_status_blocks(&status, blocks);
     }
 }
 
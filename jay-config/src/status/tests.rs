@@ -0,0 +1,84 @@
+use {
+    crate::{
+        _private::ipc::StatusClick,
+        keyboard::mods::{Modifiers, MOD4, SHIFT},
+        status::I3Bar,
+    },
+    std::cell::{Cell, RefCell},
+    uapi::{c, OwnedFd},
+};
+
+fn i3bar(click_events: bool) -> (I3Bar, OwnedFd) {
+    let (read, write) = uapi::pipe2(c::O_CLOEXEC | c::O_NONBLOCK).unwrap();
+    let i3bar = I3Bar {
+        name: "test".to_string(),
+        stdin: write,
+        click_events: Cell::new(click_events),
+        header_written: Cell::new(false),
+        components: RefCell::new(vec![
+            (Some("a".to_string()), None),
+            (Some("b".to_string()), Some("x".to_string())),
+        ]),
+    };
+    (i3bar, read)
+}
+
+fn click(block: usize, mods: Modifiers) -> StatusClick {
+    StatusClick {
+        block,
+        button: 3,
+        mods,
+        x: 110,
+        y: 2,
+        relative_x: 10,
+        relative_y: 2,
+        output_x: 100,
+        output_y: 2,
+        width: 20,
+        height: 16,
+    }
+}
+
+fn written(fd: &OwnedFd) -> String {
+    let mut buf = [0u8; 1024];
+    match uapi::read(fd.raw(), &mut buf[..]) {
+        Ok(b) => String::from_utf8(b.to_vec()).unwrap(),
+        Err(e) if e.0 == c::EAGAIN => String::new(),
+        Err(e) => panic!("{e}"),
+    }
+}
+
+#[test]
+fn click_events() {
+    let (i3bar, read) = i3bar(true);
+    i3bar.send_click(click(1, SHIFT | MOD4));
+    assert_eq!(
+        written(&read),
+        "[\n\
+         {\"name\":\"b\",\"instance\":\"x\",\"button\":3,\"modifiers\":[\"Shift\",\"Mod4\"],\
+         \"x\":110,\"y\":2,\"relative_x\":10,\"relative_y\":2,\"output_x\":100,\"output_y\":2,\
+         \"width\":20,\"height\":16},\n",
+    );
+    i3bar.send_click(click(0, Modifiers(0)));
+    assert_eq!(
+        written(&read),
+        "{\"name\":\"a\",\"button\":3,\"modifiers\":[],\
+         \"x\":110,\"y\":2,\"relative_x\":10,\"relative_y\":2,\"output_x\":100,\"output_y\":2,\
+         \"width\":20,\"height\":16},\n",
+    );
+}
+
+#[test]
+fn unknown_block() {
+    let (i3bar, read) = i3bar(true);
+    i3bar.send_click(click(2, Modifiers(0)));
+    assert_eq!(written(&read), "");
+    assert!(!i3bar.header_written.get());
+}
+
+#[test]
+fn click_events_disabled() {
+    let (i3bar, read) = i3bar(false);
+    i3bar.send_click(click(0, Modifiers(0)));
+    assert_eq!(written(&read), "");
+}
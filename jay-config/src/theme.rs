@@ -156,6 +156,88 @@ pub fn reset_font() {
     get!().reset_font()
 }
 
+/// The color scheme that applications are asked to use.
+///
+/// This preference is exposed to applications via the `org.freedesktop.appearance`
+/// namespace of the settings portal.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum ColorScheme {
+    /// Derive the color scheme from the luminance of [`colors::BACKGROUND_COLOR`].
+    Automatic,
+    /// Applications should use their default color scheme.
+    NoPreference,
+    /// Applications should prefer a dark color scheme.
+    Dark,
+    /// Applications should prefer a light color scheme.
+    Light,
+}
+
+/// Sets the color scheme that applications are asked to use.
+///
+/// The accent color advertised to applications is always
+/// [`colors::FOCUSED_TITLE_BACKGROUND_COLOR`].
+///
+/// Default: `Automatic`.
+pub fn set_color_scheme(scheme: ColorScheme) {
+    get!().set_color_scheme(scheme)
+}
+
+/// Sets whether applications are asked to use a high-contrast theme.
+///
+/// Default: `false`.
+pub fn set_high_contrast(enabled: bool) {
+    get!().set_high_contrast(enabled)
+}
+
+/// Sets whether gaps are disabled on workspaces that contain a single tiled window.
+///
+/// Default: `false`.
+pub fn set_smart_gaps(enabled: bool) {
+    get!().set_smart_gaps(enabled)
+}
+
+/// Sets the app IDs of the windows whose background is blurred.
+///
+/// The area behind such a window is blurred before the window is drawn. This is only
+/// visible if the window is translucent. The strength of the blur is controlled by
+/// [`sized::BLUR_PASSES`] and [`sized::BLUR_OFFSET`].
+///
+/// Default: none.
+pub fn set_blurred_app_ids<S: AsRef<str>>(app_ids: impl IntoIterator<Item = S>) {
+    let app_ids = app_ids
+        .into_iter()
+        .map(|s| s.as_ref().to_string())
+        .collect();
+    get!().set_blurred_app_ids(app_ids)
+}
+
+/// Sets the namespaces of the layer-shell surfaces whose background is blurred.
+///
+/// Bars and launchers usually use a fixed namespace such as `waybar` or `launcher`.
+///
+/// Default: none.
+pub fn set_blurred_layer_namespaces<S: AsRef<str>>(namespaces: impl IntoIterator<Item = S>) {
+    let namespaces = namespaces
+        .into_iter()
+        .map(|s| s.as_ref().to_string())
+        .collect();
+    get!().set_blurred_layer_namespaces(namespaces)
+}
+
+/// Sets the opacity of windows by app ID.
+///
+/// Each entry maps an app ID to an opacity between `0.0` and `1.0`. An opacity set with
+/// [`Seat::set_window_opacity`](crate::input::Seat::set_window_opacity) takes precedence.
+///
+/// Default: none.
+pub fn set_window_opacities<S: AsRef<str>>(opacities: impl IntoIterator<Item = (S, f64)>) {
+    let opacities = opacities
+        .into_iter()
+        .map(|(s, o)| (s.as_ref().to_string(), o))
+        .collect();
+    get!().set_window_opacities(opacities)
+}
+
 /// Elements of the compositor whose color can be changed.
 pub mod colors {
     use {
@@ -261,6 +343,10 @@ pub mod colors {
         ///
         /// Default: `#9d28c67f`.
         const 15 => HIGHLIGHT_COLOR,
+        /// The color of the drop shadows of floating windows.
+        ///
+        /// Default: `#0000007f`.
+        const 16 => SHADOW_COLOR,
     }
 
     /// Sets the color of GUI element.
@@ -312,5 +398,35 @@ pub mod sized {
         ///
         /// Default: 4
         const 02 => BORDER_WIDTH,
+        /// The size of the gaps between tiled windows.
+        ///
+        /// Default: 0
+        const 03 => INNER_GAP,
+        /// The size of the gaps between tiled windows and the edges of the workspace.
+        ///
+        /// Default: 0
+        const 04 => OUTER_GAP,
+        /// The radius of the rounded corners of windows.
+        ///
+        /// Setting this to 0 disables rounded corners.
+        ///
+        /// Default: 0
+        const 05 => CORNER_RADIUS,
+        /// The size of the drop shadows of floating windows.
+        ///
+        /// Setting this to 0 disables drop shadows.
+        ///
+        /// Default: 0
+        const 06 => SHADOW_SIZE,
+        /// The number of passes of the background blur.
+        ///
+        /// Every pass doubles the blur radius. Setting this to 0 disables the blur.
+        ///
+        /// Default: 3
+        const 07 => BLUR_PASSES,
+        /// The sampling distance of each pass of the background blur.
+        ///
+        /// Default: 3
+        const 08 => BLUR_OFFSET,
     }
 }
@@ -488,6 +488,10 @@ impl DrmDevice {
 pub enum GfxApi {
     OpenGl,
     Vulkan,
+    /// A software renderer that renders on the CPU.
+    ///
+    /// This is slow and should only be used if no other API is available.
+    Software,
 }
 
 /// Sets the default graphics API.
@@ -0,0 +1,228 @@
+use {
+    crate::{
+        gfx_api::{GfxTexture, SampleRect},
+        rect::Rect,
+        time::now_usec,
+        tree::WorkspaceNode,
+    },
+    jay_config::animation::{
+        Animation as ConfigAnimation, AnimationCurve, WorkspaceAnimationStyle, LAYOUT,
+        WINDOW_CLOSE, WINDOW_OPEN, WORKSPACE_SWITCH,
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+/// The size of a window relative to its final size when it starts to appear.
+pub const WINDOW_ZOOM: f64 = 0.9;
+
+pub struct Animations {
+    pub enabled: Cell<bool>,
+    pub workspace_style: Cell<WorkspaceAnimationStyle>,
+    pub workspace_switch: AnimationConfig,
+    pub window_open: AnimationConfig,
+    pub window_close: AnimationConfig,
+    pub layout: AnimationConfig,
+}
+
+impl Default for Animations {
+    fn default() -> Self {
+        Self {
+            enabled: Cell::new(false),
+            workspace_style: Cell::new(WorkspaceAnimationStyle::Slide),
+            workspace_switch: AnimationConfig::new(200, Curve::EASE_OUT),
+            window_open: AnimationConfig::new(150, Curve::EASE_OUT),
+            window_close: AnimationConfig::new(150, Curve::EASE_IN),
+            layout: AnimationConfig::new(150, Curve::EASE_OUT),
+        }
+    }
+}
+
+impl Animations {
+    pub fn reset(&self) {
+        let default = Self::default();
+        self.enabled.set(default.enabled.get());
+        self.workspace_style.set(default.workspace_style.get());
+        for (config, default) in [
+            (&self.workspace_switch, &default.workspace_switch),
+            (&self.window_open, &default.window_open),
+            (&self.window_close, &default.window_close),
+            (&self.layout, &default.layout),
+        ] {
+            config.duration_usec.set(default.duration_usec.get());
+            config.curve.set(default.curve.get());
+        }
+    }
+
+    pub fn config(&self, animation: ConfigAnimation) -> Option<&AnimationConfig> {
+        let config = match animation {
+            WORKSPACE_SWITCH => &self.workspace_switch,
+            WINDOW_OPEN => &self.window_open,
+            WINDOW_CLOSE => &self.window_close,
+            LAYOUT => &self.layout,
+            _ => return None,
+        };
+        Some(config)
+    }
+
+    /// Starts an animation at the current time.
+    ///
+    /// Returns `None` if animations are disabled.
+    pub fn start(&self, config: &AnimationConfig) -> Option<Animation> {
+        let duration_usec = config.duration_usec.get();
+        if !self.enabled.get() || duration_usec == 0 {
+            return None;
+        }
+        Some(Animation {
+            start_usec: now_usec(),
+            duration_usec,
+            curve: config.curve.get(),
+        })
+    }
+}
+
+pub struct AnimationConfig {
+    pub duration_usec: Cell<u64>,
+    pub curve: Cell<Curve>,
+}
+
+impl AnimationConfig {
+    fn new(duration_ms: u64, curve: Curve) -> Self {
+        Self {
+            duration_usec: Cell::new(duration_ms * 1000),
+            curve: Cell::new(curve),
+        }
+    }
+}
+
+/// A cubic bézier curve from `(0, 0)` to `(1, 1)`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Curve {
+    x1: f64,
+    y1: f64,
+    x2: f64,
+    y2: f64,
+}
+
+impl Curve {
+    pub const LINEAR: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+    pub const EASE_IN: Self = Self::new(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: Self = Self::new(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: Self = Self::new(0.42, 0.0, 0.58, 1.0);
+
+    pub const fn new(x1: f64, y1: f64, x2: f64, y2: f64) -> Self {
+        Self { x1, y1, x2, y2 }
+    }
+
+    /// Returns the y value of the curve at the x value `t`.
+    pub fn apply(&self, t: f64) -> f64 {
+        if t <= 0.0 {
+            return 0.0;
+        }
+        if t >= 1.0 {
+            return 1.0;
+        }
+        let x1 = self.x1.clamp(0.0, 1.0);
+        let x2 = self.x2.clamp(0.0, 1.0);
+        let mut s = t;
+        for _ in 0..8 {
+            let dx = bezier(x1, x2, s) - t;
+            if dx.abs() < 1e-7 {
+                return bezier(self.y1, self.y2, s);
+            }
+            let slope = bezier_slope(x1, x2, s);
+            if slope.abs() < 1e-7 {
+                break;
+            }
+            s -= dx / slope;
+            if !(0.0..=1.0).contains(&s) {
+                break;
+            }
+        }
+        let mut lo = 0.0;
+        let mut hi = 1.0;
+        s = t;
+        while hi - lo > 1e-7 {
+            if bezier(x1, x2, s) < t {
+                lo = s;
+            } else {
+                hi = s;
+            }
+            s = (lo + hi) / 2.0;
+        }
+        bezier(self.y1, self.y2, s)
+    }
+}
+
+impl From<AnimationCurve> for Curve {
+    fn from(value: AnimationCurve) -> Self {
+        match value {
+            AnimationCurve::Linear => Self::LINEAR,
+            AnimationCurve::EaseIn => Self::EASE_IN,
+            AnimationCurve::EaseOut => Self::EASE_OUT,
+            AnimationCurve::EaseInOut => Self::EASE_IN_OUT,
+            AnimationCurve::CubicBezier { x1, y1, x2, y2 } => Self::new(x1, y1, x2, y2),
+        }
+    }
+}
+
+fn bezier(a1: f64, a2: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * a1 + 3.0 * u * s * s * a2 + s * s * s
+}
+
+fn bezier_slope(a1: f64, a2: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+    3.0 * u * u * a1 + 6.0 * u * s * (a2 - a1) + 3.0 * s * s * (1.0 - a2)
+}
+
+/// A running animation.
+#[derive(Copy, Clone, Debug)]
+pub struct Animation {
+    start_usec: u64,
+    duration_usec: u64,
+    curve: Curve,
+}
+
+impl Animation {
+    /// Returns the progress of the animation at the given time.
+    ///
+    /// Returns `None` if the animation has finished.
+    pub fn progress(&self, time_usec: u64) -> Option<f64> {
+        let elapsed = time_usec.saturating_sub(self.start_usec);
+        if elapsed >= self.duration_usec {
+            return None;
+        }
+        Some(self.curve.apply(elapsed as f64 / self.duration_usec as f64))
+    }
+}
+
+pub fn lerp(from: i32, to: i32, t: f64) -> i32 {
+    from + ((to - from) as f64 * t).round() as i32
+}
+
+pub fn lerp_rect(from: Rect, to: Rect, t: f64) -> Rect {
+    Rect::new(
+        lerp(from.x1(), to.x1(), t),
+        lerp(from.y1(), to.y1(), t),
+        lerp(from.x2(), to.x2(), t),
+        lerp(from.y2(), to.y2(), t),
+    )
+    .unwrap_or(to)
+}
+
+/// The transition from the previously visible workspace of an output.
+pub struct WorkspaceTransition {
+    pub old: Rc<WorkspaceNode>,
+    pub animation: Animation,
+    /// Whether the new workspace comes after the old workspace in the workspace list.
+    pub forward: bool,
+}
+
+/// The last contents of a window that is being closed.
+pub struct ClosingWindow {
+    pub texture: Rc<dyn GfxTexture>,
+    pub source: SampleRect,
+    /// The absolute position of the main surface.
+    pub rect: Rect,
+    pub animation: Animation,
+}
@@ -45,6 +45,8 @@ pub trait Backend {
         let _ = idle;
     }
 
+    fn suspend(&self) {}
+
     fn import_environment(&self) -> bool {
         false
     }
@@ -132,6 +134,7 @@ pub trait HardwareCursor: Debug {
 }
 
 pub type TransformMatrix = [[f64; 2]; 2];
+pub type CalibrationMatrix = [[f32; 3]; 2];
 
 linear_ids!(InputDeviceGroupIds, InputDeviceGroupId, usize);
 
@@ -150,6 +153,7 @@ pub trait InputDevice {
         None
     }
     fn set_accel_profile(&self, profile: InputDeviceAccelProfile);
+    fn set_custom_accel_function(&self, ty: InputDeviceAccelType, step: f64, points: &[f64]);
     fn accel_speed(&self) -> Option<f64> {
         None
     }
@@ -178,6 +182,42 @@ pub trait InputDevice {
         None
     }
     fn set_natural_scrolling_enabled(&self, enabled: bool);
+    fn click_method(&self) -> Option<InputDeviceClickMethod> {
+        None
+    }
+    fn set_click_method(&self, method: InputDeviceClickMethod);
+    fn middle_button_emulation_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_middle_button_emulation_enabled(&self, enabled: bool);
+    fn scroll_method(&self) -> Option<InputDeviceScrollMethod> {
+        None
+    }
+    fn set_scroll_method(&self, method: InputDeviceScrollMethod);
+    fn scroll_button(&self) -> Option<u32> {
+        None
+    }
+    fn set_scroll_button(&self, button: u32);
+    fn disable_while_typing_enabled(&self) -> Option<bool> {
+        None
+    }
+    fn set_disable_while_typing_enabled(&self, enabled: bool);
+    fn tap_button_map(&self) -> Option<InputDeviceTapButtonMap> {
+        None
+    }
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap);
+    fn send_events_mode(&self) -> Option<InputDeviceSendEventsMode> {
+        None
+    }
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode);
+    fn rotation(&self) -> Option<u32> {
+        None
+    }
+    fn set_rotation(&self, degrees: u32);
+    fn calibration_matrix(&self) -> Option<CalibrationMatrix> {
+        None
+    }
+    fn set_calibration_matrix(&self, matrix: CalibrationMatrix);
     fn tablet_info(&self) -> Option<Box<TabletInit>> {
         None
     }
@@ -216,6 +256,42 @@ impl InputDeviceCapability {
 pub enum InputDeviceAccelProfile {
     Flat,
     Adaptive,
+    Custom,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum InputDeviceAccelType {
+    Fallback,
+    Motion,
+    Scroll,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum InputDeviceClickMethod {
+    None,
+    ButtonAreas,
+    Clickfinger,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum InputDeviceScrollMethod {
+    NoScroll,
+    TwoFinger,
+    Edge,
+    OnButtonDown,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum InputDeviceTapButtonMap {
+    LeftRightMiddle,
+    LeftMiddleRight,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum InputDeviceSendEventsMode {
+    Enabled,
+    Disabled,
+    DisabledOnExternalMouse,
 }
 
 pub enum BackendEvent {
@@ -383,6 +459,27 @@ pub enum InputEvent {
         source: Option<TabletStripEventSource>,
         position: Option<f64>,
     },
+    TouchDown {
+        time_usec: u64,
+        id: i32,
+        connector: ConnectorId,
+        x: Fixed,
+        y: Fixed,
+    },
+    TouchMotion {
+        time_usec: u64,
+        id: i32,
+        connector: ConnectorId,
+        x: Fixed,
+        y: Fixed,
+    },
+    TouchUp {
+        time_usec: u64,
+        id: i32,
+    },
+    TouchFrame {
+        time_usec: u64,
+    },
 }
 
 pub enum DrmEvent {
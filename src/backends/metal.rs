@@ -6,13 +6,15 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            Backend, InputDevice, InputDeviceAccelProfile, InputDeviceCapability,
-            InputDeviceGroupId, InputDeviceId, InputEvent, KeyState, TransformMatrix,
+            Backend, CalibrationMatrix, InputDevice, InputDeviceAccelProfile, InputDeviceAccelType,
+            InputDeviceCapability, InputDeviceClickMethod, InputDeviceGroupId, InputDeviceId,
+            InputDeviceScrollMethod, InputDeviceSendEventsMode, InputDeviceTapButtonMap,
+            InputEvent, KeyState, TransformMatrix,
         },
         backends::metal::video::{
             MetalDrmDeviceData, MetalLeaseData, MetalRenderContext, PendingDrmDevice,
         },
-        dbus::{DbusError, SignalHandler},
+        dbus::{DbusError, SignalHandler, FALSE},
         drm_feedback::DrmFeedback,
         gfx_api::GfxError,
         ifs::wl_seat::tablet::{
@@ -20,11 +22,21 @@ use {
         },
         libinput::{
             consts::{
-                AccelProfile, LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE,
-                LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT, LIBINPUT_DEVICE_CAP_TABLET_PAD,
-                LIBINPUT_DEVICE_CAP_TABLET_TOOL,
+                AccelProfile, ConfigAccelType, ConfigClickMethod, ConfigScrollMethod,
+                ConfigSendEventsMode, ConfigTapButtonMap, LIBINPUT_ACCEL_TYPE_FALLBACK,
+                LIBINPUT_ACCEL_TYPE_MOTION, LIBINPUT_ACCEL_TYPE_SCROLL,
+                LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE, LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM,
+                LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT, LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS,
+                LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER, LIBINPUT_CONFIG_CLICK_METHOD_NONE,
+                LIBINPUT_CONFIG_SCROLL_2FG, LIBINPUT_CONFIG_SCROLL_EDGE,
+                LIBINPUT_CONFIG_SCROLL_NO_SCROLL, LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN,
+                LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+                LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE,
+                LIBINPUT_CONFIG_SEND_EVENTS_ENABLED, LIBINPUT_CONFIG_STATUS_SUCCESS,
+                LIBINPUT_CONFIG_TAP_MAP_LMR, LIBINPUT_CONFIG_TAP_MAP_LRM,
+                LIBINPUT_DEVICE_CAP_TABLET_PAD, LIBINPUT_DEVICE_CAP_TABLET_TOOL,
             },
-            device::{LibInputDevice, RegisteredDevice},
+            device::{LibInputAccelConfig, LibInputDevice, RegisteredDevice},
             LibInput, LibInputAdapter, LibInputError,
         },
         logind::{LogindError, Session},
@@ -44,6 +56,7 @@ use {
             drm::{DrmError, DRM_MODE_ATOMIC_ALLOW_MODESET},
             gbm::GbmError,
         },
+        wire_dbus::org::freedesktop::login1::manager::PrepareForSleep,
     },
     bstr::ByteSlice,
     std::{
@@ -142,6 +155,9 @@ pub struct MetalBackend {
     session: Session,
     pause_handler: Cell<Option<SignalHandler>>,
     resume_handler: Cell<Option<SignalHandler>>,
+    sleep_handler: Cell<Option<SignalHandler>>,
+    sleep_inhibitor: CloneCell<Option<Rc<OwnedFd>>>,
+    sleep_future: Cell<Option<SpawnedFuture<()>>>,
     ctx: CloneCell<Option<Rc<MetalRenderContext>>>,
     default_feedback: CloneCell<Option<Rc<DrmFeedback>>>,
 }
@@ -161,6 +177,38 @@ impl MetalBackend {
         }
         pending().await
     }
+
+    fn take_sleep_inhibitor(self: &Rc<Self>) {
+        let slf = self.clone();
+        self.session.inhibit(
+            "sleep",
+            "Lock the screen before sleeping",
+            "delay",
+            move |res| match res {
+                Ok(res) => {
+                    slf.sleep_inhibitor.set(Some(res.fd.clone()));
+                }
+                Err(e) => log::warn!("Could not take the sleep inhibitor: {}", ErrorFmt(e)),
+            },
+        );
+    }
+
+    fn handle_prepare_for_sleep(self: &Rc<Self>, p: PrepareForSleep) {
+        if p.start == FALSE {
+            self.sleep_future.take();
+            self.take_sleep_inhibitor();
+            return;
+        }
+        let Some(inhibitor) = self.sleep_inhibitor.take() else {
+            return;
+        };
+        let state = self.state.clone();
+        let future = self.state.eng.spawn(async move {
+            state.prepare_for_sleep().await;
+            drop(inhibitor);
+        });
+        self.sleep_future.set(Some(future));
+    }
 }
 
 impl Backend for MetalBackend {
@@ -175,6 +223,9 @@ impl Backend for MetalBackend {
     fn clear(&self) {
         self.pause_handler.take();
         self.resume_handler.take();
+        self.sleep_handler.take();
+        self.sleep_inhibitor.take();
+        self.sleep_future.take();
         self.ctx.take();
         self.device_holder.devices.clear();
         for dev in self.device_holder.input_devices.take() {
@@ -231,6 +282,10 @@ impl Backend for MetalBackend {
         })
     }
 
+    fn suspend(&self) {
+        self.session.suspend();
+    }
+
     fn set_idle(&self, idle: bool) {
         let devices = self.device_holder.drm_devices.lock();
         for device in devices.values() {
@@ -315,6 +370,9 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
         session,
         pause_handler: Default::default(),
         resume_handler: Default::default(),
+        sleep_handler: Default::default(),
+        sleep_inhibitor: Default::default(),
+        sleep_future: Default::default(),
         ctx: Default::default(),
         default_feedback: Default::default(),
     });
@@ -336,6 +394,24 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
             Err(e) => return Err(MetalError::DeviceResumeSignalHandler(e)),
         }
     }));
+    {
+        let mtl = metal.clone();
+        let sh = metal
+            .session
+            .on_prepare_for_sleep(move |p| mtl.handle_prepare_for_sleep(p));
+        match sh {
+            Ok(sh) => {
+                metal.sleep_handler.set(Some(sh));
+                metal.take_sleep_inhibitor();
+            }
+            Err(e) => {
+                log::warn!(
+                    "Could not install the prepare-for-sleep signal handler: {}",
+                    ErrorFmt(e)
+                );
+            }
+        }
+    }
     Ok(metal)
 }
 
@@ -365,6 +441,7 @@ struct MetalInputDevice {
     // config
     desired: InputDeviceProperties,
     effective: InputDeviceProperties,
+    custom_accel_functions: RefCell<Vec<(ConfigAccelType, f64, Vec<f64>)>>,
 }
 
 #[derive(Default)]
@@ -376,6 +453,15 @@ struct InputDeviceProperties {
     drag_enabled: Cell<Option<bool>>,
     drag_lock_enabled: Cell<Option<bool>>,
     natural_scrolling_enabled: Cell<Option<bool>>,
+    click_method: Cell<Option<ConfigClickMethod>>,
+    middle_button_emulation_enabled: Cell<Option<bool>>,
+    scroll_method: Cell<Option<ConfigScrollMethod>>,
+    scroll_button: Cell<Option<u32>>,
+    disable_while_typing_enabled: Cell<Option<bool>>,
+    tap_button_map: Cell<Option<ConfigTapButtonMap>>,
+    send_events_mode: Cell<Option<ConfigSendEventsMode>>,
+    rotation: Cell<Option<u32>>,
+    calibration_matrix: Cell<Option<[f32; 6]>>,
 }
 
 #[derive(Clone)]
@@ -436,6 +522,33 @@ impl MetalInputDevice {
         if let Some(enabled) = self.desired.natural_scrolling_enabled.get() {
             self.set_natural_scrolling_enabled(enabled);
         }
+        if let Some(method) = self.desired.click_method.get() {
+            self.set_click_method_(method);
+        }
+        if let Some(enabled) = self.desired.middle_button_emulation_enabled.get() {
+            self.set_middle_button_emulation_enabled(enabled);
+        }
+        if let Some(method) = self.desired.scroll_method.get() {
+            self.set_scroll_method_(method);
+        }
+        if let Some(button) = self.desired.scroll_button.get() {
+            self.set_scroll_button(button);
+        }
+        if let Some(enabled) = self.desired.disable_while_typing_enabled.get() {
+            self.set_disable_while_typing_enabled(enabled);
+        }
+        if let Some(map) = self.desired.tap_button_map.get() {
+            self.set_tap_button_map_(map);
+        }
+        if let Some(mode) = self.desired.send_events_mode.get() {
+            self.set_send_events_mode_(mode);
+        }
+        if let Some(degrees) = self.desired.rotation.get() {
+            self.set_rotation(degrees);
+        }
+        if let Some(matrix) = self.desired.calibration_matrix.get() {
+            self.set_calibration_matrix_(matrix);
+        }
         self.fetch_effective();
     }
 
@@ -465,6 +578,46 @@ impl MetalInputDevice {
                 .natural_scrolling_enabled
                 .set(Some(device.natural_scrolling_enabled()));
         }
+        if device.click_methods() != 0 {
+            self.effective.click_method.set(Some(device.click_method()));
+        }
+        if device.middle_emulation_available() {
+            self.effective
+                .middle_button_emulation_enabled
+                .set(Some(device.middle_emulation_enabled()));
+        }
+        let scroll_methods = device.scroll_methods();
+        if scroll_methods != 0 {
+            self.effective
+                .scroll_method
+                .set(Some(device.scroll_method()));
+        }
+        if scroll_methods & LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN.raw() as u32 != 0 {
+            self.effective
+                .scroll_button
+                .set(Some(device.scroll_button()));
+        }
+        if device.dwt_available() {
+            self.effective
+                .disable_while_typing_enabled
+                .set(Some(device.dwt_enabled()));
+        }
+        if device.tap_available() {
+            self.effective
+                .tap_button_map
+                .set(Some(device.tap_button_map()));
+        }
+        self.effective
+            .send_events_mode
+            .set(Some(device.send_events_mode()));
+        if device.rotation_available() {
+            self.effective.rotation.set(Some(device.rotation()));
+        }
+        if device.has_calibration_matrix() {
+            self.effective
+                .calibration_matrix
+                .set(Some(device.calibration_matrix()));
+        }
     }
 
     fn pre_pause(&self) {
@@ -485,17 +638,98 @@ impl MetalInputDevice {
         }
     }
 
+    fn set_click_method_(&self, method: ConfigClickMethod) {
+        self.desired.click_method.set(Some(method));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().click_methods() != 0 {
+                dev.device().set_click_method(method);
+                self.effective
+                    .click_method
+                    .set(Some(dev.device().click_method()));
+            }
+        }
+    }
+
+    fn set_scroll_method_(&self, method: ConfigScrollMethod) {
+        self.desired.scroll_method.set(Some(method));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().scroll_methods() != 0 {
+                dev.device().set_scroll_method(method);
+                self.effective
+                    .scroll_method
+                    .set(Some(dev.device().scroll_method()));
+            }
+        }
+    }
+
+    fn set_tap_button_map_(&self, map: ConfigTapButtonMap) {
+        self.desired.tap_button_map.set(Some(map));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().tap_available() {
+                dev.device().set_tap_button_map(map);
+                self.effective
+                    .tap_button_map
+                    .set(Some(dev.device().tap_button_map()));
+            }
+        }
+    }
+
+    fn set_send_events_mode_(&self, mode: ConfigSendEventsMode) {
+        self.desired.send_events_mode.set(Some(mode));
+        if let Some(dev) = self.inputdev.get() {
+            dev.device().set_send_events_mode(mode);
+            self.effective
+                .send_events_mode
+                .set(Some(dev.device().send_events_mode()));
+        }
+    }
+
+    fn set_calibration_matrix_(&self, matrix: [f32; 6]) {
+        self.desired.calibration_matrix.set(Some(matrix));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().has_calibration_matrix() {
+                dev.device().set_calibration_matrix(matrix);
+                self.effective
+                    .calibration_matrix
+                    .set(Some(dev.device().calibration_matrix()));
+            }
+        }
+    }
+
     fn set_accel_profile_(&self, profile: AccelProfile) {
+        if profile == LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM && !LibInputAccelConfig::available() {
+            log::warn!("The custom acceleration profile is unavailable. It requires libinput 1.23");
+            return;
+        }
         self.desired.accel_profile.set(Some(profile));
         if let Some(dev) = self.inputdev.get() {
             if dev.device().accel_available() {
-                dev.device().set_accel_profile(profile);
+                if profile == LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM {
+                    self.apply_custom_accel(&dev.device());
+                } else {
+                    dev.device().set_accel_profile(profile);
+                }
                 self.effective
                     .accel_profile
                     .set(Some(dev.device().accel_profile()));
             }
         }
     }
+
+    fn apply_custom_accel(&self, dev: &LibInputDevice) {
+        let Some(config) = LibInputAccelConfig::new(LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM) else {
+            log::warn!("Could not create a custom acceleration configuration");
+            return;
+        };
+        for (ty, step, points) in &*self.custom_accel_functions.borrow() {
+            if config.set_points(*ty, *step, points) != LIBINPUT_CONFIG_STATUS_SUCCESS {
+                log::warn!("Ignoring invalid custom acceleration function for {:?}", ty);
+            }
+        }
+        if dev.apply_accel_config(&config) != LIBINPUT_CONFIG_STATUS_SUCCESS {
+            log::warn!("Could not apply the custom acceleration profile");
+        }
+    }
 }
 
 impl InputDevice for MetalInputDevice {
@@ -543,10 +777,27 @@ impl InputDevice for MetalInputDevice {
         let profile = match profile {
             InputDeviceAccelProfile::Flat => LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT,
             InputDeviceAccelProfile::Adaptive => LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE,
+            InputDeviceAccelProfile::Custom => LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM,
         };
         self.set_accel_profile_(profile);
     }
 
+    fn set_custom_accel_function(&self, ty: InputDeviceAccelType, step: f64, points: &[f64]) {
+        let ty = match ty {
+            InputDeviceAccelType::Fallback => LIBINPUT_ACCEL_TYPE_FALLBACK,
+            InputDeviceAccelType::Motion => LIBINPUT_ACCEL_TYPE_MOTION,
+            InputDeviceAccelType::Scroll => LIBINPUT_ACCEL_TYPE_SCROLL,
+        };
+        {
+            let functions = &mut *self.custom_accel_functions.borrow_mut();
+            functions.retain(|f| f.0 != ty);
+            functions.push((ty, step, points.to_vec()));
+        }
+        if self.desired.accel_profile.get() == Some(LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM) {
+            self.set_accel_profile_(LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM);
+        }
+    }
+
     fn set_accel_speed(&self, speed: f64) {
         self.desired.accel_speed.set(Some(speed));
         if let Some(dev) = self.inputdev.get() {
@@ -628,6 +879,7 @@ impl InputDevice for MetalInputDevice {
         let p = match p {
             LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT => InputDeviceAccelProfile::Flat,
             LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE => InputDeviceAccelProfile::Adaptive,
+            LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM => InputDeviceAccelProfile::Custom,
             _ => return None,
         };
         Some(p)
@@ -657,6 +909,161 @@ impl InputDevice for MetalInputDevice {
         self.effective.natural_scrolling_enabled.get()
     }
 
+    fn click_method(&self) -> Option<InputDeviceClickMethod> {
+        let m = match self.effective.click_method.get()? {
+            LIBINPUT_CONFIG_CLICK_METHOD_NONE => InputDeviceClickMethod::None,
+            LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS => InputDeviceClickMethod::ButtonAreas,
+            LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER => InputDeviceClickMethod::Clickfinger,
+            _ => return None,
+        };
+        Some(m)
+    }
+
+    fn set_click_method(&self, method: InputDeviceClickMethod) {
+        let method = match method {
+            InputDeviceClickMethod::None => LIBINPUT_CONFIG_CLICK_METHOD_NONE,
+            InputDeviceClickMethod::ButtonAreas => LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS,
+            InputDeviceClickMethod::Clickfinger => LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER,
+        };
+        self.set_click_method_(method);
+    }
+
+    fn middle_button_emulation_enabled(&self) -> Option<bool> {
+        self.effective.middle_button_emulation_enabled.get()
+    }
+
+    fn set_middle_button_emulation_enabled(&self, enabled: bool) {
+        self.desired
+            .middle_button_emulation_enabled
+            .set(Some(enabled));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().middle_emulation_available() {
+                dev.device().set_middle_emulation_enabled(enabled);
+                self.effective
+                    .middle_button_emulation_enabled
+                    .set(Some(dev.device().middle_emulation_enabled()));
+            }
+        }
+    }
+
+    fn scroll_method(&self) -> Option<InputDeviceScrollMethod> {
+        let m = match self.effective.scroll_method.get()? {
+            LIBINPUT_CONFIG_SCROLL_NO_SCROLL => InputDeviceScrollMethod::NoScroll,
+            LIBINPUT_CONFIG_SCROLL_2FG => InputDeviceScrollMethod::TwoFinger,
+            LIBINPUT_CONFIG_SCROLL_EDGE => InputDeviceScrollMethod::Edge,
+            LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN => InputDeviceScrollMethod::OnButtonDown,
+            _ => return None,
+        };
+        Some(m)
+    }
+
+    fn set_scroll_method(&self, method: InputDeviceScrollMethod) {
+        let method = match method {
+            InputDeviceScrollMethod::NoScroll => LIBINPUT_CONFIG_SCROLL_NO_SCROLL,
+            InputDeviceScrollMethod::TwoFinger => LIBINPUT_CONFIG_SCROLL_2FG,
+            InputDeviceScrollMethod::Edge => LIBINPUT_CONFIG_SCROLL_EDGE,
+            InputDeviceScrollMethod::OnButtonDown => LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN,
+        };
+        self.set_scroll_method_(method);
+    }
+
+    fn scroll_button(&self) -> Option<u32> {
+        self.effective.scroll_button.get()
+    }
+
+    fn set_scroll_button(&self, button: u32) {
+        self.desired.scroll_button.set(Some(button));
+        if let Some(dev) = self.inputdev.get() {
+            let methods = dev.device().scroll_methods();
+            if methods & LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN.raw() as u32 != 0 {
+                dev.device().set_scroll_button(button);
+                self.effective
+                    .scroll_button
+                    .set(Some(dev.device().scroll_button()));
+            }
+        }
+    }
+
+    fn disable_while_typing_enabled(&self) -> Option<bool> {
+        self.effective.disable_while_typing_enabled.get()
+    }
+
+    fn set_disable_while_typing_enabled(&self, enabled: bool) {
+        self.desired.disable_while_typing_enabled.set(Some(enabled));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().dwt_available() {
+                dev.device().set_dwt_enabled(enabled);
+                self.effective
+                    .disable_while_typing_enabled
+                    .set(Some(dev.device().dwt_enabled()));
+            }
+        }
+    }
+
+    fn tap_button_map(&self) -> Option<InputDeviceTapButtonMap> {
+        let m = match self.effective.tap_button_map.get()? {
+            LIBINPUT_CONFIG_TAP_MAP_LRM => InputDeviceTapButtonMap::LeftRightMiddle,
+            LIBINPUT_CONFIG_TAP_MAP_LMR => InputDeviceTapButtonMap::LeftMiddleRight,
+            _ => return None,
+        };
+        Some(m)
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        let map = match map {
+            InputDeviceTapButtonMap::LeftRightMiddle => LIBINPUT_CONFIG_TAP_MAP_LRM,
+            InputDeviceTapButtonMap::LeftMiddleRight => LIBINPUT_CONFIG_TAP_MAP_LMR,
+        };
+        self.set_tap_button_map_(map);
+    }
+
+    fn send_events_mode(&self) -> Option<InputDeviceSendEventsMode> {
+        let m = match self.effective.send_events_mode.get()? {
+            LIBINPUT_CONFIG_SEND_EVENTS_ENABLED => InputDeviceSendEventsMode::Enabled,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED => InputDeviceSendEventsMode::Disabled,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE => {
+                InputDeviceSendEventsMode::DisabledOnExternalMouse
+            }
+            _ => return None,
+        };
+        Some(m)
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        let mode = match mode {
+            InputDeviceSendEventsMode::Enabled => LIBINPUT_CONFIG_SEND_EVENTS_ENABLED,
+            InputDeviceSendEventsMode::Disabled => LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+            InputDeviceSendEventsMode::DisabledOnExternalMouse => {
+                LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE
+            }
+        };
+        self.set_send_events_mode_(mode);
+    }
+
+    fn rotation(&self) -> Option<u32> {
+        self.effective.rotation.get()
+    }
+
+    fn set_rotation(&self, degrees: u32) {
+        self.desired.rotation.set(Some(degrees));
+        if let Some(dev) = self.inputdev.get() {
+            if dev.device().rotation_available() {
+                dev.device().set_rotation(degrees);
+                self.effective.rotation.set(Some(dev.device().rotation()));
+            }
+        }
+    }
+
+    fn calibration_matrix(&self) -> Option<CalibrationMatrix> {
+        let [a, b, c, d, e, f] = self.effective.calibration_matrix.get()?;
+        Some([[a, b, c], [d, e, f]])
+    }
+
+    fn set_calibration_matrix(&self, matrix: CalibrationMatrix) {
+        let [[a, b, c], [d, e, f]] = matrix;
+        self.set_calibration_matrix_([a, b, c, d, e, f]);
+    }
+
     fn tablet_info(&self) -> Option<Box<TabletInit>> {
         let dev = self.inputdev.get()?;
         let dev = dev.device();
@@ -677,6 +1084,7 @@ impl InputDevice for MetalInputDevice {
             name: dev.name(),
             pid: dev.product(),
             vid: dev.vendor(),
+            size: dev.size(),
             path: self.syspath.as_bytes().as_bstr().to_string(),
         }))
     }
@@ -319,6 +319,7 @@ impl MetalBackend {
             desired: Default::default(),
             transform_matrix: Default::default(),
             effective: Default::default(),
+            custom_accel_functions: Default::default(),
             tablet_id: Default::default(),
             tablet_pad_id: Default::default(),
         });
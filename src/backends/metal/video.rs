@@ -645,6 +645,10 @@ impl MetalConnector {
                             return None;
                         }
                         GfxApiOpt::CopyTexture(ct) => break 'ct2 ct,
+                        GfxApiOpt::Blur(_) => {
+                            // Top-most layer must be a texture.
+                            return None;
+                        }
                     }
                 }
                 return None;
@@ -653,6 +657,10 @@ impl MetalConnector {
                 // Direct scanout with alpha factor is not supported.
                 return None;
             }
+            if ct.rounded.is_some() {
+                // Direct scanout with rounded corners is not supported.
+                return None;
+            }
             if !ct.tex.format().has_alpha && ct.target.is_covering() {
                 // Texture covers the entire screen and is opaque.
                 break 'ct ct;
@@ -676,6 +684,10 @@ impl MetalConnector {
                         // Texture could be visible.
                         return None;
                     }
+                    GfxApiOpt::Blur(_) => {
+                        // Blurred area could be visible.
+                        return None;
+                    }
                 }
             }
             if let Some(clear) = pass.clear {
@@ -1018,6 +1030,9 @@ impl MetalConnector {
                 if fb.direct_scanout_data.is_none() {
                     self.next_buffer.fetch_add(1);
                 }
+                if let Some(node) = self.state.root.outputs.get(&self.connector_id) {
+                    node.frame_submitted();
+                }
                 self.next_framebuffer.set(Some(fb));
             }
             if cursor_swap_buffer {
@@ -2157,12 +2172,18 @@ impl MetalBackend {
         connector
             .active_framebuffer
             .set(connector.next_framebuffer.take());
+        let global = self.state.root.outputs.get(&connector.connector_id);
+        if let Some(g) = &global {
+            g.frame_presented(tv_sec as u64 * 1_000_000 + tv_usec as u64);
+            if g.animation_frame_pending.get() {
+                connector.has_damage.set(true);
+            }
+        }
         if connector.has_damage.get() || connector.cursor_changed.get() {
             connector.schedule_present();
         }
         let dd = connector.display.borrow_mut();
         {
-            let global = self.state.root.outputs.get(&connector.connector_id);
             let mut rr = connector.render_result.borrow_mut();
             if let Some(g) = &global {
                 let refresh = dd.refresh;
@@ -2,10 +2,12 @@ use {
     crate::{
         async_engine::{Phase, SpawnedFuture},
         backend::{
-            AxisSource, Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorEvent,
-            ConnectorId, ConnectorKernelId, DrmDeviceId, DrmEvent, InputDevice,
-            InputDeviceAccelProfile, InputDeviceCapability, InputDeviceId, InputEvent, KeyState,
-            Mode, MonitorInfo, ScrollAxis, TransformMatrix, AXIS_120,
+            AxisSource, Backend, BackendDrmDevice, BackendEvent, CalibrationMatrix, Connector,
+            ConnectorEvent, ConnectorId, ConnectorKernelId, DrmDeviceId, DrmEvent, InputDevice,
+            InputDeviceAccelProfile, InputDeviceAccelType, InputDeviceCapability,
+            InputDeviceClickMethod, InputDeviceId, InputDeviceScrollMethod,
+            InputDeviceSendEventsMode, InputDeviceTapButtonMap, InputEvent, KeyState, Mode,
+            MonitorInfo, ScrollAxis, TransformMatrix, AXIS_120,
         },
         fixed::Fixed,
         format::XRGB8888,
@@ -690,6 +692,9 @@ impl XBackend {
             _ => return Ok(()),
         };
         output.next_msc.set(event.msc + 1);
+        if let Some(node) = self.state.root.outputs.get(&output.id) {
+            node.frame_presented(event.ust);
+        }
         let image = &output.images[output.next_image.get() % output.images.len()];
         if image.idle.get() {
             self.schedule_present(&output);
@@ -750,6 +755,7 @@ impl XBackend {
                 log::error!("Could not render screen: {}", ErrorFmt(e));
                 return;
             }
+            node.frame_submitted();
         }
 
         let pp = PresentPixmap {
@@ -1172,6 +1178,10 @@ impl InputDevice for XSeatKeyboard {
         let _ = profile;
     }
 
+    fn set_custom_accel_function(&self, ty: InputDeviceAccelType, step: f64, points: &[f64]) {
+        let _ = (ty, step, points);
+    }
+
     fn set_accel_speed(&self, speed: f64) {
         let _ = speed;
     }
@@ -1203,6 +1213,42 @@ impl InputDevice for XSeatKeyboard {
     fn set_natural_scrolling_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn set_click_method(&self, method: InputDeviceClickMethod) {
+        let _ = method;
+    }
+
+    fn set_middle_button_emulation_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_scroll_method(&self, method: InputDeviceScrollMethod) {
+        let _ = method;
+    }
+
+    fn set_scroll_button(&self, button: u32) {
+        let _ = button;
+    }
+
+    fn set_disable_while_typing_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        let _ = map;
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        let _ = mode;
+    }
+
+    fn set_rotation(&self, degrees: u32) {
+        let _ = degrees;
+    }
+
+    fn set_calibration_matrix(&self, matrix: CalibrationMatrix) {
+        let _ = matrix;
+    }
 }
 
 impl InputDevice for XSeatMouse {
@@ -1241,6 +1287,10 @@ impl InputDevice for XSeatMouse {
         let _ = profile;
     }
 
+    fn set_custom_accel_function(&self, ty: InputDeviceAccelType, step: f64, points: &[f64]) {
+        let _ = (ty, step, points);
+    }
+
     fn set_accel_speed(&self, speed: f64) {
         let _ = speed;
     }
@@ -1272,4 +1322,40 @@ impl InputDevice for XSeatMouse {
     fn set_natural_scrolling_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn set_click_method(&self, method: InputDeviceClickMethod) {
+        let _ = method;
+    }
+
+    fn set_middle_button_emulation_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_scroll_method(&self, method: InputDeviceScrollMethod) {
+        let _ = method;
+    }
+
+    fn set_scroll_button(&self, button: u32) {
+        let _ = button;
+    }
+
+    fn set_disable_while_typing_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        let _ = map;
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        let _ = mode;
+    }
+
+    fn set_rotation(&self, degrees: u32) {
+        let _ = degrees;
+    }
+
+    fn set_calibration_matrix(&self, matrix: CalibrationMatrix) {
+        let _ = matrix;
+    }
 }
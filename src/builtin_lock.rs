@@ -0,0 +1,128 @@
+use {
+    crate::{async_engine::SpawnedFuture, state::State},
+    isnt::std_1::vec::IsntVecExt,
+    jay_config::keyboard::syms::{SYM_BackSpace, SYM_Escape, SYM_KP_Enter, SYM_Return},
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        rc::Rc,
+    },
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum BuiltinLockStatus {
+    Idle,
+    Authenticating,
+    Failed,
+}
+
+/// The lock screen that is displayed if no lock client is available.
+pub struct BuiltinLock {
+    state: Rc<State>,
+    password: RefCell<Vec<u8>>,
+    status: Cell<BuiltinLockStatus>,
+    auth: Cell<Option<SpawnedFuture<()>>>,
+}
+
+impl BuiltinLock {
+    pub fn new(state: &Rc<State>) -> Rc<Self> {
+        let slf = Rc::new(Self {
+            state: state.clone(),
+            password: Default::default(),
+            status: Cell::new(BuiltinLockStatus::Idle),
+            auth: Default::default(),
+        });
+        slf.changed();
+        slf
+    }
+
+    pub fn clear(&self) {
+        self.auth.take();
+        self.clear_password();
+        self.changed();
+    }
+
+    fn clear_password(&self) {
+        let password = &mut *self.password.borrow_mut();
+        password.fill(0);
+        password.clear();
+    }
+
+    fn changed(&self) {
+        for output in self.state.root.outputs.lock().values() {
+            output.schedule_update_render_data();
+        }
+    }
+
+    /// Returns the text displayed in the center of each output.
+    pub fn prompt(&self) -> String {
+        let password = self.password.borrow();
+        if password.is_not_empty() {
+            let chars = password.iter().filter(|&&b| b & 0xc0 != 0x80).count();
+            return "\u{2022}".repeat(chars);
+        }
+        match self.status.get() {
+            BuiltinLockStatus::Idle => "Locked. Type your password to unlock.".to_string(),
+            BuiltinLockStatus::Authenticating => "Authenticating...".to_string(),
+            BuiltinLockStatus::Failed => "Authentication failed.".to_string(),
+        }
+    }
+
+    pub fn handle_key(self: &Rc<Self>, sym: u32, text: &str) {
+        if self.status.get() == BuiltinLockStatus::Authenticating {
+            return;
+        }
+        if sym == SYM_Return.0 || sym == SYM_KP_Enter.0 {
+            self.submit();
+            return;
+        }
+        {
+            let password = &mut *self.password.borrow_mut();
+            if sym == SYM_BackSpace.0 {
+                while let Some(b) = password.pop() {
+                    if b & 0xc0 != 0x80 {
+                        break;
+                    }
+                }
+            } else if sym == SYM_Escape.0 {
+                password.fill(0);
+                password.clear();
+            } else if text.chars().all(|c| !c.is_control()) {
+                password.extend_from_slice(text.as_bytes());
+            }
+        }
+        self.status.set(BuiltinLockStatus::Idle);
+        self.changed();
+    }
+
+    fn submit(self: &Rc<Self>) {
+        let password = mem::take(&mut *self.password.borrow_mut());
+        let Some(forker) = self.state.forker.get() else {
+            log::error!("Cannot authenticate because the forker is not available");
+            self.status.set(BuiltinLockStatus::Failed);
+            self.changed();
+            return;
+        };
+        self.status.set(BuiltinLockStatus::Authenticating);
+        self.changed();
+        let slf = self.clone();
+        let future = self.state.eng.spawn(async move {
+            let success = forker.authenticate(password).await;
+            let active = match slf.state.lock.builtin.get() {
+                Some(lock) => Rc::ptr_eq(&lock, &slf),
+                _ => false,
+            };
+            if !active {
+                return;
+            }
+            if success {
+                log::info!("Unlocking the screen after successful authentication");
+                slf.state.unlock();
+            } else {
+                slf.status.set(BuiltinLockStatus::Failed);
+                slf.changed();
+            }
+        });
+        self.auth.set(Some(future));
+    }
+}
@@ -1,9 +1,11 @@
 mod generate;
 mod idle;
 mod input;
+mod lock;
 mod log;
 mod quit;
 mod randr;
+mod record;
 mod run_privileged;
 pub mod screenshot;
 mod seat_test;
@@ -19,6 +21,7 @@ use {
     ::log::Level,
     clap::{Args, Parser, Subcommand, ValueEnum},
     clap_complete::Shell,
+    std::time::Duration,
 };
 
 /// A wayland compositor.
@@ -51,8 +54,12 @@ pub enum Cmd {
     Quit,
     /// Unlocks the compositor.
     Unlock,
+    /// Locks the compositor with the built-in lock screen.
+    Lock,
     /// Take a screenshot.
     Screenshot(ScreenshotArgs),
+    /// Record the screen to a file.
+    Record(RecordArgs),
     /// Inspect/modify the idle (screensaver) settings.
     Idle(IdleArgs),
     /// Run a privileged program.
@@ -94,6 +101,10 @@ pub enum IdleCmd {
     Status,
     /// Set the idle interval.
     Set(IdleSetArgs),
+    /// Temporarily ignore idle inhibitors.
+    IgnoreInhibitors(IdleIgnoreInhibitorsArgs),
+    /// Stop ignoring idle inhibitors.
+    RespectInhibitors,
 }
 
 impl Default for IdleCmd {
@@ -120,6 +131,17 @@ pub struct IdleSetArgs {
     pub interval: Vec<String>,
 }
 
+#[derive(Args, Debug)]
+pub struct IdleIgnoreInhibitorsArgs {
+    /// How long to ignore idle inhibitors.
+    ///
+    /// The duration uses the same format as the interval of `jay idle set`. Inhibitors
+    /// are respected again once the duration has elapsed, when the system resumes from
+    /// idle, or when `jay idle respect-inhibitors` is used. If no duration is given,
+    /// inhibitors are ignored until one of the latter happens.
+    pub duration: Vec<String>,
+}
+
 #[derive(ValueEnum, Debug, Copy, Clone, Hash, Default, PartialEq)]
 pub enum ScreenshotFormat {
     /// The PNG image format.
@@ -143,6 +165,47 @@ pub struct ScreenshotArgs {
     pub filename: Option<String>,
 }
 
+#[derive(Args, Debug)]
+pub struct RecordArgs {
+    /// The name of the output to record.
+    ///
+    /// If neither this nor `--window` or `--workspace` is given, the first output is
+    /// recorded.
+    #[clap(long, conflicts_with_all = ["window", "workspace"])]
+    pub output: Option<String>,
+    /// Interactively select a window to record.
+    #[clap(long, conflicts_with = "workspace")]
+    pub window: bool,
+    /// Interactively select a workspace to record.
+    #[clap(long)]
+    pub workspace: bool,
+    /// The number of frames per second in the recording.
+    #[clap(long, default_value_t = 30, value_parser = clap::value_parser!(u32).range(1..))]
+    pub fps: u32,
+    /// Stop the recording after this duration, e.g. `30s` or `2min`.
+    ///
+    /// By default, the recording runs until the program is interrupted.
+    #[clap(long, value_parser = humantime::parse_duration)]
+    pub duration: Option<Duration>,
+    /// The encoder program.
+    ///
+    /// The program must accept ffmpeg-compatible arguments. Raw frames are written to its
+    /// stdin.
+    #[clap(long, default_value = "ffmpeg")]
+    pub encoder: String,
+    /// The video codec passed to the encoder.
+    #[clap(long, default_value = "libx264")]
+    pub codec: String,
+    /// The filename of the recording
+    ///
+    /// If no filename is given, the recording will be saved under %Y-%m-%d-%H%M%S_jay.mkv
+    /// in the current directory.
+    ///
+    /// The filename can contain the usual strftime parameters. The container format is
+    /// chosen by the encoder based on the file extension.
+    pub filename: Option<String>,
+}
+
 #[derive(Args, Debug, Default)]
 pub struct RunArgs {
     /// The backends to try.
@@ -234,8 +297,10 @@ pub fn main() {
         Cmd::Quit => quit::main(cli.global),
         Cmd::SetLogLevel(a) => set_log_level::main(cli.global, a),
         Cmd::Screenshot(a) => screenshot::main(cli.global, a),
+        Cmd::Record(a) => record::main(cli.global, a),
         Cmd::Idle(a) => idle::main(cli.global, a),
         Cmd::Unlock => unlock::main(cli.global),
+        Cmd::Lock => lock::main(cli.global),
         Cmd::RunPrivileged(a) => run_privileged::main(cli.global, a),
         Cmd::SeatTest(a) => seat_test::main(cli.global, a),
         Cmd::Portal => portal::run_freestanding(cli.global),
@@ -5,7 +5,12 @@ use {
         utils::{errorfmt::ErrorFmt, stack::Stack},
         wire::{jay_compositor, jay_idle, JayIdleId, WlSurfaceId},
     },
-    std::{cell::Cell, collections::VecDeque, rc::Rc, str::FromStr},
+    std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        rc::Rc,
+        str::FromStr,
+    },
 };
 
 pub fn main(global: GlobalArgs, args: IdleArgs) {
@@ -31,6 +36,17 @@ impl Idle {
         match args.command.unwrap_or_default() {
             IdleCmd::Status => self.status(idle).await,
             IdleCmd::Set(args) => self.set(idle, args).await,
+            IdleCmd::IgnoreInhibitors(args) => {
+                let mut duration = 0;
+                if !args.duration.is_empty() {
+                    duration = parse_duration(&args.duration);
+                    if duration == 0 {
+                        fatal!("the duration must not be zero");
+                    }
+                }
+                self.set_inhibitors_ignored(idle, true, duration).await
+            }
+            IdleCmd::RespectInhibitors => self.set_inhibitors_ignored(idle, false, 0).await,
         }
     }
 
@@ -41,104 +57,185 @@ impl Idle {
         jay_idle::Interval::handle(tc, idle, interval.clone(), |iv, msg| {
             iv.set(msg.interval);
         });
+        struct Stage {
+            name: String,
+            timeout: u64,
+            actions: Vec<&'static str>,
+        }
+        let stages = Rc::new(Stack::default());
+        jay_idle::Stage::handle(tc, idle, stages.clone(), |s, msg| {
+            let mut actions = vec![];
+            if msg.dim_outputs != 0 {
+                actions.push("dim outputs");
+            }
+            if msg.power_off_outputs != 0 {
+                actions.push("power off outputs");
+            }
+            if msg.suspend != 0 {
+                actions.push("suspend");
+            }
+            s.push(Stage {
+                name: msg.name.to_string(),
+                timeout: msg.timeout,
+                actions,
+            });
+        });
         struct Inhibitor {
             surface: WlSurfaceId,
             _client_id: u64,
             pid: u64,
             comm: String,
+            app_id: String,
+            visible: bool,
         }
-        let inhibitors = Rc::new(Stack::default());
+        let inhibitors = Rc::new(RefCell::new(vec![]));
         jay_idle::Inhibitor::handle(tc, idle, inhibitors.clone(), |iv, msg| {
-            iv.push(Inhibitor {
+            iv.borrow_mut().push(Inhibitor {
                 surface: msg.surface,
                 _client_id: msg.client_id,
                 pid: msg.pid,
                 comm: msg.comm.to_string(),
+                app_id: String::new(),
+                visible: true,
             });
         });
+        jay_idle::InhibitorDetails::handle(tc, idle, inhibitors.clone(), |iv, msg| {
+            if let Some(inhibitor) = iv.borrow_mut().last_mut() {
+                inhibitor.app_id = msg.app_id.to_string();
+                inhibitor.visible = msg.visible != 0;
+            }
+        });
+        let inhibition = Rc::new(Cell::new((false, false)));
+        jay_idle::Inhibition::handle(tc, idle, inhibition.clone(), |iv, msg| {
+            iv.set((msg.ignored != 0, msg.fullscreen != 0));
+        });
         tc.round_trip().await;
-        let minutes = interval.get() / 60;
-        let seconds = interval.get() % 60;
         print!("Interval:");
-        if minutes == 0 && seconds == 0 {
-            print!(" disabled");
-        } else {
-            if minutes > 0 {
-                print!(" {} minute", minutes);
-                if minutes > 1 {
-                    print!("s");
-                }
-            }
-            if seconds > 0 {
-                print!(" {} second", seconds);
-                if seconds > 1 {
-                    print!("s");
+        print_duration(interval.get());
+        println!();
+        let mut stages = stages.take();
+        stages.sort_by_key(|s| s.timeout);
+        if stages.len() > 0 {
+            println!("Stages:");
+            for stage in stages {
+                print!("  {}:", stage.name);
+                print_duration(stage.timeout);
+                if stage.actions.len() > 0 {
+                    print!(" ({})", stage.actions.join(", "));
                 }
+                println!();
             }
         }
-        println!();
+        let (ignored, fullscreen) = inhibition.get();
+        if fullscreen {
+            println!("Fullscreen windows inhibit idle");
+        }
         let mut inhibitors = inhibitors.take();
         inhibitors.sort_by_key(|i| i.pid);
         inhibitors.sort_by_key(|i| i.surface);
         if inhibitors.len() > 0 {
-            println!("Inhibitors:");
+            match ignored {
+                true => println!("Inhibitors (ignored):"),
+                false => println!("Inhibitors:"),
+            }
             for inhibitor in inhibitors {
-                println!(
-                    "  {}, surface {}, pid {}",
-                    inhibitor.comm, inhibitor.surface, inhibitor.pid
-                );
+                print!("  {}", inhibitor.comm);
+                if !inhibitor.app_id.is_empty() {
+                    print!(" ({})", inhibitor.app_id);
+                }
+                print!(", surface {}, pid {}", inhibitor.surface, inhibitor.pid);
+                if !inhibitor.visible {
+                    print!(", not visible");
+                }
+                println!();
             }
+        } else if ignored {
+            println!("Inhibitors are ignored");
         }
     }
 
     async fn set(self, idle: JayIdleId, args: IdleSetArgs) {
         let tc = &self.tc;
-        let interval;
-        if args.interval.len() == 1 && args.interval[0] == "disabled" {
-            interval = 0;
+        let interval = if args.interval.len() == 1 && args.interval[0] == "disabled" {
+            0
         } else {
-            let comp = parse_components(&args.interval);
-            let mut minutes = None;
-            let mut seconds = None;
-            let mut pending_num = None;
-            for comp in comp {
-                match comp {
-                    Component::Number(_) if pending_num.is_some() => {
-                        fatal!("missing number unit after {}", pending_num.unwrap())
-                    }
-                    Component::Number(n) => pending_num = Some(n),
+            parse_duration(&args.interval)
+        };
+        tc.send(jay_idle::SetInterval {
+            self_id: idle,
+            interval,
+        });
+        tc.round_trip().await;
+    }
 
-                    Component::Minutes(n) if pending_num.is_none() => {
-                        fatal!("`{}` must be preceded by a number", n)
-                    }
-                    Component::Minutes(_) if minutes.is_some() => {
-                        fatal!("minutes specified multiple times")
-                    }
-                    Component::Minutes(_) => minutes = pending_num.take(),
+    async fn set_inhibitors_ignored(self, idle: JayIdleId, ignored: bool, duration: u64) {
+        let tc = &self.tc;
+        tc.send(jay_idle::SetInhibitorsIgnored {
+            self_id: idle,
+            ignored: ignored as _,
+            duration,
+        });
+        tc.round_trip().await;
+    }
+}
 
-                    Component::Seconds(n) if pending_num.is_none() => {
-                        fatal!("`{}` must be preceded by a number", n)
-                    }
-                    Component::Seconds(_) if seconds.is_some() => {
-                        fatal!("seconds specified multiple times")
-                    }
-                    Component::Seconds(_) => seconds = pending_num.take(),
-                }
+fn print_duration(secs: u64) {
+    let minutes = secs / 60;
+    let seconds = secs % 60;
+    if minutes == 0 && seconds == 0 {
+        print!(" disabled");
+    } else {
+        if minutes > 0 {
+            print!(" {} minute", minutes);
+            if minutes > 1 {
+                print!("s");
             }
-            if pending_num.is_some() {
-                fatal!("missing number unit after {}", pending_num.unwrap());
+        }
+        if seconds > 0 {
+            print!(" {} second", seconds);
+            if seconds > 1 {
+                print!("s");
             }
-            if minutes.is_none() && seconds.is_none() {
-                fatal!("minutes and/or numbers must be specified");
+        }
+    }
+}
+
+fn parse_duration(args: &[String]) -> u64 {
+    let comp = parse_components(args);
+    let mut minutes = None;
+    let mut seconds = None;
+    let mut pending_num = None;
+    for comp in comp {
+        match comp {
+            Component::Number(_) if pending_num.is_some() => {
+                fatal!("missing number unit after {}", pending_num.unwrap())
+            }
+            Component::Number(n) => pending_num = Some(n),
+
+            Component::Minutes(n) if pending_num.is_none() => {
+                fatal!("`{}` must be preceded by a number", n)
+            }
+            Component::Minutes(_) if minutes.is_some() => {
+                fatal!("minutes specified multiple times")
+            }
+            Component::Minutes(_) => minutes = pending_num.take(),
+
+            Component::Seconds(n) if pending_num.is_none() => {
+                fatal!("`{}` must be preceded by a number", n)
+            }
+            Component::Seconds(_) if seconds.is_some() => {
+                fatal!("seconds specified multiple times")
             }
-            interval = minutes.unwrap_or(0) * 60 + seconds.unwrap_or(0);
+            Component::Seconds(_) => seconds = pending_num.take(),
         }
-        tc.send(jay_idle::SetInterval {
-            self_id: idle,
-            interval,
-        });
-        tc.round_trip().await;
     }
+    if pending_num.is_some() {
+        fatal!("missing number unit after {}", pending_num.unwrap());
+    }
+    if minutes.is_none() && seconds.is_none() {
+        fatal!("minutes and/or numbers must be specified");
+    }
+    minutes.unwrap_or(0) * 60 + seconds.unwrap_or(0)
 }
 
 #[derive(Debug)]
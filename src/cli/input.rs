@@ -1,10 +1,23 @@
 use {
     crate::{
-        backend::{InputDeviceAccelProfile, InputDeviceCapability},
+        backend::{
+            CalibrationMatrix, InputDeviceAccelProfile, InputDeviceCapability,
+            InputDeviceClickMethod, InputDeviceScrollMethod, InputDeviceSendEventsMode,
+            InputDeviceTapButtonMap,
+        },
         cli::GlobalArgs,
         clientmem::ClientMem,
         libinput::consts::{
-            LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE, LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT,
+            LIBINPUT_ACCEL_TYPE_FALLBACK, LIBINPUT_ACCEL_TYPE_MOTION, LIBINPUT_ACCEL_TYPE_SCROLL,
+            LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE, LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM,
+            LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT, LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS,
+            LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER, LIBINPUT_CONFIG_CLICK_METHOD_NONE,
+            LIBINPUT_CONFIG_SCROLL_2FG, LIBINPUT_CONFIG_SCROLL_EDGE,
+            LIBINPUT_CONFIG_SCROLL_NO_SCROLL, LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE,
+            LIBINPUT_CONFIG_SEND_EVENTS_ENABLED, LIBINPUT_CONFIG_TAP_MAP_LMR,
+            LIBINPUT_CONFIG_TAP_MAP_LRM,
         },
         tools::tool_client::{with_tool_client, Handle, ToolClient},
         utils::{errorfmt::ErrorFmt, string_ext::StringExt},
@@ -119,6 +132,30 @@ pub enum DeviceCommand {
     SetPxPerWheelScroll(SetPxPerWheelScrollArgs),
     /// Set the transformation matrix.
     SetTransformMatrix(SetTransformMatrixArgs),
+    /// Set the click method.
+    SetClickMethod(SetClickMethodArgs),
+    /// Set whether middle button emulation is enabled.
+    SetMiddleButtonEmulation(SetMiddleButtonEmulationArgs),
+    /// Set the scroll method.
+    SetScrollMethod(SetScrollMethodArgs),
+    /// Set the button used by the on-button-down scroll method.
+    SetScrollButton(SetScrollButtonArgs),
+    /// Set whether the device is disabled while typing.
+    SetDisableWhileTyping(SetDisableWhileTypingArgs),
+    /// Set which buttons are generated by multi-finger taps.
+    SetTapButtonMap(SetTapButtonMapArgs),
+    /// Set whether the device sends events.
+    SetSendEventsMode(SetSendEventsModeArgs),
+    /// Set the clockwise rotation of the device.
+    SetRotation(SetRotationArgs),
+    /// Set the calibration matrix.
+    SetCalibrationMatrix(SetCalibrationMatrixArgs),
+    /// Set the part of the tablet that is mapped to the output.
+    SetTabletArea(SetTabletAreaArgs),
+    /// Set whether the tablet area is shrunk to the aspect ratio of the output.
+    SetTabletKeepAspectRatio(SetTabletKeepAspectRatioArgs),
+    /// Set the pressure curve of the tablet.
+    SetTabletPressureCurve(SetTabletPressureCurveArgs),
     /// Set the keymap of this device.
     SetKeymap(SetKeymapArgs),
     /// Retrieve the keymap of this device.
@@ -129,7 +166,9 @@ pub enum DeviceCommand {
     Detach,
     /// Maps this device to an output.
     MapToOutput(MapToOutputArgs),
-    /// Removes the mapping from this device to an output.
+    /// Maps this device to a region of the compositor space.
+    MapToRegion(MapToRegionArgs),
+    /// Removes the mapping from this device to an output or region.
     RemoveMapping,
 }
 
@@ -137,12 +176,35 @@ pub enum DeviceCommand {
 pub enum AccelProfile {
     Flat,
     Adaptive,
+    Custom,
+}
+
+#[derive(ValueEnum, Debug, Clone, Default)]
+pub enum AccelType {
+    #[default]
+    Fallback,
+    Motion,
+    Scroll,
 }
 
 #[derive(Args, Debug, Clone)]
 pub struct SetAccelProfileArgs {
     /// The profile.
     pub profile: AccelProfile,
+    /// The distance between the points of a custom profile.
+    #[arg(long, requires = "points")]
+    pub step: Option<f64>,
+    /// The comma-separated points of a custom profile.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        allow_negative_numbers = true,
+        requires = "step"
+    )]
+    pub points: Vec<f64>,
+    /// The type of motion that the points of a custom profile apply to.
+    #[arg(long, value_enum, default_value_t)]
+    pub motion_type: AccelType,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -200,12 +262,144 @@ pub struct SetTransformMatrixArgs {
     pub m22: f64,
 }
 
+#[derive(ValueEnum, Debug, Clone)]
+pub enum ClickMethod {
+    None,
+    ButtonAreas,
+    Clickfinger,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetClickMethodArgs {
+    /// The click method.
+    pub method: ClickMethod,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetMiddleButtonEmulationArgs {
+    /// Whether middle button emulation is enabled.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum ScrollMethod {
+    None,
+    TwoFinger,
+    Edge,
+    OnButtonDown,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetScrollMethodArgs {
+    /// The scroll method.
+    pub method: ScrollMethod,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetScrollButtonArgs {
+    /// The evdev code of the button, e.g. 274 for the middle button.
+    pub button: u32,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetDisableWhileTypingArgs {
+    /// Whether the device is disabled while typing.
+    #[arg(action = clap::ArgAction::Set)]
+    pub enabled: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum TapButtonMap {
+    /// 1/2/3 finger taps generate left/right/middle clicks.
+    Lrm,
+    /// 1/2/3 finger taps generate left/middle/right clicks.
+    Lmr,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetTapButtonMapArgs {
+    /// The button map.
+    pub map: TapButtonMap,
+}
+
+#[derive(ValueEnum, Debug, Clone)]
+pub enum SendEventsMode {
+    Enabled,
+    Disabled,
+    DisabledOnExternalMouse,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetSendEventsModeArgs {
+    /// The send-events mode.
+    pub mode: SendEventsMode,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetRotationArgs {
+    /// The rotation in degrees.
+    pub degrees: u32,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetCalibrationMatrixArgs {
+    pub m11: f32,
+    pub m12: f32,
+    pub m13: f32,
+    pub m21: f32,
+    pub m22: f32,
+    pub m23: f32,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetTabletAreaArgs {
+    /// The left edge of the area as a fraction of the tablet width.
+    pub x: f64,
+    /// The top edge of the area as a fraction of the tablet height.
+    pub y: f64,
+    /// The width of the area as a fraction of the tablet width.
+    pub width: f64,
+    /// The height of the area as a fraction of the tablet height.
+    pub height: f64,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetTabletKeepAspectRatioArgs {
+    /// Whether the aspect ratio is kept.
+    #[arg(action = clap::ArgAction::Set)]
+    pub keep: bool,
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct SetTabletPressureCurveArgs {
+    /// The control points of the cubic bezier curve. Use `0 0 1 1` for a linear curve.
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct MapToOutputArgs {
     /// The output to map to.
     pub output: String,
 }
 
+#[derive(Args, Debug, Clone)]
+pub struct MapToRegionArgs {
+    /// The x coordinate of the region in logical pixels.
+    #[arg(allow_negative_numbers = true)]
+    pub x: i32,
+    /// The y coordinate of the region in logical pixels.
+    #[arg(allow_negative_numbers = true)]
+    pub y: i32,
+    /// The width of the region.
+    pub width: i32,
+    /// The height of the region.
+    pub height: i32,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct AttachArgs {
     /// The seat to attach to.
@@ -271,7 +465,20 @@ struct InputDevice {
     pub natural_scrolling_enabled: Option<bool>,
     pub px_per_wheel_scroll: Option<f64>,
     pub transform_matrix: Option<[[f64; 2]; 2]>,
+    pub click_method: Option<InputDeviceClickMethod>,
+    pub middle_button_emulation_enabled: Option<bool>,
+    pub scroll_method: Option<InputDeviceScrollMethod>,
+    pub scroll_button: Option<u32>,
+    pub disable_while_typing_enabled: Option<bool>,
+    pub tap_button_map: Option<InputDeviceTapButtonMap>,
+    pub send_events_mode: Option<InputDeviceSendEventsMode>,
+    pub rotation: Option<u32>,
+    pub calibration_matrix: Option<CalibrationMatrix>,
     pub output: Option<String>,
+    pub region: Option<[i32; 4]>,
+    pub tablet_area: Option<[f64; 4]>,
+    pub tablet_keep_aspect_ratio: Option<bool>,
+    pub tablet_pressure_curve: Option<[f64; 4]>,
 }
 
 #[derive(Clone, Debug, Default)]
@@ -440,10 +647,29 @@ impl Input {
                 let profile = match a.profile {
                     AccelProfile::Flat => LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT.0,
                     AccelProfile::Adaptive => LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE.0,
+                    AccelProfile::Custom => LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM.0,
                 };
                 self.handle_error(input, |e| {
                     eprintln!("Could not set the acceleration profile: {}", e);
                 });
+                if let Some(step) = a.step {
+                    if !matches!(a.profile, AccelProfile::Custom) {
+                        eprintln!("Points can only be specified for the custom profile");
+                        std::process::exit(1);
+                    }
+                    let ty = match a.motion_type {
+                        AccelType::Fallback => LIBINPUT_ACCEL_TYPE_FALLBACK.0,
+                        AccelType::Motion => LIBINPUT_ACCEL_TYPE_MOTION.0,
+                        AccelType::Scroll => LIBINPUT_ACCEL_TYPE_SCROLL.0,
+                    };
+                    tc.send(jay_input::SetCustomAccelFunction {
+                        self_id: input,
+                        id: args.device,
+                        ty,
+                        step,
+                        points: &a.points,
+                    });
+                }
                 tc.send(jay_input::SetAccelProfile {
                     self_id: input,
                     id: args.device,
@@ -533,6 +759,162 @@ impl Input {
                     m22: a.m22,
                 });
             }
+            DeviceCommand::SetClickMethod(a) => {
+                let method = match a.method {
+                    ClickMethod::None => LIBINPUT_CONFIG_CLICK_METHOD_NONE.0,
+                    ClickMethod::ButtonAreas => LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS.0,
+                    ClickMethod::Clickfinger => LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER.0,
+                };
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the click method: {}", e);
+                });
+                tc.send(jay_input::SetClickMethod {
+                    self_id: input,
+                    id: args.device,
+                    method,
+                });
+            }
+            DeviceCommand::SetMiddleButtonEmulation(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!(
+                        "Could not modify the middle-button-emulation setting: {}",
+                        e
+                    );
+                });
+                tc.send(jay_input::SetMiddleButtonEmulation {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled as _,
+                });
+            }
+            DeviceCommand::SetScrollMethod(a) => {
+                let method = match a.method {
+                    ScrollMethod::None => LIBINPUT_CONFIG_SCROLL_NO_SCROLL.0,
+                    ScrollMethod::TwoFinger => LIBINPUT_CONFIG_SCROLL_2FG.0,
+                    ScrollMethod::Edge => LIBINPUT_CONFIG_SCROLL_EDGE.0,
+                    ScrollMethod::OnButtonDown => LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN.0,
+                };
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the scroll method: {}", e);
+                });
+                tc.send(jay_input::SetScrollMethod {
+                    self_id: input,
+                    id: args.device,
+                    method,
+                });
+            }
+            DeviceCommand::SetScrollButton(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the scroll button: {}", e);
+                });
+                tc.send(jay_input::SetScrollButton {
+                    self_id: input,
+                    id: args.device,
+                    button: a.button,
+                });
+            }
+            DeviceCommand::SetDisableWhileTyping(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not modify the disable-while-typing setting: {}", e);
+                });
+                tc.send(jay_input::SetDisableWhileTyping {
+                    self_id: input,
+                    id: args.device,
+                    enabled: a.enabled as _,
+                });
+            }
+            DeviceCommand::SetTapButtonMap(a) => {
+                let map = match a.map {
+                    TapButtonMap::Lrm => LIBINPUT_CONFIG_TAP_MAP_LRM.0,
+                    TapButtonMap::Lmr => LIBINPUT_CONFIG_TAP_MAP_LMR.0,
+                };
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the tap button map: {}", e);
+                });
+                tc.send(jay_input::SetTapButtonMap {
+                    self_id: input,
+                    id: args.device,
+                    map,
+                });
+            }
+            DeviceCommand::SetSendEventsMode(a) => {
+                let mode = match a.mode {
+                    SendEventsMode::Enabled => LIBINPUT_CONFIG_SEND_EVENTS_ENABLED.0,
+                    SendEventsMode::Disabled => LIBINPUT_CONFIG_SEND_EVENTS_DISABLED.0,
+                    SendEventsMode::DisabledOnExternalMouse => {
+                        LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE.0
+                    }
+                };
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the send-events mode: {}", e);
+                });
+                tc.send(jay_input::SetSendEventsMode {
+                    self_id: input,
+                    id: args.device,
+                    mode,
+                });
+            }
+            DeviceCommand::SetRotation(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the rotation: {}", e);
+                });
+                tc.send(jay_input::SetRotation {
+                    self_id: input,
+                    id: args.device,
+                    degrees: a.degrees,
+                });
+            }
+            DeviceCommand::SetCalibrationMatrix(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not modify the calibration matrix: {}", e);
+                });
+                tc.send(jay_input::SetCalibrationMatrix {
+                    self_id: input,
+                    id: args.device,
+                    m11: a.m11,
+                    m12: a.m12,
+                    m13: a.m13,
+                    m21: a.m21,
+                    m22: a.m22,
+                    m23: a.m23,
+                });
+            }
+            DeviceCommand::SetTabletArea(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the tablet area: {}", e);
+                });
+                tc.send(jay_input::SetTabletArea {
+                    self_id: input,
+                    id: args.device,
+                    x: a.x,
+                    y: a.y,
+                    width: a.width,
+                    height: a.height,
+                });
+            }
+            DeviceCommand::SetTabletKeepAspectRatio(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not modify the keep-aspect-ratio setting: {}", e);
+                });
+                tc.send(jay_input::SetTabletKeepAspectRatio {
+                    self_id: input,
+                    id: args.device,
+                    keep: a.keep as _,
+                });
+            }
+            DeviceCommand::SetTabletPressureCurve(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not set the pressure curve: {}", e);
+                });
+                tc.send(jay_input::SetTabletPressureCurve {
+                    self_id: input,
+                    id: args.device,
+                    x1: a.x1,
+                    y1: a.y1,
+                    x2: a.x2,
+                    y2: a.y2,
+                });
+            }
             DeviceCommand::Attach(a) => {
                 self.handle_error(input, |e| {
                     eprintln!("Could not attach the device: {}", e);
@@ -585,6 +967,19 @@ impl Input {
                     output: Some(&a.output),
                 });
             }
+            DeviceCommand::MapToRegion(a) => {
+                self.handle_error(input, |e| {
+                    eprintln!("Could not map the device to a region: {}", e);
+                });
+                tc.send(jay_input::MapToRegion {
+                    self_id: input,
+                    id: args.device,
+                    x: a.x,
+                    y: a.y,
+                    width: a.width,
+                    height: a.height,
+                });
+            }
             DeviceCommand::RemoveMapping => {
                 self.handle_error(input, |e| {
                     eprintln!("Could not remove the output mapping: {}", e);
@@ -698,6 +1093,7 @@ impl Input {
             let name = match v {
                 InputDeviceAccelProfile::Flat => "flat",
                 InputDeviceAccelProfile::Adaptive => "adaptive",
+                InputDeviceAccelProfile::Custom => "custom",
             };
             println!("{prefix}  accel profile: {}", name);
         }
@@ -725,9 +1121,68 @@ impl Input {
         if let Some(v) = &device.transform_matrix {
             println!("{prefix}  transform matrix: {:?}", v);
         }
+        if let Some(v) = &device.click_method {
+            let name = match v {
+                InputDeviceClickMethod::None => "none",
+                InputDeviceClickMethod::ButtonAreas => "button-areas",
+                InputDeviceClickMethod::Clickfinger => "clickfinger",
+            };
+            println!("{prefix}  click method: {}", name);
+        }
+        if let Some(v) = &device.middle_button_emulation_enabled {
+            println!("{prefix}  middle button emulation: {}", v);
+        }
+        if let Some(v) = &device.scroll_method {
+            let name = match v {
+                InputDeviceScrollMethod::NoScroll => "none",
+                InputDeviceScrollMethod::TwoFinger => "two-finger",
+                InputDeviceScrollMethod::Edge => "edge",
+                InputDeviceScrollMethod::OnButtonDown => "on-button-down",
+            };
+            println!("{prefix}  scroll method: {}", name);
+        }
+        if let Some(v) = &device.scroll_button {
+            println!("{prefix}  scroll button: {}", v);
+        }
+        if let Some(v) = &device.disable_while_typing_enabled {
+            println!("{prefix}  disable while typing: {}", v);
+        }
+        if let Some(v) = &device.tap_button_map {
+            let name = match v {
+                InputDeviceTapButtonMap::LeftRightMiddle => "lrm",
+                InputDeviceTapButtonMap::LeftMiddleRight => "lmr",
+            };
+            println!("{prefix}  tap button map: {}", name);
+        }
+        if let Some(v) = &device.send_events_mode {
+            let name = match v {
+                InputDeviceSendEventsMode::Enabled => "enabled",
+                InputDeviceSendEventsMode::Disabled => "disabled",
+                InputDeviceSendEventsMode::DisabledOnExternalMouse => "disabled-on-external-mouse",
+            };
+            println!("{prefix}  send events: {}", name);
+        }
+        if let Some(v) = &device.rotation {
+            println!("{prefix}  rotation: {}", v);
+        }
+        if let Some(v) = &device.calibration_matrix {
+            println!("{prefix}  calibration matrix: {:?}", v);
+        }
         if let Some(v) = &device.output {
             println!("{prefix}  mapped to output: {}", v);
         }
+        if let Some([x, y, width, height]) = &device.region {
+            println!("{prefix}  mapped to region: {width}x{height}+{x}+{y}");
+        }
+        if let Some(v) = &device.tablet_area {
+            println!("{prefix}  tablet area: {:?}", v);
+        }
+        if let Some(v) = &device.tablet_keep_aspect_ratio {
+            println!("{prefix}  keep aspect ratio: {}", v);
+        }
+        if let Some(v) = &device.tablet_pressure_curve {
+            println!("{prefix}  pressure curve: {:?}", v);
+        }
     }
 
     async fn get(self: &Rc<Self>, input: JayInputId) -> Data {
@@ -768,6 +1223,7 @@ impl Input {
             let mut accel_profile = match AccelProfile(msg.accel_profile) {
                 LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT => Some(InputDeviceAccelProfile::Flat),
                 LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE => Some(InputDeviceAccelProfile::Adaptive),
+                LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM => Some(InputDeviceAccelProfile::Custom),
                 _ => None,
             };
             if !accel_available {
@@ -791,15 +1247,98 @@ impl Input {
                     .then_some(msg.natural_scrolling_enabled != 0),
                 px_per_wheel_scroll: is_pointer.then_some(msg.px_per_wheel_scroll),
                 transform_matrix: uapi::pod_read(msg.transform_matrix).ok(),
+                click_method: None,
+                middle_button_emulation_enabled: None,
+                scroll_method: None,
+                scroll_button: None,
+                disable_while_typing_enabled: None,
+                tap_button_map: None,
+                send_events_mode: None,
+                rotation: None,
+                calibration_matrix: None,
                 output: None,
+                region: None,
+                tablet_area: None,
+                tablet_keep_aspect_ratio: None,
+                tablet_pressure_curve: None,
             });
         });
+        jay_input::InputDeviceConfig::handle(tc, input, data.clone(), |data, msg| {
+            use crate::libinput::consts::*;
+            let mut data = data.borrow_mut();
+            let Some(last) = data.input_device.last_mut() else {
+                return;
+            };
+            if msg.click_method_available != 0 {
+                last.click_method = match ConfigClickMethod(msg.click_method) {
+                    LIBINPUT_CONFIG_CLICK_METHOD_NONE => Some(InputDeviceClickMethod::None),
+                    LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS => {
+                        Some(InputDeviceClickMethod::ButtonAreas)
+                    }
+                    LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER => {
+                        Some(InputDeviceClickMethod::Clickfinger)
+                    }
+                    _ => None,
+                };
+            }
+            last.middle_button_emulation_enabled = (msg.middle_button_emulation_available != 0)
+                .then_some(msg.middle_button_emulation_enabled != 0);
+            if msg.scroll_method_available != 0 {
+                last.scroll_method = match ConfigScrollMethod(msg.scroll_method) {
+                    LIBINPUT_CONFIG_SCROLL_NO_SCROLL => Some(InputDeviceScrollMethod::NoScroll),
+                    LIBINPUT_CONFIG_SCROLL_2FG => Some(InputDeviceScrollMethod::TwoFinger),
+                    LIBINPUT_CONFIG_SCROLL_EDGE => Some(InputDeviceScrollMethod::Edge),
+                    LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN => {
+                        Some(InputDeviceScrollMethod::OnButtonDown)
+                    }
+                    _ => None,
+                };
+            }
+            last.scroll_button = (msg.scroll_button_available != 0).then_some(msg.scroll_button);
+            last.disable_while_typing_enabled = (msg.disable_while_typing_available != 0)
+                .then_some(msg.disable_while_typing_enabled != 0);
+            if last.tap_enabled.is_some() {
+                last.tap_button_map = match ConfigTapButtonMap(msg.tap_button_map) {
+                    LIBINPUT_CONFIG_TAP_MAP_LRM => Some(InputDeviceTapButtonMap::LeftRightMiddle),
+                    LIBINPUT_CONFIG_TAP_MAP_LMR => Some(InputDeviceTapButtonMap::LeftMiddleRight),
+                    _ => None,
+                };
+            }
+            if msg.send_events_available != 0 {
+                last.send_events_mode = match ConfigSendEventsMode(msg.send_events_mode) {
+                    LIBINPUT_CONFIG_SEND_EVENTS_ENABLED => Some(InputDeviceSendEventsMode::Enabled),
+                    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED => {
+                        Some(InputDeviceSendEventsMode::Disabled)
+                    }
+                    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE => {
+                        Some(InputDeviceSendEventsMode::DisabledOnExternalMouse)
+                    }
+                    _ => None,
+                };
+            }
+            last.rotation = (msg.rotation_available != 0).then_some(msg.rotation);
+            last.calibration_matrix = uapi::pod_read(msg.calibration_matrix).ok();
+        });
         jay_input::InputDeviceOutput::handle(tc, input, data.clone(), |data, msg| {
             let mut data = data.borrow_mut();
             if let Some(last) = data.input_device.last_mut() {
                 last.output = Some(msg.output.to_string());
             }
         });
+        jay_input::InputDeviceRegion::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.region = Some([msg.x, msg.y, msg.width, msg.height]);
+            }
+        });
+        jay_input::InputDeviceTablet::handle(tc, input, data.clone(), |data, msg| {
+            let mut data = data.borrow_mut();
+            if let Some(last) = data.input_device.last_mut() {
+                last.tablet_area = Some([msg.area_x, msg.area_y, msg.area_width, msg.area_height]);
+                last.tablet_keep_aspect_ratio = Some(msg.keep_aspect_ratio != 0);
+                last.tablet_pressure_curve = msg.pressure_curve.try_into().ok();
+            }
+        });
         tc.round_trip().await;
         let x = data.borrow_mut().clone();
         x
@@ -0,0 +1,26 @@
+use {
+    crate::{
+        cli::GlobalArgs,
+        tools::tool_client::{with_tool_client, ToolClient},
+        wire::jay_compositor::Lock,
+    },
+    std::rc::Rc,
+};
+
+pub fn main(global: GlobalArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let locker = Rc::new(Locker { tc: tc.clone() });
+        run(locker).await;
+    });
+}
+
+struct Locker {
+    tc: Rc<ToolClient>,
+}
+
+async fn run(locker: Rc<Locker>) {
+    let tc = &locker.tc;
+    let comp = tc.jay_compositor().await;
+    tc.send(Lock { self_id: comp });
+    tc.round_trip().await;
+}
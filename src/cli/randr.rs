@@ -77,6 +77,9 @@ pub enum ApiCmd {
     /// Use Vulkan for rendering in this card.
     #[clap(name = "vulkan")]
     Vulkan,
+    /// Use the CPU for rendering in this card.
+    #[clap(name = "software")]
+    Software,
 }
 
 #[derive(Args, Debug, Clone)]
@@ -422,6 +425,7 @@ impl Randr {
                 let api = match &api.cmd {
                     ApiCmd::OpenGl => "opengl",
                     ApiCmd::Vulkan => "vulkan",
+                    ApiCmd::Software => "software",
                 };
                 tc.send(jay_randr::SetApi {
                     self_id: randr,
@@ -0,0 +1,517 @@
+use {
+    crate::{
+        cli::{GlobalArgs, RecordArgs},
+        format::{formats, XRGB8888},
+        object::WL_DISPLAY_ID,
+        sighand,
+        time::now_usec,
+        tools::tool_client::{with_tool_client, Handle, ToolClient},
+        utils::{
+            asyncevent::AsyncEvent,
+            buf::{Buf, TypedBuf},
+            clonecell::CloneCell,
+            errorfmt::ErrorFmt,
+            queue::AsyncQueue,
+        },
+        video::{
+            dmabuf::{DmaBuf, DmaBufIds, DmaBufPlane, PlaneVec},
+            drm::Drm,
+            gbm::{GbmBo, GbmDevice, GBM_BO_USE_LINEAR},
+        },
+        wire::{
+            jay_compositor::{
+                CreateScreencast, GetOutput, GetRenderCtx, SelectToplevel, SelectWorkspace,
+            },
+            jay_render_ctx::{self, Device, NoDevice},
+            jay_screencast::{
+                AckBuffers, AllowWorkspace, Buffer, BuffersDone, Configure, Destroyed, Plane,
+                Ready, ReleaseBuffer, SetAllowAllWorkspaces, SetOutput, SetRunning, SetToplevel,
+                SetUseLinearBuffers,
+            },
+            jay_select_toplevel, jay_select_workspace, wl_display, wl_output, wl_registry,
+            JayCompositorId, JayOutputId, JayScreencastId, JayToplevelId, JayWorkspaceId, WlOutput,
+            WlOutputId, WlRegistryId, WlSeat, WlSeatId,
+        },
+    },
+    chrono::Local,
+    std::{
+        cell::{Cell, RefCell},
+        mem,
+        os::{fd::IntoRawFd, unix::process::CommandExt},
+        process::{Child, Command, Stdio},
+        rc::Rc,
+    },
+    uapi::{c, OwnedFd},
+};
+
+pub fn main(global: GlobalArgs, args: RecordArgs) {
+    with_tool_client(global.log_level.into(), |tc| async move {
+        let record = Rc::new(Record {
+            tc: tc.clone(),
+            args,
+            registry: Cell::new(WlRegistryId::NONE),
+            outputs: Default::default(),
+            seats: Default::default(),
+            gbm: Default::default(),
+            dma_buf_ids: Default::default(),
+            pending_planes: Default::default(),
+            pending_buffers: Default::default(),
+            buffers: Default::default(),
+            size: Default::default(),
+            frame: Default::default(),
+            encoder: Default::default(),
+            done: Default::default(),
+            stopping: Default::default(),
+        });
+        run(record).await;
+    });
+}
+
+struct Record {
+    tc: Rc<ToolClient>,
+    args: RecordArgs,
+    registry: Cell<WlRegistryId>,
+    outputs: RefCell<Vec<u32>>,
+    seats: RefCell<Vec<u32>>,
+    gbm: CloneCell<Option<Rc<GbmDevice>>>,
+    dma_buf_ids: DmaBufIds,
+    pending_planes: RefCell<PlaneVec<DmaBufPlane>>,
+    pending_buffers: RefCell<Vec<DmaBuf>>,
+    buffers: RefCell<Vec<Rc<GbmBo>>>,
+    size: Cell<Option<(i32, i32)>>,
+    frame: RefCell<Option<Buf>>,
+    encoder: RefCell<Option<Encoder>>,
+    done: AsyncEvent,
+    stopping: Cell<bool>,
+}
+
+struct Encoder {
+    child: Child,
+    stdin: Rc<OwnedFd>,
+}
+
+enum Target {
+    Output(JayOutputId),
+    Workspace(JayOutputId, JayWorkspaceId),
+    Toplevel(JayToplevelId),
+}
+
+async fn run(record: Rc<Record>) {
+    let tc = &record.tc;
+    let comp = tc.jay_compositor().await;
+    record.get_globals().await;
+    record.create_gbm_device(comp).await;
+    let target = if record.args.window {
+        record.select_toplevel(comp).await
+    } else if record.args.workspace {
+        record.select_workspace(comp).await
+    } else {
+        record.find_output(comp).await
+    };
+    let sc: JayScreencastId = tc.id();
+    tc.send(CreateScreencast {
+        self_id: comp,
+        id: sc,
+    });
+    Plane::handle(tc, sc, record.clone(), |r, ev| {
+        r.pending_planes.borrow_mut().push(DmaBufPlane {
+            offset: ev.offset,
+            stride: ev.stride,
+            fd: ev.fd,
+        });
+    });
+    Buffer::handle(tc, sc, record.clone(), |r, ev| {
+        if ev.format != XRGB8888.drm {
+            let name = formats()
+                .get(&ev.format)
+                .map(|f| f.name)
+                .unwrap_or("unknown");
+            fatal!(
+                "The compositor sent a buffer with unsupported format {}",
+                name
+            );
+        }
+        let planes = mem::take(&mut *r.pending_planes.borrow_mut());
+        r.pending_buffers.borrow_mut().push(DmaBuf {
+            id: r.dma_buf_ids.next(),
+            width: ev.width,
+            height: ev.height,
+            format: XRGB8888,
+            modifier: ev.modifier,
+            planes,
+        });
+    });
+    BuffersDone::handle(tc, sc, record.clone(), move |r, ev| {
+        r.import_buffers();
+        r.tc.send(AckBuffers {
+            self_id: sc,
+            serial: ev.serial,
+        });
+    });
+    Ready::handle(tc, sc, record.clone(), move |r, ev| {
+        r.copy_frame(ev.idx as usize);
+        r.tc.send(ReleaseBuffer {
+            self_id: sc,
+            idx: ev.idx,
+        });
+    });
+    Destroyed::handle(tc, sc, record.clone(), |r, _| {
+        log::info!("The recorded object has been destroyed");
+        r.done.trigger();
+    });
+    match target {
+        Target::Output(output) => {
+            tc.send(SetOutput {
+                self_id: sc,
+                output,
+            });
+            tc.send(SetAllowAllWorkspaces {
+                self_id: sc,
+                allow_all: 1,
+            });
+        }
+        Target::Workspace(output, workspace) => {
+            tc.send(SetOutput {
+                self_id: sc,
+                output,
+            });
+            tc.send(AllowWorkspace {
+                self_id: sc,
+                workspace,
+            });
+        }
+        Target::Toplevel(id) => {
+            tc.send(SetToplevel { self_id: sc, id });
+        }
+    }
+    tc.send(SetUseLinearBuffers {
+        self_id: sc,
+        use_linear: 1,
+    });
+    tc.send(SetRunning {
+        self_id: sc,
+        running: 1,
+    });
+    tc.send(Configure { self_id: sc });
+    let signals = tc.eng.spawn(record.clone().handle_signals());
+    let writer = tc.eng.spawn(record.clone().write_frames());
+    record.done.triggered().await;
+    record.stopping.set(true);
+    writer.await;
+    drop(signals);
+    record.finish().await;
+}
+
+impl Record {
+    async fn get_globals(self: &Rc<Self>) {
+        let tc = &self.tc;
+        let registry: WlRegistryId = tc.id();
+        tc.send(wl_display::GetRegistry {
+            self_id: WL_DISPLAY_ID,
+            registry,
+        });
+        wl_registry::Global::handle(tc, registry, self.clone(), |r, g| {
+            if g.interface == WlOutput.name() {
+                r.outputs.borrow_mut().push(g.name);
+            } else if g.interface == WlSeat.name() {
+                r.seats.borrow_mut().push(g.name);
+            }
+        });
+        tc.round_trip().await;
+        self.registry.set(registry);
+    }
+
+    async fn create_gbm_device(&self, comp: JayCompositorId) {
+        let tc = &self.tc;
+        let ctx = tc.id();
+        tc.send(GetRenderCtx {
+            self_id: comp,
+            id: ctx,
+        });
+        let res = Rc::new(AsyncQueue::new());
+        NoDevice::handle(tc, ctx, res.clone(), |res, _| {
+            res.push(None);
+        });
+        Device::handle(tc, ctx, res.clone(), |res, ev| {
+            res.push(Some(ev.fd));
+        });
+        let Some(fd) = res.pop().await else {
+            fatal!("The compositor does not have a render device");
+        };
+        tc.send(jay_render_ctx::Destroy { self_id: ctx });
+        let drm = match Drm::reopen(fd.raw(), false) {
+            Ok(drm) => drm,
+            Err(e) => fatal!("Could not open the drm device: {}", ErrorFmt(e)),
+        };
+        let gbm = match GbmDevice::new(&drm) {
+            Ok(g) => g,
+            Err(e) => fatal!("Could not create a gbm device: {}", ErrorFmt(e)),
+        };
+        self.gbm.set(Some(Rc::new(gbm)));
+    }
+
+    async fn handle_signals(self: Rc<Self>) {
+        let fd = match sighand::block(&[c::SIGINT, c::SIGTERM]) {
+            Ok(fd) => fd,
+            Err(e) => {
+                log::error!("Could not handle signals: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let mut buf = TypedBuf::<c::signalfd_siginfo>::new();
+        if let Err(e) = self.tc.ring.read(&fd, buf.buf()).await {
+            log::error!("Could not read from signal fd: {}", ErrorFmt(e));
+            return;
+        }
+        log::info!("Stopping the recording");
+        self.done.trigger();
+    }
+
+    fn bind_seat(&self) -> WlSeatId {
+        let Some(&name) = self.seats.borrow().first() else {
+            fatal!("The compositor does not have any seats");
+        };
+        let id: WlSeatId = self.tc.id();
+        self.tc.send(wl_registry::Bind {
+            self_id: self.registry.get(),
+            name,
+            interface: WlSeat.name(),
+            version: 1,
+            id: id.into(),
+        });
+        id
+    }
+
+    fn bind_output(&self, comp: JayCompositorId, name: u32) -> (WlOutputId, JayOutputId) {
+        let tc = &self.tc;
+        let output: WlOutputId = tc.id();
+        tc.send(wl_registry::Bind {
+            self_id: self.registry.get(),
+            name,
+            interface: WlOutput.name(),
+            version: 4,
+            id: output.into(),
+        });
+        let jo: JayOutputId = tc.id();
+        tc.send(GetOutput {
+            self_id: comp,
+            id: jo,
+            output,
+        });
+        (output, jo)
+    }
+
+    async fn find_output(self: &Rc<Self>, comp: JayCompositorId) -> Target {
+        let tc = &self.tc;
+        let names = Rc::new(RefCell::new(vec![]));
+        for &global in self.outputs.borrow().iter() {
+            let (output, jo) = self.bind_output(comp, global);
+            wl_output::Name::handle(tc, output, (names.clone(), jo), |(names, jo), ev| {
+                names.borrow_mut().push((ev.name.to_string(), *jo));
+            });
+        }
+        tc.round_trip().await;
+        let names = names.borrow();
+        let found = match &self.args.output {
+            Some(name) => names.iter().find(|(n, _)| n == name),
+            _ => names.first(),
+        };
+        match found {
+            Some((_, jo)) => Target::Output(*jo),
+            _ => match &self.args.output {
+                Some(name) => fatal!("There is no output named {}", name),
+                _ => fatal!("The compositor does not have any outputs"),
+            },
+        }
+    }
+
+    async fn select_toplevel(&self, comp: JayCompositorId) -> Target {
+        let tc = &self.tc;
+        let seat = self.bind_seat();
+        let id = tc.id();
+        tc.send(SelectToplevel {
+            self_id: comp,
+            id,
+            seat,
+        });
+        let res = Rc::new(AsyncQueue::new());
+        jay_select_toplevel::Done::handle(tc, id, res.clone(), |res, ev| {
+            res.push(ev.id);
+        });
+        let tl = res.pop().await;
+        if tl.is_none() {
+            fatal!("No window was selected");
+        }
+        Target::Toplevel(tl)
+    }
+
+    async fn select_workspace(&self, comp: JayCompositorId) -> Target {
+        let tc = &self.tc;
+        let seat = self.bind_seat();
+        let id = tc.id();
+        tc.send(SelectWorkspace {
+            self_id: comp,
+            id,
+            seat,
+        });
+        let res = Rc::new(AsyncQueue::new());
+        jay_select_workspace::Cancelled::handle(tc, id, res.clone(), |res, _| {
+            res.push(None);
+        });
+        jay_select_workspace::Selected::handle(tc, id, res.clone(), |res, ev| {
+            res.push(Some((ev.output, ev.id)));
+        });
+        let Some((output, ws)) = res.pop().await else {
+            fatal!("No workspace was selected");
+        };
+        let (_, jo) = self.bind_output(comp, output);
+        Target::Workspace(jo, ws)
+    }
+
+    fn import_buffers(&self) {
+        let Some(gbm) = self.gbm.get() else {
+            return;
+        };
+        let mut buffers = self.buffers.borrow_mut();
+        buffers.clear();
+        for dmabuf in self.pending_buffers.borrow_mut().drain(..) {
+            match gbm.import_dmabuf(&dmabuf, GBM_BO_USE_LINEAR) {
+                Ok(bo) => buffers.push(Rc::new(bo)),
+                Err(e) => fatal!("Could not import screencast buffer: {}", ErrorFmt(e)),
+            }
+        }
+    }
+
+    fn copy_frame(&self, idx: usize) {
+        let Some(bo) = self.buffers.borrow().get(idx).cloned() else {
+            return;
+        };
+        let dmabuf = bo.dmabuf();
+        let (width, height) = match self.size.get() {
+            Some(s) => s,
+            None => {
+                let size = (dmabuf.width, dmabuf.height);
+                self.size.set(Some(size));
+                self.start_encoder(size);
+                size
+            }
+        };
+        let map = match bo.map() {
+            Ok(map) => map,
+            Err(e) => {
+                log::error!("Could not map screencast buffer: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let data = unsafe { map.data() };
+        let src_stride = map.stride() as usize;
+        let dst_stride = width as usize * 4;
+        let row_len = dmabuf.width.min(width) as usize * 4;
+        let mut frame = Buf::new(dst_stride * height as usize);
+        for row in 0..dmabuf.height.min(height) as usize {
+            let src = &data[row * src_stride..][..row_len];
+            frame[row * dst_stride..][..row_len].copy_from_slice(src);
+        }
+        *self.frame.borrow_mut() = Some(frame);
+    }
+
+    fn start_encoder(&self, (width, height): (i32, i32)) {
+        let filename = match &self.args.filename {
+            Some(f) => f.clone(),
+            _ => "%Y-%m-%d-%H%M%S_jay.mkv".to_string(),
+        };
+        let filename = Local::now().format(&filename).to_string();
+        let mut command = Command::new(&self.args.encoder);
+        command
+            .args(["-hide_banner", "-loglevel", "error", "-y"])
+            .args(["-f", "rawvideo", "-pix_fmt", "bgr0"])
+            .args(["-video_size", &format!("{width}x{height}")])
+            .args(["-framerate", &self.args.fps.to_string()])
+            .args(["-i", "-"])
+            .args(["-vf", "pad=ceil(iw/2)*2:ceil(ih/2)*2"])
+            .args(["-c:v", &self.args.codec, "-pix_fmt", "yuv420p"])
+            .arg(&filename)
+            .stdin(Stdio::piped())
+            // Keep the encoder out of the foreground process group so that it is not
+            // interrupted before it has received all frames.
+            .process_group(0);
+        // The signals are blocked in this process so that they can be read from a
+        // signalfd. The encoder inherits the signal mask and would otherwise ignore them.
+        let mut signals: c::sigset_t = uapi::pod_zeroed();
+        for signal in [c::SIGINT, c::SIGTERM] {
+            uapi::sigaddset(&mut signals, signal).unwrap();
+        }
+        unsafe {
+            command.pre_exec(move || {
+                uapi::pthread_sigmask(c::SIG_UNBLOCK, Some(&signals), None)?;
+                Ok(())
+            });
+        }
+        let mut child = match command.spawn() {
+            Ok(c) => c,
+            Err(e) => fatal!("Could not spawn `{}`: {}", self.args.encoder, ErrorFmt(e)),
+        };
+        let stdin = child.stdin.take().unwrap();
+        let stdin = Rc::new(OwnedFd::new(stdin.into_raw_fd()));
+        log::info!("Recording to {}", filename);
+        *self.encoder.borrow_mut() = Some(Encoder { child, stdin });
+    }
+
+    async fn write_frames(self: Rc<Self>) {
+        let fps = self.args.fps as u64;
+        let max_frames = self
+            .args
+            .duration
+            .map(|d| (d.as_micros() as u64 * fps).div_ceil(1_000_000));
+        let mut start = None;
+        let mut frames = 0;
+        while !self.stopping.get() {
+            let Some(mut frame) = self.frame.borrow_mut().as_mut().map(Buf::clone) else {
+                if let Err(e) = self.tc.wheel.timeout(1000 / fps).await {
+                    fatal!("Could not wait for the next frame: {}", ErrorFmt(e));
+                }
+                continue;
+            };
+            let Some(stdin) = self.encoder.borrow().as_ref().map(|e| e.stdin.clone()) else {
+                return;
+            };
+            let mut pos = 0;
+            while pos < frame.len() {
+                match self.tc.ring.write(&stdin, frame.slice(pos..), None).await {
+                    Ok(n) => pos += n,
+                    Err(e) => {
+                        log::error!("Could not write to the encoder: {}", ErrorFmt(e));
+                        self.done.trigger();
+                        return;
+                    }
+                }
+            }
+            frames += 1;
+            if Some(frames) == max_frames {
+                self.done.trigger();
+                return;
+            }
+            let start = *start.get_or_insert_with(now_usec);
+            let next = start + frames * 1_000_000 / fps;
+            let now = now_usec();
+            if next > now {
+                if let Err(e) = self.tc.wheel.timeout((next - now) / 1000).await {
+                    fatal!("Could not wait for the next frame: {}", ErrorFmt(e));
+                }
+            }
+        }
+    }
+
+    async fn finish(&self) {
+        let Some(encoder) = self.encoder.borrow_mut().take() else {
+            fatal!("No frame was recorded");
+        };
+        let Encoder { mut child, stdin } = encoder;
+        drop(stdin);
+        match child.wait() {
+            Ok(s) if s.success() => {}
+            Ok(s) => fatal!("The encoder exited with {}", s),
+            Err(e) => fatal!("Could not wait for the encoder: {}", ErrorFmt(e)),
+        }
+    }
+}
@@ -115,16 +115,21 @@ pub fn buf_to_bytes(dma_buf_ids: &DmaBufIds, buf: &Dmabuf, format: ScreenshotFor
     };
     let data = unsafe { bo_map.data() };
     if format == ScreenshotFormat::Qoi {
-        return xrgb8888_encode_qoi(data, buf.width, buf.height, buf.stride);
+        return xrgb8888_encode_qoi(data, buf.width, buf.height, bo_map.stride() as u32);
     }
+    xrgb8888_encode_png(data, buf.width, buf.height, bo_map.stride() as u32)
+}
 
+pub fn xrgb8888_encode_png(data: &[u8], width: u32, height: u32, stride: u32) -> Vec<u8> {
     let mut out = vec![];
     {
-        let mut image_data = Vec::with_capacity(data.len());
-        for i in 0..data.len() / 4 {
-            image_data.extend_from_slice(&[data[4 * i + 2], data[4 * i + 1], data[4 * i + 0], 255])
+        let mut image_data = Vec::with_capacity((width * height * 4) as usize);
+        for row in data.chunks(stride as usize).take(height as usize) {
+            for px in row[..(width * 4) as usize].chunks_exact(4) {
+                image_data.extend_from_slice(&[px[2], px[1], px[0], 255])
+            }
         }
-        let mut encoder = Encoder::new(&mut out, buf.width, buf.height);
+        let mut encoder = Encoder::new(&mut out, width, height);
         encoder.set_color(ColorType::Rgba);
         encoder.set_depth(BitDepth::Eight);
         encoder.set_srgb(SrgbRenderingIntent::Perceptual);
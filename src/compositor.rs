@@ -161,6 +161,8 @@ fn start_compositor2(
         backend_events: AsyncQueue::new(),
         seat_ids: Default::default(),
         seat_queue: Default::default(),
+        scratchpad: Default::default(),
+        marks: Default::default(),
         slow_clients: AsyncQueue::new(),
         none_surface_ext: Rc::new(NoneSurfaceExt),
         tree_changed_sent: Cell::new(false),
@@ -168,6 +170,8 @@ fn start_compositor2(
         input_device_ids: Default::default(),
         input_device_handlers: Default::default(),
         theme: Default::default(),
+        animations: Default::default(),
+        damage_log: Default::default(),
         pending_container_layout: Default::default(),
         pending_container_render_data: Default::default(),
         pending_output_render_data: Default::default(),
@@ -183,6 +187,7 @@ fn start_compositor2(
         outputs: Default::default(),
         drm_devs: Default::default(),
         status: Default::default(),
+        status_blocks: Default::default(),
         idle: IdleState {
             input: Default::default(),
             change: Default::default(),
@@ -190,7 +195,15 @@ fn start_compositor2(
             timeout_changed: Default::default(),
             inhibitors: Default::default(),
             inhibitors_changed: Default::default(),
+            registered_inhibitors: Default::default(),
+            inhibitors_ignored: Default::default(),
+            inhibitors_ignored_timeout: Default::default(),
+            inhibitors_ignored_changed: Default::default(),
+            fullscreen_inhibits: Default::default(),
             backend_idle: Cell::new(true),
+            stages: Default::default(),
+            stages_changed: Default::default(),
+            dimmed: Default::default(),
         },
         run_args,
         xwayland: XWaylandState {
@@ -213,6 +226,8 @@ fn start_compositor2(
         lock: ScreenlockState {
             locked: Cell::new(false),
             lock: Default::default(),
+            builtin: Default::default(),
+            changed: Default::default(),
         },
         scales,
         cursor_sizes: Default::default(),
@@ -220,7 +235,9 @@ fn start_compositor2(
         testers: Default::default(),
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
+        appearance_watchers: Default::default(),
         default_workspace_capture: Cell::new(true),
+        default_workspace_layout: Default::default(),
         default_gfx_api: Cell::new(GfxApi::OpenGl),
         activation_tokens: Default::default(),
         toplevel_lists: Default::default(),
@@ -461,6 +478,12 @@ fn create_dummy_output(state: &Rc<State>) {
         screencasts: Default::default(),
         hardware_cursor_needs_render: Cell::new(false),
         screencopies: Default::default(),
+        workspace_transition: Default::default(),
+        closing_windows: Default::default(),
+        last_presentation_usec: Cell::new(0),
+        animation_frame_pending: Cell::new(false),
+        lock_frame_pending: Cell::new(false),
+        lock_presented: Cell::new(false),
     });
     let dummy_workspace = Rc::new(WorkspaceNode {
         id: state.node_ids.next(),
@@ -482,6 +505,9 @@ fn create_dummy_output(state: &Rc<State>) {
         title_texture: Cell::new(None),
         attention_requests: Default::default(),
         render_highlight: Default::default(),
+        layout: Default::default(),
+        retile_scheduled: Cell::new(false),
+        scroll: Default::default(),
     });
     *dummy_workspace.output_link.borrow_mut() =
         Some(dummy_output.workspaces.add_last(dummy_workspace.clone()));
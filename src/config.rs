@@ -17,11 +17,11 @@ use {
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{InitMessage, ServerFeature, ServerMessage, V1InitMessage},
+            ipc::{InitMessage, ServerFeature, ServerMessage, StatusClick, V1InitMessage},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat, SwitchEvent},
-        keyboard::{mods::Modifiers, syms::KeySym},
+        input::{InputDevice, Seat, SwitchEvent, TabletPadControl},
+        keyboard::{mods::Modifiers, syms::KeySym, KeyboardLayout},
         video::{Connector, DrmDevice},
     },
     libloading::Library,
@@ -145,6 +145,30 @@ impl ConfigProxy {
         self.send(&ServerMessage::Idle);
     }
 
+    /// Returns whether the config handles this event.
+    pub fn before_sleep(&self) -> bool {
+        let Some(handler) = self.handler.get() else {
+            return false;
+        };
+        if !handler.before_sleep_enabled.get() {
+            return false;
+        }
+        handler.send(&ServerMessage::BeforeSleep);
+        true
+    }
+
+    pub fn idle_stage_entered(&self, name: &str) {
+        self.send(&ServerMessage::IdleStageEntered {
+            name: name.to_string(),
+        });
+    }
+
+    pub fn idle_stage_resumed(&self, name: &str) {
+        self.send(&ServerMessage::IdleStageResumed {
+            name: name.to_string(),
+        });
+    }
+
     pub fn switch_event(&self, seat: SeatId, input_device: InputDeviceId, event: SwitchEvent) {
         self.send(&ServerMessage::SwitchEvent {
             seat: Seat(seat.raw() as _),
@@ -152,6 +176,32 @@ impl ConfigProxy {
             event,
         });
     }
+
+    pub fn tablet_pad_event(
+        &self,
+        input_device: InputDeviceId,
+        control: TabletPadControl,
+        index: u32,
+        delta: f64,
+    ) {
+        self.send(&ServerMessage::TabletPadEvent {
+            input_device: InputDevice(input_device.raw() as _),
+            control,
+            index,
+            delta,
+        });
+    }
+
+    pub fn status_clicked(&self, click: StatusClick) {
+        self.send(&ServerMessage::StatusClicked { click });
+    }
+
+    pub fn keyboard_layout_changed(&self, seat: SeatId, layout: KeyboardLayout) {
+        self.send(&ServerMessage::KeyboardLayoutChanged {
+            seat: Seat(seat.raw() as _),
+            layout,
+        });
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -201,6 +251,7 @@ impl ConfigProxy {
             timers_by_id: Default::default(),
             pollable_id: Default::default(),
             pollables: Default::default(),
+            before_sleep_enabled: Default::default(),
         });
         let init_msg = bincode_ops()
             .serialize(&InitMessage::V1(V1InitMessage {}))
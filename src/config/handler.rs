@@ -1,20 +1,26 @@
 use {
     crate::{
+        animation::AnimationConfig,
         async_engine::SpawnedFuture,
         backend::{
-            self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceCapability,
-            InputDeviceId,
+            self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceAccelType,
+            InputDeviceCapability, InputDeviceClickMethod, InputDeviceId, InputDeviceScrollMethod,
+            InputDeviceSendEventsMode, InputDeviceTapButtonMap,
         },
         compositor::MAX_EXTENTS,
         config::ConfigProxy,
-        ifs::wl_seat::{SeatId, WlSeatGlobal},
+        ifs::wl_seat::{
+            tablet::{PressureCurve, TabletArea},
+            SeatId, WlSeatGlobal,
+        },
         io_uring::TaskResultExt,
+        rect::Rect,
         scale::Scale,
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized, DEFAULT_FONT},
         tree::{
             move_ws_to_output, ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase,
-            OutputNode, WsMoveConfig,
+            OutputNode, WorkspaceNode, WsMoveConfig,
         },
         utils::{
             asyncevent::AsyncEvent,
@@ -32,23 +38,39 @@ use {
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage, WorkspaceSource},
+            ipc::{ClientMessage, Response, ServerMessage, StatusBlock, WorkspaceSource},
             PollableId, WireMode,
         },
+        animation::{Animation, AnimationCurve, WorkspaceAnimationStyle},
         input::{
-            acceleration::{AccelProfile, ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_FLAT},
+            acceleration::{
+                AccelProfile, AccelType, ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_CUSTOM,
+                ACCEL_PROFILE_FLAT, ACCEL_TYPE_FALLBACK, ACCEL_TYPE_MOTION, ACCEL_TYPE_SCROLL,
+            },
             capability::{
                 Capability, CAP_GESTURE, CAP_KEYBOARD, CAP_POINTER, CAP_SWITCH, CAP_TABLET_PAD,
                 CAP_TABLET_TOOL, CAP_TOUCH,
             },
-            FocusFollowsMouseMode, InputDevice, Seat,
+            click_method::{
+                ClickMethod, CLICK_METHOD_BUTTON_AREAS, CLICK_METHOD_CLICKFINGER, CLICK_METHOD_NONE,
+            },
+            scroll_method::{
+                ScrollMethod, SCROLL_METHOD_EDGE, SCROLL_METHOD_NONE, SCROLL_METHOD_ON_BUTTON_DOWN,
+                SCROLL_METHOD_TWO_FINGER,
+            },
+            send_events::{
+                SendEventsMode, SEND_EVENTS_DISABLED, SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE,
+                SEND_EVENTS_ENABLED,
+            },
+            tap_button_map::{TapButtonMap, TAP_BUTTON_MAP_LMR, TAP_BUTTON_MAP_LRM},
+            FocusFollowsMouseMode, InputDevice, Seat, TabletPadControl,
         },
-        keyboard::{mods::Modifiers, syms::KeySym, Keymap},
+        keyboard::{mods::Modifiers, syms::KeySym, Keymap, KeymapNames},
         logging::LogLevel,
-        theme::{colors::Colorable, sized::Resizable},
+        theme::{colors::Colorable, sized::Resizable, ColorScheme},
         timer::Timer as JayTimer,
         video::{Connector, DrmDevice, GfxApi, Transform},
-        Axis, Direction, Workspace,
+        Axis, ContainerLayout, Direction, IdleStage, Workspace, WorkspaceLayout,
     },
     libloading::Library,
     log::Level,
@@ -80,6 +102,8 @@ pub(super) struct ConfigProxyHandler {
 
     pub pollable_id: NumCell<u64>,
     pub pollables: CopyHashMap<PollableId, Rc<Pollable>>,
+
+    pub before_sleep_enabled: Cell<bool>,
 }
 
 pub struct Pollable {
@@ -273,6 +297,9 @@ impl ConfigProxyHandler {
             for seat in self.state.globals.seats.lock().values() {
                 seat.clear_shortcuts();
             }
+            for dev in self.state.input_device_handlers.borrow().values() {
+                dev.data.tablet_pad_bindings.borrow_mut().clear();
+            }
         }
         config.configure(true);
         self.state.config.set(Some(Rc::new(config)));
@@ -349,6 +376,91 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_input_region(
+        &self,
+        device: InputDevice,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let region = Rect::new_sized(x, y, width, height)
+            .filter(|r| !r.is_empty())
+            .ok_or(CphError::InvalidInputRegion)?;
+        dev.set_region(region);
+        Ok(())
+    }
+
+    fn handle_set_tablet_area(
+        &self,
+        device: InputDevice,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let area = TabletArea {
+            x,
+            y,
+            width,
+            height,
+        };
+        if !area.is_valid() {
+            return Err(CphError::InvalidTabletArea);
+        }
+        dev.tablet_area.set(area);
+        Ok(())
+    }
+
+    fn handle_set_tablet_keep_aspect_ratio(
+        &self,
+        device: InputDevice,
+        keep: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.tablet_keep_aspect_ratio.set(keep);
+        Ok(())
+    }
+
+    fn handle_set_tablet_pressure_curve(
+        &self,
+        device: InputDevice,
+        curve: Option<[f64; 4]>,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let curve = match curve {
+            None => None,
+            Some([x1, y1, x2, y2]) => {
+                let curve = PressureCurve { x1, y1, x2, y2 };
+                if !curve.is_valid() {
+                    return Err(CphError::InvalidPressureCurve);
+                }
+                Some(curve)
+            }
+        };
+        dev.tablet_pressure_curve.set(curve);
+        Ok(())
+    }
+
+    fn handle_set_tablet_pad_binding(
+        &self,
+        device: InputDevice,
+        control: TabletPadControl,
+        index: u32,
+        bound: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let bindings = &mut *dev.tablet_pad_bindings.borrow_mut();
+        if bound {
+            bindings.insert((control, index), None);
+        } else {
+            bindings.remove(&(control, index));
+        }
+        Ok(())
+    }
+
     fn handle_remove_input_mapping(&self, input_device: InputDevice) -> Result<(), CphError> {
         let dev = self.get_device_handler_data(input_device)?;
         dev.set_output(None);
@@ -359,6 +471,11 @@ impl ConfigProxyHandler {
         self.state.set_status(status);
     }
 
+    fn handle_set_status_blocks(&self, status: &str, blocks: Vec<StatusBlock>) {
+        let blocks = blocks.into_iter().map(|b| b.start..b.end).collect();
+        self.state.set_status_blocks(status, blocks);
+    }
+
     fn get_timer(&self, timer: JayTimer) -> Result<Rc<TimerData>, CphError> {
         match self.timers_by_id.get(&timer.0) {
             Some(t) => Ok(t),
@@ -481,6 +598,60 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_keymap_from_names(&self, names: KeymapNames) -> Result<(), CphError> {
+        let res = self.state.xkb_ctx.keymap_from_names(
+            names.rules.as_deref(),
+            names.model.as_deref(),
+            names.layout.as_deref(),
+            names.variant.as_deref(),
+            names.options.as_deref(),
+        );
+        let (keymap, res) = match res {
+            Ok(keymap) => {
+                let id = Keymap(self.id());
+                self.keymaps.set(id, keymap);
+                (id, Ok(()))
+            }
+            Err(e) => (Keymap::INVALID, Err(CphError::ParseKeymapError(e))),
+        };
+        self.respond(Response::ParseKeymap { keymap });
+        res
+    }
+
+    fn handle_seat_set_keyboard_layout(
+        &self,
+        seat: Seat,
+        layout: Option<u32>,
+    ) -> Result<(), CphError> {
+        self.get_seat(seat)?.set_keyboard_layout(layout);
+        Ok(())
+    }
+
+    fn handle_set_remember_keyboard_layout(
+        &self,
+        seat: Seat,
+        remember: bool,
+    ) -> Result<(), CphError> {
+        self.get_seat(seat)?.set_remember_keyboard_layout(remember);
+        Ok(())
+    }
+
+    fn handle_seat_get_keyboard_layout(&self, seat: Seat) -> Result<(), CphError> {
+        let layout = self.get_seat(seat)?.keyboard_layout();
+        self.respond(Response::SeatGetKeyboardLayout { layout });
+        Ok(())
+    }
+
+    fn handle_device_set_keyboard_layout(
+        &self,
+        device: InputDevice,
+        layout: Option<u32>,
+    ) -> Result<(), CphError> {
+        self.get_device_handler_data(device)?
+            .set_keyboard_layout(layout);
+        Ok(())
+    }
+
     fn handle_get_repeat_rate(&self, seat: Seat) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         let (rate, delay) = seat.get_rate();
@@ -615,12 +786,31 @@ impl ConfigProxyHandler {
         let profile = match accel_profile {
             ACCEL_PROFILE_FLAT => InputDeviceAccelProfile::Flat,
             ACCEL_PROFILE_ADAPTIVE => InputDeviceAccelProfile::Adaptive,
+            ACCEL_PROFILE_CUSTOM => InputDeviceAccelProfile::Custom,
             _ => return Err(CphError::UnknownAccelProfile(accel_profile)),
         };
         dev.device.set_accel_profile(profile);
         Ok(())
     }
 
+    fn handle_set_custom_accel_function(
+        &self,
+        device: InputDevice,
+        ty: AccelType,
+        step: f64,
+        points: &[f64],
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let ty = match ty {
+            ACCEL_TYPE_FALLBACK => InputDeviceAccelType::Fallback,
+            ACCEL_TYPE_MOTION => InputDeviceAccelType::Motion,
+            ACCEL_TYPE_SCROLL => InputDeviceAccelType::Scroll,
+            _ => return Err(CphError::UnknownAccelType(ty)),
+        };
+        dev.device.set_custom_accel_function(ty, step, points);
+        Ok(())
+    }
+
     fn handle_set_accel_speed(&self, device: InputDevice, speed: f64) -> Result<(), CphError> {
         let dev = self.get_device_handler_data(device)?;
         dev.device.set_accel_speed(speed);
@@ -665,6 +855,114 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_click_method(
+        &self,
+        device: InputDevice,
+        method: ClickMethod,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let method = match method {
+            CLICK_METHOD_NONE => InputDeviceClickMethod::None,
+            CLICK_METHOD_BUTTON_AREAS => InputDeviceClickMethod::ButtonAreas,
+            CLICK_METHOD_CLICKFINGER => InputDeviceClickMethod::Clickfinger,
+            _ => return Err(CphError::UnknownClickMethod(method)),
+        };
+        dev.device.set_click_method(method);
+        Ok(())
+    }
+
+    fn handle_set_middle_button_emulation_enabled(
+        &self,
+        device: InputDevice,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_middle_button_emulation_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_set_scroll_method(
+        &self,
+        device: InputDevice,
+        method: ScrollMethod,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let method = match method {
+            SCROLL_METHOD_NONE => InputDeviceScrollMethod::NoScroll,
+            SCROLL_METHOD_TWO_FINGER => InputDeviceScrollMethod::TwoFinger,
+            SCROLL_METHOD_EDGE => InputDeviceScrollMethod::Edge,
+            SCROLL_METHOD_ON_BUTTON_DOWN => InputDeviceScrollMethod::OnButtonDown,
+            _ => return Err(CphError::UnknownScrollMethod(method)),
+        };
+        dev.device.set_scroll_method(method);
+        Ok(())
+    }
+
+    fn handle_set_scroll_button(&self, device: InputDevice, button: u32) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_scroll_button(button);
+        Ok(())
+    }
+
+    fn handle_set_disable_while_typing_enabled(
+        &self,
+        device: InputDevice,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_disable_while_typing_enabled(enabled);
+        Ok(())
+    }
+
+    fn handle_set_tap_button_map(
+        &self,
+        device: InputDevice,
+        map: TapButtonMap,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let map = match map {
+            TAP_BUTTON_MAP_LRM => InputDeviceTapButtonMap::LeftRightMiddle,
+            TAP_BUTTON_MAP_LMR => InputDeviceTapButtonMap::LeftMiddleRight,
+            _ => return Err(CphError::UnknownTapButtonMap(map)),
+        };
+        dev.device.set_tap_button_map(map);
+        Ok(())
+    }
+
+    fn handle_set_send_events_mode(
+        &self,
+        device: InputDevice,
+        mode: SendEventsMode,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let mode = match mode {
+            SEND_EVENTS_ENABLED => InputDeviceSendEventsMode::Enabled,
+            SEND_EVENTS_DISABLED => InputDeviceSendEventsMode::Disabled,
+            SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE => {
+                InputDeviceSendEventsMode::DisabledOnExternalMouse
+            }
+            _ => return Err(CphError::UnknownSendEventsMode(mode)),
+        };
+        dev.device.set_send_events_mode(mode);
+        Ok(())
+    }
+
+    fn handle_set_rotation(&self, device: InputDevice, degrees: u32) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_rotation(degrees);
+        Ok(())
+    }
+
+    fn handle_set_calibration_matrix(
+        &self,
+        device: InputDevice,
+        matrix: [[f32; 3]; 2],
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        dev.device.set_calibration_matrix(matrix);
+        Ok(())
+    }
+
     fn handle_set_transform_matrix(
         &self,
         device: InputDevice,
@@ -817,24 +1115,57 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
-    fn handle_move_to_output(
+    fn get_workspace_node(
         &self,
         workspace: WorkspaceSource,
-        connector: Connector,
-    ) -> Result<(), CphError> {
-        let output = self.get_output_node(connector)?;
+    ) -> Result<Option<Rc<WorkspaceNode>>, CphError> {
         let ws = match workspace {
             WorkspaceSource::Explicit(ws) => {
                 let name = self.get_workspace(ws)?;
-                match self.state.workspaces.get(name.as_str()) {
-                    Some(ws) => ws,
-                    _ => return Ok(()),
-                }
+                self.state.workspaces.get(name.as_str())
             }
-            WorkspaceSource::Seat(s) => match self.get_seat(s)?.get_output().workspace.get() {
-                Some(ws) => ws,
-                _ => return Ok(()),
-            },
+            WorkspaceSource::Seat(s) => self.get_seat(s)?.get_output().workspace.get(),
+        };
+        Ok(ws)
+    }
+
+    fn handle_get_workspace_layout(&self, workspace: WorkspaceSource) -> Result<(), CphError> {
+        let layout = match self.get_workspace_node(workspace)? {
+            Some(ws) => ws.layout.get(),
+            _ => self.state.default_workspace_layout.get(),
+        };
+        self.respond(Response::GetWorkspaceLayout {
+            layout: layout.into(),
+        });
+        Ok(())
+    }
+
+    fn handle_set_workspace_layout(
+        &self,
+        workspace: WorkspaceSource,
+        layout: WorkspaceLayout,
+    ) -> Result<(), CphError> {
+        if let Some(ws) = self.get_workspace_node(workspace)? {
+            if !ws.is_dummy {
+                ws.set_layout(layout.into());
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_set_default_workspace_layout(&self, layout: WorkspaceLayout) {
+        self.state.default_workspace_layout.set(layout.into());
+    }
+
+    fn handle_move_to_output(
+        &self,
+        workspace: WorkspaceSource,
+        connector: Connector,
+    ) -> Result<(), CphError> {
+        let output = self.get_output_node(connector)?;
+        let ws = match self.get_workspace_node(workspace)? {
+            Some(ws) => ws,
+            _ => return Ok(()),
         };
         if ws.is_dummy || output.is_dummy {
             return Ok(());
@@ -861,6 +1192,22 @@ impl ConfigProxyHandler {
         self.state.idle.set_timeout(timeout);
     }
 
+    fn handle_set_idle_stages(&self, stages: Vec<IdleStage>) {
+        self.state.idle.set_stages(stages);
+    }
+
+    fn handle_enable_before_sleep(&self) {
+        self.before_sleep_enabled.set(true);
+    }
+
+    fn handle_lock(&self) {
+        self.state.lock_builtin();
+    }
+
+    fn handle_set_fullscreen_inhibits_idle(&self, enabled: bool) {
+        self.state.idle.set_fullscreen_inhibits(enabled);
+    }
+
     fn handle_set_explicit_sync_enabled(&self, enabled: bool) {
         self.state.explicit_sync_enabled.set(enabled);
     }
@@ -1276,6 +1623,97 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_sticky(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        self.respond(Response::GetSticky {
+            sticky: seat.get_sticky().unwrap_or(false),
+        });
+        Ok(())
+    }
+
+    fn handle_set_sticky(&self, seat: Seat, sticky: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_sticky(sticky);
+        Ok(())
+    }
+
+    fn handle_get_container_layout(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let layout = match (seat.get_mono(), seat.get_stacked(), seat.get_split()) {
+            (Some(true), Some(true), _) => ContainerLayout::Stacked,
+            (Some(true), _, _) => ContainerLayout::Tabbed,
+            (_, _, Some(ContainerSplit::Vertical)) => ContainerLayout::SplitVertical,
+            _ => ContainerLayout::SplitHorizontal,
+        };
+        self.respond(Response::GetContainerLayout { layout });
+        Ok(())
+    }
+
+    fn handle_set_container_layout(
+        &self,
+        seat: Seat,
+        layout: ContainerLayout,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        match layout {
+            ContainerLayout::SplitHorizontal | ContainerLayout::SplitVertical => {
+                let split = match layout {
+                    ContainerLayout::SplitVertical => ContainerSplit::Vertical,
+                    _ => ContainerSplit::Horizontal,
+                };
+                seat.set_mono(false);
+                seat.set_split(split);
+            }
+            ContainerLayout::Tabbed | ContainerLayout::Stacked => {
+                seat.set_stacked(layout == ContainerLayout::Stacked);
+                seat.set_mono(true);
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.mark(mark);
+        Ok(())
+    }
+
+    fn handle_unmark(&self, seat: Seat, mark: Option<&str>) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.unmark(mark);
+        Ok(())
+    }
+
+    fn handle_focus_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.focus_mark(mark);
+        Ok(())
+    }
+
+    fn handle_swap_with_mark(&self, seat: Seat, mark: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.swap_with_mark(mark);
+        Ok(())
+    }
+
+    fn handle_move_to_scratchpad(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.move_to_scratchpad();
+        Ok(())
+    }
+
+    fn handle_toggle_scratchpad(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.toggle_scratchpad();
+        Ok(())
+    }
+
+    fn handle_set_window_opacity(&self, seat: Seat, opacity: f64) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_window_opacity(opacity as f32);
+        Ok(())
+    }
+
     fn handle_add_pollable(self: &Rc<Self>, fd: i32) -> Result<(), CphError> {
         let fd = match fcntl_dupfd_cloexec(fd, 0) {
             Ok(fd) => Rc::new(fd),
@@ -1376,6 +1814,7 @@ impl ConfigProxyHandler {
             }
         }
         self.state.root.clone().node_visit(&mut V);
+        self.state.appearance_changed();
     }
 
     fn get_sized(&self, sized: Resizable) -> Result<ThemeSized, CphError> {
@@ -1383,6 +1822,12 @@ impl ConfigProxyHandler {
         let sized = match sized {
             TITLE_HEIGHT => ThemeSized::title_height,
             BORDER_WIDTH => ThemeSized::border_width,
+            INNER_GAP => ThemeSized::inner_gap,
+            OUTER_GAP => ThemeSized::outer_gap,
+            CORNER_RADIUS => ThemeSized::corner_radius,
+            SHADOW_SIZE => ThemeSized::shadow_size,
+            BLUR_PASSES => ThemeSized::blur_passes,
+            BLUR_OFFSET => ThemeSized::blur_offset,
             _ => return Err(CphError::UnknownSized(sized.0)),
         };
         Ok(sized)
@@ -1413,6 +1858,79 @@ impl ConfigProxyHandler {
         self.colors_changed();
     }
 
+    fn handle_set_color_scheme(&self, scheme: ColorScheme) {
+        self.state.theme.color_scheme.set(scheme);
+        self.state.appearance_changed();
+    }
+
+    fn handle_set_high_contrast(&self, enabled: bool) {
+        self.state.theme.high_contrast.set(enabled);
+        self.state.appearance_changed();
+    }
+
+    fn handle_set_smart_gaps(&self, enabled: bool) {
+        self.state.theme.smart_gaps.set(enabled);
+        self.spaces_change();
+    }
+
+    fn handle_set_blurred_app_ids(&self, app_ids: Vec<String>) {
+        *self.state.theme.blur_app_ids.borrow_mut() = app_ids.into_iter().collect();
+        self.state.damage();
+    }
+
+    fn handle_set_blurred_layer_namespaces(&self, namespaces: Vec<String>) {
+        *self.state.theme.blur_layer_namespaces.borrow_mut() = namespaces.into_iter().collect();
+        self.state.damage();
+    }
+
+    fn handle_set_window_opacities(&self, opacities: Vec<(String, f64)>) {
+        *self.state.theme.window_opacities.borrow_mut() = opacities
+            .into_iter()
+            .map(|(app_id, opacity)| (app_id, (opacity as f32).clamp(0.0, 1.0)))
+            .collect();
+        self.state.damage();
+    }
+
+    fn handle_set_animations_enabled(&self, enabled: bool) {
+        self.state.animations.enabled.set(enabled);
+    }
+
+    fn get_animation(&self, animation: Animation) -> Result<&AnimationConfig, CphError> {
+        match self.state.animations.config(animation) {
+            Some(config) => Ok(config),
+            _ => Err(CphError::UnknownAnimation(animation.0)),
+        }
+    }
+
+    fn handle_set_animation_duration(
+        &self,
+        animation: Animation,
+        duration: Duration,
+    ) -> Result<(), CphError> {
+        let config = self.get_animation(animation)?;
+        let usec = duration.as_micros().try_into().unwrap_or(u64::MAX);
+        config.duration_usec.set(usec);
+        Ok(())
+    }
+
+    fn handle_set_animation_curve(
+        &self,
+        animation: Animation,
+        curve: AnimationCurve,
+    ) -> Result<(), CphError> {
+        let config = self.get_animation(animation)?;
+        config.curve.set(curve.into());
+        Ok(())
+    }
+
+    fn handle_set_workspace_animation_style(&self, style: WorkspaceAnimationStyle) {
+        self.state.animations.workspace_style.set(style);
+    }
+
+    fn handle_reset_animations(&self) {
+        self.state.animations.reset();
+    }
+
     fn handle_reset_sizes(&self) {
         self.state.theme.sizes.reset();
         self.spaces_change();
@@ -1452,6 +1970,7 @@ impl ConfigProxyHandler {
             BAR_STATUS_TEXT_COLOR => &colors.bar_text,
             ATTENTION_REQUESTED_BACKGROUND_COLOR => &colors.attention_requested_background,
             HIGHLIGHT_COLOR => &colors.highlight,
+            SHADOW_COLOR => &colors.shadow,
             _ => return Err(CphError::UnknownColor(colorable.0)),
         };
         Ok(colorable)
@@ -1780,6 +2299,12 @@ impl ConfigProxyHandler {
                 .handle_get_input_device_devnode(device)
                 .wrn("get_input_device_devnode")?,
             ClientMessage::SetIdle { timeout } => self.handle_set_idle(timeout),
+            ClientMessage::SetIdleStages { stages } => self.handle_set_idle_stages(stages),
+            ClientMessage::SetFullscreenInhibitsIdle { enabled } => {
+                self.handle_set_fullscreen_inhibits_idle(enabled)
+            }
+            ClientMessage::EnableBeforeSleep => self.handle_enable_before_sleep(),
+            ClientMessage::Lock => self.handle_lock(),
             ClientMessage::MoveToOutput {
                 workspace,
                 connector,
@@ -1816,6 +2341,161 @@ impl ConfigProxyHandler {
             ClientMessage::RemoveInputMapping { input_device } => self
                 .handle_remove_input_mapping(input_device)
                 .wrn("remove_input_mapping")?,
+            ClientMessage::SetColorScheme { scheme } => self.handle_set_color_scheme(scheme),
+            ClientMessage::SetHighContrast { enabled } => self.handle_set_high_contrast(enabled),
+            ClientMessage::SetSmartGaps { enabled } => self.handle_set_smart_gaps(enabled),
+            ClientMessage::SetWindowOpacity { seat, opacity } => self
+                .handle_set_window_opacity(seat, opacity)
+                .wrn("set_window_opacity")?,
+            ClientMessage::SetBlurredAppIds { app_ids } => self.handle_set_blurred_app_ids(app_ids),
+            ClientMessage::SetBlurredLayerNamespaces { namespaces } => {
+                self.handle_set_blurred_layer_namespaces(namespaces)
+            }
+            ClientMessage::SetAnimationsEnabled { enabled } => {
+                self.handle_set_animations_enabled(enabled)
+            }
+            ClientMessage::SetAnimationDuration {
+                animation,
+                duration,
+            } => self
+                .handle_set_animation_duration(animation, duration)
+                .wrn("set_animation_duration")?,
+            ClientMessage::SetAnimationCurve { animation, curve } => self
+                .handle_set_animation_curve(animation, curve)
+                .wrn("set_animation_curve")?,
+            ClientMessage::SetWorkspaceAnimationStyle { style } => {
+                self.handle_set_workspace_animation_style(style)
+            }
+            ClientMessage::ResetAnimations => self.handle_reset_animations(),
+            ClientMessage::MoveToScratchpad { seat } => self
+                .handle_move_to_scratchpad(seat)
+                .wrn("move_to_scratchpad")?,
+            ClientMessage::ToggleScratchpad { seat } => self
+                .handle_toggle_scratchpad(seat)
+                .wrn("toggle_scratchpad")?,
+            ClientMessage::GetSticky { seat } => self.handle_get_sticky(seat).wrn("get_sticky")?,
+            ClientMessage::SetSticky { seat, sticky } => {
+                self.handle_set_sticky(seat, sticky).wrn("set_sticky")?
+            }
+            ClientMessage::Mark { seat, mark } => self.handle_mark(seat, &mark).wrn("mark")?,
+            ClientMessage::Unmark { seat, mark } => {
+                self.handle_unmark(seat, mark.as_deref()).wrn("unmark")?
+            }
+            ClientMessage::FocusMark { seat, mark } => {
+                self.handle_focus_mark(seat, &mark).wrn("focus_mark")?
+            }
+            ClientMessage::SwapWithMark { seat, mark } => self
+                .handle_swap_with_mark(seat, &mark)
+                .wrn("swap_with_mark")?,
+            ClientMessage::GetContainerLayout { seat } => self
+                .handle_get_container_layout(seat)
+                .wrn("get_container_layout")?,
+            ClientMessage::SetContainerLayout { seat, layout } => self
+                .handle_set_container_layout(seat, layout)
+                .wrn("set_container_layout")?,
+            ClientMessage::GetWorkspaceLayout { workspace } => self
+                .handle_get_workspace_layout(workspace)
+                .wrn("get_workspace_layout")?,
+            ClientMessage::SetWorkspaceLayout { workspace, layout } => self
+                .handle_set_workspace_layout(workspace, layout)
+                .wrn("set_workspace_layout")?,
+            ClientMessage::SetDefaultWorkspaceLayout { layout } => {
+                self.handle_set_default_workspace_layout(layout)
+            }
+            ClientMessage::SetStatusBlocks { status, blocks } => {
+                self.handle_set_status_blocks(status, blocks)
+            }
+            ClientMessage::KeymapFromNames { names } => self
+                .handle_keymap_from_names(names)
+                .wrn("keymap_from_names")?,
+            ClientMessage::SeatNextKeyboardLayout { seat } => self
+                .handle_seat_set_keyboard_layout(seat, None)
+                .wrn("seat_next_keyboard_layout")?,
+            ClientMessage::SeatSetKeyboardLayout { seat, layout } => self
+                .handle_seat_set_keyboard_layout(seat, Some(layout))
+                .wrn("seat_set_keyboard_layout")?,
+            ClientMessage::SeatGetKeyboardLayout { seat } => self
+                .handle_seat_get_keyboard_layout(seat)
+                .wrn("seat_get_keyboard_layout")?,
+            ClientMessage::DeviceNextKeyboardLayout { device } => self
+                .handle_device_set_keyboard_layout(device, None)
+                .wrn("device_next_keyboard_layout")?,
+            ClientMessage::DeviceSetKeyboardLayout { device, layout } => self
+                .handle_device_set_keyboard_layout(device, Some(layout))
+                .wrn("device_set_keyboard_layout")?,
+            ClientMessage::SetRememberKeyboardLayout { seat, remember } => self
+                .handle_set_remember_keyboard_layout(seat, remember)
+                .wrn("set_remember_keyboard_layout")?,
+            ClientMessage::SetClickMethod { device, method } => self
+                .handle_set_click_method(device, method)
+                .wrn("set_click_method")?,
+            ClientMessage::SetMiddleButtonEmulationEnabled { device, enabled } => self
+                .handle_set_middle_button_emulation_enabled(device, enabled)
+                .wrn("set_middle_button_emulation_enabled")?,
+            ClientMessage::SetScrollMethod { device, method } => self
+                .handle_set_scroll_method(device, method)
+                .wrn("set_scroll_method")?,
+            ClientMessage::SetScrollButton { device, button } => self
+                .handle_set_scroll_button(device, button)
+                .wrn("set_scroll_button")?,
+            ClientMessage::SetDisableWhileTypingEnabled { device, enabled } => self
+                .handle_set_disable_while_typing_enabled(device, enabled)
+                .wrn("set_disable_while_typing_enabled")?,
+            ClientMessage::SetTapButtonMap { device, map } => self
+                .handle_set_tap_button_map(device, map)
+                .wrn("set_tap_button_map")?,
+            ClientMessage::SetSendEventsMode { device, mode } => self
+                .handle_set_send_events_mode(device, mode)
+                .wrn("set_send_events_mode")?,
+            ClientMessage::SetRotation { device, degrees } => self
+                .handle_set_rotation(device, degrees)
+                .wrn("set_rotation")?,
+            ClientMessage::SetCalibrationMatrix { device, matrix } => self
+                .handle_set_calibration_matrix(device, matrix)
+                .wrn("set_calibration_matrix")?,
+            ClientMessage::SetCustomAccelFunction {
+                device,
+                ty,
+                step,
+                points,
+            } => self
+                .handle_set_custom_accel_function(device, ty, step, &points)
+                .wrn("set_custom_accel_function")?,
+            ClientMessage::SetInputRegion {
+                device,
+                x,
+                y,
+                width,
+                height,
+            } => self
+                .handle_set_input_region(device, x, y, width, height)
+                .wrn("set_input_region")?,
+            ClientMessage::SetTabletArea {
+                device,
+                x,
+                y,
+                width,
+                height,
+            } => self
+                .handle_set_tablet_area(device, x, y, width, height)
+                .wrn("set_tablet_area")?,
+            ClientMessage::SetTabletKeepAspectRatio { device, keep } => self
+                .handle_set_tablet_keep_aspect_ratio(device, keep)
+                .wrn("set_tablet_keep_aspect_ratio")?,
+            ClientMessage::SetTabletPressureCurve { device, curve } => self
+                .handle_set_tablet_pressure_curve(device, curve)
+                .wrn("set_tablet_pressure_curve")?,
+            ClientMessage::SetTabletPadBinding {
+                device,
+                control,
+                index,
+                bound,
+            } => self
+                .handle_set_tablet_pad_binding(device, control, index, bound)
+                .wrn("set_tablet_pad_binding")?,
+            ClientMessage::SetWindowOpacities { opacities } => {
+                self.handle_set_window_opacities(opacities)
+            }
         }
         Ok(())
     }
@@ -1825,6 +2505,16 @@ impl ConfigProxyHandler {
 enum CphError {
     #[error("Tried to set an unknown accel profile: {}", (.0).0)]
     UnknownAccelProfile(AccelProfile),
+    #[error("Tried to set an unknown acceleration type: {}", (.0).0)]
+    UnknownAccelType(AccelType),
+    #[error("Tried to set an unknown click method: {}", (.0).0)]
+    UnknownClickMethod(ClickMethod),
+    #[error("Tried to set an unknown scroll method: {}", (.0).0)]
+    UnknownScrollMethod(ScrollMethod),
+    #[error("Tried to set an unknown tap button map: {}", (.0).0)]
+    UnknownTapButtonMap(TapButtonMap),
+    #[error("Tried to set an unknown send-events mode: {}", (.0).0)]
+    UnknownSendEventsMode(SendEventsMode),
     #[error("Queried unknown capability: {}", (.0).0)]
     UnknownCapability(Capability),
     #[error("The sized {0} is outside the valid range [{}, {}] for component {}", .1.min(), .1.max(), .1.name())]
@@ -1863,6 +2553,8 @@ enum CphError {
     UnknownColor(u32),
     #[error("Sized element {0} is not known")]
     UnknownSized(u32),
+    #[error("Animation {0} is not known")]
+    UnknownAnimation(u32),
     #[error("Could not parse the message")]
     ParsingFailed(#[source] bincode::Error),
     #[error("Could not process a `{0}` request")]
@@ -1877,6 +2569,12 @@ enum CphError {
     NegativeCursorSize,
     #[error("Config referred to a pollable that does not exist")]
     PollableDoesNotExist,
+    #[error("The input region must have a positive size")]
+    InvalidInputRegion,
+    #[error("The tablet area must be a non-empty part of the range [0, 1]")]
+    InvalidTabletArea,
+    #[error("The pressure curve control points must be in the range [0, 1]")]
+    InvalidPressureCurve,
 }
 
 trait WithRequestName {
@@ -6,6 +6,7 @@ use {
         compositor::{DISPLAY, WAYLAND_DISPLAY},
         forker::io::{IoIn, IoOut},
         io_uring::IoUring,
+        pam,
         state::State,
         utils::{
             buffd::BufFdError,
@@ -46,6 +47,7 @@ pub struct ForkerProxy {
     outgoing: AsyncQueue<ServerMessage>,
     next_id: NumCell<u32>,
     pending_pidfds: CopyHashMap<u32, Weak<PidfdHandoff>>,
+    pending_auths: CopyHashMap<u32, Weak<AuthHandoff>>,
     fds: RefCell<Vec<Rc<OwnedFd>>>,
 }
 
@@ -54,6 +56,11 @@ struct PidfdHandoff {
     waiter: Cell<Option<Waker>>,
 }
 
+struct AuthHandoff {
+    success: Cell<Option<bool>>,
+    waiter: Cell<Option<Waker>>,
+}
+
 #[derive(Debug, Error)]
 pub enum ForkerError {
     #[error("Could not create a socketpair")]
@@ -98,6 +105,7 @@ impl ForkerProxy {
                 outgoing: Default::default(),
                 next_id: Default::default(),
                 pending_pidfds: Default::default(),
+                pending_auths: Default::default(),
                 fds: Default::default(),
             }),
             Forked::Child { .. } => {
@@ -149,6 +157,29 @@ impl ForkerProxy {
         .await
     }
 
+    /// Authenticates the current user with PAM.
+    ///
+    /// Returns whether the authentication succeeded.
+    pub async fn authenticate(&self, password: Vec<u8>) -> bool {
+        let id = self.next_id.fetch_add(1);
+        let handoff = Rc::new(AuthHandoff {
+            success: Cell::new(None),
+            waiter: Cell::new(None),
+        });
+        self.pending_auths.set(id, Rc::downgrade(&handoff));
+        self.outgoing
+            .push(ServerMessage::Authenticate { id, password });
+        futures_util::future::poll_fn(|ctx| {
+            if let Some(success) = handoff.success.take() {
+                Poll::Ready(success)
+            } else {
+                handoff.waiter.set(Some(ctx.waker().clone()));
+                Poll::Pending
+            }
+        })
+        .await
+    }
+
     pub async fn xwayland(
         &self,
         stderr: Rc<OwnedFd>,
@@ -221,6 +252,18 @@ impl ForkerProxy {
         match msg {
             ForkerMessage::Log { level, msg } => self.handle_log(level, &msg),
             ForkerMessage::PidFd { id, success, pid } => self.handle_pidfd(id, success, io, pid),
+            ForkerMessage::Authenticated { id, success } => self.handle_authenticated(id, success),
+        }
+    }
+
+    fn handle_authenticated(&self, id: u32, success: bool) {
+        if let Some(handoff) = self.pending_auths.remove(&id) {
+            if let Some(handoff) = handoff.upgrade() {
+                handoff.success.set(Some(success));
+                if let Some(w) = handoff.waiter.take() {
+                    w.wake();
+                }
+            }
         }
     }
 
@@ -295,6 +338,10 @@ enum ServerMessage {
         fds: Vec<i32>,
         pidfd_id: Option<u32>,
     },
+    Authenticate {
+        id: u32,
+        password: Vec<u8>,
+    },
 }
 
 #[derive(Serialize, Deserialize)]
@@ -308,6 +355,10 @@ enum ForkerMessage {
         success: bool,
         pid: c::pid_t,
     },
+    Authenticated {
+        id: u32,
+        success: bool,
+    },
 }
 
 struct Forker {
@@ -393,6 +444,7 @@ impl Forker {
                 fds,
                 pidfd_id,
             } => self.handle_spawn(prog, args, env, fds, io, pidfd_id),
+            ServerMessage::Authenticate { id, password } => self.authenticate(id, password),
         }
     }
 
@@ -522,8 +574,75 @@ impl Forker {
             }
         }
     }
+
+    fn authenticate(self: &Rc<Self>, id: u32, mut password: Vec<u8>) {
+        let (read, mut write) = pipe2(c::O_CLOEXEC).unwrap();
+        let res = match fork_with_pidfd(false) {
+            Ok(o) => o,
+            Err(e) => {
+                password.fill(0);
+                self.outgoing.push(ForkerMessage::Log {
+                    level: log::Level::Error as usize,
+                    msg: ErrorFmt(e).to_string(),
+                });
+                self.outgoing
+                    .push(ForkerMessage::Authenticated { id, success: false });
+                return;
+            }
+        };
+        match res {
+            Forked::Parent { pid, .. } => {
+                password.fill(0);
+                drop(write);
+                let slf = self.clone();
+                let auth = self.ae.spawn(async move {
+                    let read = Rc::new(read);
+                    let mut success = false;
+                    if let Err(e) = slf.ring.readable(&read).await {
+                        log::error!(
+                            "Cannot wait for the child fd to become readable: {}",
+                            ErrorFmt(e)
+                        );
+                    } else {
+                        let mut s = String::new();
+                        let _ = Fd::new(read.raw()).read_to_string(&mut s);
+                        if s == AUTH_SUCCESS {
+                            success = true;
+                        } else {
+                            if s.is_empty() {
+                                s = "The authentication process died".to_string();
+                            }
+                            slf.outgoing.push(ForkerMessage::Log {
+                                level: log::Level::Warn as _,
+                                msg: s,
+                            });
+                        }
+                    }
+                    slf.outgoing
+                        .push(ForkerMessage::Authenticated { id, success });
+                    slf.pending_spawns.remove(&pid);
+                });
+                self.pending_spawns.set(pid, auth);
+            }
+            Forked::Child { .. } => {
+                // PAM modules such as pam_unix wait for their helper processes.
+                unsafe {
+                    c::signal(c::SIGCHLD, c::SIG_DFL);
+                }
+                let msg = match pam::authenticate(&password) {
+                    Ok(()) => AUTH_SUCCESS.to_string(),
+                    Err(e) => ErrorFmt(e).to_string(),
+                };
+                password.fill(0);
+                let _ = write.write_all(msg.as_bytes());
+                std::process::exit(0);
+            }
+        }
+    }
 }
 
+const AUTH_SUCCESS: &str = "success";
+
 #[derive(Debug, Error)]
 enum SpawnError {
     #[error("exec failed")]
@@ -155,7 +155,7 @@ pub static XRGB8888: &Format = &Format {
     opaque: None,
 };
 
-static ABGR8888: &Format = &Format {
+pub static ABGR8888: &Format = &Format {
     name: "abgr8888",
     shm_info: Some(FormatShmInfo {
         bpp: 4,
@@ -172,7 +172,7 @@ static ABGR8888: &Format = &Format {
     opaque: Some(XBGR8888),
 };
 
-static XBGR8888: &Format = &Format {
+pub static XBGR8888: &Format = &Format {
     name: "xbgr8888",
     shm_info: Some(FormatShmInfo {
         bpp: 4,
@@ -8,6 +8,7 @@ use {
         scale::Scale,
         state::State,
         theme::Color,
+        time::now_usec,
         tree::{Node, OutputNode},
         utils::{clonecell::UnsafeCellCloneSafe, transform_ext::TransformExt},
         video::{dmabuf::DmaBuf, drm::sync_obj::SyncObjCtx, gbm::GbmDevice, Modifier},
@@ -17,7 +18,7 @@ use {
     jay_config::video::{GfxApi, Transform},
     std::{
         any::Any,
-        cell::Cell,
+        cell::{Cell, RefCell},
         error::Error,
         ffi::CString,
         fmt::{Debug, Formatter},
@@ -33,6 +34,7 @@ pub enum GfxApiOpt {
     Sync,
     FillRect(FillRect),
     CopyTexture(CopyTexture),
+    Blur(Blur),
 }
 
 pub struct GfxRenderPass {
@@ -134,10 +136,165 @@ impl FramebufferRect {
     }
 }
 
+/// A rounded rectangle in framebuffer pixels that limits the area affected by an operation.
+///
+/// Pixels outside of the rectangle are discarded. The edges are smoothed over `blur`
+/// pixels. This is used for rounded corners and drop shadows.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RoundedRect {
+    pub x1: f32,
+    pub y1: f32,
+    pub x2: f32,
+    pub y2: f32,
+    pub radius: f32,
+    pub blur: f32,
+}
+
+impl RoundedRect {
+    /// Returns the fraction of the pixel centered at `(x, y)` that lies within the
+    /// rectangle.
+    pub fn coverage(&self, x: f32, y: f32) -> f32 {
+        let cx = (self.x1 + self.x2) * 0.5;
+        let cy = (self.y1 + self.y2) * 0.5;
+        let qx = (x - cx).abs() - (self.x2 - self.x1) * 0.5 + self.radius;
+        let qy = (y - cy).abs() - (self.y2 - self.y1) * 0.5 + self.radius;
+        let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
+        let d = outside + qx.max(qy).min(0.0) - self.radius;
+        (0.5 - d / self.blur.max(1.0)).clamp(0.0, 1.0)
+    }
+}
+
 #[derive(Debug)]
 pub struct FillRect {
     pub rect: FramebufferRect,
     pub color: Color,
+    pub rounded: Option<RoundedRect>,
+}
+
+/// Blurs the contents of the framebuffer behind a rounded rectangle.
+///
+/// The blur is a dual kawase blur: the area is downsampled `passes` times and then
+/// upsampled again. `offset` is the sampling distance in pixels of each pass.
+///
+/// If `cache` is set, the blurred contents can be reused in later frames.
+#[derive(Debug)]
+pub struct Blur {
+    pub rect: RoundedRect,
+    pub passes: u32,
+    pub offset: f32,
+    pub cache: Option<Rc<BlurCache>>,
+}
+
+impl Blur {
+    /// Returns the pixels of a framebuffer of the given size that are covered by the
+    /// blurred area.
+    pub fn bounds(&self, width: i32, height: i32) -> Option<[i32; 4]> {
+        let x1 = (self.rect.x1.floor() as i32).clamp(0, width);
+        let y1 = (self.rect.y1.floor() as i32).clamp(0, height);
+        let x2 = (self.rect.x2.ceil() as i32).clamp(0, width);
+        let y2 = (self.rect.y2.ceil() as i32).clamp(0, height);
+        if x1 >= x2 || y1 >= y2 {
+            return None;
+        }
+        Some([x1, y1, x2, y2])
+    }
+
+    /// Returns the number of passes that can be applied to an area of the given size.
+    ///
+    /// Every pass halves the larger side of the area until it is a single pixel wide.
+    pub fn effective_passes(&self, width: i32, height: i32) -> u32 {
+        self.passes.min(width.max(height).max(1).ilog2())
+    }
+
+    /// Returns the size of the image produced by the nth downsampling pass.
+    pub fn level_size(width: i32, height: i32, n: u32) -> (i32, i32) {
+        ((width >> n).max(1), (height >> n).max(1))
+    }
+}
+
+/// The parameters that a cached blur must match to be reused.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BlurCacheKey {
+    pub format: u32,
+    pub bounds: [i32; 4],
+    pub passes: u32,
+    pub offset: u32,
+}
+
+impl BlurCacheKey {
+    pub fn new(b: &Blur, format: &Format, bounds: [i32; 4], passes: u32) -> Self {
+        Self {
+            format: format.drm,
+            bounds,
+            passes,
+            offset: b.offset.to_bits(),
+        }
+    }
+}
+
+/// The frame in which a blur is rendered.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct BlurCacheFrame {
+    /// The serial of the damage log when the frame was rendered.
+    pub serial: u64,
+    /// The logical extents of the rendered scene.
+    pub scene: Rect,
+    /// The logical rectangle that is blurred.
+    pub rect: Rect,
+}
+
+/// Keeps the blurred contents behind a surface alive between frames.
+///
+/// The scene renderer sets `valid` before each frame if the contents behind the blurred
+/// area have not changed since the cached data was produced. The graphics API stores the
+/// data in a format of its choosing.
+#[derive(Default)]
+pub struct BlurCache {
+    pub valid: Cell<bool>,
+    /// The frame that is currently being rendered.
+    pub pending: Cell<Option<BlurCacheFrame>>,
+    /// The frame in which the cached data was produced or last reused.
+    pub produced: Cell<Option<BlurCacheFrame>>,
+    data: RefCell<Option<(BlurCacheKey, Rc<dyn Any>)>>,
+}
+
+impl BlurCache {
+    /// Returns the cached data if it can be reused for a blur with the given key.
+    pub fn get<T: 'static>(&self, key: &BlurCacheKey) -> Option<Rc<T>> {
+        if !self.valid.get() {
+            return None;
+        }
+        let data = self.data.borrow();
+        let (k, data) = data.as_ref()?;
+        if k != key {
+            return None;
+        }
+        let data = data.clone().downcast().ok()?;
+        self.produced.set(self.pending.get());
+        Some(data)
+    }
+
+    /// Returns the cached data regardless of whether it can be reused.
+    ///
+    /// This can be used to recycle resources.
+    pub fn last<T: 'static>(&self) -> Option<Rc<T>> {
+        let data = self.data.borrow();
+        data.as_ref()?.1.clone().downcast().ok()
+    }
+
+    /// Stores data produced in the pending frame.
+    pub fn set<T: 'static>(&self, key: BlurCacheKey, data: Rc<T>) {
+        *self.data.borrow_mut() = Some((key, data));
+        self.produced.set(self.pending.get());
+    }
+}
+
+impl Debug for BlurCache {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BlurCache")
+            .field("valid", &self.valid.get())
+            .finish_non_exhaustive()
+    }
 }
 
 pub struct CopyTexture {
@@ -148,6 +305,7 @@ pub struct CopyTexture {
     pub acquire_sync: AcquireSync,
     pub release_sync: ReleaseSync,
     pub alpha: Option<f32>,
+    pub rounded: Option<RoundedRect>,
 }
 
 #[derive(Clone, Debug)]
@@ -275,6 +433,8 @@ impl dyn GfxFramebuffer {
             transform,
             fb_width: width as _,
             fb_height: height as _,
+            rounded: None,
+            alpha: 1.0,
         }
     }
 
@@ -336,10 +496,14 @@ impl dyn GfxFramebuffer {
             state,
             result,
             logical_extents: node.node_absolute_position().at_point(0, 0),
+            scene: node.node_absolute_position(),
             pixel_extents: {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            opacity: 1.0,
+            animation_time_usec: now_usec(),
+            animating: false,
         };
         node.node_render(&mut renderer, 0, 0, None);
         if let Some(rect) = cursor_rect {
@@ -470,10 +634,14 @@ impl dyn GfxFramebuffer {
             state,
             result: None,
             logical_extents: Rect::new_empty(0, 0),
+            scene: Rect::new_empty(0, 0),
             pixel_extents: {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            opacity: 1.0,
+            animation_time_usec: now_usec(),
+            animating: false,
         };
         cursor.render_hardware_cursor(&mut renderer);
         self.render(ops, Some(&Color::TRANSPARENT))
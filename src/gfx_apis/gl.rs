@@ -68,19 +68,19 @@ macro_rules! dynload {
 use {
     crate::{
         gfx_api::{
-            AcquireSync, CopyTexture, FillRect, GfxApiOpt, GfxContext, GfxError, GfxTexture,
-            ReleaseSync, SyncFile,
+            AcquireSync, Blur, BlurCacheKey, CopyTexture, FillRect, GfxApiOpt, GfxContext,
+            GfxError, GfxTexture, ReleaseSync, RoundedRect, SyncFile,
         },
         gfx_apis::gl::{
-            gl::texture::image_target,
+            gl::{blur_buffer::GlBlurBuffer, texture::image_target},
             renderer::{
-                context::{GlRenderContext, TexCopyType, TexSourceType},
+                context::{BlurProg, GlRenderContext, RoundedUniforms, TexCopyType, TexSourceType},
                 framebuffer::Framebuffer,
                 texture::Texture,
             },
             sys::{
-                GL_BLEND, GL_FALSE, GL_FLOAT, GL_LINEAR, GL_TEXTURE0, GL_TEXTURE_MIN_FILTER,
-                GL_TRIANGLES, GL_TRIANGLE_STRIP,
+                GLint, GL_BLEND, GL_FALSE, GL_FLOAT, GL_FRAMEBUFFER, GL_LINEAR, GL_RGB, GL_RGBA,
+                GL_TEXTURE0, GL_TEXTURE_2D, GL_TEXTURE_MIN_FILTER, GL_TRIANGLES, GL_TRIANGLE_STRIP,
             },
         },
         theme::Color,
@@ -91,6 +91,7 @@ use {
             gbm::GbmError,
         },
     },
+    ahash::AHashMap,
     isnt::std_1::vec::IsntVecExt,
     once_cell::sync::Lazy,
     std::{cell::RefCell, rc::Rc, sync::Arc},
@@ -198,11 +199,24 @@ enum RenderError {
     UnsupportedShmFormat(&'static str),
 }
 
+/// The number of frames after which unused blur buffers are destroyed.
+const BLUR_BUFFER_LIFETIME: u64 = 60;
+
 #[derive(Default)]
 struct GfxGlState {
     triangles: RefCell<Vec<[f32; 2]>>,
     fill_rect: VecStorage<&'static FillRect>,
     copy_tex: VecStorage<&'static CopyTexture>,
+    blur_buffers: AHashMap<BlurBufferKey, (Rc<GlBlurBuffer>, u64)>,
+    frame: u64,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+struct BlurBufferKey {
+    format: GLint,
+    renderable: bool,
+    width: i32,
+    height: i32,
 }
 
 fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
@@ -214,6 +228,7 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
     let copy_tex = &mut *copy_tex;
     let mut triangles = state.triangles.borrow_mut();
     let triangles = &mut *triangles;
+    state.frame += 1;
     let mut i = 0;
     while i < ops.len() {
         macro_rules! has_ops {
@@ -239,6 +254,13 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
                     copy_tex.push(c);
                     i += 1;
                 }
+                GfxApiOpt::Blur(b) => {
+                    if has_ops!() {
+                        break;
+                    }
+                    blur(fb, &mut state.blur_buffers, state.frame, b);
+                    i += 1;
+                }
             }
         }
         if fill_rect.is_not_empty() {
@@ -249,6 +271,10 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
                 let mut color = None;
                 while i < fill_rect.len() {
                     let fr = fill_rect[i];
+                    if fr.rounded.is_some() {
+                        i += 1;
+                        continue;
+                    }
                     match color {
                         None => color = Some(fr.color),
                         Some(c) if c == fr.color => {}
@@ -269,11 +295,22 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) -> Option<SyncFile> {
                     fill_boxes3(&fb.ctx, triangles, &color);
                 }
             }
+            for fr in &*fill_rect {
+                if let Some(rounded) = &fr.rounded {
+                    fill_rounded(&fb.ctx, fr, rounded);
+                }
+            }
         }
         for tex in &*copy_tex {
             render_texture(&fb.ctx, tex);
         }
     }
+    if !state.blur_buffers.is_empty() {
+        let frame = state.frame;
+        state
+            .blur_buffers
+            .retain(|_, (_, used)| *used + BLUR_BUFFER_LIFETIME > frame);
+    }
     if fb.ctx.ctx.dpy.explicit_sync {
         let file = match fb.ctx.ctx.export_sync_file() {
             Ok(f) => SyncFile(Rc::new(f)),
@@ -316,6 +353,232 @@ fn fill_boxes3(ctx: &GlRenderContext, boxes: &[[f32; 2]], color: &Color) {
     }
 }
 
+fn fill_rounded(ctx: &GlRenderContext, fr: &FillRect, rounded: &RoundedRect) {
+    let gles = ctx.ctx.dpy.gles;
+    let color = &fr.color;
+    let pos = fr.rect.to_points();
+    unsafe {
+        (gles.glEnable)(GL_BLEND);
+        (gles.glUseProgram)(ctx.fill_rounded_prog.prog);
+        (gles.glUniform4f)(
+            ctx.fill_rounded_prog_color,
+            color.r,
+            color.g,
+            color.b,
+            color.a,
+        );
+        set_rounded_uniforms(ctx, &ctx.fill_rounded_prog_rounded, rounded);
+        (gles.glVertexAttribPointer)(
+            ctx.fill_rounded_prog_pos as _,
+            2,
+            GL_FLOAT,
+            GL_FALSE,
+            0,
+            pos.as_ptr() as _,
+        );
+        (gles.glEnableVertexAttribArray)(ctx.fill_rounded_prog_pos as _);
+        (gles.glDrawArrays)(GL_TRIANGLE_STRIP, 0, 4);
+        (gles.glDisableVertexAttribArray)(ctx.fill_rounded_prog_pos as _);
+    }
+}
+
+fn blur(
+    fb: &Framebuffer,
+    buffers: &mut AHashMap<BlurBufferKey, (Rc<GlBlurBuffer>, u64)>,
+    frame: u64,
+    b: &Blur,
+) {
+    let Some([x1, y1, x2, y2]) = b.bounds(fb.gl.width, fb.gl.height) else {
+        return;
+    };
+    let (width, height) = (x2 - x1, y2 - y1);
+    let passes = b.effective_passes(width, height);
+    if passes == 0 {
+        return;
+    }
+    let ctx = &fb.ctx;
+    let key = BlurCacheKey::new(b, fb.gl.rb.format, [x1, y1, x2, y2], passes);
+    if let Some(cache) = &b.cache {
+        if let Some(buffer) = cache.get::<GlBlurBuffer>(&key) {
+            if rc_eq(&buffer.ctx, &ctx.ctx) {
+                unsafe {
+                    blur_result(fb, b, [x1, y1, x2, y2], &buffer);
+                }
+                return;
+            }
+        }
+    }
+    let cached = b.cache.as_ref().and_then(|cache| {
+        let (width, height) = Blur::level_size(width, height, 1);
+        if let Some(buffer) = cache.last::<GlBlurBuffer>() {
+            if rc_eq(&buffer.ctx, &ctx.ctx) && buffer.width == width && buffer.height == height {
+                return Some(buffer);
+            }
+        }
+        let res = unsafe { GlBlurBuffer::new(&ctx.ctx, width, height, GL_RGBA, true) };
+        match res {
+            Ok(b) => Some(Rc::new(b)),
+            Err(e) => {
+                log::error!("Could not create a blur cache buffer: {}", ErrorFmt(e));
+                None
+            }
+        }
+    });
+    let mut levels = Vec::with_capacity(passes as usize + 1);
+    for n in 0..=passes {
+        if let (1, Some(buffer)) = (n, &cached) {
+            levels.push(buffer.clone());
+            continue;
+        }
+        let (width, height) = Blur::level_size(width, height, n);
+        let key = BlurBufferKey {
+            format: match (n, fb.gl.rb.format.has_alpha) {
+                (0, false) => GL_RGB,
+                _ => GL_RGBA,
+            },
+            renderable: n > 0,
+            width,
+            height,
+        };
+        let buffer = match buffers.get_mut(&key) {
+            Some((buffer, used)) => {
+                *used = frame;
+                buffer.clone()
+            }
+            _ => {
+                let res = unsafe {
+                    GlBlurBuffer::new(&ctx.ctx, width, height, key.format, key.renderable)
+                };
+                match res {
+                    Ok(b) => {
+                        let b = Rc::new(b);
+                        buffers.insert(key, (b.clone(), frame));
+                        b
+                    }
+                    Err(e) => {
+                        log::error!("Could not create a blur buffer: {}", ErrorFmt(e));
+                        return;
+                    }
+                }
+            }
+        };
+        levels.push(buffer);
+    }
+    let gles = ctx.ctx.dpy.gles;
+    let full = [[1.0, -1.0], [-1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+    unsafe {
+        (gles.glBindFramebuffer)(GL_FRAMEBUFFER, fb.gl.fbo);
+        (gles.glActiveTexture)(GL_TEXTURE0);
+        (gles.glBindTexture)(GL_TEXTURE_2D, levels[0].tex);
+        (gles.glCopyTexSubImage2D)(GL_TEXTURE_2D, 0, 0, 0, x1, y1, width, height);
+        (gles.glDisable)(GL_BLEND);
+        for n in 1..levels.len() {
+            let dst = &levels[n];
+            (gles.glBindFramebuffer)(GL_FRAMEBUFFER, dst.fbo);
+            (gles.glViewport)(0, 0, dst.width, dst.height);
+            let size = (dst.width, dst.height);
+            blur_pass(
+                ctx,
+                &ctx.blur_down_prog,
+                &levels[n - 1],
+                &full,
+                size,
+                b.offset,
+            );
+        }
+        for n in (1..levels.len() - 1).rev() {
+            let dst = &levels[n];
+            (gles.glBindFramebuffer)(GL_FRAMEBUFFER, dst.fbo);
+            (gles.glViewport)(0, 0, dst.width, dst.height);
+            let size = (dst.width, dst.height);
+            blur_pass(
+                ctx,
+                &ctx.blur_up_prog,
+                &levels[n + 1],
+                &full,
+                size,
+                b.offset,
+            );
+        }
+        (gles.glBindFramebuffer)(GL_FRAMEBUFFER, fb.gl.fbo);
+        (gles.glViewport)(0, 0, fb.gl.width, fb.gl.height);
+        (gles.glEnable)(GL_BLEND);
+        blur_result(fb, b, [x1, y1, x2, y2], &levels[1]);
+    }
+    if let (Some(cache), Some(buffer)) = (&b.cache, cached) {
+        cache.set(key, buffer);
+    }
+}
+
+/// Upsamples the first blur level into the framebuffer.
+unsafe fn blur_result(fb: &Framebuffer, b: &Blur, [x1, y1, x2, y2]: [i32; 4], src: &GlBlurBuffer) {
+    let ctx = &fb.ctx;
+    let gles = ctx.ctx.dpy.gles;
+    let prog = &ctx.blur_up_rounded_prog;
+    (gles.glUseProgram)(prog.prog.prog);
+    set_rounded_uniforms(ctx, &prog.rounded, &b.rect);
+    let (width, height) = (x2 - x1, y2 - y1);
+    let [x1, x2] = [x1, x2].map(|x| 2.0 * x as f32 / fb.gl.width as f32 - 1.0);
+    let [y1, y2] = [y1, y2].map(|y| 2.0 * y as f32 / fb.gl.height as f32 - 1.0);
+    let pos = [[x2, y1], [x1, y1], [x2, y2], [x1, y2]];
+    blur_pass(ctx, prog, src, &pos, (width, height), b.offset);
+    (gles.glBindTexture)(GL_TEXTURE_2D, 0);
+}
+
+/// Renders `src` into the currently bound framebuffer at `pos`.
+///
+/// `dst_size` determines the size of the sampling offsets.
+unsafe fn blur_pass(
+    ctx: &GlRenderContext,
+    prog: &BlurProg,
+    src: &GlBlurBuffer,
+    pos: &[[f32; 2]; 4],
+    (dst_width, dst_height): (i32, i32),
+    offset: f32,
+) {
+    let gles = ctx.ctx.dpy.gles;
+    let texcoord: [[f32; 2]; 4] = [[1.0, 0.0], [0.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+    (gles.glBindTexture)(GL_TEXTURE_2D, src.tex);
+    (gles.glUseProgram)(prog.prog.prog);
+    (gles.glUniform1i)(prog.tex, 0);
+    (gles.glUniform2f)(
+        prog.half_pixel,
+        offset * 0.5 / dst_width as f32,
+        offset * 0.5 / dst_height as f32,
+    );
+    (gles.glVertexAttribPointer)(
+        prog.texcoord as _,
+        2,
+        GL_FLOAT,
+        GL_FALSE,
+        0,
+        texcoord.as_ptr() as _,
+    );
+    (gles.glVertexAttribPointer)(prog.pos as _, 2, GL_FLOAT, GL_FALSE, 0, pos.as_ptr() as _);
+    (gles.glEnableVertexAttribArray)(prog.texcoord as _);
+    (gles.glEnableVertexAttribArray)(prog.pos as _);
+    (gles.glDrawArrays)(GL_TRIANGLE_STRIP, 0, 4);
+    (gles.glDisableVertexAttribArray)(prog.texcoord as _);
+    (gles.glDisableVertexAttribArray)(prog.pos as _);
+}
+
+unsafe fn set_rounded_uniforms(
+    ctx: &GlRenderContext,
+    uniforms: &RoundedUniforms,
+    rounded: &RoundedRect,
+) {
+    let gles = ctx.ctx.dpy.gles;
+    (gles.glUniform4f)(
+        uniforms.rect,
+        rounded.x1,
+        rounded.y1,
+        rounded.x2,
+        rounded.y2,
+    );
+    (gles.glUniform1f)(uniforms.radius, rounded.radius);
+    (gles.glUniform1f)(uniforms.blur, rounded.blur);
+}
+
 fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
     let texture = tex.tex.as_gl();
     assert!(rc_eq(&ctx.ctx, &texture.ctx.ctx));
@@ -340,9 +603,10 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
             },
             false => &ctx.tex_internal,
         };
-        let copy_type = match tex.alpha.is_some() {
-            true => TexCopyType::Multiply,
-            false => TexCopyType::Identity,
+        let copy_type = match (tex.rounded.is_some(), tex.alpha.is_some()) {
+            (true, _) => TexCopyType::Rounded,
+            (false, true) => TexCopyType::Multiply,
+            (false, false) => TexCopyType::Identity,
         };
         let source_type = match texture.gl.format.has_alpha {
             true => TexSourceType::HasAlpha,
@@ -362,8 +626,11 @@ fn render_texture(ctx: &GlRenderContext, tex: &CopyTexture) {
         let texcoord = tex.source.to_points();
         let pos = tex.target.to_points();
 
-        if let Some(alpha) = tex.alpha {
-            (gles.glUniform1f)(prog.alpha, alpha);
+        if copy_type != TexCopyType::Identity {
+            (gles.glUniform1f)(prog.alpha, tex.alpha.unwrap_or(1.0));
+        }
+        if let Some(rounded) = &tex.rounded {
+            set_rounded_uniforms(ctx, &prog.rounded, rounded);
         }
 
         (gles.glVertexAttribPointer)(
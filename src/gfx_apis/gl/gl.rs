@@ -1,3 +1,4 @@
+pub mod blur_buffer;
 pub mod frame_buffer;
 pub mod program;
 pub mod render_buffer;
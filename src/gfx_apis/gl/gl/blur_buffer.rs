@@ -0,0 +1,94 @@
+use {
+    crate::gfx_apis::gl::{
+        egl::context::EglContext,
+        gl::sys::{
+            GLint, GLuint, GL_CLAMP_TO_EDGE, GL_COLOR_ATTACHMENT0, GL_FRAMEBUFFER,
+            GL_FRAMEBUFFER_COMPLETE, GL_LINEAR, GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER,
+            GL_TEXTURE_MIN_FILTER, GL_TEXTURE_WRAP_S, GL_TEXTURE_WRAP_T, GL_UNSIGNED_BYTE,
+        },
+        RenderError,
+    },
+    std::{ptr, rc::Rc},
+};
+
+/// A texture that is used as an intermediate image by the blur passes.
+pub struct GlBlurBuffer {
+    pub ctx: Rc<EglContext>,
+    pub tex: GLuint,
+    pub fbo: GLuint,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl GlBlurBuffer {
+    /// Creates a new buffer.
+    ///
+    /// If `renderable` is false, the buffer is only used as the destination of copies
+    /// and no framebuffer is created.
+    pub(in crate::gfx_apis::gl) unsafe fn new(
+        ctx: &Rc<EglContext>,
+        width: i32,
+        height: i32,
+        format: GLint,
+        renderable: bool,
+    ) -> Result<Self, RenderError> {
+        let gles = ctx.dpy.gles;
+        let mut tex = 0;
+        (gles.glGenTextures)(1, &mut tex);
+        (gles.glBindTexture)(GL_TEXTURE_2D, tex);
+        (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_CLAMP_TO_EDGE);
+        (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, GL_CLAMP_TO_EDGE);
+        (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_MIN_FILTER, GL_LINEAR);
+        (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_MAG_FILTER, GL_LINEAR);
+        (gles.glTexImage2D)(
+            GL_TEXTURE_2D,
+            0,
+            format,
+            width,
+            height,
+            0,
+            format as _,
+            GL_UNSIGNED_BYTE as _,
+            ptr::null(),
+        );
+        (gles.glBindTexture)(GL_TEXTURE_2D, 0);
+        let mut buffer = GlBlurBuffer {
+            ctx: ctx.clone(),
+            tex,
+            fbo: 0,
+            width,
+            height,
+        };
+        if renderable {
+            (gles.glGenFramebuffers)(1, &mut buffer.fbo);
+            (gles.glBindFramebuffer)(GL_FRAMEBUFFER, buffer.fbo);
+            (gles.glFramebufferTexture2D)(
+                GL_FRAMEBUFFER,
+                GL_COLOR_ATTACHMENT0,
+                GL_TEXTURE_2D,
+                tex,
+                0,
+            );
+            let status = (gles.glCheckFramebufferStatus)(GL_FRAMEBUFFER);
+            (gles.glBindFramebuffer)(GL_FRAMEBUFFER, 0);
+            if status != GL_FRAMEBUFFER_COMPLETE {
+                return Err(RenderError::CreateFramebuffer);
+            }
+        }
+        Ok(buffer)
+    }
+}
+
+impl Drop for GlBlurBuffer {
+    fn drop(&mut self) {
+        let _ = self.ctx.with_current(|| {
+            unsafe {
+                if self.fbo != 0 {
+                    (self.ctx.dpy.gles.glDeleteFramebuffers)(1, &self.fbo);
+                }
+                (self.ctx.dpy.gles.glDeleteTextures)(1, &self.tex);
+            }
+            Ok(())
+        });
+    }
+}
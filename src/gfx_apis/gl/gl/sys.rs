@@ -13,6 +13,7 @@ pub type GLuint = c::c_uint;
 
 egl_transparent!(GLeglImageOES);
 
+pub const GL_RGB: GLint = 0x1907;
 pub const GL_RGBA: GLint = 0x1908;
 pub const GL_RGBA8: GLenum = 0x8058;
 pub const GL_BGRA_EXT: GLint = 0x80E1;
@@ -32,7 +33,6 @@ pub const GL_RENDERBUFFER: GLenum = 0x8D41;
 pub const GL_TEXTURE0: GLenum = 0x84C0;
 pub const GL_TEXTURE_2D: GLenum = 0x0DE1;
 pub const GL_TEXTURE_EXTERNAL_OES: GLenum = 0x8D65;
-#[allow(dead_code)]
 pub const GL_TEXTURE_MAG_FILTER: GLenum = 0x2800;
 pub const GL_TEXTURE_MIN_FILTER: GLenum = 0x2801;
 pub const GL_TEXTURE_WRAP_S: GLenum = 0x2802;
@@ -104,6 +104,16 @@ dynload! {
             ty: GLenum,
             pixels: *const c::c_void,
         ),
+        glCopyTexSubImage2D: unsafe fn(
+            target: GLenum,
+            level: GLint,
+            xoffset: GLint,
+            yoffset: GLint,
+            x: GLint,
+            y: GLint,
+            width: GLsizei,
+            height: GLsizei,
+        ),
 
         glEnable: unsafe fn(cap: GLenum),
         glDisable: unsafe fn(cap: GLenum),
@@ -132,6 +142,7 @@ dynload! {
         glGetAttribLocation: unsafe fn(prog: GLuint, name: *const GLchar) -> GLint,
         glUniform1i: unsafe fn(location: GLint, v0: GLint),
         glUniform1f: unsafe fn(location: GLint, v0: GLfloat),
+        glUniform2f: unsafe fn(location: GLint, v0: GLfloat, v1: GLfloat),
         glUniform4f: unsafe fn(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat, v3: GLfloat),
         glVertexAttribPointer: unsafe fn(
             index: GLuint,
@@ -38,20 +38,84 @@ pub(crate) struct TexProg {
     pub(crate) texcoord: GLint,
     pub(crate) tex: GLint,
     pub(crate) alpha: GLint,
+    pub(crate) rounded: RoundedUniforms,
 }
 
 impl TexProg {
-    unsafe fn from(prog: GlProgram, alpha_multiplier: bool) -> Self {
+    unsafe fn from(prog: GlProgram, alpha_multiplier: bool, rounded: bool) -> Self {
         let alpha = match alpha_multiplier {
             true => prog.get_uniform_location(ustr!("alpha")),
             false => 0,
         };
+        let rounded = match rounded {
+            true => RoundedUniforms::from(&prog),
+            false => Default::default(),
+        };
         Self {
             pos: prog.get_attrib_location(ustr!("pos")),
             texcoord: prog.get_attrib_location(ustr!("texcoord")),
             tex: prog.get_uniform_location(ustr!("tex")),
             alpha,
+            rounded,
+            prog,
+        }
+    }
+}
+
+pub(crate) struct BlurProg {
+    pub(crate) prog: GlProgram,
+    pub(crate) pos: GLint,
+    pub(crate) texcoord: GLint,
+    pub(crate) tex: GLint,
+    pub(crate) half_pixel: GLint,
+    pub(crate) rounded: RoundedUniforms,
+}
+
+impl BlurProg {
+    unsafe fn new(
+        ctx: &Rc<EglContext>,
+        vert: &str,
+        frag: &str,
+        down: bool,
+        rounded: bool,
+    ) -> Result<Self, RenderError> {
+        let mut src = String::new();
+        if down {
+            src.push_str("#define DOWN\n");
+        }
+        if rounded {
+            src.push_str("#define ROUNDED\n");
+        }
+        src.push_str(frag);
+        let prog = GlProgram::from_shaders(ctx, vert, &src)?;
+        let rounded = match rounded {
+            true => RoundedUniforms::from(&prog),
+            false => Default::default(),
+        };
+        Ok(Self {
+            pos: prog.get_attrib_location(ustr!("pos")),
+            texcoord: prog.get_attrib_location(ustr!("texcoord")),
+            tex: prog.get_uniform_location(ustr!("tex")),
+            half_pixel: prog.get_uniform_location(ustr!("half_pixel")),
+            rounded,
             prog,
+        })
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct RoundedUniforms {
+    pub(crate) rect: GLint,
+    pub(crate) radius: GLint,
+    pub(crate) blur: GLint,
+}
+
+impl RoundedUniforms {
+    unsafe fn from(prog: &GlProgram) -> Self {
+        Self {
+            rect: prog.get_uniform_location(ustr!("rect")),
+            radius: prog.get_uniform_location(ustr!("radius")),
+            blur: prog.get_uniform_location(ustr!("blur")),
         }
     }
 }
@@ -60,6 +124,7 @@ impl TexProg {
 pub(in crate::gfx_apis::gl) enum TexCopyType {
     Identity,
     Multiply,
+    Rounded,
 }
 
 #[derive(Copy, Clone, PartialEq, Enum)]
@@ -82,6 +147,15 @@ pub(in crate::gfx_apis::gl) struct GlRenderContext {
     pub(crate) fill_prog_pos: GLint,
     pub(crate) fill_prog_color: GLint,
 
+    pub(crate) fill_rounded_prog: GlProgram,
+    pub(crate) fill_rounded_prog_pos: GLint,
+    pub(crate) fill_rounded_prog_color: GLint,
+    pub(crate) fill_rounded_prog_rounded: RoundedUniforms,
+
+    pub(crate) blur_down_prog: BlurProg,
+    pub(crate) blur_up_prog: BlurProg,
+    pub(crate) blur_up_rounded_prog: BlurProg,
+
     pub(crate) gfx_ops: RefCell<Vec<GfxApiOpt>>,
     pub(in crate::gfx_apis::gl) gl_state: RefCell<GfxGlState>,
 
@@ -116,7 +190,7 @@ impl GlRenderContext {
         let tex_vert = include_str!("../shaders/tex.vert.glsl");
         let tex_frag = include_str!("../shaders/tex.frag.glsl");
         let create_programs = |external: bool| {
-            let create_program = |alpha_multiplier: bool, alpha: bool| {
+            let create_program = |alpha_multiplier: bool, alpha: bool, rounded: bool| {
                 let mut tex_frac_src = String::new();
                 if external {
                     tex_frac_src.push_str("#define EXTERNAL\n");
@@ -127,18 +201,25 @@ impl GlRenderContext {
                 if alpha {
                     tex_frac_src.push_str("#define ALPHA\n");
                 }
+                if rounded {
+                    tex_frac_src.push_str("#define ROUNDED\n");
+                }
                 tex_frac_src.push_str(tex_frag);
                 let prog = GlProgram::from_shaders(ctx, tex_vert, &tex_frac_src)?;
-                Ok::<_, RenderError>(TexProg::from(prog, alpha_multiplier))
+                Ok::<_, RenderError>(TexProg::from(prog, alpha_multiplier, rounded))
             };
             Ok::<_, RenderError>(enum_map! {
                 TexCopyType::Identity => enum_map! {
-                    TexSourceType::Opaque => create_program(false, false)?,
-                    TexSourceType::HasAlpha => create_program(false, true)?,
+                    TexSourceType::Opaque => create_program(false, false, false)?,
+                    TexSourceType::HasAlpha => create_program(false, true, false)?,
                 },
                 TexCopyType::Multiply => enum_map! {
-                    TexSourceType::Opaque => create_program(true, false)?,
-                    TexSourceType::HasAlpha => create_program(true, true)?,
+                    TexSourceType::Opaque => create_program(true, false, false)?,
+                    TexSourceType::HasAlpha => create_program(true, true, false)?,
+                },
+                TexCopyType::Rounded => enum_map! {
+                    TexSourceType::Opaque => create_program(true, false, true)?,
+                    TexSourceType::HasAlpha => create_program(true, true, true)?,
                 },
             })
         };
@@ -148,11 +229,15 @@ impl GlRenderContext {
         } else {
             None
         };
-        let fill_prog = GlProgram::from_shaders(
-            ctx,
-            include_str!("../shaders/fill.vert.glsl"),
-            include_str!("../shaders/fill.frag.glsl"),
-        )?;
+        let fill_vert = include_str!("../shaders/fill.vert.glsl");
+        let fill_frag = include_str!("../shaders/fill.frag.glsl");
+        let fill_prog = GlProgram::from_shaders(ctx, fill_vert, fill_frag)?;
+        let fill_rounded_prog =
+            GlProgram::from_shaders(ctx, fill_vert, &format!("#define ROUNDED\n{}", fill_frag))?;
+        let blur_frag = include_str!("../shaders/blur.frag.glsl");
+        let blur_down_prog = BlurProg::new(ctx, tex_vert, blur_frag, true, false)?;
+        let blur_up_prog = BlurProg::new(ctx, tex_vert, blur_frag, false, false)?;
+        let blur_up_rounded_prog = BlurProg::new(ctx, tex_vert, blur_frag, false, true)?;
         Ok(Self {
             ctx: ctx.clone(),
             gbm: ctx.dpy.gbm.clone(),
@@ -167,6 +252,15 @@ impl GlRenderContext {
             fill_prog_color: fill_prog.get_uniform_location(ustr!("color")),
             fill_prog,
 
+            fill_rounded_prog_pos: fill_rounded_prog.get_attrib_location(ustr!("pos")),
+            fill_rounded_prog_color: fill_rounded_prog.get_uniform_location(ustr!("color")),
+            fill_rounded_prog_rounded: RoundedUniforms::from(&fill_rounded_prog),
+            fill_rounded_prog,
+
+            blur_down_prog,
+            blur_up_prog,
+            blur_up_rounded_prog,
+
             gfx_ops: Default::default(),
             gl_state: Default::default(),
 
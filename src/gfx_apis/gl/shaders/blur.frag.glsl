@@ -0,0 +1,46 @@
+#if defined(ROUNDED) && defined(GL_FRAGMENT_PRECISION_HIGH)
+precision highp float;
+#else
+precision mediump float;
+#endif
+varying vec2 v_texcoord;
+uniform sampler2D tex;
+uniform vec2 half_pixel;
+#ifdef ROUNDED
+uniform vec4 rect;
+uniform float radius;
+uniform float blur;
+
+float coverage() {
+	vec2 center = (rect.xy + rect.zw) * 0.5;
+	vec2 q = abs(gl_FragCoord.xy - center) - (rect.zw - rect.xy) * 0.5 + radius;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - radius;
+	return clamp(0.5 - d / max(blur, 1.0), 0.0, 1.0);
+}
+#endif
+
+void main() {
+	vec2 uv = v_texcoord;
+	vec2 hp = half_pixel;
+#ifdef DOWN
+	vec4 sum = texture2D(tex, uv) * 4.0;
+	sum += texture2D(tex, uv - hp);
+	sum += texture2D(tex, uv + hp);
+	sum += texture2D(tex, uv + vec2(hp.x, -hp.y));
+	sum += texture2D(tex, uv - vec2(hp.x, -hp.y));
+	gl_FragColor = sum / 8.0;
+#else // !DOWN
+	vec4 sum = texture2D(tex, uv + vec2(-hp.x * 2.0, 0.0));
+	sum += texture2D(tex, uv + vec2(-hp.x, hp.y)) * 2.0;
+	sum += texture2D(tex, uv + vec2(0.0, hp.y * 2.0));
+	sum += texture2D(tex, uv + vec2(hp.x, hp.y)) * 2.0;
+	sum += texture2D(tex, uv + vec2(hp.x * 2.0, 0.0));
+	sum += texture2D(tex, uv + vec2(hp.x, -hp.y)) * 2.0;
+	sum += texture2D(tex, uv + vec2(0.0, -hp.y * 2.0));
+	sum += texture2D(tex, uv + vec2(-hp.x, -hp.y)) * 2.0;
+	gl_FragColor = sum / 12.0;
+#endif // DOWN
+#ifdef ROUNDED
+	gl_FragColor *= coverage();
+#endif
+}
@@ -1,6 +1,26 @@
+#if defined(ROUNDED) && defined(GL_FRAGMENT_PRECISION_HIGH)
+precision highp float;
+#else
 precision mediump float;
+#endif
 uniform vec4 color;
+#ifdef ROUNDED
+uniform vec4 rect;
+uniform float radius;
+uniform float blur;
+
+float coverage() {
+	vec2 center = (rect.xy + rect.zw) * 0.5;
+	vec2 q = abs(gl_FragCoord.xy - center) - (rect.zw - rect.xy) * 0.5 + radius;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - radius;
+	return clamp(0.5 - d / max(blur, 1.0), 0.0, 1.0);
+}
+#endif
 
 void main() {
+#ifdef ROUNDED
+	gl_FragColor = color * coverage();
+#else
 	gl_FragColor = color;
+#endif
 }
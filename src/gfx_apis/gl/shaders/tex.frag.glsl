@@ -2,7 +2,11 @@
 #extension GL_OES_EGL_image_external : require
 #endif
 
+#if defined(ROUNDED) && defined(GL_FRAGMENT_PRECISION_HIGH)
+precision highp float;
+#else
 precision mediump float;
+#endif
 varying vec2 v_texcoord;
 #ifdef EXTERNAL
 uniform samplerExternalOES tex;
@@ -12,6 +16,18 @@ uniform sampler2D tex;
 #ifdef ALPHA_MULTIPLIER
 uniform float alpha;
 #endif
+#ifdef ROUNDED
+uniform vec4 rect;
+uniform float radius;
+uniform float blur;
+
+float coverage() {
+	vec2 center = (rect.xy + rect.zw) * 0.5;
+	vec2 q = abs(gl_FragCoord.xy - center) - (rect.zw - rect.xy) * 0.5 + radius;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - radius;
+	return clamp(0.5 - d / max(blur, 1.0), 0.0, 1.0);
+}
+#endif
 
 void main() {
 #ifdef ALPHA
@@ -31,4 +47,8 @@ void main() {
 #endif // ALPHA_MULTIPLIER
 
 #endif // ALPHA
+
+#ifdef ROUNDED
+	gl_FragColor *= coverage();
+#endif
 }
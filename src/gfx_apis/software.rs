@@ -0,0 +1,450 @@
+use {
+    crate::{
+        clientmem::ClientMemError,
+        gfx_api::{
+            AcquireSync, Blur, BlurCacheKey, CopyTexture, FillRect, GfxApiOpt, GfxContext,
+            GfxError, RoundedRect,
+        },
+        gfx_apis::software::{
+            context::SoftwareContext,
+            pixels::{Pixels, BPP},
+            texture::Texture,
+        },
+        theme::Color,
+        utils::{errorfmt::ErrorFmt, oserror::OsError},
+        video::{
+            dmabuf::{dma_buf_export_sync_file, DMA_BUF_SYNC_READ},
+            drm::{Drm, DrmError},
+            gbm::GbmError,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+    uapi::c,
+};
+
+mod context;
+mod framebuffer;
+mod image;
+mod pixels;
+#[cfg(test)]
+mod tests;
+mod texture;
+
+pub(super) fn create_gfx_context(drm: &Drm) -> Result<Rc<dyn GfxContext>, GfxError> {
+    SoftwareContext::new(drm)
+        .map(|v| Rc::new(v) as Rc<dyn GfxContext>)
+        .map_err(|e| e.into())
+}
+
+#[derive(Debug, Error)]
+enum SoftwareError {
+    #[error(transparent)]
+    DrmError(#[from] DrmError),
+    #[error("Could not create a GBM device")]
+    Gbm(#[source] GbmError),
+    #[error("The DRM device does not have a render node")]
+    NoRenderNode,
+    #[error("Format {0} is not supported by the software renderer")]
+    UnsupportedFormat(&'static str),
+    #[error("The requested modifier is not supported")]
+    UnsupportedModifier,
+    #[error("Only single-plane dmabufs are supported")]
+    UnsupportedPlaneCount,
+    #[error("Image size is invalid")]
+    InvalidSize,
+    #[error("Shm buffer is too small")]
+    SmallShmBuffer,
+    #[error("Dmabuf is too small")]
+    SmallDmaBuf,
+    #[error("Could not determine the size of the dmabuf")]
+    DmaBufSize(#[source] OsError),
+    #[error("Could not map the dmabuf")]
+    MapDmaBuf(#[source] ClientMemError),
+    #[error("Could not access the dmabuf")]
+    ClientMem(#[source] ClientMemError),
+    #[error("The dmabuf cannot be mapped writable")]
+    ReadOnlyImage,
+    #[error("Requested area is out of bounds")]
+    OutOfBounds,
+}
+
+impl From<SoftwareError> for GfxError {
+    fn from(value: SoftwareError) -> Self {
+        Self(Box::new(value))
+    }
+}
+
+fn run_ops(fb: &Pixels, ops: &[GfxApiOpt], clear: Option<&Color>) -> Result<(), SoftwareError> {
+    fb.write(|data| {
+        if let Some(c) = clear {
+            fill(fb, data, [0, 0, fb.width, fb.height], c, None);
+        }
+        for op in ops {
+            match op {
+                GfxApiOpt::Sync => {}
+                GfxApiOpt::FillRect(fr) => fill_rect(fb, data, fr),
+                GfxApiOpt::CopyTexture(ct) => copy_texture(fb, data, ct),
+                GfxApiOpt::Blur(b) => blur(fb, data, b),
+            }
+        }
+    })
+}
+
+/// Converts normalized device coordinates to pixel coordinates.
+fn to_pixels(fb: &Pixels, [x, y]: [f32; 2]) -> [f32; 2] {
+    [
+        (x + 1.0) * 0.5 * fb.width as f32,
+        (y + 1.0) * 0.5 * fb.height as f32,
+    ]
+}
+
+/// Returns the pixels covered by the bounding box of the points.
+fn bounds(fb: &Pixels, points: &[[f32; 2]; 4]) -> [i32; 4] {
+    let mut min = [f32::INFINITY; 2];
+    let mut max = [f32::NEG_INFINITY; 2];
+    for p in points {
+        for i in 0..2 {
+            min[i] = min[i].min(p[i]);
+            max[i] = max[i].max(p[i]);
+        }
+    }
+    let clamp = |v: f32, max: i32| (v.round() as i32).clamp(0, max);
+    [
+        clamp(min[0], fb.width),
+        clamp(min[1], fb.height),
+        clamp(max[0], fb.width),
+        clamp(max[1], fb.height),
+    ]
+}
+
+fn fill_rect(fb: &Pixels, data: &mut [u8], fr: &FillRect) {
+    let points = fr.rect.to_points().map(|p| to_pixels(fb, p));
+    fill(
+        fb,
+        data,
+        bounds(fb, &points),
+        &fr.color,
+        fr.rounded.as_ref(),
+    );
+}
+
+fn fill(
+    fb: &Pixels,
+    data: &mut [u8],
+    [x1, y1, x2, y2]: [i32; 4],
+    color: &Color,
+    rounded: Option<&RoundedRect>,
+) {
+    if x1 >= x2 || y1 >= y2 {
+        return;
+    }
+    let src = [color.r, color.g, color.b, color.a];
+    for y in y1..y2 {
+        let row = &mut data[y as usize * fb.stride as usize..][..x2 as usize * BPP];
+        for (x, px) in row[x1 as usize * BPP..].chunks_exact_mut(BPP).enumerate() {
+            let mut src = src;
+            if let Some(rounded) = rounded {
+                let coverage = rounded.coverage((x1 as usize + x) as f32 + 0.5, y as f32 + 0.5);
+                src = src.map(|c| c * coverage);
+            }
+            let dst = match src[3] >= 1.0 {
+                true => src,
+                false => blend(src, fb.pf.read(px)),
+            };
+            fb.pf.write(px, dst);
+        }
+    }
+}
+
+/// Returns whether pending writes to the texture have completed.
+///
+/// The CPU cannot wait for fences asynchronously like the GPU APIs do. Instead of
+/// blocking the compositor, textures whose fence has not yet been signaled are skipped.
+fn texture_ready(tex: &Pixels, acquire_sync: &AcquireSync) -> bool {
+    let sync_file = match acquire_sync {
+        AcquireSync::SyncFile { sync_file } => sync_file.0.clone(),
+        AcquireSync::Implicit => {
+            let Some(dmabuf) = tex.dmabuf() else {
+                return true;
+            };
+            match dma_buf_export_sync_file(&dmabuf.planes[0].fd, DMA_BUF_SYNC_READ) {
+                Ok(fd) => Rc::new(fd),
+                Err(e) => {
+                    log::warn!("Could not export implicit fence: {}", ErrorFmt(e));
+                    return true;
+                }
+            }
+        }
+        AcquireSync::None | AcquireSync::Unnecessary => return true,
+    };
+    let mut pollfd = [c::pollfd {
+        fd: sync_file.raw(),
+        events: c::POLLIN,
+        revents: 0,
+    }];
+    match uapi::poll(&mut pollfd, 0) {
+        Ok(n) => n > 0,
+        Err(e) => {
+            log::error!(
+                "Could not wait for sync file: {}",
+                ErrorFmt(OsError::from(e))
+            );
+            true
+        }
+    }
+}
+
+fn copy_texture(fb: &Pixels, dst_data: &mut [u8], ct: &CopyTexture) {
+    let Some(tex) = ct.tex.as_any().downcast_ref::<Texture>() else {
+        log::error!("Texture was not created by the software renderer");
+        return;
+    };
+    let src = &*tex.pixels;
+    if std::ptr::eq(src, fb) {
+        log::warn!("Cannot copy a texture onto itself");
+        return;
+    }
+    if !texture_ready(src, &ct.acquire_sync) {
+        log::debug!("Texture is not ready yet. Skipping it.");
+        return;
+    }
+    let target = ct.target.to_points().map(|p| to_pixels(fb, p));
+    let source = ct
+        .source
+        .to_points()
+        .map(|[x, y]| [x * src.width as f32, y * src.height as f32]);
+    // Solve for the affine map from target pixels to source texels. The vertices are
+    // ordered [top_right, top_left, bottom_right, bottom_left].
+    let sub = |a: [f32; 2], b: [f32; 2]| [a[0] - b[0], a[1] - b[1]];
+    let e1 = sub(target[0], target[1]);
+    let e2 = sub(target[3], target[1]);
+    let s1 = sub(source[0], source[1]);
+    let s2 = sub(source[3], source[1]);
+    let det = e1[0] * e2[1] - e1[1] * e2[0];
+    if det.abs() < f32::EPSILON {
+        return;
+    }
+    let du = [e2[1] / det, -e2[0] / det];
+    let dv = [-e1[1] / det, e1[0] / det];
+    let dx = [s1[0] * du[0] + s2[0] * dv[0], s1[1] * du[0] + s2[1] * dv[0]];
+    let dy = [s1[0] * du[1] + s2[0] * dv[1], s1[1] * du[1] + s2[1] * dv[1]];
+    let [x1, y1, x2, y2] = bounds(fb, &target);
+    if x1 >= x2 || y1 >= y2 {
+        return;
+    }
+    let origin = sub([x1 as f32 + 0.5, y1 as f32 + 0.5], target[1]);
+    let start = [
+        source[1][0] + origin[0] * dx[0] + origin[1] * dy[0],
+        source[1][1] + origin[0] * dx[1] + origin[1] * dy[1],
+    ];
+    let is_unit = |v: f32| {
+        let v = v.abs();
+        v < 1e-4 || (v - 1.0).abs() < 1e-4
+    };
+    let nearest = dx.into_iter().chain(dy).all(is_unit);
+    let opaque = !src.pf.has_alpha() && ct.alpha.is_none() && ct.rounded.is_none();
+    let res = src.read(|src_data| {
+        let fetch = |x: i32, y: i32| {
+            let x = x.clamp(0, src.width - 1) as usize;
+            let y = y.clamp(0, src.height - 1) as usize;
+            src.pf
+                .read(&src_data[y * src.stride as usize + x * BPP..][..BPP])
+        };
+        let sample = |[x, y]: [f32; 2]| {
+            if nearest {
+                return fetch(x.floor() as i32, y.floor() as i32);
+            }
+            let (x, y) = (x - 0.5, y - 0.5);
+            let (fx, fy) = (x - x.floor(), y - y.floor());
+            let (x, y) = (x.floor() as i32, y.floor() as i32);
+            let lerp = |a: [f32; 4], b: [f32; 4], t: f32| {
+                let mut res = [0.0; 4];
+                for i in 0..4 {
+                    res[i] = a[i] + (b[i] - a[i]) * t;
+                }
+                res
+            };
+            let top = lerp(fetch(x, y), fetch(x + 1, y), fx);
+            let bottom = lerp(fetch(x, y + 1), fetch(x + 1, y + 1), fx);
+            lerp(top, bottom, fy)
+        };
+        for y in y1..y2 {
+            let row = &mut dst_data[y as usize * fb.stride as usize..][..x2 as usize * BPP];
+            let rows = (y - y1) as f32;
+            let mut pos = [start[0] + rows * dy[0], start[1] + rows * dy[1]];
+            for (x, px) in row[x1 as usize * BPP..].chunks_exact_mut(BPP).enumerate() {
+                let mut color = sample(pos);
+                pos = [pos[0] + dx[0], pos[1] + dx[1]];
+                if let Some(alpha) = ct.alpha {
+                    color = color.map(|c| c * alpha);
+                }
+                if let Some(rounded) = &ct.rounded {
+                    let coverage = rounded.coverage((x1 as usize + x) as f32 + 0.5, y as f32 + 0.5);
+                    color = color.map(|c| c * coverage);
+                }
+                if !opaque {
+                    color = blend(color, fb.pf.read(px));
+                }
+                fb.pf.write(px, color);
+            }
+        }
+    });
+    if let Err(e) = res {
+        log::error!("Could not read the texture: {}", ErrorFmt(e));
+    }
+}
+
+/// The taps of the downsampling pass in half pixels of the destination.
+const BLUR_DOWN: [(f32, f32, f32); 5] = [
+    (0.0, 0.0, 4.0 / 8.0),
+    (-1.0, -1.0, 1.0 / 8.0),
+    (1.0, 1.0, 1.0 / 8.0),
+    (1.0, -1.0, 1.0 / 8.0),
+    (-1.0, 1.0, 1.0 / 8.0),
+];
+
+/// The taps of the upsampling pass in half pixels of the destination.
+const BLUR_UP: [(f32, f32, f32); 8] = [
+    (-2.0, 0.0, 1.0 / 12.0),
+    (-1.0, 1.0, 2.0 / 12.0),
+    (0.0, 2.0, 1.0 / 12.0),
+    (1.0, 1.0, 2.0 / 12.0),
+    (2.0, 0.0, 1.0 / 12.0),
+    (1.0, -1.0, 2.0 / 12.0),
+    (0.0, -2.0, 1.0 / 12.0),
+    (-1.0, -1.0, 2.0 / 12.0),
+];
+
+struct BlurLevel {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl BlurLevel {
+    /// Samples the level with bilinear filtering at normalized coordinates.
+    fn sample(&self, u: f32, v: f32) -> [f32; 4] {
+        let x = u * self.width as f32 - 0.5;
+        let y = v * self.height as f32 - 0.5;
+        let (fx, fy) = (x - x.floor(), y - y.floor());
+        let fetch = |x: f32, y: f32| {
+            let x = (x as isize).clamp(0, self.width as isize - 1) as usize;
+            let y = (y as isize).clamp(0, self.height as isize - 1) as usize;
+            self.pixels[y * self.width + x]
+        };
+        let (x, y) = (x.floor(), y.floor());
+        let [tl, tr, bl, br] = [
+            fetch(x, y),
+            fetch(x + 1.0, y),
+            fetch(x, y + 1.0),
+            fetch(x + 1.0, y + 1.0),
+        ];
+        let mut res = [0.0; 4];
+        for i in 0..4 {
+            let top = tl[i] + (tr[i] - tl[i]) * fx;
+            let bottom = bl[i] + (br[i] - bl[i]) * fx;
+            res[i] = top + (bottom - top) * fy;
+        }
+        res
+    }
+
+    fn resample(&self, width: usize, height: usize, offset: f32, taps: &[(f32, f32, f32)]) -> Self {
+        let hx = offset * 0.5 / width as f32;
+        let hy = offset * 0.5 / height as f32;
+        let mut pixels = Vec::with_capacity(width * height);
+        for y in 0..height {
+            let v = (y as f32 + 0.5) / height as f32;
+            for x in 0..width {
+                let u = (x as f32 + 0.5) / width as f32;
+                let mut sum = [0.0; 4];
+                for &(dx, dy, weight) in taps {
+                    let color = self.sample(u + dx * hx, v + dy * hy);
+                    for i in 0..4 {
+                        sum[i] += color[i] * weight;
+                    }
+                }
+                pixels.push(sum);
+            }
+        }
+        Self {
+            width,
+            height,
+            pixels,
+        }
+    }
+}
+
+fn blur(fb: &Pixels, data: &mut [u8], b: &Blur) {
+    let Some(bounds @ [x1, y1, x2, y2]) = b.bounds(fb.width, fb.height) else {
+        return;
+    };
+    let passes = b.effective_passes(x2 - x1, y2 - y1);
+    let key = BlurCacheKey::new(b, fb.format, bounds, passes);
+    let blurred = match b.cache.as_ref().and_then(|c| c.get::<BlurLevel>(&key)) {
+        Some(blurred) => blurred,
+        _ => {
+            let blurred = Rc::new(blur_area(fb, data, bounds, passes, b.offset));
+            if let Some(cache) = &b.cache {
+                cache.set(key, blurred.clone());
+            }
+            blurred
+        }
+    };
+    for y in y1..y2 {
+        let row = &mut data[y as usize * fb.stride as usize..][..x2 as usize * BPP];
+        let src = &blurred.pixels[(y - y1) as usize * blurred.width..];
+        for (x, px) in row[x1 as usize * BPP..].chunks_exact_mut(BPP).enumerate() {
+            let coverage = b
+                .rect
+                .coverage((x1 as usize + x) as f32 + 0.5, y as f32 + 0.5);
+            let color = src[x].map(|c| c * coverage);
+            fb.pf.write(px, blend(color, fb.pf.read(px)));
+        }
+    }
+}
+
+/// Returns the blurred contents of an area of the framebuffer.
+fn blur_area(
+    fb: &Pixels,
+    data: &[u8],
+    [x1, y1, x2, y2]: [i32; 4],
+    passes: u32,
+    offset: f32,
+) -> BlurLevel {
+    let (width, height) = (x2 - x1, y2 - y1);
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for y in y1..y2 {
+        let row = &data[y as usize * fb.stride as usize..][..x2 as usize * BPP];
+        pixels.extend(
+            row[x1 as usize * BPP..]
+                .chunks_exact(BPP)
+                .map(|px| fb.pf.read(px)),
+        );
+    }
+    let mut levels = vec![BlurLevel {
+        width: width as usize,
+        height: height as usize,
+        pixels,
+    }];
+    for n in 1..=passes {
+        let (w, h) = Blur::level_size(width, height, n);
+        let level = levels[n as usize - 1].resample(w as usize, h as usize, offset, &BLUR_DOWN);
+        levels.push(level);
+    }
+    for n in (0..passes as usize).rev() {
+        let (w, h) = (levels[n].width, levels[n].height);
+        levels[n] = levels[n + 1].resample(w, h, offset, &BLUR_UP);
+    }
+    levels.swap_remove(0)
+}
+
+/// Blends premultiplied `src` over premultiplied `dst`.
+fn blend(src: [f32; 4], dst: [f32; 4]) -> [f32; 4] {
+    let inv = 1.0 - src[3];
+    let mut res = [0.0; 4];
+    for i in 0..4 {
+        res[i] = src[i] + dst[i] * inv;
+    }
+    res
+}
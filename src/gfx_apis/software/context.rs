@@ -0,0 +1,137 @@
+use {
+    crate::{
+        format::Format,
+        gfx_api::{
+            GfxApiOpt, GfxContext, GfxError, GfxFormat, GfxFramebuffer, GfxImage, GfxTexture,
+            ResetStatus,
+        },
+        gfx_apis::software::{
+            framebuffer::Framebuffer,
+            image::Image,
+            pixels::{Pixels, FORMATS},
+            texture::Texture,
+            SoftwareError,
+        },
+        video::{
+            dmabuf::DmaBuf,
+            drm::{sync_obj::SyncObjCtx, Drm},
+            gbm::GbmDevice,
+            LINEAR_MODIFIER,
+        },
+    },
+    ahash::AHashMap,
+    indexmap::IndexSet,
+    jay_config::video::GfxApi,
+    std::{
+        cell::{Cell, RefCell},
+        ffi::CString,
+        fmt::{Debug, Formatter},
+        rc::Rc,
+    },
+};
+
+pub(in crate::gfx_apis::software) struct SoftwareContext {
+    pub gbm: GbmDevice,
+    pub sync_ctx: Rc<SyncObjCtx>,
+    pub render_node: Rc<CString>,
+    pub formats: Rc<AHashMap<u32, GfxFormat>>,
+    pub gfx_ops: RefCell<Vec<GfxApiOpt>>,
+}
+
+impl Debug for SoftwareContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SoftwareContext").finish_non_exhaustive()
+    }
+}
+
+impl SoftwareContext {
+    pub(in crate::gfx_apis::software) fn new(drm: &Drm) -> Result<Self, SoftwareError> {
+        let render_node = drm
+            .get_render_node()?
+            .ok_or(SoftwareError::NoRenderNode)
+            .map(Rc::new)?;
+        let gbm = GbmDevice::new(drm).map_err(SoftwareError::Gbm)?;
+        let mut formats = AHashMap::new();
+        for format in FORMATS {
+            let modifiers: IndexSet<_> = [LINEAR_MODIFIER].into_iter().collect();
+            formats.insert(
+                format.drm,
+                GfxFormat {
+                    format,
+                    read_modifiers: modifiers.clone(),
+                    write_modifiers: modifiers,
+                },
+            );
+        }
+        Ok(Self {
+            sync_ctx: Rc::new(SyncObjCtx::new(gbm.drm.fd())),
+            gbm,
+            render_node,
+            formats: Rc::new(formats),
+            gfx_ops: Default::default(),
+        })
+    }
+}
+
+impl GfxContext for SoftwareContext {
+    fn reset_status(&self) -> Option<ResetStatus> {
+        None
+    }
+
+    fn render_node(&self) -> Rc<CString> {
+        self.render_node.clone()
+    }
+
+    fn formats(&self) -> Rc<AHashMap<u32, GfxFormat>> {
+        self.formats.clone()
+    }
+
+    fn dmabuf_img(self: Rc<Self>, buf: &DmaBuf) -> Result<Rc<dyn GfxImage>, GfxError> {
+        let pixels = Pixels::from_dmabuf(buf)?;
+        Ok(Rc::new(Image {
+            ctx: self,
+            pixels: Rc::new(pixels),
+        }))
+    }
+
+    fn shmem_texture(
+        self: Rc<Self>,
+        _old: Option<Rc<dyn GfxTexture>>,
+        data: &[Cell<u8>],
+        format: &'static Format,
+        width: i32,
+        height: i32,
+        stride: i32,
+    ) -> Result<Rc<dyn GfxTexture>, GfxError> {
+        let pixels = Pixels::from_shm(data, format, width, height, stride)?;
+        Ok(Rc::new(Texture {
+            pixels: Rc::new(pixels),
+        }))
+    }
+
+    fn gbm(&self) -> &GbmDevice {
+        &self.gbm
+    }
+
+    fn gfx_api(&self) -> GfxApi {
+        GfxApi::Software
+    }
+
+    fn create_fb(
+        self: Rc<Self>,
+        width: i32,
+        height: i32,
+        _stride: i32,
+        format: &'static Format,
+    ) -> Result<Rc<dyn GfxFramebuffer>, GfxError> {
+        let pixels = Pixels::new(width, height, format)?;
+        Ok(Rc::new(Framebuffer {
+            ctx: self,
+            pixels: Rc::new(pixels),
+        }))
+    }
+
+    fn sync_obj_ctx(&self) -> &Rc<SyncObjCtx> {
+        &self.sync_ctx
+    }
+}
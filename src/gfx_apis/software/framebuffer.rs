@@ -0,0 +1,66 @@
+use {
+    crate::{
+        format::Format,
+        gfx_api::{GfxApiOpt, GfxError, GfxFramebuffer, SyncFile},
+        gfx_apis::software::{context::SoftwareContext, pixels::Pixels, run_ops},
+        theme::Color,
+    },
+    std::{
+        cell::Cell,
+        fmt::{Debug, Formatter},
+        mem,
+        rc::Rc,
+    },
+};
+
+pub struct Framebuffer {
+    pub(in crate::gfx_apis::software) ctx: Rc<SoftwareContext>,
+    pub(in crate::gfx_apis::software) pixels: Rc<Pixels>,
+}
+
+impl Debug for Framebuffer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Framebuffer").finish_non_exhaustive()
+    }
+}
+
+impl GfxFramebuffer for Framebuffer {
+    fn take_render_ops(&self) -> Vec<GfxApiOpt> {
+        mem::take(&mut *self.ctx.gfx_ops.borrow_mut())
+    }
+
+    fn physical_size(&self) -> (i32, i32) {
+        (self.pixels.width, self.pixels.height)
+    }
+
+    fn render(
+        &self,
+        mut ops: Vec<GfxApiOpt>,
+        clear: Option<&Color>,
+    ) -> Result<Option<SyncFile>, GfxError> {
+        let res = run_ops(&self.pixels, &ops, clear);
+        ops.clear();
+        *self.ctx.gfx_ops.borrow_mut() = ops;
+        res?;
+        Ok(None)
+    }
+
+    fn copy_to_shm(
+        self: Rc<Self>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        stride: i32,
+        format: &'static Format,
+        shm: &[Cell<u8>],
+    ) -> Result<(), GfxError> {
+        self.pixels
+            .copy_to_shm(x, y, width, height, stride, format, shm)
+            .map_err(|e| e.into())
+    }
+
+    fn format(&self) -> &'static Format {
+        self.pixels.format
+    }
+}
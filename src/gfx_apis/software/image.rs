@@ -0,0 +1,41 @@
+use {
+    crate::{
+        gfx_api::{GfxError, GfxFramebuffer, GfxImage, GfxTexture},
+        gfx_apis::software::{
+            context::SoftwareContext, framebuffer::Framebuffer, pixels::Pixels, texture::Texture,
+            SoftwareError,
+        },
+    },
+    std::rc::Rc,
+};
+
+pub struct Image {
+    pub(in crate::gfx_apis::software) ctx: Rc<SoftwareContext>,
+    pub(in crate::gfx_apis::software) pixels: Rc<Pixels>,
+}
+
+impl GfxImage for Image {
+    fn to_framebuffer(self: Rc<Self>) -> Result<Rc<dyn GfxFramebuffer>, GfxError> {
+        if !self.pixels.writable() {
+            return Err(SoftwareError::ReadOnlyImage.into());
+        }
+        Ok(Rc::new(Framebuffer {
+            ctx: self.ctx.clone(),
+            pixels: self.pixels.clone(),
+        }))
+    }
+
+    fn to_texture(self: Rc<Self>) -> Result<Rc<dyn GfxTexture>, GfxError> {
+        Ok(Rc::new(Texture {
+            pixels: self.pixels.clone(),
+        }))
+    }
+
+    fn width(&self) -> i32 {
+        self.pixels.width
+    }
+
+    fn height(&self) -> i32 {
+        self.pixels.height
+    }
+}
@@ -0,0 +1,311 @@
+use {
+    crate::{
+        clientmem::{ClientMem, ClientMemOffset},
+        format::{Format, ABGR8888, ARGB8888, XBGR8888, XRGB8888},
+        gfx_apis::software::SoftwareError,
+        utils::errorfmt::ErrorFmt,
+        video::{
+            dmabuf::{
+                dma_buf_sync, DmaBuf, DMA_BUF_SYNC_END, DMA_BUF_SYNC_READ, DMA_BUF_SYNC_START,
+                DMA_BUF_SYNC_WRITE,
+            },
+            LINEAR_MODIFIER,
+        },
+    },
+    std::{
+        cell::{Cell, UnsafeCell},
+        rc::Rc,
+        slice,
+    },
+    uapi::{c, OwnedFd},
+};
+
+pub(super) const BPP: usize = 4;
+
+pub(super) const FORMATS: [&Format; 4] = [ARGB8888, XRGB8888, ABGR8888, XBGR8888];
+
+/// The memory layout of a supported format.
+///
+/// All supported formats use 4 bytes per pixel and store premultiplied alpha.
+#[derive(Copy, Clone, Debug)]
+pub(super) struct PixelFormat {
+    bgr: bool,
+    alpha: bool,
+}
+
+impl PixelFormat {
+    pub(super) fn get(format: &Format) -> Option<Self> {
+        let (bgr, alpha) = match format.drm {
+            f if f == ARGB8888.drm => (true, true),
+            f if f == XRGB8888.drm => (true, false),
+            f if f == ABGR8888.drm => (false, true),
+            f if f == XBGR8888.drm => (false, false),
+            _ => return None,
+        };
+        Some(Self { bgr, alpha })
+    }
+
+    pub(super) fn has_alpha(self) -> bool {
+        self.alpha
+    }
+
+    /// Reads a pixel as premultiplied RGBA.
+    #[inline]
+    pub(super) fn read(self, px: &[u8]) -> [f32; 4] {
+        let (r, b) = match self.bgr {
+            true => (px[2], px[0]),
+            false => (px[0], px[2]),
+        };
+        let a = match self.alpha {
+            true => to_f32(px[3]),
+            false => 1.0,
+        };
+        [to_f32(r), to_f32(px[1]), to_f32(b), a]
+    }
+
+    /// Writes a premultiplied RGBA pixel.
+    #[inline]
+    pub(super) fn write(self, px: &mut [u8], [r, g, b, a]: [f32; 4]) {
+        let (r, b) = match self.bgr {
+            true => (b, r),
+            false => (r, b),
+        };
+        px[0] = to_u8(r);
+        px[1] = to_u8(g);
+        px[2] = to_u8(b);
+        px[3] = match self.alpha {
+            true => to_u8(a),
+            false => 255,
+        };
+    }
+}
+
+#[inline]
+fn to_f32(c: u8) -> f32 {
+    c as f32 * (1.0 / 255.0)
+}
+
+#[inline]
+fn to_u8(c: f32) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0 + 0.5) as u8
+}
+
+enum Memory {
+    Owned(UnsafeCell<Vec<u8>>),
+    DmaBuf {
+        dmabuf: DmaBuf,
+        mem: ClientMemOffset,
+        writable: bool,
+    },
+}
+
+/// A block of CPU-accessible pixel memory.
+pub(super) struct Pixels {
+    pub width: i32,
+    pub height: i32,
+    pub stride: i32,
+    pub format: &'static Format,
+    pub pf: PixelFormat,
+    mem: Memory,
+}
+
+impl Pixels {
+    pub(super) fn new(
+        width: i32,
+        height: i32,
+        format: &'static Format,
+    ) -> Result<Self, SoftwareError> {
+        let pf = get_format(format)?;
+        if width <= 0 || height <= 0 {
+            return Err(SoftwareError::InvalidSize);
+        }
+        let stride = width * BPP as i32;
+        Ok(Self {
+            width,
+            height,
+            stride,
+            format,
+            pf,
+            mem: Memory::Owned(UnsafeCell::new(vec![0; stride as usize * height as usize])),
+        })
+    }
+
+    pub(super) fn from_shm(
+        data: &[Cell<u8>],
+        format: &'static Format,
+        width: i32,
+        height: i32,
+        stride: i32,
+    ) -> Result<Self, SoftwareError> {
+        let pixels = Self::new(width, height, format)?;
+        if stride < pixels.stride || (stride as usize) * (height as usize) > data.len() {
+            return Err(SoftwareError::SmallShmBuffer);
+        }
+        let row_len = pixels.stride as usize;
+        pixels.write(|dst| {
+            for (y, dst) in dst.chunks_exact_mut(row_len).enumerate() {
+                let src = &data[y * stride as usize..][..row_len];
+                for (d, s) in dst.iter_mut().zip(src) {
+                    *d = s.get();
+                }
+            }
+        })?;
+        Ok(pixels)
+    }
+
+    pub(super) fn from_dmabuf(buf: &DmaBuf) -> Result<Self, SoftwareError> {
+        let pf = get_format(buf.format)?;
+        if buf.modifier != LINEAR_MODIFIER {
+            return Err(SoftwareError::UnsupportedModifier);
+        }
+        if buf.planes.len() != 1 {
+            return Err(SoftwareError::UnsupportedPlaneCount);
+        }
+        if buf.width <= 0 || buf.height <= 0 {
+            return Err(SoftwareError::InvalidSize);
+        }
+        let plane = &buf.planes[0];
+        if (plane.stride as usize) < buf.width as usize * BPP {
+            return Err(SoftwareError::SmallDmaBuf);
+        }
+        let len = plane.offset as usize + plane.stride as usize * buf.height as usize;
+        let size = match uapi::lseek(plane.fd.raw(), 0, c::SEEK_END) {
+            Ok(s) => s as u64,
+            Err(e) => return Err(SoftwareError::DmaBufSize(e.into())),
+        };
+        if size < len as u64 {
+            return Err(SoftwareError::SmallDmaBuf);
+        }
+        let map = |read_only| ClientMem::new(plane.fd.raw(), len, read_only);
+        let (mem, writable) = match map(false) {
+            Ok(m) => (m, true),
+            Err(_) => match map(true) {
+                Ok(m) => (m, false),
+                Err(e) => return Err(SoftwareError::MapDmaBuf(e)),
+            },
+        };
+        Ok(Self {
+            width: buf.width,
+            height: buf.height,
+            stride: plane.stride as _,
+            format: buf.format,
+            pf,
+            mem: Memory::DmaBuf {
+                dmabuf: buf.clone(),
+                mem: Rc::new(mem).offset(plane.offset as _),
+                writable,
+            },
+        })
+    }
+
+    pub(super) fn dmabuf(&self) -> Option<&DmaBuf> {
+        match &self.mem {
+            Memory::Owned(_) => None,
+            Memory::DmaBuf { dmabuf, .. } => Some(dmabuf),
+        }
+    }
+
+    pub(super) fn writable(&self) -> bool {
+        match &self.mem {
+            Memory::Owned(_) => true,
+            Memory::DmaBuf { writable, .. } => *writable,
+        }
+    }
+
+    /// Calls `f` with the pixel rows of this buffer.
+    ///
+    /// Dmabufs are accessed in the same SIGBUS-safe way as shm buffers. If the client
+    /// truncates the buffer, an error is returned instead of crashing the compositor.
+    ///
+    /// `f` must not access this buffer through another reference.
+    pub(super) fn read<T>(&self, f: impl FnOnce(&[u8]) -> T) -> Result<T, SoftwareError> {
+        self.access(false, |d| f(d))
+    }
+
+    /// Like [`Self::read`] but allows the buffer to be modified.
+    pub(super) fn write<T>(&self, f: impl FnOnce(&mut [u8]) -> T) -> Result<T, SoftwareError> {
+        if !self.writable() {
+            return Err(SoftwareError::ReadOnlyImage);
+        }
+        self.access(true, f)
+    }
+
+    fn access<T>(&self, write: bool, f: impl FnOnce(&mut [u8]) -> T) -> Result<T, SoftwareError> {
+        let len = self.stride as usize * self.height as usize;
+        match &self.mem {
+            Memory::Owned(v) => Ok(f(unsafe { &mut (*v.get())[..len] })),
+            Memory::DmaBuf { dmabuf, mem, .. } => {
+                let fd = &dmabuf.planes[0].fd;
+                sync(fd, DMA_BUF_SYNC_START, write);
+                let res = mem.access(|data| {
+                    let data = &data[..len];
+                    f(unsafe { slice::from_raw_parts_mut(data.as_ptr() as *mut u8, len) })
+                });
+                sync(fd, DMA_BUF_SYNC_END, write);
+                res.map_err(SoftwareError::ClientMem)
+            }
+        }
+    }
+
+    /// Copies an area of this buffer into shared memory, converting the format if
+    /// necessary.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn copy_to_shm(
+        &self,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        stride: i32,
+        format: &'static Format,
+        shm: &[Cell<u8>],
+    ) -> Result<(), SoftwareError> {
+        let pf = get_format(format)?;
+        if x < 0
+            || y < 0
+            || width <= 0
+            || height <= 0
+            || x + width > self.width
+            || y + height > self.height
+        {
+            return Err(SoftwareError::OutOfBounds);
+        }
+        let row_len = width as usize * BPP;
+        if (stride as usize) < row_len || stride as usize * height as usize > shm.len() {
+            return Err(SoftwareError::SmallShmBuffer);
+        }
+        self.read(|data| {
+            let mut px = [0; BPP];
+            for row in 0..height as usize {
+                let src = &data[(y as usize + row) * self.stride as usize + x as usize * BPP..];
+                let dst = &shm[row * stride as usize..][..row_len];
+                if format == self.format {
+                    for (d, s) in dst.iter().zip(src) {
+                        d.set(*s);
+                    }
+                    continue;
+                }
+                for (dst, src) in dst.chunks_exact(BPP).zip(src.chunks_exact(BPP)) {
+                    pf.write(&mut px, self.pf.read(src));
+                    for (d, s) in dst.iter().zip(px) {
+                        d.set(s);
+                    }
+                }
+            }
+        })
+    }
+}
+
+fn sync(fd: &OwnedFd, flags: u32, write: bool) {
+    let mut flags = flags | DMA_BUF_SYNC_READ;
+    if write {
+        flags |= DMA_BUF_SYNC_WRITE;
+    }
+    if let Err(e) = dma_buf_sync(fd, flags) {
+        log::warn!("Could not synchronize dmabuf access: {}", ErrorFmt(e));
+    }
+}
+
+fn get_format(format: &'static Format) -> Result<PixelFormat, SoftwareError> {
+    PixelFormat::get(format).ok_or(SoftwareError::UnsupportedFormat(format.name))
+}
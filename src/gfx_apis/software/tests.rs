@@ -0,0 +1,189 @@
+use {
+    crate::{
+        format::{Format, ABGR8888, ARGB8888, XRGB8888},
+        gfx_api::{
+            AcquireSync, Blur, BlurCache, CopyTexture, FillRect, FramebufferRect, GfxApiOpt,
+            ReleaseSync, RoundedRect, SampleRect,
+        },
+        gfx_apis::software::{blend, pixels::Pixels, run_ops, texture::Texture},
+        theme::Color,
+        utils::windows::WindowsExt,
+    },
+    jay_config::video::Transform,
+    std::{cell::Cell, rc::Rc},
+};
+
+fn pixels(width: i32, height: i32, format: &'static Format, data: &[[u8; 4]]) -> Pixels {
+    let data: Vec<_> = data.iter().flatten().map(|b| Cell::new(*b)).collect();
+    Pixels::from_shm(&data, format, width, height, width * 4).unwrap()
+}
+
+fn contents(pixels: &Pixels) -> Vec<[u8; 4]> {
+    pixels
+        .read(|d| {
+            d.chunks_exact(4)
+                .map(|c| [c[0], c[1], c[2], c[3]])
+                .collect()
+        })
+        .unwrap()
+}
+
+fn rect(fb: &Pixels, x1: f32, y1: f32, x2: f32, y2: f32) -> FramebufferRect {
+    FramebufferRect::new(
+        x1,
+        y1,
+        x2,
+        y2,
+        Transform::None,
+        fb.width as f32,
+        fb.height as f32,
+    )
+}
+
+fn copy_texture(fb: &Pixels, tex: Pixels, alpha: Option<f32>) -> GfxApiOpt {
+    GfxApiOpt::CopyTexture(CopyTexture {
+        tex: Rc::new(Texture {
+            pixels: Rc::new(tex),
+        }),
+        source: SampleRect::identity(),
+        target: rect(fb, 0.0, 0.0, fb.width as f32, fb.height as f32),
+        buffer_resv: None,
+        acquire_sync: AcquireSync::None,
+        release_sync: ReleaseSync::None,
+        alpha,
+        rounded: None,
+    })
+}
+
+#[test]
+fn blend_over() {
+    assert_eq!(
+        blend([0.0; 4], [0.25, 0.5, 0.75, 1.0]),
+        [0.25, 0.5, 0.75, 1.0]
+    );
+    assert_eq!(blend([1.0; 4], [0.25, 0.5, 0.75, 1.0]), [1.0; 4]);
+    assert_eq!(
+        blend([0.5, 0.0, 0.0, 0.5], [0.0, 0.0, 1.0, 1.0]),
+        [0.5, 0.0, 0.5, 1.0],
+    );
+}
+
+#[test]
+fn clear_and_fill() {
+    let fb = Pixels::new(4, 2, ARGB8888).unwrap();
+    let fill = GfxApiOpt::FillRect(FillRect {
+        rect: rect(&fb, 1.0, 0.0, 3.0, 1.0),
+        color: Color::from_rgb(0xff, 0, 0),
+        rounded: None,
+    });
+    run_ops(&fb, &[fill], Some(&Color::from_rgb(0, 0, 0xff))).unwrap();
+    let blue = [0xff, 0, 0, 0xff];
+    let red = [0, 0, 0xff, 0xff];
+    assert_eq!(
+        contents(&fb),
+        [blue, red, red, blue, blue, blue, blue, blue],
+    );
+}
+
+#[test]
+fn fill_translucent() {
+    let fb = Pixels::new(1, 1, XRGB8888).unwrap();
+    let fill = GfxApiOpt::FillRect(FillRect {
+        rect: rect(&fb, 0.0, 0.0, 1.0, 1.0),
+        color: Color::from_rgba_straight(0xff, 0xff, 0xff, 0x80),
+        rounded: None,
+    });
+    run_ops(&fb, &[fill], Some(&Color::from_rgb(0, 0, 0))).unwrap();
+    assert_eq!(contents(&fb), [[0x80, 0x80, 0x80, 0xff]]);
+}
+
+#[test]
+fn copy_opaque() {
+    let data = [
+        [1, 2, 3, 0xff],
+        [4, 5, 6, 0xff],
+        [7, 8, 9, 0xff],
+        [10, 11, 12, 0xff],
+    ];
+    let fb = Pixels::new(2, 2, XRGB8888).unwrap();
+    let tex = pixels(2, 2, XRGB8888, &data);
+    run_ops(&fb, &[copy_texture(&fb, tex, None)], None).unwrap();
+    assert_eq!(contents(&fb), data);
+}
+
+#[test]
+fn copy_converts_format() {
+    let fb = Pixels::new(1, 1, ARGB8888).unwrap();
+    let tex = pixels(1, 1, ABGR8888, &[[1, 2, 3, 0xff]]);
+    run_ops(&fb, &[copy_texture(&fb, tex, None)], None).unwrap();
+    assert_eq!(contents(&fb), [[3, 2, 1, 0xff]]);
+}
+
+#[test]
+fn copy_with_alpha() {
+    let fb = Pixels::new(1, 1, XRGB8888).unwrap();
+    let tex = pixels(1, 1, XRGB8888, &[[0xff, 0xff, 0xff, 0xff]]);
+    let clear = Color::from_rgb(0, 0, 0);
+    run_ops(&fb, &[copy_texture(&fb, tex, Some(0.5))], Some(&clear)).unwrap();
+    assert_eq!(contents(&fb), [[0x80, 0x80, 0x80, 0xff]]);
+}
+
+#[test]
+fn copy_scaled() {
+    let fb = Pixels::new(4, 1, XRGB8888).unwrap();
+    let tex = pixels(2, 1, XRGB8888, &[[0, 0, 0, 0xff], [0xff, 0xff, 0xff, 0xff]]);
+    run_ops(&fb, &[copy_texture(&fb, tex, None)], None).unwrap();
+    let contents = contents(&fb);
+    // The outer pixels are clamped to the edges of the texture, the inner pixels are
+    // interpolated.
+    assert_eq!(contents[0], [0, 0, 0, 0xff]);
+    assert_eq!(contents[3], [0xff, 0xff, 0xff, 0xff]);
+    for [l, r] in contents.array_windows_ext::<2>() {
+        assert!(l[0] < r[0]);
+    }
+}
+
+#[test]
+fn copy_to_shm() {
+    let fb = pixels(2, 1, ARGB8888, &[[1, 2, 3, 4], [5, 6, 7, 8]]);
+    let shm: Vec<_> = (0..4).map(|_| Cell::new(0)).collect();
+    fb.copy_to_shm(1, 0, 1, 1, 4, ABGR8888, &shm).unwrap();
+    let shm: Vec<_> = shm.iter().map(|c| c.get()).collect();
+    assert_eq!(shm, [7, 6, 5, 8]);
+}
+
+#[test]
+fn blur_cache() {
+    let black = [0, 0, 0, 0xff];
+    let white = [0xff, 0xff, 0xff, 0xff];
+    let cache = Rc::new(BlurCache::default());
+    let blur = || {
+        GfxApiOpt::Blur(Blur {
+            rect: RoundedRect {
+                x1: 0.0,
+                y1: 0.0,
+                x2: 4.0,
+                y2: 1.0,
+                radius: 0.0,
+                blur: 0.0,
+            },
+            passes: 1,
+            offset: 1.0,
+            cache: Some(cache.clone()),
+        })
+    };
+    let fb = pixels(4, 1, XRGB8888, &[black, white, black, white]);
+    run_ops(&fb, &[blur()], None).unwrap();
+    let blurred = contents(&fb);
+    assert_ne!(blurred, [black; 4]);
+    // The cached result is used while the cache is valid, even though the contents of
+    // the framebuffer have changed.
+    cache.valid.set(true);
+    let fb = pixels(4, 1, XRGB8888, &[black; 4]);
+    run_ops(&fb, &[blur()], None).unwrap();
+    assert_eq!(contents(&fb), blurred);
+    cache.valid.set(false);
+    let fb = pixels(4, 1, XRGB8888, &[black; 4]);
+    run_ops(&fb, &[blur()], None).unwrap();
+    assert_eq!(contents(&fb), [black; 4]);
+}
@@ -0,0 +1,61 @@
+use {
+    crate::{
+        format::Format,
+        gfx_api::{GfxError, GfxTexture},
+        gfx_apis::software::pixels::Pixels,
+        video::dmabuf::DmaBuf,
+    },
+    std::{
+        any::Any,
+        cell::Cell,
+        fmt::{Debug, Formatter},
+        rc::Rc,
+    },
+};
+
+pub struct Texture {
+    pub(in crate::gfx_apis::software) pixels: Rc<Pixels>,
+}
+
+impl Debug for Texture {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Texture").finish_non_exhaustive()
+    }
+}
+
+impl GfxTexture for Texture {
+    fn size(&self) -> (i32, i32) {
+        (self.pixels.width, self.pixels.height)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+
+    fn read_pixels(
+        self: Rc<Self>,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        stride: i32,
+        format: &'static Format,
+        shm: &[Cell<u8>],
+    ) -> Result<(), GfxError> {
+        self.pixels
+            .copy_to_shm(x, y, width, height, stride, format, shm)
+            .map_err(|e| e.into())
+    }
+
+    fn dmabuf(&self) -> Option<&DmaBuf> {
+        self.pixels.dmabuf()
+    }
+
+    fn format(&self) -> &'static Format {
+        self.pixels.format
+    }
+}
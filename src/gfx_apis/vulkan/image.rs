@@ -217,6 +217,85 @@ impl VulkanRenderer {
         }))
     }
 
+    /// Creates an image that is used as an intermediate image by the blur passes.
+    ///
+    /// If `renderable` is false, the image is only used as the destination of copies.
+    pub(super) fn create_blur_image(
+        self: &Rc<Self>,
+        format: &'static Format,
+        width: u32,
+        height: u32,
+        renderable: bool,
+    ) -> Result<Rc<VulkanImage>, VulkanError> {
+        let usage = ImageUsageFlags::SAMPLED
+            | match renderable {
+                true => ImageUsageFlags::COLOR_ATTACHMENT,
+                false => ImageUsageFlags::TRANSFER_DST,
+            };
+        let create_info = ImageCreateInfo::builder()
+            .image_type(ImageType::TYPE_2D)
+            .format(format.vk_format)
+            .mip_levels(1)
+            .array_layers(1)
+            .tiling(ImageTiling::OPTIMAL)
+            .samples(SampleCountFlags::TYPE_1)
+            .sharing_mode(SharingMode::EXCLUSIVE)
+            .initial_layout(ImageLayout::UNDEFINED)
+            .extent(Extent3D {
+                width,
+                height,
+                depth: 1,
+            })
+            .usage(usage)
+            .build();
+        let image = unsafe { self.device.device.create_image(&create_info, None) };
+        let image = image.map_err(VulkanError::CreateImage)?;
+        let destroy_image = OnDrop(|| unsafe { self.device.device.destroy_image(image, None) });
+        let memory_requirements =
+            unsafe { self.device.device.get_image_memory_requirements(image) };
+        let allocation =
+            self.allocator
+                .alloc(&memory_requirements, UsageFlags::FAST_DEVICE_ACCESS, false)?;
+        let res = unsafe {
+            self.device
+                .device
+                .bind_image_memory(image, allocation.memory, allocation.offset)
+        };
+        res.map_err(VulkanError::BindImageMemory)?;
+        let texture_view = self.device.create_image_view(image, format, false)?;
+        let render_view = match renderable {
+            true => {
+                let view = self.device.create_image_view(image, format, true);
+                if view.is_err() {
+                    unsafe {
+                        self.device.device.destroy_image_view(texture_view, None);
+                    }
+                }
+                Some(view?)
+            }
+            false => None,
+        };
+        destroy_image.forget();
+        Ok(Rc::new(VulkanImage {
+            renderer: self.clone(),
+            format,
+            width,
+            height,
+            stride: 0,
+            texture_view,
+            render_view,
+            image,
+            is_undefined: Cell::new(true),
+            ty: VulkanImageMemory::Internal(VulkanShmImage {
+                to_flush: Default::default(),
+                size: memory_requirements.size,
+                stride: 0,
+                _allocation: allocation,
+            }),
+            render_ops: Default::default(),
+        }))
+    }
+
     pub fn import_dmabuf(
         self: &Rc<Self>,
         dmabuf: &DmaBuf,
@@ -1,10 +1,10 @@
 use {
     crate::{
         async_engine::SpawnedFuture,
-        format::Format,
+        format::{Format, ARGB8888},
         gfx_api::{
-            AcquireSync, BufferResv, BufferResvUser, GfxApiOpt, GfxFormat, GfxFramebuffer,
-            GfxTexture, ReleaseSync, SyncFile,
+            AcquireSync, Blur, BlurCacheKey, BufferResv, BufferResvUser, GfxApiOpt, GfxFormat,
+            GfxFramebuffer, GfxTexture, ReleaseSync, SyncFile,
         },
         gfx_apis::vulkan::{
             allocator::VulkanAllocator,
@@ -15,16 +15,22 @@ use {
             pipeline::{PipelineCreateInfo, VulkanPipeline},
             semaphore::VulkanSemaphore,
             shaders::{
-                FillFragPushConstants, FillVertPushConstants, TexFragPushConstants,
-                TexVertPushConstants, VulkanShader, FILL_FRAG, FILL_VERT, TEX_FRAG,
-                TEX_FRAG_MULT_ALPHA, TEX_FRAG_MULT_OPAQUE, TEX_VERT,
+                BlurFragPushConstants, BlurFragRoundedPushConstants, FillFragPushConstants,
+                FillFragRoundedPushConstants, FillVertPushConstants, TexFragPushConstants,
+                TexFragRoundedPushConstants, TexVertPushConstants, VulkanShader, BLUR_FRAG_DOWN,
+                BLUR_FRAG_UP, BLUR_FRAG_UP_ROUNDED, FILL_FRAG, FILL_FRAG_ROUNDED, FILL_VERT,
+                TEX_FRAG, TEX_FRAG_MULT_ALPHA, TEX_FRAG_MULT_OPAQUE, TEX_FRAG_ROUNDED_ALPHA,
+                TEX_FRAG_ROUNDED_OPAQUE, TEX_VERT,
             },
             staging::VulkanStagingBuffer,
             VulkanError,
         },
         io_uring::IoUring,
         theme::Color,
-        utils::{copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell, stack::Stack},
+        utils::{
+            copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell, rc_eq::rc_eq,
+            stack::Stack,
+        },
         video::dmabuf::{dma_buf_export_sync_file, DMA_BUF_SYNC_READ, DMA_BUF_SYNC_WRITE},
     },
     ahash::AHashMap,
@@ -34,9 +40,9 @@ use {
             BufferMemoryBarrier2, ClearColorValue, ClearValue, CommandBuffer,
             CommandBufferBeginInfo, CommandBufferSubmitInfo, CommandBufferUsageFlags,
             CopyBufferToImageInfo2, DependencyInfo, DependencyInfoKHR, DescriptorImageInfo,
-            DescriptorType, Extent2D, Extent3D, Fence, ImageAspectFlags, ImageLayout,
+            DescriptorType, Extent2D, Extent3D, Fence, ImageAspectFlags, ImageCopy, ImageLayout,
             ImageMemoryBarrier2, ImageMemoryBarrier2Builder, ImageSubresourceLayers,
-            ImageSubresourceRange, PipelineBindPoint, PipelineStageFlags2, Rect2D,
+            ImageSubresourceRange, Offset3D, PipelineBindPoint, PipelineStageFlags2, Rect2D,
             RenderingAttachmentInfo, RenderingInfo, SemaphoreSubmitInfo, SemaphoreSubmitInfoKHR,
             ShaderStageFlags, SubmitInfo2, Viewport, WriteDescriptorSet, QUEUE_FAMILY_FOREIGN_EXT,
         },
@@ -58,7 +64,12 @@ pub struct VulkanRenderer {
     pub(super) formats: Rc<AHashMap<u32, GfxFormat>>,
     pub(super) device: Rc<VulkanDevice>,
     pub(super) fill_pipeline: Rc<VulkanPipeline>,
+    pub(super) fill_rounded_pipeline: Rc<VulkanPipeline>,
     pub(super) tex_pipelines: EnumMap<TexCopyType, EnumMap<TexSourceType, Rc<VulkanPipeline>>>,
+    pub(super) blur_down_pipeline: Rc<VulkanPipeline>,
+    pub(super) blur_up_pipeline: Rc<VulkanPipeline>,
+    pub(super) blur_up_rounded_pipeline: Rc<VulkanPipeline>,
+    pub(super) blur_images: RefCell<AHashMap<BlurImageKey, (Rc<VulkanImage>, u64)>>,
     pub(super) command_pool: Rc<VulkanCommandPool>,
     pub(super) command_buffers: Stack<Rc<VulkanCommandBuffer>>,
     pub(super) wait_semaphores: Stack<Rc<VulkanSemaphore>>,
@@ -77,10 +88,22 @@ pub(super) struct UsedTexture {
     release_sync: ReleaseSync,
 }
 
+/// The number of frames after which unused blur images are destroyed.
+const BLUR_IMAGE_LIFETIME: u64 = 60;
+
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub(super) struct BlurImageKey {
+    format: u32,
+    renderable: bool,
+    width: i32,
+    height: i32,
+}
+
 #[derive(Enum)]
 pub(super) enum TexCopyType {
     Identity,
     Multiply,
+    Rounded,
 }
 
 #[derive(Enum)]
@@ -116,20 +139,32 @@ pub(super) struct PendingFrame {
 
 impl VulkanDevice {
     pub fn create_renderer(self: &Rc<Self>) -> Result<Rc<VulkanRenderer>, VulkanError> {
+        let fill_vert_shader = self.create_shader(FILL_VERT)?;
         let fill_pipeline = self.create_pipeline::<FillVertPushConstants, FillFragPushConstants>(
             PipelineCreateInfo {
-                vert: self.create_shader(FILL_VERT)?,
+                vert: fill_vert_shader.clone(),
                 frag: self.create_shader(FILL_FRAG)?,
                 alpha: true,
                 frag_descriptor_set_layout: None,
             },
         )?;
+        let fill_rounded_pipeline = self
+            .create_pipeline::<FillVertPushConstants, FillFragRoundedPushConstants>(
+                PipelineCreateInfo {
+                    vert: fill_vert_shader,
+                    frag: self.create_shader(FILL_FRAG_ROUNDED)?,
+                    alpha: true,
+                    frag_descriptor_set_layout: None,
+                },
+            )?;
         let sampler = self.create_sampler()?;
         let tex_descriptor_set_layout = self.create_descriptor_set_layout(&sampler)?;
         let tex_vert_shader = self.create_shader(TEX_VERT)?;
         let tex_frag_shader = self.create_shader(TEX_FRAG)?;
         let tex_frag_mult_opaque_shader = self.create_shader(TEX_FRAG_MULT_OPAQUE)?;
         let tex_frag_mult_alpha_shader = self.create_shader(TEX_FRAG_MULT_ALPHA)?;
+        let tex_frag_rounded_opaque_shader = self.create_shader(TEX_FRAG_ROUNDED_OPAQUE)?;
+        let tex_frag_rounded_alpha_shader = self.create_shader(TEX_FRAG_ROUNDED_ALPHA)?;
         let create_tex_pipeline = |alpha| {
             self.create_pipeline::<TexVertPushConstants, ()>(PipelineCreateInfo {
                 vert: tex_vert_shader.clone(),
@@ -146,10 +181,45 @@ impl VulkanDevice {
                 frag_descriptor_set_layout: Some(tex_descriptor_set_layout.clone()),
             })
         };
+        let create_tex_rounded_pipeline = |frag: &Rc<VulkanShader>| {
+            self.create_pipeline::<TexVertPushConstants, TexFragRoundedPushConstants>(
+                PipelineCreateInfo {
+                    vert: tex_vert_shader.clone(),
+                    frag: frag.clone(),
+                    alpha: true,
+                    frag_descriptor_set_layout: Some(tex_descriptor_set_layout.clone()),
+                },
+            )
+        };
+        let create_blur_pipeline = |frag: &[u8]| {
+            self.create_pipeline::<TexVertPushConstants, BlurFragPushConstants>(
+                PipelineCreateInfo {
+                    vert: tex_vert_shader.clone(),
+                    frag: self.create_shader(frag)?,
+                    alpha: false,
+                    frag_descriptor_set_layout: Some(tex_descriptor_set_layout.clone()),
+                },
+            )
+        };
+        let blur_down_pipeline = create_blur_pipeline(BLUR_FRAG_DOWN)?;
+        let blur_up_pipeline = create_blur_pipeline(BLUR_FRAG_UP)?;
+        let blur_up_rounded_pipeline = self
+            .create_pipeline::<TexVertPushConstants, BlurFragRoundedPushConstants>(
+                PipelineCreateInfo {
+                    vert: tex_vert_shader.clone(),
+                    frag: self.create_shader(BLUR_FRAG_UP_ROUNDED)?,
+                    alpha: true,
+                    frag_descriptor_set_layout: Some(tex_descriptor_set_layout.clone()),
+                },
+            )?;
         let tex_opaque_pipeline = create_tex_pipeline(false)?;
         let tex_alpha_pipeline = create_tex_pipeline(true)?;
         let tex_mult_opaque_pipeline = create_tex_mult_pipeline(&tex_frag_mult_opaque_shader)?;
         let tex_mult_alpha_pipeline = create_tex_mult_pipeline(&tex_frag_mult_alpha_shader)?;
+        let tex_rounded_opaque_pipeline =
+            create_tex_rounded_pipeline(&tex_frag_rounded_opaque_shader)?;
+        let tex_rounded_alpha_pipeline =
+            create_tex_rounded_pipeline(&tex_frag_rounded_alpha_shader)?;
         let command_pool = self.create_command_pool()?;
         let formats: AHashMap<u32, _> = self
             .formats
@@ -180,6 +250,7 @@ impl VulkanDevice {
             formats: Rc::new(formats),
             device: self.clone(),
             fill_pipeline,
+            fill_rounded_pipeline,
             tex_pipelines: enum_map! {
                 TexCopyType::Identity => enum_map! {
                     TexSourceType::HasAlpha => tex_alpha_pipeline.clone(),
@@ -189,7 +260,15 @@ impl VulkanDevice {
                     TexSourceType::HasAlpha => tex_mult_alpha_pipeline.clone(),
                     TexSourceType::Opaque => tex_mult_opaque_pipeline.clone(),
                 },
+                TexCopyType::Rounded => enum_map! {
+                    TexSourceType::HasAlpha => tex_rounded_alpha_pipeline.clone(),
+                    TexSourceType::Opaque => tex_rounded_opaque_pipeline.clone(),
+                },
             },
+            blur_down_pipeline,
+            blur_up_pipeline,
+            blur_up_rounded_pipeline,
+            blur_images: Default::default(),
             command_pool,
             command_buffers: Default::default(),
             wait_semaphores: Default::default(),
@@ -440,12 +519,17 @@ impl VulkanRenderer {
         }
     }
 
-    fn record_draws(&self, buf: CommandBuffer, opts: &[GfxApiOpt]) -> Result<(), VulkanError> {
+    fn record_draws(
+        self: &Rc<Self>,
+        buf: CommandBuffer,
+        fb: &VulkanImage,
+        opts: &[GfxApiOpt],
+    ) -> Result<(), VulkanError> {
         let dev = &self.device.device;
-        let mut current_pipeline = None;
-        let mut bind = |pipeline: &VulkanPipeline| {
-            if current_pipeline != Some(pipeline.pipeline) {
-                current_pipeline = Some(pipeline.pipeline);
+        let current_pipeline = Cell::new(None);
+        let bind = |pipeline: &VulkanPipeline| {
+            if current_pipeline.get() != Some(pipeline.pipeline) {
+                current_pipeline.set(Some(pipeline.pipeline));
                 unsafe {
                     dev.cmd_bind_pipeline(buf, PipelineBindPoint::GRAPHICS, pipeline.pipeline);
                 }
@@ -455,36 +539,56 @@ impl VulkanRenderer {
             match opt {
                 GfxApiOpt::Sync => {}
                 GfxApiOpt::FillRect(r) => {
-                    bind(&self.fill_pipeline);
+                    let pipeline = match r.rounded {
+                        Some(_) => &self.fill_rounded_pipeline,
+                        None => &self.fill_pipeline,
+                    };
+                    bind(pipeline);
                     let vert = FillVertPushConstants {
                         pos: r.rect.to_points(),
                     };
-                    let frag = FillFragPushConstants {
-                        color: r.color.to_array_srgb(),
-                    };
+                    let color = r.color.to_array_srgb();
                     unsafe {
                         dev.cmd_push_constants(
                             buf,
-                            self.fill_pipeline.pipeline_layout,
+                            pipeline.pipeline_layout,
                             ShaderStageFlags::VERTEX,
                             0,
                             uapi::as_bytes(&vert),
                         );
-                        dev.cmd_push_constants(
-                            buf,
-                            self.fill_pipeline.pipeline_layout,
-                            ShaderStageFlags::FRAGMENT,
-                            self.fill_pipeline.frag_push_offset,
-                            uapi::as_bytes(&frag),
-                        );
+                        if let Some(rounded) = &r.rounded {
+                            let frag = FillFragRoundedPushConstants {
+                                color,
+                                rect: [rounded.x1, rounded.y1, rounded.x2, rounded.y2],
+                                radius: rounded.radius,
+                                blur: rounded.blur,
+                            };
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::FRAGMENT,
+                                pipeline.frag_push_offset,
+                                uapi::as_bytes(&frag),
+                            );
+                        } else {
+                            let frag = FillFragPushConstants { color };
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::FRAGMENT,
+                                pipeline.frag_push_offset,
+                                uapi::as_bytes(&frag),
+                            );
+                        }
                         dev.cmd_draw(buf, 4, 1, 0, 0);
                     }
                 }
                 GfxApiOpt::CopyTexture(c) => {
                     let tex = c.tex.as_vk(&self.device.device);
-                    let copy_type = match c.alpha.is_some() {
-                        true => TexCopyType::Multiply,
-                        false => TexCopyType::Identity,
+                    let copy_type = match (c.rounded.is_some(), c.alpha.is_some()) {
+                        (true, _) => TexCopyType::Rounded,
+                        (false, true) => TexCopyType::Multiply,
+                        (false, false) => TexCopyType::Identity,
                     };
                     let source_type = match tex.format.has_alpha {
                         true => TexSourceType::HasAlpha,
@@ -518,7 +622,22 @@ impl VulkanRenderer {
                             0,
                             uapi::as_bytes(&vert),
                         );
-                        if let Some(alpha) = c.alpha {
+                        if let Some(rounded) = &c.rounded {
+                            let frag = TexFragRoundedPushConstants {
+                                alpha: c.alpha.unwrap_or(1.0),
+                                _padding: [0.0; 3],
+                                rect: [rounded.x1, rounded.y1, rounded.x2, rounded.y2],
+                                radius: rounded.radius,
+                                blur: rounded.blur,
+                            };
+                            dev.cmd_push_constants(
+                                buf,
+                                pipeline.pipeline_layout,
+                                ShaderStageFlags::FRAGMENT,
+                                mem::size_of_val(&vert) as _,
+                                uapi::as_bytes(&frag),
+                            );
+                        } else if let Some(alpha) = c.alpha {
                             let frag = TexFragPushConstants { alpha };
                             dev.cmd_push_constants(
                                 buf,
@@ -531,11 +650,348 @@ impl VulkanRenderer {
                         dev.cmd_draw(buf, 4, 1, 0, 0);
                     }
                 }
+                GfxApiOpt::Blur(b) => {
+                    self.record_blur(buf, fb, b);
+                    current_pipeline.set(None);
+                }
             }
         }
         Ok(())
     }
 
+    fn blur_images(
+        self: &Rc<Self>,
+        format: &'static Format,
+        width: i32,
+        height: i32,
+        passes: u32,
+        cached: Option<&Rc<VulkanImage>>,
+    ) -> Result<Vec<Rc<VulkanImage>>, VulkanError> {
+        let frame = self.last_point.get();
+        let mut images = self.blur_images.borrow_mut();
+        let mut levels = Vec::with_capacity(passes as usize + 1);
+        for n in 0..=passes {
+            if let (1, Some(image)) = (n, cached) {
+                levels.push(image.clone());
+                continue;
+            }
+            let (width, height) = Blur::level_size(width, height, n);
+            let format = match n {
+                0 => format,
+                _ => ARGB8888,
+            };
+            let key = BlurImageKey {
+                format: format.drm,
+                renderable: n > 0,
+                width,
+                height,
+            };
+            let image = match images.get_mut(&key) {
+                Some((image, used)) => {
+                    *used = frame;
+                    image.clone()
+                }
+                _ => {
+                    let image =
+                        self.create_blur_image(format, width as _, height as _, key.renderable)?;
+                    images.insert(key, (image.clone(), frame));
+                    image
+                }
+            };
+            levels.push(image);
+        }
+        Ok(levels)
+    }
+
+    fn evict_blur_images(&self) {
+        let mut images = self.blur_images.borrow_mut();
+        if images.is_not_empty() {
+            let frame = self.last_point.get();
+            images.retain(|_, (_, used)| *used + BLUR_IMAGE_LIFETIME > frame);
+        }
+    }
+
+    fn record_blur(self: &Rc<Self>, buf: CommandBuffer, fb: &VulkanImage, b: &Blur) {
+        let Some([x1, y1, x2, y2]) = b.bounds(fb.width as _, fb.height as _) else {
+            return;
+        };
+        let (width, height) = (x2 - x1, y2 - y1);
+        let passes = b.effective_passes(width, height);
+        if passes == 0 {
+            return;
+        }
+        let key = BlurCacheKey::new(b, fb.format, [x1, y1, x2, y2], passes);
+        if let Some(cache) = &b.cache {
+            if let Some(image) = cache.get::<VulkanImage>(&key) {
+                if rc_eq(&image.renderer, self) {
+                    self.memory.borrow_mut().textures.push(UsedTexture {
+                        tex: image.clone(),
+                        resv: None,
+                        acquire_sync: AcquireSync::None,
+                        release_sync: ReleaseSync::None,
+                    });
+                    self.record_blur_result(buf, fb, b, [x1, y1, x2, y2], &image);
+                    return;
+                }
+            }
+        }
+        let cached = b.cache.as_ref().and_then(|cache| {
+            let (width, height) = Blur::level_size(width, height, 1);
+            if let Some(image) = cache.last::<VulkanImage>() {
+                if rc_eq(&image.renderer, self)
+                    && image.width == width as u32
+                    && image.height == height as u32
+                {
+                    return Some(image);
+                }
+            }
+            match self.create_blur_image(ARGB8888, width as _, height as _, true) {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    log::error!("Could not create a blur cache image: {}", ErrorFmt(e));
+                    None
+                }
+            }
+        });
+        let levels = match self.blur_images(fb.format, width, height, passes, cached.as_ref()) {
+            Ok(l) => l,
+            Err(e) => {
+                log::error!("Could not create blur images: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        self.memory
+            .borrow_mut()
+            .textures
+            .extend(levels.iter().map(|tex| UsedTexture {
+                tex: tex.clone(),
+                resv: None,
+                acquire_sync: AcquireSync::None,
+                release_sync: ReleaseSync::None,
+            }));
+        let dev = &self.device.device;
+        self.end_rendering(buf);
+        let barriers = [
+            image_barrier()
+                .image(fb.image)
+                .old_layout(ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .new_layout(ImageLayout::TRANSFER_SRC_OPTIMAL)
+                .src_access_mask(AccessFlags2::COLOR_ATTACHMENT_WRITE)
+                .src_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+                .dst_access_mask(AccessFlags2::TRANSFER_READ)
+                .dst_stage_mask(PipelineStageFlags2::TRANSFER)
+                .build(),
+            image_barrier()
+                .image(levels[0].image)
+                .old_layout(ImageLayout::UNDEFINED)
+                .new_layout(ImageLayout::TRANSFER_DST_OPTIMAL)
+                .src_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+                .dst_access_mask(AccessFlags2::TRANSFER_WRITE)
+                .dst_stage_mask(PipelineStageFlags2::TRANSFER)
+                .build(),
+        ];
+        let subresource = ImageSubresourceLayers {
+            aspect_mask: ImageAspectFlags::COLOR,
+            mip_level: 0,
+            base_array_layer: 0,
+            layer_count: 1,
+        };
+        let region = ImageCopy {
+            src_subresource: subresource,
+            src_offset: Offset3D { x: x1, y: y1, z: 0 },
+            dst_subresource: subresource,
+            dst_offset: Default::default(),
+            extent: Extent3D {
+                width: width as _,
+                height: height as _,
+                depth: 1,
+            },
+        };
+        unsafe {
+            dev.cmd_pipeline_barrier2(
+                buf,
+                &DependencyInfo::builder().image_memory_barriers(&barriers),
+            );
+            dev.cmd_copy_image(
+                buf,
+                fb.image,
+                ImageLayout::TRANSFER_SRC_OPTIMAL,
+                levels[0].image,
+                ImageLayout::TRANSFER_DST_OPTIMAL,
+                slice::from_ref(&region),
+            );
+        }
+        let barriers = [
+            image_barrier()
+                .image(fb.image)
+                .old_layout(ImageLayout::TRANSFER_SRC_OPTIMAL)
+                .new_layout(ImageLayout::COLOR_ATTACHMENT_OPTIMAL)
+                .src_access_mask(AccessFlags2::TRANSFER_READ)
+                .src_stage_mask(PipelineStageFlags2::TRANSFER)
+                .dst_access_mask(
+                    AccessFlags2::COLOR_ATTACHMENT_READ | AccessFlags2::COLOR_ATTACHMENT_WRITE,
+                )
+                .dst_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+                .build(),
+            image_barrier()
+                .image(levels[0].image)
+                .old_layout(ImageLayout::TRANSFER_DST_OPTIMAL)
+                .new_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+                .src_access_mask(AccessFlags2::TRANSFER_WRITE)
+                .src_stage_mask(PipelineStageFlags2::TRANSFER)
+                .dst_access_mask(AccessFlags2::SHADER_SAMPLED_READ)
+                .dst_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+                .build(),
+        ];
+        unsafe {
+            dev.cmd_pipeline_barrier2(
+                buf,
+                &DependencyInfo::builder().image_memory_barriers(&barriers),
+            );
+        }
+        let passes = passes as usize;
+        for n in 1..=passes {
+            self.record_blur_pass(buf, &self.blur_down_pipeline, &levels[n - 1], &levels[n], b);
+        }
+        for n in (1..passes).rev() {
+            self.record_blur_pass(buf, &self.blur_up_pipeline, &levels[n + 1], &levels[n], b);
+        }
+        if let (Some(cache), Some(image)) = (&b.cache, cached) {
+            cache.set(key, image);
+        }
+        self.begin_rendering(buf, fb, None);
+        self.set_viewport(buf, fb);
+        self.record_blur_result(buf, fb, b, [x1, y1, x2, y2], &levels[1]);
+    }
+
+    /// Upsamples the first blur level into the framebuffer.
+    fn record_blur_result(
+        &self,
+        buf: CommandBuffer,
+        fb: &VulkanImage,
+        b: &Blur,
+        [x1, y1, x2, y2]: [i32; 4],
+        src: &VulkanImage,
+    ) {
+        let (width, height) = (x2 - x1, y2 - y1);
+        let pipeline = &self.blur_up_rounded_pipeline;
+        let [x1, x2] = [x1, x2].map(|x| 2.0 * x as f32 / fb.width as f32 - 1.0);
+        let [y1, y2] = [y1, y2].map(|y| 2.0 * y as f32 / fb.height as f32 - 1.0);
+        let vert = TexVertPushConstants {
+            pos: [[x2, y1], [x1, y1], [x2, y2], [x1, y2]],
+            tex_pos: BLUR_TEX_POS,
+        };
+        let frag = BlurFragRoundedPushConstants {
+            half_pixel: [
+                b.offset * 0.5 / width as f32,
+                b.offset * 0.5 / height as f32,
+            ],
+            _padding: [0.0; 2],
+            rect: [b.rect.x1, b.rect.y1, b.rect.x2, b.rect.y2],
+            radius: b.rect.radius,
+            blur: b.rect.blur,
+        };
+        self.record_blur_draw(buf, pipeline, src, &vert, uapi::as_bytes(&frag));
+    }
+
+    /// Renders `src` into all of `dst`.
+    fn record_blur_pass(
+        &self,
+        buf: CommandBuffer,
+        pipeline: &VulkanPipeline,
+        src: &VulkanImage,
+        dst: &VulkanImage,
+        b: &Blur,
+    ) {
+        let dev = &self.device.device;
+        let barrier = image_barrier()
+            .image(dst.image)
+            .old_layout(ImageLayout::UNDEFINED)
+            .new_layout(ImageLayout::GENERAL)
+            .src_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+            .dst_access_mask(AccessFlags2::COLOR_ATTACHMENT_WRITE)
+            .dst_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+            .build();
+        unsafe {
+            dev.cmd_pipeline_barrier2(
+                buf,
+                &DependencyInfo::builder().image_memory_barriers(slice::from_ref(&barrier)),
+            );
+        }
+        self.begin_rendering(buf, dst, None);
+        self.set_viewport(buf, dst);
+        let vert = TexVertPushConstants {
+            pos: [[1.0, -1.0], [-1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]],
+            tex_pos: BLUR_TEX_POS,
+        };
+        let frag = BlurFragPushConstants {
+            half_pixel: [
+                b.offset * 0.5 / dst.width as f32,
+                b.offset * 0.5 / dst.height as f32,
+            ],
+        };
+        self.record_blur_draw(buf, pipeline, src, &vert, uapi::as_bytes(&frag));
+        self.end_rendering(buf);
+        let barrier = image_barrier()
+            .image(dst.image)
+            .old_layout(ImageLayout::GENERAL)
+            .new_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL)
+            .src_access_mask(AccessFlags2::COLOR_ATTACHMENT_WRITE)
+            .src_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+            .dst_access_mask(AccessFlags2::SHADER_SAMPLED_READ)
+            .dst_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+            .build();
+        unsafe {
+            dev.cmd_pipeline_barrier2(
+                buf,
+                &DependencyInfo::builder().image_memory_barriers(slice::from_ref(&barrier)),
+            );
+        }
+    }
+
+    fn record_blur_draw(
+        &self,
+        buf: CommandBuffer,
+        pipeline: &VulkanPipeline,
+        src: &VulkanImage,
+        vert: &TexVertPushConstants,
+        frag: &[u8],
+    ) {
+        let dev = &self.device.device;
+        let image_info = DescriptorImageInfo::builder()
+            .image_view(src.texture_view)
+            .image_layout(ImageLayout::SHADER_READ_ONLY_OPTIMAL);
+        let write_descriptor_set = WriteDescriptorSet::builder()
+            .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(slice::from_ref(&image_info))
+            .build();
+        unsafe {
+            dev.cmd_bind_pipeline(buf, PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            self.device.push_descriptor.cmd_push_descriptor_set(
+                buf,
+                PipelineBindPoint::GRAPHICS,
+                pipeline.pipeline_layout,
+                0,
+                slice::from_ref(&write_descriptor_set),
+            );
+            dev.cmd_push_constants(
+                buf,
+                pipeline.pipeline_layout,
+                ShaderStageFlags::VERTEX,
+                0,
+                uapi::as_bytes(vert),
+            );
+            dev.cmd_push_constants(
+                buf,
+                pipeline.pipeline_layout,
+                ShaderStageFlags::FRAGMENT,
+                pipeline.frag_push_offset,
+                frag,
+            );
+            dev.cmd_draw(buf, 4, 1, 0, 0);
+        }
+    }
+
     fn end_rendering(&self, buf: CommandBuffer) {
         unsafe {
             self.device.device.cmd_end_rendering(buf);
@@ -983,8 +1439,9 @@ impl VulkanRenderer {
         self.secondary_barriers(buf.buffer);
         self.begin_rendering(buf.buffer, fb, clear);
         self.set_viewport(buf.buffer, fb);
-        self.record_draws(buf.buffer, opts)?;
+        self.record_draws(buf.buffer, fb, opts)?;
         self.end_rendering(buf.buffer);
+        self.evict_blur_images();
         self.final_barriers(buf.buffer, fb);
         self.end_command_buffer(buf.buffer)?;
         self.create_wait_semaphores(fb)?;
@@ -1014,6 +1471,7 @@ impl VulkanRenderer {
             f.waiter.take();
         });
         pending_frames.clear();
+        self.blur_images.borrow_mut().clear();
     }
 }
 
@@ -1053,6 +1511,8 @@ impl dyn GfxTexture {
     }
 }
 
+const BLUR_TEX_POS: [[f32; 2]; 4] = [[1.0, 0.0], [0.0, 0.0], [1.0, 1.0], [0.0, 1.0]];
+
 fn image_barrier() -> ImageMemoryBarrier2Builder<'static> {
     ImageMemoryBarrier2::builder().subresource_range(
         ImageSubresourceRange::builder()
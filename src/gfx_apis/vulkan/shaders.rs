@@ -7,12 +7,22 @@ use {
 
 pub const FILL_VERT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/fill.vert.spv"));
 pub const FILL_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/fill.frag.spv"));
+pub const FILL_FRAG_ROUNDED: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/fill.frag.rounded.spv"));
 pub const TEX_VERT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/tex.vert.spv"));
 pub const TEX_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.spv"));
 pub const TEX_FRAG_MULT_OPAQUE: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+opaque.spv"));
 pub const TEX_FRAG_MULT_ALPHA: &[u8] =
     include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.mult+alpha.spv"));
+pub const TEX_FRAG_ROUNDED_OPAQUE: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.rounded+opaque.spv"));
+pub const TEX_FRAG_ROUNDED_ALPHA: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.rounded+alpha.spv"));
+pub const BLUR_FRAG_DOWN: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/blur.frag.down.spv"));
+pub const BLUR_FRAG_UP: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/blur.frag.up.spv"));
+pub const BLUR_FRAG_UP_ROUNDED: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/blur.frag.up+rounded.spv"));
 
 pub struct VulkanShader {
     pub(super) device: Rc<VulkanDevice>,
@@ -35,6 +45,17 @@ pub struct FillFragPushConstants {
 
 unsafe impl Packed for FillFragPushConstants {}
 
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct FillFragRoundedPushConstants {
+    pub color: [f32; 4],
+    pub rect: [f32; 4],
+    pub radius: f32,
+    pub blur: f32,
+}
+
+unsafe impl Packed for FillFragRoundedPushConstants {}
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)]
 pub struct TexVertPushConstants {
@@ -48,8 +69,37 @@ pub struct TexFragPushConstants {
     pub alpha: f32,
 }
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct TexFragRoundedPushConstants {
+    pub alpha: f32,
+    pub _padding: [f32; 3],
+    pub rect: [f32; 4],
+    pub radius: f32,
+    pub blur: f32,
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct BlurFragPushConstants {
+    pub half_pixel: [f32; 2],
+}
+
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct BlurFragRoundedPushConstants {
+    pub half_pixel: [f32; 2],
+    pub _padding: [f32; 2],
+    pub rect: [f32; 4],
+    pub radius: f32,
+    pub blur: f32,
+}
+
 unsafe impl Packed for TexVertPushConstants {}
 unsafe impl Packed for TexFragPushConstants {}
+unsafe impl Packed for TexFragRoundedPushConstants {}
+unsafe impl Packed for BlurFragPushConstants {}
+unsafe impl Packed for BlurFragRoundedPushConstants {}
 
 impl VulkanDevice {
     pub(super) fn create_shader(
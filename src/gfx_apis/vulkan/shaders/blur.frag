@@ -0,0 +1,48 @@
+#version 450
+
+layout(push_constant, std430) uniform Data {
+	layout(offset = 64) vec2 half_pixel;
+#ifdef ROUNDED
+	layout(offset = 80) vec4 rect;
+	layout(offset = 96) float radius;
+	layout(offset = 100) float blur;
+#endif
+} data;
+layout(set = 0, binding = 0) uniform sampler2D tex;
+layout(location = 0) in vec2 tex_pos;
+layout(location = 0) out vec4 out_color;
+
+#ifdef ROUNDED
+float coverage() {
+	vec2 center = (data.rect.xy + data.rect.zw) * 0.5;
+	vec2 q = abs(gl_FragCoord.xy - center) - (data.rect.zw - data.rect.xy) * 0.5 + data.radius;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - data.radius;
+	return clamp(0.5 - d / max(data.blur, 1.0), 0.0, 1.0);
+}
+#endif
+
+void main() {
+	vec2 uv = tex_pos;
+	vec2 hp = data.half_pixel;
+#ifdef DOWN
+	vec4 sum = textureLod(tex, uv, 0) * 4.0;
+	sum += textureLod(tex, uv - hp, 0);
+	sum += textureLod(tex, uv + hp, 0);
+	sum += textureLod(tex, uv + vec2(hp.x, -hp.y), 0);
+	sum += textureLod(tex, uv - vec2(hp.x, -hp.y), 0);
+	out_color = sum / 8.0;
+#else // !DOWN
+	vec4 sum = textureLod(tex, uv + vec2(-hp.x * 2.0, 0.0), 0);
+	sum += textureLod(tex, uv + vec2(-hp.x, hp.y), 0) * 2.0;
+	sum += textureLod(tex, uv + vec2(0.0, hp.y * 2.0), 0);
+	sum += textureLod(tex, uv + vec2(hp.x, hp.y), 0) * 2.0;
+	sum += textureLod(tex, uv + vec2(hp.x * 2.0, 0.0), 0);
+	sum += textureLod(tex, uv + vec2(hp.x, -hp.y), 0) * 2.0;
+	sum += textureLod(tex, uv + vec2(0.0, -hp.y * 2.0), 0);
+	sum += textureLod(tex, uv + vec2(-hp.x, -hp.y), 0) * 2.0;
+	out_color = sum / 12.0;
+#endif // DOWN
+#ifdef ROUNDED
+	out_color *= coverage();
+#endif
+}
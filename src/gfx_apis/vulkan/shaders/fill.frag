@@ -2,10 +2,28 @@
 
 layout(push_constant, std430) uniform Data {
 	layout(offset = 32) vec4 color;
+#ifdef ROUNDED
+	layout(offset = 48) vec4 rect;
+	layout(offset = 64) float radius;
+	layout(offset = 68) float blur;
+#endif
 } data;
 
 layout(location = 0) out vec4 out_color;
 
+#ifdef ROUNDED
+float coverage() {
+	vec2 center = (data.rect.xy + data.rect.zw) * 0.5;
+	vec2 q = abs(gl_FragCoord.xy - center) - (data.rect.zw - data.rect.xy) * 0.5 + data.radius;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - data.radius;
+	return clamp(0.5 - d / max(data.blur, 1.0), 0.0, 1.0);
+}
+#endif
+
 void main() {
+#ifdef ROUNDED
+	out_color = data.color * coverage();
+#else
 	out_color = data.color;
+#endif
 }
@@ -3,19 +3,37 @@
 #ifdef ALPHA_MULTIPLIER
 layout(push_constant, std430) uniform Data {
 	layout(offset = 64) float mul;
+#ifdef ROUNDED
+	layout(offset = 80) vec4 rect;
+	layout(offset = 96) float radius;
+	layout(offset = 100) float blur;
+#endif
 } data;
 #endif
 layout(set = 0, binding = 0) uniform sampler2D tex;
 layout(location = 0) in vec2 tex_pos;
 layout(location = 0) out vec4 out_color;
 
+#ifdef ROUNDED
+float coverage() {
+	vec2 center = (data.rect.xy + data.rect.zw) * 0.5;
+	vec2 q = abs(gl_FragCoord.xy - center) - (data.rect.zw - data.rect.xy) * 0.5 + data.radius;
+	float d = length(max(q, 0.0)) + min(max(q.x, q.y), 0.0) - data.radius;
+	return clamp(0.5 - d / max(data.blur, 1.0), 0.0, 1.0);
+}
+#endif
+
 void main() {
 #ifdef ALPHA_MULTIPLIER
 #ifdef ALPHA
 	out_color = textureLod(tex, tex_pos, 0) * data.mul;
-#endif // !ALPHA
+#else // !ALPHA
 	out_color = vec4(textureLod(tex, tex_pos, 0).rgb * data.mul, data.mul);
+#endif // ALPHA
 #else // !ALPHA_MULTIPLIER
 	out_color = textureLod(tex, tex_pos, 0);
+#endif // ALPHA_MULTIPLIER
+#ifdef ROUNDED
+	out_color *= coverage();
 #endif
 }
@@ -5,7 +5,9 @@ pub mod ext_idle_notifier_v1;
 pub mod ext_session_lock_manager_v1;
 pub mod ext_session_lock_v1;
 pub mod ipc;
+pub mod jay_appearance;
 pub mod jay_compositor;
+pub mod jay_global_shortcuts;
 pub mod jay_idle;
 pub mod jay_input;
 pub mod jay_log_file;
@@ -19,6 +21,7 @@ pub mod jay_seat_events;
 pub mod jay_select_toplevel;
 pub mod jay_select_workspace;
 pub mod jay_toplevel;
+pub mod jay_virtual_input;
 pub mod jay_workspace;
 pub mod jay_workspace_watcher;
 pub mod org_kde_kwin_server_decoration;
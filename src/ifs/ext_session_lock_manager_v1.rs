@@ -54,7 +54,8 @@ impl ExtSessionLockManagerV1RequestHandler for ExtSessionLockManagerV1 {
     }
 
     fn lock(&self, req: Lock, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let did_lock = self.client.state.lock.locked.get() == false;
+        let state = &self.client.state;
+        let did_lock = state.lock.locked.get() == false || state.lock.builtin.is_some();
         let new = Rc::new(ExtSessionLockV1 {
             id: req.id,
             client: self.client.clone(),
@@ -67,12 +68,16 @@ impl ExtSessionLockManagerV1RequestHandler for ExtSessionLockManagerV1 {
         self.client.add_client_obj(&new)?;
         if did_lock {
             log::info!("Client {} locks the screen", self.client.id);
-            let state = &self.client.state;
+            if let Some(builtin) = state.lock.builtin.take() {
+                builtin.clear();
+            }
             for seat in state.globals.seats.lock().values() {
                 seat.prepare_for_lock();
             }
             state.lock.locked.set(true);
             state.lock.lock.set(Some(new.clone()));
+            state.reset_lock_presented();
+            state.lock.changed.trigger();
             state.tree_changed();
             state.damage();
             new.send_locked();
@@ -34,15 +34,27 @@ impl ExtSessionLockV1 {
         self.send_finished();
         self.finished.set(true);
     }
+
+    fn abandon(&self) {
+        if self.finished.get() {
+            return;
+        }
+        let state = &self.client.state;
+        if state.lock.lock.take().is_some() && state.lock.locked.get() {
+            log::warn!(
+                "Client {} abandoned the lock. Falling back to the built-in lock screen.",
+                self.client.id
+            );
+            state.lock_builtin();
+        }
+    }
 }
 
 impl ExtSessionLockV1RequestHandler for ExtSessionLockV1 {
     type Error = ExtSessionLockV1Error;
 
     fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        if !self.finished.get() {
-            self.client.state.lock.lock.take();
-        }
+        self.abandon();
         self.client.remove_obj(self)?;
         Ok(())
     }
@@ -90,6 +102,7 @@ impl ExtSessionLockV1RequestHandler for ExtSessionLockV1 {
             let state = &self.client.state;
             state.lock.locked.set(false);
             state.lock.lock.take();
+            state.lock.changed.trigger();
             for output in state.root.outputs.lock().values() {
                 if let Some(surface) = output.set_lock_surface(None) {
                     surface.destroy_node();
@@ -110,9 +123,7 @@ object_base! {
 
 impl Object for ExtSessionLockV1 {
     fn break_loops(&self) {
-        if !self.finished.get() {
-            self.client.state.lock.lock.take();
-        }
+        self.abandon();
     }
 }
 
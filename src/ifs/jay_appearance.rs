@@ -0,0 +1,76 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        fixed::Fixed,
+        leaks::Tracker,
+        object::{Object, Version},
+        theme::Appearance,
+        wire::{jay_appearance::*, JayAppearanceId},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct JayAppearance {
+    pub id: JayAppearanceId,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl JayAppearance {
+    pub fn send_appearance(&self, appearance: &Appearance) {
+        self.client.event(ColorScheme {
+            self_id: self.id,
+            color_scheme: appearance.color_scheme,
+        });
+        let [r, g, b] = appearance.accent_color.map(|c| Fixed::from_f64(c as f64));
+        self.client.event(AccentColor {
+            self_id: self.id,
+            r,
+            g,
+            b,
+        });
+        self.client.event(Contrast {
+            self_id: self.id,
+            contrast: appearance.contrast,
+        });
+        self.client.event(Done { self_id: self.id });
+    }
+
+    fn remove_from_state(&self) {
+        self.client
+            .state
+            .appearance_watchers
+            .remove(&(self.client.id, self.id));
+    }
+}
+
+impl JayAppearanceRequestHandler for JayAppearance {
+    type Error = JayAppearanceError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.remove_from_state();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayAppearance;
+    version = Version(1);
+}
+
+impl Object for JayAppearance {
+    fn break_loops(&self) {
+        self.remove_from_state();
+    }
+}
+
+simple_add_obj!(JayAppearance);
+
+#[derive(Debug, Error)]
+pub enum JayAppearanceError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(JayAppearanceError, ClientError);
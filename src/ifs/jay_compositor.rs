@@ -4,6 +4,8 @@ use {
         client::{Client, ClientCaps, ClientError, CAP_JAY_COMPOSITOR},
         globals::{Global, GlobalName},
         ifs::{
+            jay_appearance::JayAppearance,
+            jay_global_shortcuts::JayGlobalShortcuts,
             jay_idle::JayIdle,
             jay_input::JayInput,
             jay_log_file::JayLogFile,
@@ -16,6 +18,7 @@ use {
             jay_seat_events::JaySeatEvents,
             jay_select_toplevel::{JaySelectToplevel, JayToplevelSelector},
             jay_select_workspace::{JaySelectWorkspace, JayWorkspaceSelector},
+            jay_virtual_input::JayVirtualInput,
             jay_workspace_watcher::JayWorkspaceWatcher,
         },
         leaks::Tracker,
@@ -87,13 +90,23 @@ impl Cap {
     pub const NONE: u16 = 0;
     pub const WINDOW_CAPTURE: u16 = 1;
     pub const SELECT_WORKSPACE: u16 = 2;
+    pub const VIRTUAL_INPUT: u16 = 3;
+    pub const GLOBAL_SHORTCUTS: u16 = 4;
+    pub const APPEARANCE: u16 = 5;
 }
 
 impl JayCompositor {
     fn send_capabilities(&self) {
         self.client.event(Capabilities {
             self_id: self.id,
-            cap: &[Cap::NONE, Cap::WINDOW_CAPTURE, Cap::SELECT_WORKSPACE],
+            cap: &[
+                Cap::NONE,
+                Cap::WINDOW_CAPTURE,
+                Cap::SELECT_WORKSPACE,
+                Cap::VIRTUAL_INPUT,
+                Cap::GLOBAL_SHORTCUTS,
+                Cap::APPEARANCE,
+            ],
         });
     }
 
@@ -215,23 +228,16 @@ impl JayCompositorRequestHandler for JayCompositor {
     }
 
     fn unlock(&self, _req: Unlock, _slf: &Rc<Self>) -> Result<(), Self::Error> {
-        let state = &self.client.state;
-        if state.lock.locked.replace(false) {
-            if let Some(lock) = state.lock.lock.take() {
-                lock.finish();
-            }
-            for output in state.root.outputs.lock().values() {
-                if let Some(surface) = output.set_lock_surface(None) {
-                    surface.destroy_node();
-                }
-            }
-            state.tree_changed();
-            state.damage();
-        }
+        self.client.state.unlock();
         self.client.symmetric_delete.set(true);
         Ok(())
     }
 
+    fn lock(&self, _req: Lock, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.client.state.lock_builtin();
+        Ok(())
+    }
+
     fn get_seats(&self, _req: GetSeats, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         for seat in self.client.state.globals.seats.lock().values() {
             self.client.event(Seat {
@@ -382,6 +388,52 @@ impl JayCompositorRequestHandler for JayCompositor {
         seat.global.select_workspace(selector);
         Ok(())
     }
+
+    fn create_virtual_input(
+        &self,
+        req: CreateVirtualInput,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let seat = self.client.lookup(req.seat)?;
+        let obj = Rc::new(JayVirtualInput::new(req.id, &self.client, &seat.global));
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn create_global_shortcuts(
+        &self,
+        req: CreateGlobalShortcuts,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let seat = self.client.lookup(req.seat)?;
+        let obj = Rc::new(JayGlobalShortcuts {
+            id: req.id,
+            client: self.client.clone(),
+            seat: seat.global.clone(),
+            tracker: Default::default(),
+            bindings: Default::default(),
+        });
+        track!(self.client, obj);
+        self.client.add_client_obj(&obj)?;
+        Ok(())
+    }
+
+    fn watch_appearance(&self, req: WatchAppearance, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let watcher = Rc::new(JayAppearance {
+            id: req.id,
+            client: self.client.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, watcher);
+        self.client.add_client_obj(&watcher)?;
+        self.client
+            .state
+            .appearance_watchers
+            .set((self.client.id, req.id), watcher.clone());
+        watcher.send_appearance(&self.client.state.theme.appearance());
+        Ok(())
+    }
 }
 
 object_base! {
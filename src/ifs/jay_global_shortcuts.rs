@@ -0,0 +1,103 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::wl_seat::WlSeatGlobal,
+        leaks::Tracker,
+        object::{Object, Version},
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+    },
+    ahash::AHashMap,
+    jay_config::keyboard::{
+        mods::{Modifiers, CAPS, NUM, RELEASE},
+        syms::KeySym,
+    },
+    std::{cell::RefCell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct JayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    pub bindings: RefCell<AHashMap<u32, (Modifiers, KeySym)>>,
+}
+
+impl JayGlobalShortcuts {
+    pub fn send_activated(&self, id: u32, time_usec: u64) {
+        self.client.event(Activated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    pub fn send_deactivated(&self, id: u32, time_usec: u64) {
+        self.client.event(Deactivated {
+            self_id: self.id,
+            id,
+            time_usec,
+        });
+    }
+
+    fn detach(&self) {
+        for (id, (mods, sym)) in self.bindings.borrow_mut().drain() {
+            self.seat.remove_global_shortcut(self, id, mods, sym);
+        }
+        self.seat.remove_active_global_shortcuts(self);
+    }
+}
+
+impl JayGlobalShortcutsRequestHandler for JayGlobalShortcuts {
+    type Error = JayGlobalShortcutsError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn bind(&self, req: Bind, slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let mods = Modifiers(req.mods);
+        if mods.0 & (CAPS.0 | NUM.0 | RELEASE.0) != 0 {
+            return Err(JayGlobalShortcutsError::InvalidModifiers(req.mods));
+        }
+        let sym = KeySym(req.keysym);
+        let prev = self.bindings.borrow_mut().insert(req.id, (mods, sym));
+        if let Some((mods, sym)) = prev {
+            self.seat.remove_global_shortcut(self, req.id, mods, sym);
+        }
+        self.seat.add_global_shortcut(slf, req.id, mods, sym);
+        Ok(())
+    }
+
+    fn unbind(&self, req: Unbind, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let prev = self.bindings.borrow_mut().remove(&req.id);
+        if let Some((mods, sym)) = prev {
+            self.seat.remove_global_shortcut(self, req.id, mods, sym);
+        }
+        Ok(())
+    }
+}
+
+object_base! {
+    self = JayGlobalShortcuts;
+    version = Version(1);
+}
+
+impl Object for JayGlobalShortcuts {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(JayGlobalShortcuts);
+
+#[derive(Debug, Error)]
+pub enum JayGlobalShortcutsError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Shortcuts cannot use the modifiers {0:x}")]
+    InvalidModifiers(u32),
+}
+efrom!(JayGlobalShortcutsError, ClientError);
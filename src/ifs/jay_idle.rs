@@ -25,8 +25,21 @@ impl JayIdle {
         });
     }
 
-    fn send_inhibitor(&self, surface: &ZwpIdleInhibitorV1) {
-        let surface = &surface.surface;
+    fn send_stages(&self) {
+        for stage in self.client.state.idle.stages.borrow().iter() {
+            self.client.event(Stage {
+                self_id: self.id,
+                name: &stage.name,
+                timeout: stage.timeout.as_secs(),
+                dim_outputs: stage.dim_outputs as _,
+                power_off_outputs: stage.power_off_outputs as _,
+                suspend: stage.suspend as _,
+            });
+        }
+    }
+
+    fn send_inhibitor(&self, inhibitor: &ZwpIdleInhibitorV1) {
+        let surface = &inhibitor.surface;
         self.client.event(Inhibitor {
             self_id: self.id,
             surface: surface.id,
@@ -34,6 +47,30 @@ impl JayIdle {
             pid: surface.client.pid_info.pid as _,
             comm: &surface.client.pid_info.comm,
         });
+        let mut app_id = String::new();
+        if let Some(tl) = surface.get_root().get_toplevel() {
+            app_id = tl.tl_data().app_id.borrow().clone();
+        }
+        let visible = self
+            .client
+            .state
+            .idle
+            .inhibitors
+            .contains(&inhibitor.inhibit_id);
+        self.client.event(InhibitorDetails {
+            self_id: self.id,
+            app_id: &app_id,
+            visible: visible as _,
+        });
+    }
+
+    fn send_inhibition(&self) {
+        let idle = &self.client.state.idle;
+        self.client.event(Inhibition {
+            self_id: self.id,
+            ignored: idle.inhibitors_ignored.get() as _,
+            fullscreen: idle.fullscreen_inhibits.get() as _,
+        });
     }
 }
 
@@ -42,8 +79,10 @@ impl JayIdleRequestHandler for JayIdle {
 
     fn get_status(&self, _req: GetStatus, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.send_interval();
+        self.send_stages();
+        self.send_inhibition();
         {
-            let inhibitors = self.client.state.idle.inhibitors.lock();
+            let inhibitors = self.client.state.idle.registered_inhibitors.lock();
             for inhibitor in inhibitors.values() {
                 self.send_inhibitor(inhibitor);
             }
@@ -56,6 +95,22 @@ impl JayIdleRequestHandler for JayIdle {
         self.client.state.idle.set_timeout(interval);
         Ok(())
     }
+
+    fn set_inhibitors_ignored(
+        &self,
+        req: SetInhibitorsIgnored,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        let duration = match req.duration {
+            0 => None,
+            n => Some(Duration::from_secs(n)),
+        };
+        self.client
+            .state
+            .idle
+            .set_inhibitors_ignored(req.ignored != 0, duration);
+        Ok(())
+    }
 }
 
 object_base! {
@@ -1,15 +1,33 @@
 use {
     crate::{
-        backend::{self, InputDeviceAccelProfile, InputDeviceId},
+        backend::{
+            self, InputDeviceAccelProfile, InputDeviceAccelType, InputDeviceClickMethod,
+            InputDeviceId, InputDeviceScrollMethod, InputDeviceSendEventsMode,
+            InputDeviceTapButtonMap,
+        },
         client::{Client, ClientError},
         clientmem::{ClientMem, ClientMemError},
-        ifs::wl_seat::WlSeatGlobal,
+        ifs::wl_seat::{
+            tablet::{PressureCurve, TabletArea},
+            WlSeatGlobal,
+        },
         leaks::Tracker,
         libinput::consts::{
-            AccelProfile, LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE,
-            LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT,
+            AccelProfile, ConfigAccelType, ConfigClickMethod, ConfigScrollMethod,
+            ConfigSendEventsMode, ConfigTapButtonMap, LIBINPUT_ACCEL_TYPE_FALLBACK,
+            LIBINPUT_ACCEL_TYPE_MOTION, LIBINPUT_ACCEL_TYPE_SCROLL,
+            LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE, LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM,
+            LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT, LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS,
+            LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER, LIBINPUT_CONFIG_CLICK_METHOD_NONE,
+            LIBINPUT_CONFIG_SCROLL_2FG, LIBINPUT_CONFIG_SCROLL_EDGE,
+            LIBINPUT_CONFIG_SCROLL_NO_SCROLL, LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED,
+            LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE,
+            LIBINPUT_CONFIG_SEND_EVENTS_ENABLED, LIBINPUT_CONFIG_TAP_MAP_LMR,
+            LIBINPUT_CONFIG_TAP_MAP_LRM,
         },
         object::{Object, Version},
+        rect::Rect,
         state::{DeviceHandlerData, InputDeviceData},
         utils::errorfmt::ErrorFmt,
         wire::{jay_input::*, JayInputId},
@@ -112,6 +130,7 @@ impl JayInput {
                 Some(p) => match p {
                     InputDeviceAccelProfile::Flat => LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT.0,
                     InputDeviceAccelProfile::Adaptive => LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE.0,
+                    InputDeviceAccelProfile::Custom => LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM.0,
                 },
             },
             accel_speed: dev.accel_speed().unwrap_or_default(),
@@ -129,6 +148,72 @@ impl JayInput {
                 .map(uapi::as_bytes)
                 .unwrap_or_default(),
         });
+        let click_method = dev.click_method();
+        let middle_button_emulation = dev.middle_button_emulation_enabled();
+        let scroll_method = dev.scroll_method();
+        let scroll_button = dev.scroll_button();
+        let disable_while_typing = dev.disable_while_typing_enabled();
+        let send_events_mode = dev.send_events_mode();
+        let rotation = dev.rotation();
+        let calibration_matrix = dev.calibration_matrix();
+        self.client.event(InputDeviceConfig {
+            self_id: self.id,
+            id: data.id.raw(),
+            click_method_available: click_method.is_some() as _,
+            click_method: match click_method {
+                None => 0,
+                Some(m) => match m {
+                    InputDeviceClickMethod::None => LIBINPUT_CONFIG_CLICK_METHOD_NONE.0,
+                    InputDeviceClickMethod::ButtonAreas => {
+                        LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS.0
+                    }
+                    InputDeviceClickMethod::Clickfinger => {
+                        LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER.0
+                    }
+                },
+            },
+            middle_button_emulation_available: middle_button_emulation.is_some() as _,
+            middle_button_emulation_enabled: middle_button_emulation.unwrap_or_default() as _,
+            scroll_method_available: scroll_method.is_some() as _,
+            scroll_method: match scroll_method {
+                None => 0,
+                Some(m) => match m {
+                    InputDeviceScrollMethod::NoScroll => LIBINPUT_CONFIG_SCROLL_NO_SCROLL.0,
+                    InputDeviceScrollMethod::TwoFinger => LIBINPUT_CONFIG_SCROLL_2FG.0,
+                    InputDeviceScrollMethod::Edge => LIBINPUT_CONFIG_SCROLL_EDGE.0,
+                    InputDeviceScrollMethod::OnButtonDown => {
+                        LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN.0
+                    }
+                },
+            },
+            scroll_button_available: scroll_button.is_some() as _,
+            scroll_button: scroll_button.unwrap_or_default(),
+            disable_while_typing_available: disable_while_typing.is_some() as _,
+            disable_while_typing_enabled: disable_while_typing.unwrap_or_default() as _,
+            tap_button_map: match dev.tap_button_map() {
+                None | Some(InputDeviceTapButtonMap::LeftRightMiddle) => {
+                    LIBINPUT_CONFIG_TAP_MAP_LRM.0
+                }
+                Some(InputDeviceTapButtonMap::LeftMiddleRight) => LIBINPUT_CONFIG_TAP_MAP_LMR.0,
+            },
+            send_events_available: send_events_mode.is_some() as _,
+            send_events_mode: match send_events_mode {
+                None => 0,
+                Some(m) => match m {
+                    InputDeviceSendEventsMode::Enabled => LIBINPUT_CONFIG_SEND_EVENTS_ENABLED.0,
+                    InputDeviceSendEventsMode::Disabled => LIBINPUT_CONFIG_SEND_EVENTS_DISABLED.0,
+                    InputDeviceSendEventsMode::DisabledOnExternalMouse => {
+                        LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE.0
+                    }
+                },
+            },
+            rotation_available: rotation.is_some() as _,
+            rotation: rotation.unwrap_or_default(),
+            calibration_matrix: calibration_matrix
+                .as_ref()
+                .map(uapi::as_bytes)
+                .unwrap_or_default(),
+        });
         if let Some(output) = data.data.output.get() {
             if let Some(output) = output.get() {
                 self.client.event(InputDeviceOutput {
@@ -138,6 +223,34 @@ impl JayInput {
                 });
             }
         }
+        if let Some(region) = data.data.region.get() {
+            self.client.event(InputDeviceRegion {
+                self_id: self.id,
+                id: data.id.raw(),
+                x: region.x1(),
+                y: region.y1(),
+                width: region.width(),
+                height: region.height(),
+            });
+        }
+        if data.data.tablet_init.is_some() {
+            let area = data.data.tablet_area.get();
+            let curve = data
+                .data
+                .tablet_pressure_curve
+                .get()
+                .map(|c| [c.x1, c.y1, c.x2, c.y2]);
+            self.client.event(InputDeviceTablet {
+                self_id: self.id,
+                id: data.id.raw(),
+                area_x: area.x,
+                area_y: area.y,
+                area_width: area.width,
+                area_height: area.height,
+                keep_aspect_ratio: data.data.tablet_keep_aspect_ratio.get() as _,
+                pressure_curve: curve.as_ref().map(|c| &c[..]).unwrap_or_default(),
+            });
+        }
     }
 
     fn device(&self, id: u32) -> Result<Rc<DeviceHandlerData>, JayInputError> {
@@ -231,6 +344,7 @@ impl JayInputRequestHandler for JayInput {
             let profile = match AccelProfile(req.profile) {
                 LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT => InputDeviceAccelProfile::Flat,
                 LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE => InputDeviceAccelProfile::Adaptive,
+                LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM => InputDeviceAccelProfile::Custom,
                 _ => return Err(JayInputError::UnknownAccelerationProfile(req.profile)),
             };
             dev.device.set_accel_profile(profile);
@@ -238,6 +352,25 @@ impl JayInputRequestHandler for JayInput {
         })
     }
 
+    fn set_custom_accel_function(
+        &self,
+        req: SetCustomAccelFunction<'_>,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let ty = match ConfigAccelType(req.ty) {
+                LIBINPUT_ACCEL_TYPE_FALLBACK => InputDeviceAccelType::Fallback,
+                LIBINPUT_ACCEL_TYPE_MOTION => InputDeviceAccelType::Motion,
+                LIBINPUT_ACCEL_TYPE_SCROLL => InputDeviceAccelType::Scroll,
+                _ => return Err(JayInputError::UnknownAccelerationType(req.ty)),
+            };
+            dev.device
+                .set_custom_accel_function(ty, req.step, req.points);
+            Ok(())
+        })
+    }
+
     fn set_accel_speed(&self, req: SetAccelSpeed, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.or_error(|| {
             let dev = self.device(req.id)?;
@@ -424,6 +557,184 @@ impl JayInputRequestHandler for JayInput {
             Ok(())
         })
     }
+
+    fn set_click_method(&self, req: SetClickMethod, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let method = match ConfigClickMethod(req.method) {
+                LIBINPUT_CONFIG_CLICK_METHOD_NONE => InputDeviceClickMethod::None,
+                LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS => InputDeviceClickMethod::ButtonAreas,
+                LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER => InputDeviceClickMethod::Clickfinger,
+                _ => return Err(JayInputError::UnknownClickMethod(req.method)),
+            };
+            dev.device.set_click_method(method);
+            Ok(())
+        })
+    }
+
+    fn set_middle_button_emulation(
+        &self,
+        req: SetMiddleButtonEmulation,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device
+                .set_middle_button_emulation_enabled(req.enabled != 0);
+            Ok(())
+        })
+    }
+
+    fn set_scroll_method(&self, req: SetScrollMethod, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let method = match ConfigScrollMethod(req.method) {
+                LIBINPUT_CONFIG_SCROLL_NO_SCROLL => InputDeviceScrollMethod::NoScroll,
+                LIBINPUT_CONFIG_SCROLL_2FG => InputDeviceScrollMethod::TwoFinger,
+                LIBINPUT_CONFIG_SCROLL_EDGE => InputDeviceScrollMethod::Edge,
+                LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN => InputDeviceScrollMethod::OnButtonDown,
+                _ => return Err(JayInputError::UnknownScrollMethod(req.method)),
+            };
+            dev.device.set_scroll_method(method);
+            Ok(())
+        })
+    }
+
+    fn set_scroll_button(&self, req: SetScrollButton, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device.set_scroll_button(req.button);
+            Ok(())
+        })
+    }
+
+    fn set_disable_while_typing(
+        &self,
+        req: SetDisableWhileTyping,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device
+                .set_disable_while_typing_enabled(req.enabled != 0);
+            Ok(())
+        })
+    }
+
+    fn set_tap_button_map(&self, req: SetTapButtonMap, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let map = match ConfigTapButtonMap(req.map) {
+                LIBINPUT_CONFIG_TAP_MAP_LRM => InputDeviceTapButtonMap::LeftRightMiddle,
+                LIBINPUT_CONFIG_TAP_MAP_LMR => InputDeviceTapButtonMap::LeftMiddleRight,
+                _ => return Err(JayInputError::UnknownTapButtonMap(req.map)),
+            };
+            dev.device.set_tap_button_map(map);
+            Ok(())
+        })
+    }
+
+    fn set_send_events_mode(
+        &self,
+        req: SetSendEventsMode,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let mode = match ConfigSendEventsMode(req.mode) {
+                LIBINPUT_CONFIG_SEND_EVENTS_ENABLED => InputDeviceSendEventsMode::Enabled,
+                LIBINPUT_CONFIG_SEND_EVENTS_DISABLED => InputDeviceSendEventsMode::Disabled,
+                LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE => {
+                    InputDeviceSendEventsMode::DisabledOnExternalMouse
+                }
+                _ => return Err(JayInputError::UnknownSendEventsMode(req.mode)),
+            };
+            dev.device.set_send_events_mode(mode);
+            Ok(())
+        })
+    }
+
+    fn set_rotation(&self, req: SetRotation, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device.set_rotation(req.degrees);
+            Ok(())
+        })
+    }
+
+    fn set_calibration_matrix(
+        &self,
+        req: SetCalibrationMatrix,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.device
+                .set_calibration_matrix([[req.m11, req.m12, req.m13], [req.m21, req.m22, req.m23]]);
+            Ok(())
+        })
+    }
+
+    fn map_to_region(&self, req: MapToRegion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let region = Rect::new_sized(req.x, req.y, req.width, req.height)
+                .filter(|r| !r.is_empty())
+                .ok_or(JayInputError::InvalidRegion)?;
+            dev.set_region(region);
+            Ok(())
+        })
+    }
+
+    fn set_tablet_area(&self, req: SetTabletArea, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let area = TabletArea {
+                x: req.x,
+                y: req.y,
+                width: req.width,
+                height: req.height,
+            };
+            if !area.is_valid() {
+                return Err(JayInputError::InvalidTabletArea);
+            }
+            dev.tablet_area.set(area);
+            Ok(())
+        })
+    }
+
+    fn set_tablet_keep_aspect_ratio(
+        &self,
+        req: SetTabletKeepAspectRatio,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            dev.tablet_keep_aspect_ratio.set(req.keep != 0);
+            Ok(())
+        })
+    }
+
+    fn set_tablet_pressure_curve(
+        &self,
+        req: SetTabletPressureCurve,
+        _slf: &Rc<Self>,
+    ) -> Result<(), Self::Error> {
+        self.or_error(|| {
+            let dev = self.device(req.id)?;
+            let curve = PressureCurve {
+                x1: req.x1,
+                y1: req.y1,
+                x2: req.x2,
+                y2: req.y2,
+            };
+            if !curve.is_valid() {
+                return Err(JayInputError::InvalidPressureCurve);
+            }
+            dev.tablet_pressure_curve.set(Some(curve));
+            Ok(())
+        })
+    }
 }
 
 object_base! {
@@ -445,6 +756,16 @@ pub enum JayInputError {
     DeviceDoesNotExist(u32),
     #[error("There is no acceleration profile with id {0}")]
     UnknownAccelerationProfile(i32),
+    #[error("There is no acceleration type with id {0}")]
+    UnknownAccelerationType(i32),
+    #[error("There is no click method with id {0}")]
+    UnknownClickMethod(i32),
+    #[error("There is no scroll method with id {0}")]
+    UnknownScrollMethod(i32),
+    #[error("There is no tap button map with id {0}")]
+    UnknownTapButtonMap(i32),
+    #[error("There is no send-events mode with id {0}")]
+    UnknownSendEventsMode(i32),
     #[error("Repeat rate must not be negative")]
     NegativeRepeatRate,
     #[error("Repeat delay must not be negative")]
@@ -455,5 +776,11 @@ pub enum JayInputError {
     XkbCommonError(#[from] XkbCommonError),
     #[error("Output is not connected")]
     OutputNotConnected,
+    #[error("The region must have a positive size")]
+    InvalidRegion,
+    #[error("The tablet area must be a non-empty part of the range [0, 1]")]
+    InvalidTabletArea,
+    #[error("The pressure curve control points must be in the range [0, 1]")]
+    InvalidPressureCurve,
 }
 efrom!(JayInputError, ClientError);
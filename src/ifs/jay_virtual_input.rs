@@ -0,0 +1,489 @@
+use {
+    crate::{
+        backend::{
+            AxisSource, CalibrationMatrix, ConnectorId, InputDevice, InputDeviceAccelProfile,
+            InputDeviceAccelType, InputDeviceCapability, InputDeviceClickMethod, InputDeviceId,
+            InputDeviceScrollMethod, InputDeviceSendEventsMode, InputDeviceTapButtonMap,
+            InputEvent, KeyState, ScrollAxis, TransformMatrix, AXIS_120,
+        },
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::wl_seat::{
+            wl_pointer::{HORIZONTAL_SCROLL, PRESSED, VERTICAL_SCROLL},
+            WlSeatGlobal, PX_PER_SCROLL,
+        },
+        leaks::Tracker,
+        object::{Object, Version},
+        state::DeviceHandlerData,
+        time::now_usec,
+        wire::{jay_virtual_input::*, JayVirtualInputId, WlOutputId},
+    },
+    ahash::{AHashMap, AHashSet},
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    thiserror::Error,
+};
+
+pub struct JayVirtualInput {
+    pub id: JayVirtualInputId,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub tracker: Tracker<Self>,
+    pub data: Rc<DeviceHandlerData>,
+    pub pressed_keys: RefCell<AHashSet<u32>>,
+    pub pressed_buttons: RefCell<AHashSet<u32>>,
+    /// The keys and whether shift was pressed for the pressed keysyms.
+    pub pressed_keysyms: RefCell<AHashMap<u32, (u32, bool)>>,
+    pub touch_points: RefCell<AHashSet<i32>>,
+}
+
+const KEY_LEFTSHIFT: u32 = 42;
+
+impl JayVirtualInput {
+    pub fn new(id: JayVirtualInputId, client: &Rc<Client>, seat: &Rc<WlSeatGlobal>) -> Self {
+        let state = &client.state;
+        let device = Rc::new(VirtualInputDevice {
+            id: state.input_device_ids.next(),
+            name: Rc::new(format!("virtual input of client {}", client.id)),
+        });
+        let data = Rc::new(DeviceHandlerData {
+            seat: Default::default(),
+            px_per_scroll_wheel: Cell::new(PX_PER_SCROLL),
+            device,
+            syspath: None,
+            devnode: None,
+            keymap: Default::default(),
+            xkb_state: Default::default(),
+            output: Default::default(),
+            region: Default::default(),
+            tablet_init: None,
+            tablet_pad_init: None,
+            tablet_area: Default::default(),
+            tablet_keep_aspect_ratio: Cell::new(false),
+            tablet_pressure_curve: Default::default(),
+            tablet_pad_bindings: Default::default(),
+            tablet_pad_forwarded_buttons: Default::default(),
+        });
+        data.set_seat(Some(seat.clone()));
+        Self {
+            id,
+            client: client.clone(),
+            seat: seat.clone(),
+            tracker: Default::default(),
+            data,
+            pressed_keys: Default::default(),
+            pressed_buttons: Default::default(),
+            pressed_keysyms: Default::default(),
+            touch_points: Default::default(),
+        }
+    }
+
+    fn event(&self, event: InputEvent) {
+        self.seat.event(&self.data, event);
+        self.seat.mark_last_active();
+        self.client.state.input_occurred();
+    }
+
+    fn release_all(&self) {
+        let time_usec = now_usec();
+        let buttons: Vec<_> = self.pressed_buttons.borrow_mut().drain().collect();
+        for button in buttons {
+            self.event(InputEvent::Button {
+                time_usec,
+                button,
+                state: KeyState::Released,
+            });
+        }
+        self.pressed_keysyms.borrow_mut().clear();
+        let keys: Vec<_> = self.pressed_keys.borrow_mut().drain().collect();
+        for key in keys {
+            self.event(InputEvent::Key {
+                time_usec,
+                key,
+                state: KeyState::Released,
+            });
+        }
+        let touch_points: Vec<_> = self.touch_points.borrow_mut().drain().collect();
+        if !touch_points.is_empty() {
+            for id in touch_points {
+                self.event(InputEvent::TouchUp { time_usec, id });
+            }
+            self.event(InputEvent::TouchFrame { time_usec });
+        }
+    }
+
+    fn key(&self, key: u32, state: u32) -> Result<(), JayVirtualInputError> {
+        if let Some(state) = key_state(&self.pressed_keys, key, state)? {
+            self.event(InputEvent::Key {
+                time_usec: now_usec(),
+                key,
+                state,
+            });
+        }
+        Ok(())
+    }
+
+    /// Converts a position in the pixel coordinates of an output to a position relative
+    /// to its connector.
+    fn output_position(
+        &self,
+        output: WlOutputId,
+        x: Fixed,
+        y: Fixed,
+    ) -> Result<Option<(ConnectorId, Fixed, Fixed)>, JayVirtualInputError> {
+        let output = self.client.lookup(output)?;
+        let Some(global) = output.global.get() else {
+            return Ok(None);
+        };
+        let pos = global.pos.get();
+        let (width, height) = global.pixel_size();
+        if width <= 0 || height <= 0 {
+            return Ok(None);
+        }
+        let x = x.to_f64() * pos.width() as f64 / width as f64;
+        let y = y.to_f64() * pos.height() as f64 / height as f64;
+        Ok(Some((
+            global.connector.connector.id(),
+            Fixed::from_f64(x),
+            Fixed::from_f64(y),
+        )))
+    }
+}
+
+fn key_state(
+    pressed: &RefCell<AHashSet<u32>>,
+    key: u32,
+    state: u32,
+) -> Result<Option<KeyState>, JayVirtualInputError> {
+    let pressed = &mut *pressed.borrow_mut();
+    let state = match state {
+        0 => match pressed.remove(&key) {
+            true => KeyState::Released,
+            false => return Ok(None),
+        },
+        PRESSED => match pressed.insert(key) {
+            true => KeyState::Pressed,
+            false => return Ok(None),
+        },
+        _ => return Err(JayVirtualInputError::UnknownState(state)),
+    };
+    Ok(Some(state))
+}
+
+fn scroll_axis(axis: u32) -> Result<ScrollAxis, JayVirtualInputError> {
+    match axis {
+        VERTICAL_SCROLL => Ok(ScrollAxis::Vertical),
+        HORIZONTAL_SCROLL => Ok(ScrollAxis::Horizontal),
+        _ => Err(JayVirtualInputError::UnknownAxis(axis)),
+    }
+}
+
+impl JayVirtualInputRequestHandler for JayVirtualInput {
+    type Error = JayVirtualInputError;
+
+    fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.release_all();
+        self.data.set_seat(None);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn motion(&self, req: Motion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.event(InputEvent::Motion {
+            time_usec: now_usec(),
+            dx: req.dx,
+            dy: req.dy,
+            dx_unaccelerated: req.dx,
+            dy_unaccelerated: req.dy,
+        });
+        Ok(())
+    }
+
+    fn motion_absolute(&self, req: MotionAbsolute, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some((connector, x, y)) = self.output_position(req.output, req.x, req.y)? else {
+            return Ok(());
+        };
+        self.event(InputEvent::ConnectorPosition {
+            time_usec: now_usec(),
+            connector,
+            x,
+            y,
+        });
+        Ok(())
+    }
+
+    fn button(&self, req: Button, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if let Some(state) = key_state(&self.pressed_buttons, req.button, req.state)? {
+            self.event(InputEvent::Button {
+                time_usec: now_usec(),
+                button: req.button,
+                state,
+            });
+        }
+        Ok(())
+    }
+
+    fn axis(&self, req: Axis, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let axis = scroll_axis(req.axis)?;
+        self.event(InputEvent::AxisSource {
+            source: AxisSource::Continuous,
+        });
+        self.event(InputEvent::AxisPx {
+            dist: req.value,
+            axis,
+            inverted: false,
+        });
+        Ok(())
+    }
+
+    fn axis_discrete(&self, req: AxisDiscrete, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let axis = scroll_axis(req.axis)?;
+        self.event(InputEvent::AxisSource {
+            source: AxisSource::Wheel,
+        });
+        self.event(InputEvent::Axis120 {
+            dist: req.steps.saturating_mul(AXIS_120),
+            axis,
+            inverted: false,
+        });
+        Ok(())
+    }
+
+    fn axis_stop(&self, req: AxisStop, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let axis = scroll_axis(req.axis)?;
+        self.event(InputEvent::AxisStop { axis });
+        Ok(())
+    }
+
+    fn frame(&self, _req: Frame, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.event(InputEvent::AxisFrame {
+            time_usec: now_usec(),
+        });
+        Ok(())
+    }
+
+    fn key(&self, req: Key, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.key(req.key, req.state)
+    }
+
+    fn keysym(&self, req: Keysym, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        match req.state {
+            0 => {
+                let pressed = self.pressed_keysyms.borrow_mut().remove(&req.keysym);
+                if let Some((key, shift)) = pressed {
+                    self.key(key, 0)?;
+                    if shift {
+                        self.key(KEY_LEFTSHIFT, 0)?;
+                    }
+                }
+            }
+            PRESSED => {
+                if self.pressed_keysyms.borrow().contains_key(&req.keysym) {
+                    return Ok(());
+                }
+                let xkb_state = self.data.get_effective_xkb_state(&self.seat);
+                let Some((key, shift)) = xkb_state.borrow().find_keysym(req.keysym) else {
+                    log::debug!("The keymap does not contain the keysym {:#x}", req.keysym);
+                    return Ok(());
+                };
+                self.pressed_keysyms
+                    .borrow_mut()
+                    .insert(req.keysym, (key, shift));
+                if shift {
+                    self.key(KEY_LEFTSHIFT, PRESSED)?;
+                }
+                self.key(key, PRESSED)?;
+            }
+            _ => return Err(JayVirtualInputError::UnknownState(req.state)),
+        }
+        Ok(())
+    }
+
+    fn touch_down(&self, req: TouchDown, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some((connector, x, y)) = self.output_position(req.output, req.x, req.y)? else {
+            return Ok(());
+        };
+        if self.touch_points.borrow_mut().insert(req.id) {
+            self.event(InputEvent::TouchDown {
+                time_usec: now_usec(),
+                id: req.id,
+                connector,
+                x,
+                y,
+            });
+        }
+        Ok(())
+    }
+
+    fn touch_motion(&self, req: TouchMotion, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        let Some((connector, x, y)) = self.output_position(req.output, req.x, req.y)? else {
+            return Ok(());
+        };
+        if self.touch_points.borrow().contains(&req.id) {
+            self.event(InputEvent::TouchMotion {
+                time_usec: now_usec(),
+                id: req.id,
+                connector,
+                x,
+                y,
+            });
+        }
+        Ok(())
+    }
+
+    fn touch_up(&self, req: TouchUp, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        if self.touch_points.borrow_mut().remove(&req.id) {
+            self.event(InputEvent::TouchUp {
+                time_usec: now_usec(),
+                id: req.id,
+            });
+        }
+        Ok(())
+    }
+
+    fn touch_frame(&self, _req: TouchFrame, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.event(InputEvent::TouchFrame {
+            time_usec: now_usec(),
+        });
+        Ok(())
+    }
+}
+
+struct VirtualInputDevice {
+    id: InputDeviceId,
+    name: Rc<String>,
+}
+
+impl InputDevice for VirtualInputDevice {
+    fn id(&self) -> InputDeviceId {
+        self.id
+    }
+
+    fn removed(&self) -> bool {
+        false
+    }
+
+    fn event(&self) -> Option<InputEvent> {
+        None
+    }
+
+    fn on_change(&self, _cb: Rc<dyn Fn()>) {
+        // nothing
+    }
+
+    fn grab(&self, _grab: bool) {
+        // nothing
+    }
+
+    fn has_capability(&self, cap: InputDeviceCapability) -> bool {
+        match cap {
+            InputDeviceCapability::Keyboard
+            | InputDeviceCapability::Pointer
+            | InputDeviceCapability::Touch => true,
+            _ => false,
+        }
+    }
+
+    fn set_left_handed(&self, _left_handed: bool) {
+        // nothing
+    }
+
+    fn set_accel_profile(&self, _profile: InputDeviceAccelProfile) {
+        // nothing
+    }
+
+    fn set_custom_accel_function(&self, _ty: InputDeviceAccelType, _step: f64, _points: &[f64]) {
+        // nothing
+    }
+
+    fn set_accel_speed(&self, _speed: f64) {
+        // nothing
+    }
+
+    fn set_transform_matrix(&self, _matrix: TransformMatrix) {
+        // nothing
+    }
+
+    fn name(&self) -> Rc<String> {
+        self.name.clone()
+    }
+
+    fn set_tap_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_drag_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_drag_lock_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_natural_scrolling_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_click_method(&self, _method: InputDeviceClickMethod) {
+        // nothing
+    }
+
+    fn set_middle_button_emulation_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_scroll_method(&self, _method: InputDeviceScrollMethod) {
+        // nothing
+    }
+
+    fn set_scroll_button(&self, _button: u32) {
+        // nothing
+    }
+
+    fn set_disable_while_typing_enabled(&self, _enabled: bool) {
+        // nothing
+    }
+
+    fn set_tap_button_map(&self, _map: InputDeviceTapButtonMap) {
+        // nothing
+    }
+
+    fn set_send_events_mode(&self, _mode: InputDeviceSendEventsMode) {
+        // nothing
+    }
+
+    fn set_rotation(&self, _degrees: u32) {
+        // nothing
+    }
+
+    fn set_calibration_matrix(&self, _matrix: CalibrationMatrix) {
+        // nothing
+    }
+}
+
+object_base! {
+    self = JayVirtualInput;
+    version = Version(1);
+}
+
+impl Object for JayVirtualInput {
+    fn break_loops(&self) {
+        self.release_all();
+        self.data.set_seat(None);
+    }
+}
+
+simple_add_obj!(JayVirtualInput);
+
+#[derive(Debug, Error)]
+pub enum JayVirtualInputError {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Unknown button or key state {0}")]
+    UnknownState(u32),
+    #[error("Unknown axis {0}")]
+    UnknownAxis(u32),
+}
+efrom!(JayVirtualInputError, ClientError);
@@ -42,6 +42,7 @@ use {
                 zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
                 DynDataSource, IpcError,
             },
+            jay_global_shortcuts::JayGlobalShortcuts,
             wl_output::WlOutputGlobal,
             wl_seat::{
                 gesture_owner::GestureOwnerHolder,
@@ -71,21 +72,31 @@ use {
         time::now_usec,
         tree::{
             generic_node_visitor, ContainerNode, ContainerSplit, Direction, FoundNode, Node,
-            OutputNode, ToplevelNode, WorkspaceNode,
+            NodeId, OutputNode, PlaceholderNode, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
         utils::{
-            asyncevent::AsyncEvent, bindings::PerClientBindings, clonecell::CloneCell,
-            copyhashmap::CopyHashMap, errorfmt::ErrorFmt, linkedlist::LinkedNode, numcell::NumCell,
-            rc_eq::rc_eq, smallmap::SmallMap,
+            asyncevent::AsyncEvent,
+            bindings::PerClientBindings,
+            clonecell::CloneCell,
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+            linkedlist::LinkedNode,
+            numcell::NumCell,
+            rc_eq::rc_eq,
+            smallmap::{SmallMap, SmallMapMut},
         },
         wire::{
             wl_seat::*, ExtIdleNotificationV1Id, WlDataDeviceId, WlKeyboardId, WlPointerId,
-            WlSeatId, ZwlrDataControlDeviceV1Id, ZwpPrimarySelectionDeviceV1Id,
+            WlSeatId, WlTouchId, ZwlrDataControlDeviceV1Id, ZwpPrimarySelectionDeviceV1Id,
             ZwpRelativePointerV1Id, ZwpTextInputV3Id,
         },
         xkbcommon::{DynKeyboardState, KeyboardState, KeymapId, XkbKeymap, XkbState},
     },
-    ahash::AHashMap,
+    ahash::{AHashMap, AHashSet},
+    jay_config::{
+        input::TabletPadControl,
+        keyboard::{mods::Modifiers, KeyboardLayout},
+    },
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -104,14 +115,16 @@ pub use {
 
 pub const POINTER: u32 = 1;
 const KEYBOARD: u32 = 2;
-#[allow(dead_code)]
-const TOUCH: u32 = 4;
+pub const TOUCH: u32 = 4;
 
 #[allow(dead_code)]
 const MISSING_CAPABILITY: u32 = 0;
 
 pub const BTN_LEFT: u32 = 0x110;
 pub const BTN_RIGHT: u32 = 0x111;
+pub const BTN_MIDDLE: u32 = 0x112;
+pub const BTN_SIDE: u32 = 0x113;
+pub const BTN_EXTRA: u32 = 0x114;
 
 pub const SEAT_NAME_SINCE: Version = Version(2);
 
@@ -138,6 +151,21 @@ impl Drop for DroppedDnd {
 
 linear_ids!(SeatIds, SeatId);
 
+/// The shortcuts registered for a combination of a keysym and modifiers.
+#[derive(Default)]
+struct SeatShortcut {
+    /// The modifier mask if the shortcut was registered by the config.
+    config_mask: Option<u32>,
+    /// The shortcuts registered via the global shortcuts portal.
+    global: SmallVec<[(Rc<JayGlobalShortcuts>, u32); 1]>,
+}
+
+impl SeatShortcut {
+    fn is_empty(&self) -> bool {
+        self.config_mask.is_none() && self.global.is_empty()
+    }
+}
+
 pub struct WlSeatGlobal {
     id: SeatId,
     name: GlobalName,
@@ -175,7 +203,8 @@ pub struct WlSeatGlobal {
     kb_owner: KbOwnerHolder,
     gesture_owner: GestureOwnerHolder,
     dropped_dnd: RefCell<Option<DroppedDnd>>,
-    shortcuts: RefCell<AHashMap<u32, SmallMap<u32, u32, 2>>>,
+    shortcuts: RefCell<AHashMap<u32, SmallMapMut<u32, SeatShortcut, 2>>>,
+    active_global_shortcuts: RefCell<AHashMap<u32, SmallVec<[(Rc<JayGlobalShortcuts>, u32); 1]>>>,
     queue_link: RefCell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
     changes: NumCell<u32>,
@@ -188,10 +217,15 @@ pub struct WlSeatGlobal {
     input_method_grab: CloneCell<Option<Rc<ZwpInputMethodKeyboardGrabV2>>>,
     forward: Cell<bool>,
     focus_follows_mouse: Cell<bool>,
+    remember_keyboard_layout: Cell<bool>,
     swipe_bindings: PerClientBindings<ZwpPointerGestureSwipeV1>,
     pinch_bindings: PerClientBindings<ZwpPointerGesturePinchV1>,
     hold_bindings: PerClientBindings<ZwpPointerGestureHoldV1>,
     tablet: TabletSeatData,
+    /// The surfaces that received the touch down of the active touch points.
+    touch_points: CopyHashMap<i32, Rc<WlSurface>>,
+    /// The clients that have received touch events since the last frame.
+    touch_frame_clients: RefCell<AHashSet<ClientId>>,
 }
 
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
@@ -240,6 +274,7 @@ impl WlSeatGlobal {
             gesture_owner: Default::default(),
             dropped_dnd: RefCell::new(None),
             shortcuts: Default::default(),
+            active_global_shortcuts: Default::default(),
             queue_link: Default::default(),
             tree_changed_handler: Cell::new(None),
             changes: NumCell::new(CHANGE_CURSOR_MOVED | CHANGE_TREE),
@@ -253,10 +288,13 @@ impl WlSeatGlobal {
             input_method_grab: Default::default(),
             forward: Cell::new(false),
             focus_follows_mouse: Cell::new(true),
+            remember_keyboard_layout: Cell::new(false),
             swipe_bindings: Default::default(),
             pinch_bindings: Default::default(),
             hold_bindings: Default::default(),
             tablet: Default::default(),
+            touch_points: Default::default(),
+            touch_frame_clients: Default::default(),
         });
         slf.pointer_cursor.set_owner(slf.clone());
         let seat = slf.clone();
@@ -472,6 +510,49 @@ impl WlSeatGlobal {
         });
     }
 
+    /// Switches the layout of the seat keymap. `None` selects the next layout.
+    pub fn set_keyboard_layout(&self, layout: Option<u32>) {
+        self.set_xkb_layout(&self.seat_xkb_state.get(), layout);
+    }
+
+    fn set_xkb_layout(&self, xkb_state: &Rc<RefCell<XkbState>>, layout: Option<u32>) {
+        let latest_id = self.latest_kb_state.get().borrow().id;
+        let (layout, is_latest) = {
+            let xkb_state = &mut *xkb_state.borrow_mut();
+            let num = xkb_state.keymap().num_layouts();
+            let layout = layout.unwrap_or((xkb_state.mods().group + 1) % num.max(1));
+            if layout >= num || !xkb_state.set_layout(layout) {
+                return;
+            }
+            (
+                keyboard_layout(xkb_state),
+                xkb_state.kb_state.id == latest_id,
+            )
+        };
+        if is_latest {
+            let kb_state = &xkb_state.borrow().kb_state;
+            self.state.for_each_seat_tester(|t| {
+                t.send_modifiers(self.id, &kb_state.mods);
+            });
+            match self.input_method_grab.get() {
+                Some(g) => g.on_modifiers(kb_state),
+                _ => self.keyboard_node.get().node_on_mods(self, kb_state),
+            }
+        }
+        self.keyboard_layout_changed(layout);
+    }
+
+    fn keyboard_layout_changed(&self, layout: KeyboardLayout) {
+        if let Some(config) = self.state.config.get() {
+            config.keyboard_layout_changed(self.id, layout);
+        }
+    }
+
+    /// Returns the active layout of the seat keymap.
+    pub fn keyboard_layout(&self) -> KeyboardLayout {
+        keyboard_layout(&self.seat_xkb_state.get().borrow())
+    }
+
     pub fn get_xkb_state(&self, keymap: &Rc<XkbKeymap>) -> Option<Rc<RefCell<XkbState>>> {
         if let Some(weak) = self.xkb_states.get(&keymap.id) {
             if let Some(state) = weak.upgrade() {
@@ -510,10 +591,19 @@ impl WlSeatGlobal {
         None
     }
 
+    /// Returns the effective modifiers of the most recently used keyboard.
+    pub fn modifiers(&self) -> Modifiers {
+        Modifiers(self.latest_kb_state.get().borrow().mods.mods_effective)
+    }
+
     pub fn get_mono(&self) -> Option<bool> {
         self.kb_parent_container().map(|c| c.mono_child.is_some())
     }
 
+    pub fn get_stacked(&self) -> Option<bool> {
+        self.kb_parent_container().map(|c| c.stacked.get())
+    }
+
     pub fn get_split(&self) -> Option<ContainerSplit> {
         self.kb_parent_container().map(|c| c.split.get())
     }
@@ -529,6 +619,12 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn set_stacked(&self, stacked: bool) {
+        if let Some(c) = self.kb_parent_container() {
+            c.set_stacked(stacked);
+        }
+    }
+
     pub fn set_split(&self, axis: ContainerSplit) {
         if let Some(c) = self.kb_parent_container() {
             c.set_split(axis);
@@ -583,6 +679,40 @@ impl WlSeatGlobal {
         self.set_tl_floating(tl, floating);
     }
 
+    pub fn get_sticky(self: &Rc<Self>) -> Option<bool> {
+        match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => Some(tl.tl_data().sticky.get()),
+            _ => None,
+        }
+    }
+
+    pub fn set_sticky(self: &Rc<Self>, sticky: bool) {
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        tl.tl_data().sticky.set(sticky);
+        if !sticky {
+            return;
+        }
+        let Some(float) = tl.tl_data().parent.get().and_then(|p| p.node_into_float()) else {
+            return;
+        };
+        let ws = float.workspace.get().output.get().ensure_workspace();
+        if ws.id != float.workspace.get().id {
+            float.set_workspace(&ws);
+        }
+    }
+
+    pub fn set_window_opacity(self: &Rc<Self>, opacity: f32) {
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        tl.tl_data().opacity.set(Some(opacity.clamp(0.0, 1.0)));
+        self.state.damage();
+    }
+
     pub fn set_tl_floating(self: &Rc<Self>, tl: Rc<dyn ToplevelNode>, floating: bool) {
         let data = tl.tl_data();
         if data.is_fullscreen.get() {
@@ -596,6 +726,7 @@ impl WlSeatGlobal {
             _ => return,
         };
         if !floating {
+            data.scratchpad_link.take();
             parent.cnode_remove_child2(tl.tl_as_node(), true);
             self.state.map_tiled(tl);
         } else if let Some(ws) = data.workspace.get() {
@@ -605,6 +736,191 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn mark(self: &Rc<Self>, mark: &str) {
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        if let Some(prev) = self.state.marks.set(mark.to_string(), tl.clone()) {
+            if prev.node_id() == tl.node_id() {
+                return;
+            }
+            let data = prev.tl_data();
+            data.marks.borrow_mut().retain(|m| m != mark);
+            data.marks_changed();
+        }
+        let data = tl.tl_data();
+        data.marks.borrow_mut().push(mark.to_string());
+        data.marks_changed();
+    }
+
+    pub fn unmark(self: &Rc<Self>, mark: Option<&str>) {
+        let (tl, marks) = match mark {
+            Some(mark) => match self.state.marks.remove(mark) {
+                Some(tl) => (tl, vec![mark.to_string()]),
+                _ => return,
+            },
+            _ => match self.keyboard_node.get().node_toplevel() {
+                Some(tl) => {
+                    let marks = tl.tl_data().marks.borrow().clone();
+                    (tl, marks)
+                }
+                _ => return,
+            },
+        };
+        let data = tl.tl_data();
+        for mark in &marks {
+            self.state.marks.remove(mark);
+        }
+        data.marks.borrow_mut().retain(|m| !marks.contains(m));
+        data.marks_changed();
+    }
+
+    pub fn focus_mark(self: &Rc<Self>, mark: &str) {
+        let Some(tl) = self.state.marks.get(mark) else {
+            return;
+        };
+        let Some(ws) = tl.tl_data().workspace.get() else {
+            return;
+        };
+        if !ws.visible.get() {
+            self.state.show_workspace(self, &ws.name);
+        }
+        let mut node = tl.clone();
+        while let Some(parent) = node.tl_data().parent.get() {
+            let Some(container) = parent.node_into_container() else {
+                break;
+            };
+            container.activate_node(node.tl_as_node());
+            node = container;
+        }
+        if tl.node_visible() {
+            self.focus_toplevel(tl);
+        }
+    }
+
+    pub fn swap_with_mark(self: &Rc<Self>, mark: &str) {
+        let Some(a) = self.keyboard_node.get().node_toplevel() else {
+            return;
+        };
+        let Some(b) = self.state.marks.get(mark) else {
+            return;
+        };
+        if a.node_id() == b.node_id() {
+            return;
+        }
+        if a.tl_data().is_fullscreen.get() || b.tl_data().is_fullscreen.get() {
+            return;
+        }
+        let (Some(pa), Some(pb)) = (a.tl_data().parent.get(), b.tl_data().parent.get()) else {
+            return;
+        };
+        if a.node_is_placeholder() || b.node_is_placeholder() {
+            return;
+        }
+        let is_ancestor = |node: &Rc<dyn ToplevelNode>, ancestor: NodeId| {
+            let mut parent = node.tl_data().parent.get();
+            while let Some(p) = parent {
+                if p.node_id() == ancestor {
+                    return true;
+                }
+                parent = p.node_toplevel().and_then(|p| p.tl_data().parent.get());
+            }
+            false
+        };
+        if is_ancestor(&a, b.node_id()) || is_ancestor(&b, a.node_id()) {
+            return;
+        }
+        let tmp = Rc::new(PlaceholderNode::new_for(&self.state, a.clone()));
+        pa.clone().cnode_replace_child(a.tl_as_node(), tmp.clone());
+        pb.cnode_replace_child(b.tl_as_node(), a.clone());
+        pa.cnode_replace_child(tmp.tl_as_node(), b.clone());
+        tmp.tl_data().parent.take();
+        tmp.tl_destroy();
+        if !a.node_visible() && b.node_visible() {
+            self.focus_toplevel(b);
+        }
+        self.state.tree_changed();
+        self.state.damage();
+    }
+
+    pub fn move_to_scratchpad(self: &Rc<Self>) {
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        if tl.node_is_placeholder() {
+            return;
+        }
+        let data = tl.tl_data();
+        if data.is_fullscreen.get() {
+            return;
+        }
+        let link = data.scratchpad_link.take();
+        let link = match link {
+            Some(link) => {
+                self.state.scratchpad.add_last_existing(&link.to_ref());
+                link
+            }
+            _ => self.state.scratchpad.add_last(tl.clone()),
+        };
+        data.scratchpad_link.set(Some(link));
+        self.hide_scratchpad_window(&tl);
+    }
+
+    pub fn toggle_scratchpad(self: &Rc<Self>) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            let data = tl.tl_data();
+            if let Some(link) = data.scratchpad_link.take() {
+                self.state.scratchpad.add_last_existing(&link.to_ref());
+                data.scratchpad_link.set(Some(link));
+                self.hide_scratchpad_window(&tl);
+                return;
+            }
+        }
+        let output = self.get_output();
+        if output.is_dummy {
+            return;
+        }
+        let tl = self
+            .state
+            .scratchpad
+            .iter()
+            .find(|tl| tl.tl_data().parent.get().is_none());
+        let Some(tl) = tl else {
+            return;
+        };
+        let tl = tl.deref().clone();
+        let ws = output.ensure_workspace();
+        let (width, height) = tl.tl_data().float_size(&ws);
+        self.state
+            .map_floating(tl.clone(), width, height, &ws, None);
+        tl.tl_into_node()
+            .node_do_focus(self, Direction::Unspecified);
+        self.state.tree_changed();
+        self.state.damage();
+    }
+
+    fn hide_scratchpad_window(self: &Rc<Self>, tl: &Rc<dyn ToplevelNode>) {
+        let data = tl.tl_data();
+        let Some(parent) = data.parent.take() else {
+            return;
+        };
+        let ws = data.workspace.take();
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        parent.cnode_remove_child2(tl.tl_as_node(), true);
+        tl.tl_set_visible(false);
+        if let Some(ws) = ws {
+            if ws.visible.get() {
+                for focus in kb_foci {
+                    ws.clone().node_do_focus(&focus, Direction::Unspecified);
+                }
+            }
+        }
+        self.state.tree_changed();
+        self.state.damage();
+    }
+
     pub fn get_rate(&self) -> (i32, i32) {
         self.repeat_rate.get()
     }
@@ -838,6 +1154,7 @@ impl WlSeatGlobal {
     pub fn clear(self: &Rc<Self>) {
         mem::take(self.pointer_stack.borrow_mut().deref_mut());
         mem::take(self.found_tree.borrow_mut().deref_mut());
+        self.touch_points.clear();
         self.keyboard_node.set(self.state.root.clone());
         self.state
             .root
@@ -862,6 +1179,8 @@ impl WlSeatGlobal {
         self.text_input.take();
         self.input_method.take();
         self.input_method_grab.take();
+        self.shortcuts.borrow_mut().clear();
+        self.active_global_shortcuts.borrow_mut().clear();
         self.swipe_bindings.clear();
         self.pinch_bindings.clear();
         self.hold_bindings.clear();
@@ -890,6 +1209,7 @@ impl WlSeatGlobal {
             pointers: Default::default(),
             relative_pointers: Default::default(),
             keyboards: Default::default(),
+            touches: Default::default(),
             version,
             tracker: Default::default(),
         });
@@ -955,6 +1275,34 @@ impl WlSeatGlobal {
     pub fn set_focus_follows_mouse(&self, focus_follows_mouse: bool) {
         self.focus_follows_mouse.set(focus_follows_mouse);
     }
+
+    pub fn set_remember_keyboard_layout(&self, remember: bool) {
+        self.remember_keyboard_layout.set(remember);
+    }
+
+    /// Stores the active layout of the seat keymap in the toplevel containing `node`.
+    fn save_keyboard_layout(&self, node: &Rc<dyn Node>) {
+        if !self.remember_keyboard_layout.get() {
+            return;
+        }
+        if let Some(tl) = node.clone().node_toplevel() {
+            let layout = self.seat_xkb_state.get().borrow().mods().group;
+            tl.tl_data().keyboard_layouts.insert(self.id, layout);
+        }
+    }
+
+    /// Restores the layout of the seat keymap that was active when the toplevel
+    /// containing `node` last had the focus. Toplevels that have never had the focus use
+    /// the first layout.
+    fn restore_keyboard_layout(&self, node: &Rc<dyn Node>) {
+        if !self.remember_keyboard_layout.get() {
+            return;
+        }
+        if let Some(tl) = node.clone().node_toplevel() {
+            let layout = tl.tl_data().keyboard_layouts.get(&self.id).unwrap_or(0);
+            self.set_keyboard_layout(Some(layout));
+        }
+    }
 }
 
 impl CursorUserOwner for WlSeatGlobal {
@@ -991,6 +1339,7 @@ pub struct WlSeat {
     pointers: CopyHashMap<WlPointerId, Rc<WlPointer>>,
     relative_pointers: CopyHashMap<ZwpRelativePointerV1Id, Rc<ZwpRelativePointerV1>>,
     keyboards: CopyHashMap<WlKeyboardId, Rc<WlKeyboard>>,
+    touches: CopyHashMap<WlTouchId, Rc<WlTouch>>,
     version: Version,
     tracker: Tracker<Self>,
 }
@@ -1001,7 +1350,7 @@ impl WlSeat {
     fn send_capabilities(self: &Rc<Self>) {
         self.client.event(Capabilities {
             self_id: self.id,
-            capabilities: POINTER | KEYBOARD,
+            capabilities: POINTER | KEYBOARD | TOUCH,
         })
     }
 
@@ -1056,6 +1405,7 @@ impl WlSeatRequestHandler for WlSeat {
         let p = Rc::new(WlTouch::new(req.id, slf));
         track!(self.client, p);
         self.client.add_client_obj(&p)?;
+        self.touches.set(req.id, p);
         Ok(())
     }
 
@@ -1093,6 +1443,7 @@ impl Object for WlSeat {
         self.pointers.clear();
         self.relative_pointers.clear();
         self.keyboards.clear();
+        self.touches.clear();
     }
 }
 
@@ -1112,6 +1463,14 @@ pub enum WlSeatError {
 efrom!(WlSeatError, ClientError);
 efrom!(WlSeatError, WlKeyboardError);
 
+pub fn keyboard_layout(xkb_state: &XkbState) -> KeyboardLayout {
+    let index = xkb_state.mods().group;
+    KeyboardLayout {
+        index,
+        name: xkb_state.keymap().layout_name(index),
+    }
+}
+
 pub fn collect_kb_foci2(node: Rc<dyn Node>, seats: &mut SmallVec<[Rc<WlSeatGlobal>; 3]>) {
     node.node_visit(&mut generic_node_visitor(|node| {
         node.node_seat_state().for_each_kb_focus(|s| seats.push(s));
@@ -1160,7 +1519,14 @@ impl DeviceHandlerData {
         self.update_xkb_state();
     }
 
-    fn get_effective_xkb_state(&self, seat: &WlSeatGlobal) -> Rc<RefCell<XkbState>> {
+    /// Switches the layout of the effective keymap. `None` selects the next layout.
+    pub fn set_keyboard_layout(&self, layout: Option<u32>) {
+        if let Some(seat) = self.seat.get() {
+            seat.set_xkb_layout(&self.get_effective_xkb_state(&seat), layout);
+        }
+    }
+
+    pub fn get_effective_xkb_state(&self, seat: &WlSeatGlobal) -> Rc<RefCell<XkbState>> {
         match self.xkb_state.get() {
             Some(s) => s,
             _ => seat.seat_xkb_state.get(),
@@ -1186,6 +1552,7 @@ impl DeviceHandlerData {
     }
 
     pub fn set_output(&self, output: Option<&WlOutputGlobal>) {
+        self.region.take();
         match output {
             None => {
                 log::info!("Removing output mapping of {}", self.device.name());
@@ -1198,7 +1565,16 @@ impl DeviceHandlerData {
         }
     }
 
+    pub fn set_region(&self, region: Rect) {
+        log::info!("Mapping {} to {:?}", self.device.name(), region);
+        self.output.take();
+        self.region.set(Some(region));
+    }
+
     pub fn get_rect(&self, state: &State) -> Rect {
+        if let Some(region) = self.region.get() {
+            return region;
+        }
         if let Some(output) = self.output.get() {
             if let Some(output) = output.get() {
                 return output.pos.get();
@@ -1206,4 +1582,27 @@ impl DeviceHandlerData {
         }
         state.root.extents.get()
     }
+
+    /// Returns whether the pad control is bound by the config.
+    pub fn tablet_pad_control_bound(&self, control: TabletPadControl, index: u32) -> bool {
+        self.tablet_pad_bindings
+            .borrow()
+            .contains_key(&(control, index))
+    }
+
+    /// Updates the position of a bound ring or strip and returns the distance moved
+    /// since the last event.
+    ///
+    /// Ring positions wrap around at 360 degrees.
+    pub fn tablet_pad_control_delta(
+        &self,
+        control: TabletPadControl,
+        index: u32,
+        position: Option<f64>,
+    ) -> Option<f64> {
+        let bindings = &mut *self.tablet_pad_bindings.borrow_mut();
+        let last = bindings.get_mut(&(control, index))?;
+        let prev = mem::replace(last, position);
+        Some(tablet::pad_control_delta(control, prev?, position?))
+    }
 }
@@ -14,8 +14,10 @@ use {
                 },
                 DynDataSource,
             },
+            jay_global_shortcuts::JayGlobalShortcuts,
             wl_seat::{
-                tablet::{TabletPad, TabletPadId, TabletTool, TabletToolId},
+                keyboard_layout,
+                tablet::{PadButtonState, TabletPad, TabletPadId, TabletTool, TabletToolId},
                 text_input::TextDisconnectReason,
                 wl_keyboard::{self, WlKeyboard},
                 wl_pointer::{
@@ -24,29 +26,30 @@ use {
                     AXIS_STOP_SINCE_VERSION, AXIS_VALUE120_SINCE_VERSION, IDENTICAL, INVERTED,
                     POINTER_FRAME_SINCE_VERSION, WHEEL_TILT, WHEEL_TILT_SINCE_VERSION,
                 },
+                wl_touch::WlTouch,
                 zwp_pointer_constraints_v1::{ConstraintType, SeatConstraintStatus},
                 zwp_relative_pointer_v1::ZwpRelativePointerV1,
-                Dnd, SeatId, WlSeat, WlSeatGlobal, CHANGE_CURSOR_MOVED, CHANGE_TREE,
+                Dnd, SeatId, SeatShortcut, WlSeat, WlSeatGlobal, CHANGE_CURSOR_MOVED, CHANGE_TREE,
             },
             wl_surface::{xdg_surface::xdg_popup::XdgPopup, WlSurface},
         },
         object::Version,
         state::DeviceHandlerData,
-        tree::{Direction, Node, ToplevelNode},
+        tree::{Direction, FindTreeUsecase, FoundNode, Node, ToplevelNode},
         utils::{bitflags::BitflagsExt, smallmap::SmallMap},
         wire::WlDataOfferId,
         xkbcommon::{KeyboardState, XkbState, XKB_KEY_DOWN, XKB_KEY_UP},
     },
     isnt::std_1::primitive::{IsntSlice2Ext, IsntSliceExt},
     jay_config::{
-        input::SwitchEvent,
+        input::{SwitchEvent, TabletPadControl},
         keyboard::{
             mods::{Modifiers, CAPS, NUM, RELEASE},
             syms::{KeySym, SYM_Escape},
         },
     },
     smallvec::SmallVec,
-    std::{cell::RefCell, collections::hash_map::Entry, rc::Rc},
+    std::{cell::RefCell, rc::Rc},
 };
 
 #[derive(Default)]
@@ -234,7 +237,11 @@ impl WlSeatGlobal {
             | InputEvent::TabletPadButton { time_usec, .. }
             | InputEvent::TabletPadModeSwitch { time_usec, .. }
             | InputEvent::TabletPadRing { time_usec, .. }
-            | InputEvent::TabletPadStrip { time_usec, .. } => {
+            | InputEvent::TabletPadStrip { time_usec, .. }
+            | InputEvent::TouchDown { time_usec, .. }
+            | InputEvent::TouchMotion { time_usec, .. }
+            | InputEvent::TouchUp { time_usec, .. }
+            | InputEvent::TouchFrame { time_usec, .. } => {
                 self.last_input_usec.set(time_usec);
                 if self.idle_notifications.is_not_empty() {
                     for (_, notification) in self.idle_notifications.lock().drain() {
@@ -278,6 +285,10 @@ impl WlSeatGlobal {
             InputEvent::TabletPadModeSwitch { .. } => {}
             InputEvent::TabletPadRing { .. } => {}
             InputEvent::TabletPadStrip { .. } => {}
+            InputEvent::TouchDown { .. } => {}
+            InputEvent::TouchMotion { .. } => {}
+            InputEvent::TouchUp { .. } => {}
+            InputEvent::TouchFrame { .. } => {}
         }
         match event {
             InputEvent::Key {
@@ -374,8 +385,12 @@ impl WlSeatGlobal {
             InputEvent::TabletToolChanged {
                 time_usec,
                 id,
-                changes: change,
-            } => self.tablet_event_tool_changes(id, time_usec, dev.get_rect(&self.state), &change),
+                changes: mut change,
+            } => {
+                let rect = dev.get_rect(&self.state);
+                change.apply_device_settings(dev, rect);
+                self.tablet_event_tool_changes(id, time_usec, rect, &change)
+            }
             InputEvent::TabletToolButton {
                 time_usec,
                 id,
@@ -390,7 +405,31 @@ impl WlSeatGlobal {
                 id,
                 button,
                 state,
-            } => self.tablet_event_pad_button(id, time_usec, button, state),
+            } => {
+                let forward = match state {
+                    PadButtonState::Pressed => {
+                        if dev.tablet_pad_control_bound(TabletPadControl::Button, button) {
+                            self.tablet_pad_binding_event(
+                                dev,
+                                TabletPadControl::Button,
+                                button,
+                                0.0,
+                            );
+                            false
+                        } else {
+                            dev.tablet_pad_forwarded_buttons.borrow_mut().insert(button);
+                            true
+                        }
+                    }
+                    PadButtonState::Released => dev
+                        .tablet_pad_forwarded_buttons
+                        .borrow_mut()
+                        .remove(&button),
+                };
+                if forward {
+                    self.tablet_event_pad_button(id, time_usec, button, state)
+                }
+            }
             InputEvent::TabletPadModeSwitch {
                 time_usec,
                 pad,
@@ -403,14 +442,50 @@ impl WlSeatGlobal {
                 ring,
                 source,
                 angle,
-            } => self.tablet_event_pad_ring(pad, ring, source, angle, time_usec),
+            } => {
+                if dev.tablet_pad_control_bound(TabletPadControl::Ring, ring) {
+                    if let Some(delta) =
+                        dev.tablet_pad_control_delta(TabletPadControl::Ring, ring, angle)
+                    {
+                        self.tablet_pad_binding_event(dev, TabletPadControl::Ring, ring, delta);
+                    }
+                } else {
+                    self.tablet_event_pad_ring(pad, ring, source, angle, time_usec)
+                }
+            }
             InputEvent::TabletPadStrip {
                 time_usec,
                 pad,
                 strip,
                 source,
                 position,
-            } => self.tablet_event_pad_strip(pad, strip, source, position, time_usec),
+            } => {
+                if dev.tablet_pad_control_bound(TabletPadControl::Strip, strip) {
+                    if let Some(delta) =
+                        dev.tablet_pad_control_delta(TabletPadControl::Strip, strip, position)
+                    {
+                        self.tablet_pad_binding_event(dev, TabletPadControl::Strip, strip, delta);
+                    }
+                } else {
+                    self.tablet_event_pad_strip(pad, strip, source, position, time_usec)
+                }
+            }
+            InputEvent::TouchDown {
+                time_usec,
+                id,
+                connector,
+                x,
+                y,
+            } => self.touch_down_event(time_usec, id, connector, x, y),
+            InputEvent::TouchMotion {
+                time_usec,
+                id,
+                connector,
+                x,
+                y,
+            } => self.touch_motion_event(time_usec, id, connector, x, y),
+            InputEvent::TouchUp { time_usec, id } => self.touch_up_event(time_usec, id),
+            InputEvent::TouchFrame { .. } => self.touch_frame_event(),
         }
     }
 
@@ -440,6 +515,101 @@ impl WlSeatGlobal {
         self.cursor_moved(time_usec);
     }
 
+    /// Converts a position relative to a connector into global coordinates.
+    fn touch_position(&self, connector: ConnectorId, x: Fixed, y: Fixed) -> Option<(Fixed, Fixed)> {
+        let output = self.state.root.outputs.get(&connector)?;
+        let pos = output.global.pos.get();
+        Some((x + Fixed::from_int(pos.x1()), y + Fixed::from_int(pos.y1())))
+    }
+
+    fn touch_down_event(
+        self: &Rc<Self>,
+        time_usec: u64,
+        id: i32,
+        connector: ConnectorId,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        let Some((x, y)) = self.touch_position(connector, x, y) else {
+            return;
+        };
+        let x_int = x.round_down();
+        let y_int = y.round_down();
+        let mut tree = vec![FoundNode {
+            node: self.state.root.clone(),
+            x: x_int,
+            y: y_int,
+        }];
+        self.state
+            .root
+            .node_find_tree_at(x_int, y_int, &mut tree, FindTreeUsecase::None);
+        let Some(last) = tree.pop() else {
+            return;
+        };
+        let Some(surface) = last.node.node_into_surface() else {
+            return;
+        };
+        let (x, y) = (x.apply_fract(last.x), y.apply_fract(last.y));
+        self.touch_points.set(id, surface.clone());
+        self.touch_frame_clients
+            .borrow_mut()
+            .insert(surface.client.id);
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(Version::ALL, surface.client.id, |t| {
+            t.send_down(serial, time, surface.id, id, x, y)
+        });
+        if let Some(node) = surface.get_focus_node(self.id) {
+            self.focus_node(node);
+        }
+    }
+
+    fn touch_motion_event(
+        self: &Rc<Self>,
+        time_usec: u64,
+        id: i32,
+        connector: ConnectorId,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        let Some(surface) = self.touch_points.get(&id) else {
+            return;
+        };
+        let Some((x, y)) = self.touch_position(connector, x, y) else {
+            return;
+        };
+        let surface_pos = surface.buffer_abs_pos.get();
+        let (x, y) = (x - surface_pos.x1(), y - surface_pos.y1());
+        self.touch_frame_clients
+            .borrow_mut()
+            .insert(surface.client.id);
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(Version::ALL, surface.client.id, |t| {
+            t.send_motion(time, id, x, y)
+        });
+    }
+
+    fn touch_up_event(self: &Rc<Self>, time_usec: u64, id: i32) {
+        let Some(surface) = self.touch_points.remove(&id) else {
+            return;
+        };
+        self.touch_frame_clients
+            .borrow_mut()
+            .insert(surface.client.id);
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(Version::ALL, surface.client.id, |t| {
+            t.send_up(serial, time, id)
+        });
+    }
+
+    fn touch_frame_event(self: &Rc<Self>) {
+        let clients: Vec<_> = self.touch_frame_clients.borrow_mut().drain().collect();
+        for client in clients {
+            self.for_each_touch(Version::ALL, client, |t| t.send_frame());
+        }
+    }
+
     fn motion_event(
         self: &Rc<Self>,
         time_usec: u64,
@@ -590,6 +760,18 @@ impl WlSeatGlobal {
         self.gesture_owner.hold_end(self, time_usec, cancelled)
     }
 
+    fn tablet_pad_binding_event(
+        &self,
+        dev: &DeviceHandlerData,
+        control: TabletPadControl,
+        index: u32,
+        delta: f64,
+    ) {
+        if let Some(config) = self.state.config.get() {
+            config.tablet_pad_event(dev.device.id(), control, index, delta);
+        }
+    }
+
     fn switch_event(self: &Rc<Self>, dev: InputDeviceId, time_usec: u64, event: SwitchEvent) {
         self.state.for_each_seat_tester(|t| {
             t.send_switch_event(self.id, dev, time_usec, event);
@@ -627,7 +809,9 @@ impl WlSeatGlobal {
             }
         };
         let mut shortcuts = SmallVec::<[_; 1]>::new();
+        let mut global_shortcuts = SmallVec::<[_; 1]>::new();
         let new_mods;
+        let old_layout = xkb_state.mods().group;
         {
             let mut mods = xkb_state.mods().mods_effective & !(CAPS.0 | NUM.0);
             if state == wl_keyboard::RELEASED {
@@ -641,13 +825,18 @@ impl WlSeatGlobal {
                 }
                 if !self.state.lock.locked.get() {
                     if let Some(key_mods) = scs.get(&sym) {
-                        for (key_mods, mask) in key_mods {
-                            if mods & mask == key_mods {
-                                shortcuts.push(InvokedShortcut {
-                                    unmasked_mods: Modifiers(mods),
-                                    effective_mods: Modifiers(key_mods),
-                                    sym: KeySym(sym),
-                                });
+                        for (&key_mods, sc) in key_mods {
+                            if let Some(mask) = sc.config_mask {
+                                if mods & mask == key_mods {
+                                    shortcuts.push(InvokedShortcut {
+                                        unmasked_mods: Modifiers(mods),
+                                        effective_mods: Modifiers(key_mods),
+                                        sym: KeySym(sym),
+                                    });
+                                }
+                            }
+                            if mods == key_mods {
+                                global_shortcuts.extend(sc.global.iter().cloned());
                             }
                         }
                     }
@@ -661,9 +850,23 @@ impl WlSeatGlobal {
         let node = self.keyboard_node.get();
         let input_method_grab = self.input_method_grab.get();
         let mut forward = true;
-        if shortcuts.is_not_empty() {
+        if state == wl_keyboard::RELEASED {
+            let active = self.active_global_shortcuts.borrow_mut().remove(&key);
+            for (gs, id) in active.into_iter().flatten() {
+                gs.send_deactivated(id, time_usec);
+            }
+        }
+        if shortcuts.is_not_empty() || global_shortcuts.is_not_empty() {
             self.forward.set(state == wl_keyboard::RELEASED);
-            if let Some(config) = self.state.config.get() {
+            if global_shortcuts.is_not_empty() {
+                for (gs, id) in &global_shortcuts {
+                    gs.send_activated(*id, time_usec);
+                }
+                self.active_global_shortcuts
+                    .borrow_mut()
+                    .insert(key, global_shortcuts);
+            }
+            if let Some(config) = self.state.config.get().filter(|_| shortcuts.is_not_empty()) {
                 let id = xkb_state.kb_state.id;
                 drop(xkb_state);
                 for shortcut in shortcuts {
@@ -677,7 +880,11 @@ impl WlSeatGlobal {
             }
             forward = self.forward.get();
         }
-        if forward {
+        if let Some(lock) = self.state.lock.builtin.get() {
+            if state == wl_keyboard::PRESSED {
+                lock.handle_key(xkb_state.keysym(key), &xkb_state.utf8(key));
+            }
+        } else if forward {
             match &input_method_grab {
                 Some(g) => g.on_key(time_usec, key, state, &xkb_state.kb_state),
                 _ => node.node_on_key(self, time_usec, key, state, &xkb_state.kb_state),
@@ -700,8 +907,12 @@ impl WlSeatGlobal {
                 xkb_state.kb_state.pressed_keys.insert(key);
             }
         }
+        let layout = (xkb_state.mods().group != old_layout).then(|| keyboard_layout(&xkb_state));
         drop(xkb_state);
         self.latest_kb_state.set(xkb_state_rc);
+        if let Some(layout) = layout {
+            self.keyboard_layout_changed(layout);
+        }
     }
 }
 
@@ -780,6 +991,18 @@ impl WlSeatGlobal {
         })
     }
 
+    fn for_each_touch<C>(&self, ver: Version, client: ClientId, mut f: C)
+    where
+        C: FnMut(&Rc<WlTouch>),
+    {
+        self.for_each_seat(ver, client, |seat| {
+            let touches = seat.touches.lock();
+            for touch in touches.values() {
+                f(touch);
+            }
+        })
+    }
+
     pub fn for_each_data_device<C>(&self, ver: Version, client: ClientId, mut f: C)
     where
         C: FnMut(&Rc<WlDataDevice>),
@@ -862,23 +1085,59 @@ impl WlSeatGlobal {
     }
 
     pub fn clear_shortcuts(&self) {
-        self.shortcuts.borrow_mut().clear();
+        self.shortcuts.borrow_mut().retain(|_, key_mods| {
+            for (_, sc) in key_mods.iter_mut() {
+                sc.config_mask = None;
+            }
+            key_mods.remove_if(|_, sc| sc.is_empty());
+            !key_mods.is_empty()
+        });
+    }
+
+    fn modify_shortcut(&self, mods: Modifiers, keysym: KeySym, f: impl FnOnce(&mut SeatShortcut)) {
+        let mut shortcuts = self.shortcuts.borrow_mut();
+        let key_mods = shortcuts.entry(keysym.0).or_default();
+        f(key_mods.get_or_default_mut(mods.0));
+        key_mods.remove_if(|_, sc| sc.is_empty());
+        if key_mods.is_empty() {
+            shortcuts.remove(&keysym.0);
+        }
     }
 
     pub fn add_shortcut(&self, mod_mask: Modifiers, mods: Modifiers, keysym: KeySym) {
-        self.shortcuts
-            .borrow_mut()
-            .entry(keysym.0)
-            .or_default()
-            .insert(mods.0, mod_mask.0);
+        self.modify_shortcut(mods, keysym, |sc| sc.config_mask = Some(mod_mask.0));
     }
 
     pub fn remove_shortcut(&self, mods: Modifiers, keysym: KeySym) {
-        if let Entry::Occupied(mut oe) = self.shortcuts.borrow_mut().entry(keysym.0) {
-            oe.get_mut().remove(&mods.0);
-            if oe.get().is_empty() {
-                oe.remove();
-            }
+        self.modify_shortcut(mods, keysym, |sc| sc.config_mask = None);
+    }
+
+    pub fn add_global_shortcut(
+        &self,
+        gs: &Rc<JayGlobalShortcuts>,
+        id: u32,
+        mods: Modifiers,
+        keysym: KeySym,
+    ) {
+        self.modify_shortcut(mods, keysym, |sc| sc.global.push((gs.clone(), id)));
+    }
+
+    pub fn remove_global_shortcut(
+        &self,
+        gs: &JayGlobalShortcuts,
+        id: u32,
+        mods: Modifiers,
+        keysym: KeySym,
+    ) {
+        self.modify_shortcut(mods, keysym, |sc| {
+            sc.global
+                .retain(|(a, a_id)| a.client.id != gs.client.id || a.id != gs.id || *a_id != id);
+        });
+    }
+
+    pub fn remove_active_global_shortcuts(&self, gs: &JayGlobalShortcuts) {
+        for active in self.active_global_shortcuts.borrow_mut().values_mut() {
+            active.retain(|(a, _)| a.client.id != gs.client.id || a.id != gs.id);
         }
     }
 
@@ -68,6 +68,7 @@ impl KbOwner for DefaultKbOwner {
         if old.node_is_xwayland_surface() && !node.node_is_xwayland_surface() {
             seat.state.xwayland.queue.push(XWaylandEvent::ActivateRoot);
         }
+        seat.save_keyboard_layout(&old);
         old.node_on_unfocus(seat);
         if old.node_seat_state().unfocus(seat) {
             old.node_active_changed(false);
@@ -79,6 +80,7 @@ impl KbOwner for DefaultKbOwner {
         // log::info!("focus {}", node.node_id());
         node.clone().node_on_focus(seat);
         seat.keyboard_node.set(node.clone());
+        seat.restore_keyboard_layout(&node);
         seat.tablet_on_keyboard_node_change();
     }
 }
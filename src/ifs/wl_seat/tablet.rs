@@ -1,5 +1,6 @@
 use {
     crate::{
+        animation::Curve,
         backend::{InputDeviceGroupId, InputDeviceId},
         cursor_user::CursorUser,
         ifs::{
@@ -17,10 +18,13 @@ use {
             wl_surface::WlSurface,
         },
         object::Version,
+        rect::Rect,
+        state::DeviceHandlerData,
         time::now_usec,
         tree::{FoundNode, Node},
         utils::{bindings::PerClientBindings, clonecell::CloneCell, copyhashmap::CopyHashMap},
     },
+    jay_config::input::TabletPadControl,
     std::{
         cell::{Cell, RefCell},
         rc::Rc,
@@ -41,6 +45,9 @@ pub mod zwp_tablet_seat_v2;
 pub mod zwp_tablet_tool_v2;
 pub mod zwp_tablet_v2;
 
+#[cfg(test)]
+mod tests;
+
 #[derive(Default)]
 pub struct TabletSeatData {
     seats: PerClientBindings<ZwpTabletSeatV2>,
@@ -57,6 +64,8 @@ pub struct TabletInit {
     pub pid: u32,
     pub vid: u32,
     pub path: String,
+    /// The physical size of the tablet in mm.
+    pub size: Option<(f64, f64)>,
 }
 
 #[derive(Debug, Clone)]
@@ -245,6 +254,126 @@ pub struct TabletToolWheelChange {
     pub clicks: i32,
 }
 
+/// The part of a tablet that is mapped to the output. All values are fractions of the
+/// tablet size.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct TabletArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Default for TabletArea {
+    fn default() -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: 1.0,
+            height: 1.0,
+        }
+    }
+}
+
+impl TabletArea {
+    pub fn is_valid(&self) -> bool {
+        let range = 0.0..=1.0;
+        range.contains(&self.x)
+            && range.contains(&self.y)
+            && self.width > 0.0
+            && self.height > 0.0
+            && range.contains(&(self.x + self.width))
+            && range.contains(&(self.y + self.height))
+    }
+
+    /// Shrinks the area around its center so that it has the same aspect ratio as the
+    /// target rectangle.
+    fn with_aspect_ratio(mut self, (tablet_width, tablet_height): (f64, f64), rect: Rect) -> Self {
+        let width = self.width * tablet_width;
+        let height = self.height * tablet_height;
+        if width <= 0.0 || height <= 0.0 || rect.is_empty() {
+            return self;
+        }
+        let target = rect.width() as f64 / rect.height() as f64;
+        if width / height > target {
+            let new_width = self.width * (height * target / width);
+            self.x += (self.width - new_width) / 2.0;
+            self.width = new_width;
+        } else {
+            let new_height = self.height * (width / target / height);
+            self.y += (self.height - new_height) / 2.0;
+            self.height = new_height;
+        }
+        self
+    }
+
+    fn map(&self, pos: &mut TabletTool2dChange<TabletToolPositionChange>) {
+        if self.width > 0.0 {
+            pos.x.x = ((pos.x.x - self.x) / self.width).clamp(0.0, 1.0);
+        }
+        if self.height > 0.0 {
+            pos.y.x = ((pos.y.x - self.y) / self.height).clamp(0.0, 1.0);
+        }
+    }
+}
+
+/// A cubic bezier curve from (0, 0) to (1, 1) that is applied to the tool pressure.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PressureCurve {
+    pub x1: f64,
+    pub y1: f64,
+    pub x2: f64,
+    pub y2: f64,
+}
+
+impl PressureCurve {
+    pub fn is_valid(&self) -> bool {
+        [self.x1, self.y1, self.x2, self.y2]
+            .iter()
+            .all(|v| (0.0..=1.0).contains(v))
+    }
+
+    fn apply(&self, pressure: f64) -> f64 {
+        Curve::new(self.x1, self.y1, self.x2, self.y2).apply(pressure)
+    }
+}
+
+/// Returns the distance between two positions of a pad ring or strip.
+pub(super) fn pad_control_delta(control: TabletPadControl, prev: f64, position: f64) -> f64 {
+    let mut delta = position - prev;
+    if control == TabletPadControl::Ring {
+        if delta > 180.0 {
+            delta -= 360.0;
+        } else if delta <= -180.0 {
+            delta += 360.0;
+        }
+    }
+    delta
+}
+
+impl TabletToolChanges {
+    /// Applies the tablet settings of the device to the changes reported by the
+    /// backend.
+    pub fn apply_device_settings(&mut self, dev: &DeviceHandlerData, rect: Rect) {
+        if let Some(pos) = &mut self.pos {
+            let mut area = dev.tablet_area.get();
+            if dev.tablet_keep_aspect_ratio.get() {
+                if let Some(size) = dev.tablet_init.as_ref().and_then(|t| t.size) {
+                    area = area.with_aspect_ratio(size, rect);
+                }
+            }
+            if area != TabletArea::default() {
+                area.map(pos);
+            }
+        }
+        if let Some(curve) = dev.tablet_pressure_curve.get() {
+            if let Some(pressure) = &mut self.pressure {
+                *pressure = curve.apply(*pressure);
+            }
+        }
+    }
+}
+
 impl WlSeatGlobal {
     fn tablet_add_seat(&self, seat: &Rc<ZwpTabletSeatV2>) {
         self.tablet.seats.add(&seat.client, seat);
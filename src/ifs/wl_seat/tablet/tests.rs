@@ -0,0 +1,125 @@
+use {
+    crate::{
+        ifs::wl_seat::tablet::{
+            pad_control_delta, PressureCurve, TabletArea, TabletTool2dChange,
+            TabletToolPositionChange,
+        },
+        rect::Rect,
+    },
+    jay_config::input::TabletPadControl,
+};
+
+fn rect(width: i32, height: i32) -> Rect {
+    Rect::new_sized(0, 0, width, height).unwrap()
+}
+
+fn map(area: &TabletArea, x: f64, y: f64) -> (f64, f64) {
+    let mut pos = TabletTool2dChange {
+        x: TabletToolPositionChange { x, dx: 0.0 },
+        y: TabletToolPositionChange { x: y, dx: 0.0 },
+    };
+    area.map(&mut pos);
+    (pos.x.x, pos.y.x)
+}
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-6, "{a} != {b}");
+}
+
+#[test]
+fn aspect_ratio_wide_tablet() {
+    let area = TabletArea::default().with_aspect_ratio((200.0, 100.0), rect(100, 100));
+    assert_eq!(
+        area,
+        TabletArea {
+            x: 0.25,
+            y: 0.0,
+            width: 0.5,
+            height: 1.0,
+        },
+    );
+}
+
+#[test]
+fn aspect_ratio_wide_output() {
+    let area = TabletArea::default().with_aspect_ratio((200.0, 100.0), rect(400, 100));
+    assert_eq!(
+        area,
+        TabletArea {
+            x: 0.0,
+            y: 0.25,
+            width: 1.0,
+            height: 0.5,
+        },
+    );
+}
+
+#[test]
+fn aspect_ratio_empty_output() {
+    let area = TabletArea::default().with_aspect_ratio((200.0, 100.0), Rect::default());
+    assert_eq!(area, TabletArea::default());
+}
+
+#[test]
+fn map_area() {
+    let area = TabletArea {
+        x: 0.25,
+        y: 0.5,
+        width: 0.5,
+        height: 0.25,
+    };
+    assert_eq!(map(&area, 0.25, 0.5), (0.0, 0.0));
+    assert_eq!(map(&area, 0.5, 0.625), (0.5, 0.5));
+    assert_eq!(map(&area, 0.75, 0.75), (1.0, 1.0));
+    // Positions outside of the area are clamped to its edges.
+    assert_eq!(map(&area, 0.0, 1.0), (0.0, 1.0));
+}
+
+#[test]
+fn pressure_curve() {
+    let linear = PressureCurve {
+        x1: 0.0,
+        y1: 0.0,
+        x2: 1.0,
+        y2: 1.0,
+    };
+    for p in [0.0, 0.1, 0.5, 0.9, 1.0] {
+        assert_close(linear.apply(p), p);
+    }
+    assert_eq!(linear.apply(-1.0), 0.0);
+    assert_eq!(linear.apply(2.0), 1.0);
+    let soft = PressureCurve {
+        x1: 0.0,
+        y1: 0.5,
+        x2: 0.5,
+        y2: 1.0,
+    };
+    let hard = PressureCurve {
+        x1: 0.5,
+        y1: 0.0,
+        x2: 1.0,
+        y2: 0.5,
+    };
+    for p in [0.1, 0.5, 0.9] {
+        assert!(soft.apply(p) > p);
+        assert!(hard.apply(p) < p);
+    }
+    assert_close(soft.apply(0.5), 1.0 - hard.apply(0.5));
+}
+
+#[test]
+fn ring_wraparound() {
+    let ring = TabletPadControl::Ring;
+    assert_eq!(pad_control_delta(ring, 10.0, 30.0), 20.0);
+    assert_eq!(pad_control_delta(ring, 350.0, 10.0), 20.0);
+    assert_eq!(pad_control_delta(ring, 10.0, 350.0), -20.0);
+    assert_eq!(pad_control_delta(ring, 0.0, 180.0), 180.0);
+    assert_eq!(pad_control_delta(ring, 180.0, 0.0), 180.0);
+}
+
+#[test]
+fn strip_no_wraparound() {
+    let strip = TabletPadControl::Strip;
+    assert_close(pad_control_delta(strip, 0.9, 0.1), -0.8);
+    assert_close(pad_control_delta(strip, 0.1, 0.9), 0.8);
+}
@@ -1,10 +1,11 @@
 use {
     crate::{
         client::ClientError,
+        fixed::Fixed,
         ifs::wl_seat::WlSeat,
         leaks::Tracker,
         object::Object,
-        wire::{wl_touch::*, WlTouchId},
+        wire::{wl_touch::*, WlSurfaceId, WlTouchId},
     },
     std::rc::Rc,
     thiserror::Error,
@@ -39,12 +40,56 @@ impl WlTouch {
             tracker: Default::default(),
         }
     }
+
+    pub fn send_down(
+        &self,
+        serial: u32,
+        time: u32,
+        surface: WlSurfaceId,
+        id: i32,
+        x: Fixed,
+        y: Fixed,
+    ) {
+        self.seat.client.event(Down {
+            self_id: self.id,
+            serial,
+            time,
+            surface,
+            id,
+            x,
+            y,
+        })
+    }
+
+    pub fn send_up(&self, serial: u32, time: u32, id: i32) {
+        self.seat.client.event(Up {
+            self_id: self.id,
+            serial,
+            time,
+            id,
+        })
+    }
+
+    pub fn send_motion(&self, time: u32, id: i32, x: Fixed, y: Fixed) {
+        self.seat.client.event(Motion {
+            self_id: self.id,
+            time,
+            id: id as u32,
+            x,
+            y,
+        })
+    }
+
+    pub fn send_frame(&self) {
+        self.seat.client.event(Frame { self_id: self.id })
+    }
 }
 
 impl WlTouchRequestHandler for WlTouch {
     type Error = WlTouchError;
 
     fn release(&self, _req: Release, _slf: &Rc<Self>) -> Result<(), Self::Error> {
+        self.seat.touches.remove(&self.id);
         self.seat.client.remove_obj(self)?;
         Ok(())
     }
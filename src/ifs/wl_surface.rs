@@ -937,6 +937,7 @@ impl WlSurface {
         if self.destroyed.get() {
             return Ok(());
         }
+        let old_extents = self.abs_extents();
         self.ext.get().before_apply_commit(pending)?;
         let mut scale_changed = false;
         if let Some(scale) = pending.scale.take() {
@@ -1139,10 +1140,18 @@ impl WlSurface {
             }
         }
         self.ext.get().after_apply_commit();
-        self.client.state.damage();
+        let root = self.get_root().id;
+        let state = &self.client.state;
+        state.damage_log.damage(old_extents, root);
+        state.damage_surface(self.abs_extents(), root);
         Ok(())
     }
 
+    fn abs_extents(&self) -> Rect {
+        let pos = self.buffer_abs_pos.get();
+        self.extents.get().move_(pos.x1(), pos.y1())
+    }
+
     fn verify_explicit_sync(&self, pending: &mut PendingState) -> Result<(), WlSurfaceError> {
         pending.explicit_sync = self.sync_obj_surface.is_some();
         if !pending.explicit_sync {
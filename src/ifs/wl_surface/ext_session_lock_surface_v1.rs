@@ -88,6 +88,10 @@ impl ExtSessionLockSurfaceV1 {
 }
 
 impl SurfaceExt for ExtSessionLockSurfaceV1 {
+    fn after_apply_commit(self: Rc<Self>) {
+        self.client.state.lock.changed.trigger();
+    }
+
     fn extents_changed(&self) {
         self.client.state.tree_changed();
     }
@@ -285,6 +285,7 @@ impl Xwindow {
             Change::Unmap => self.tl_set_visible(false),
             Change::Map => {
                 self.tl_set_visible(true);
+                self.toplevel_data.animate_open();
                 self.toplevel_data.broadcast(self.clone());
             }
             Change::None => {}
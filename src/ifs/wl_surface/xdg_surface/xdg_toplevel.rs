@@ -429,6 +429,7 @@ impl XdgToplevel {
             } else {
                 self.map_tiled();
             }
+            self.toplevel_data.animate_open();
             self.extents_changed();
             if let Some(workspace) = self.xdg.workspace.get() {
                 let output = workspace.output.get();
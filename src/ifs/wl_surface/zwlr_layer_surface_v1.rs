@@ -1,6 +1,7 @@
 use {
     crate::{
         client::{Client, ClientError},
+        gfx_api::BlurCache,
         ifs::{
             wl_output::OutputGlobalOpt,
             wl_seat::NodeSeatState,
@@ -60,6 +61,7 @@ pub struct ZwlrLayerSurfaceV1 {
     keyboard_interactivity: Cell<u32>,
     link: Cell<Option<LinkedNode<Rc<Self>>>>,
     seat_state: NodeSeatState,
+    pub blur_cache: Rc<BlurCache>,
 }
 
 #[derive(Default)]
@@ -123,6 +125,7 @@ impl ZwlrLayerSurfaceV1 {
             keyboard_interactivity: Cell::new(0),
             link: Cell::new(None),
             seat_state: Default::default(),
+            blur_cache: Default::default(),
         }
     }
 
@@ -26,6 +26,11 @@ impl ZwpIdleInhibitorV1RequestHandler for ZwpIdleInhibitorV1 {
 
     fn destroy(&self, _req: Destroy, _slf: &Rc<Self>) -> Result<(), Self::Error> {
         self.client.remove_obj(self)?;
+        self.client
+            .state
+            .idle
+            .registered_inhibitors
+            .remove(&self.inhibit_id);
         if self.surface.idle_inhibitors.remove(&self.id).is_some() {
             self.deactivate();
         }
@@ -36,6 +41,11 @@ impl ZwpIdleInhibitorV1RequestHandler for ZwpIdleInhibitorV1 {
 impl ZwpIdleInhibitorV1 {
     pub fn install(self: &Rc<Self>) -> Result<(), ZwpIdleInhibitorV1Error> {
         self.surface.idle_inhibitors.insert(self.id, self.clone());
+        self.client
+            .state
+            .idle
+            .registered_inhibitors
+            .set(self.inhibit_id, self.clone());
         if self.surface.visible.get() {
             self.activate();
         }
@@ -58,6 +68,11 @@ object_base! {
 
 impl Object for ZwpIdleInhibitorV1 {
     fn break_loops(&self) {
+        self.client
+            .state
+            .idle
+            .registered_inhibitors
+            .remove(&self.inhibit_id);
         self.deactivate();
     }
 }
@@ -2,10 +2,11 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         backend::{
-            AxisSource, Backend, BackendEvent, Connector, ConnectorEvent, ConnectorId,
-            ConnectorKernelId, DrmDeviceId, InputDevice, InputDeviceAccelProfile,
-            InputDeviceCapability, InputDeviceId, InputEvent, KeyState, Mode, MonitorInfo,
-            ScrollAxis, TransformMatrix,
+            AxisSource, Backend, BackendEvent, CalibrationMatrix, Connector, ConnectorEvent,
+            ConnectorId, ConnectorKernelId, DrmDeviceId, InputDevice, InputDeviceAccelProfile,
+            InputDeviceAccelType, InputDeviceCapability, InputDeviceClickMethod, InputDeviceId,
+            InputDeviceScrollMethod, InputDeviceSendEventsMode, InputDeviceTapButtonMap,
+            InputEvent, KeyState, Mode, MonitorInfo, ScrollAxis, TransformMatrix,
         },
         compositor::TestFuture,
         drm_feedback::DrmFeedback,
@@ -21,6 +22,7 @@ use {
         video::drm::{ConnectorType, Drm},
     },
     bstr::ByteSlice,
+    jay_config::video::GfxApi,
     std::{any::Any, cell::Cell, io, os::unix::ffi::OsStrExt, pin::Pin, rc::Rc},
     thiserror::Error,
     uapi::c,
@@ -47,6 +49,7 @@ pub struct TestBackend {
     pub default_kb: Rc<TestBackendKb>,
     pub render_context_installed: Cell<bool>,
     pub idle: TEEH<bool>,
+    pub suspend: TEEH<()>,
 }
 
 impl TestBackend {
@@ -117,6 +120,7 @@ impl TestBackend {
             default_kb,
             render_context_installed: Cell::new(false),
             idle: Rc::new(Default::default()),
+            suspend: Rc::new(Default::default()),
         }
     }
 
@@ -186,7 +190,7 @@ impl TestBackend {
             }
         };
         let drm = Drm::open_existing(file);
-        let ctx = match self.state.create_gfx_context(&drm, None) {
+        let ctx = match self.state.create_gfx_context(&drm, Some(GfxApi::Software)) {
             Ok(ctx) => ctx,
             Err(e) => return Err(TestBackendError::RenderContext(e)),
         };
@@ -217,6 +221,10 @@ impl Backend for TestBackend {
         self.idle.push(idle);
     }
 
+    fn suspend(&self) {
+        self.suspend.push(());
+    }
+
     fn supports_presentation_feedback(&self) -> bool {
         true
     }
@@ -318,6 +326,42 @@ impl TestBackendMouse {
         }
     }
 
+    pub fn touch_down(&self, connector: &TestConnector, id: i32, x: f64, y: f64) {
+        self.common.event(InputEvent::TouchDown {
+            time_usec: now_usec(),
+            id,
+            connector: connector.id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+        self.touch_frame();
+    }
+
+    pub fn touch_motion(&self, connector: &TestConnector, id: i32, x: f64, y: f64) {
+        self.common.event(InputEvent::TouchMotion {
+            time_usec: now_usec(),
+            id,
+            connector: connector.id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+        self.touch_frame();
+    }
+
+    pub fn touch_up(&self, id: i32) {
+        self.common.event(InputEvent::TouchUp {
+            time_usec: now_usec(),
+            id,
+        });
+        self.touch_frame();
+    }
+
+    fn touch_frame(&self) {
+        self.common.event(InputEvent::TouchFrame {
+            time_usec: now_usec(),
+        });
+    }
+
     pub fn scroll(&self, dy: i32) {
         self.common.event(InputEvent::AxisSource {
             source: AxisSource::Wheel,
@@ -441,6 +485,10 @@ trait TestInputDevice: InputDevice {
         let _ = profile;
     }
 
+    fn set_custom_accel_function(&self, ty: InputDeviceAccelType, step: f64, points: &[f64]) {
+        let _ = (ty, step, points);
+    }
+
     fn set_accel_speed(&self, speed: f64) {
         let _ = speed;
     }
@@ -464,6 +512,42 @@ trait TestInputDevice: InputDevice {
     fn set_natural_scrolling_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
+
+    fn set_click_method(&self, method: InputDeviceClickMethod) {
+        let _ = method;
+    }
+
+    fn set_middle_button_emulation_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_scroll_method(&self, method: InputDeviceScrollMethod) {
+        let _ = method;
+    }
+
+    fn set_scroll_button(&self, button: u32) {
+        let _ = button;
+    }
+
+    fn set_disable_while_typing_enabled(&self, enabled: bool) {
+        let _ = enabled;
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        let _ = map;
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        let _ = mode;
+    }
+
+    fn set_rotation(&self, degrees: u32) {
+        let _ = degrees;
+    }
+
+    fn set_calibration_matrix(&self, matrix: CalibrationMatrix) {
+        let _ = matrix;
+    }
 }
 
 impl<T: TestInputDevice> InputDevice for T {
@@ -499,6 +583,10 @@ impl<T: TestInputDevice> InputDevice for T {
         <Self as TestInputDevice>::set_accel_profile(self, profile)
     }
 
+    fn set_custom_accel_function(&self, ty: InputDeviceAccelType, step: f64, points: &[f64]) {
+        <Self as TestInputDevice>::set_custom_accel_function(self, ty, step, points)
+    }
+
     fn set_accel_speed(&self, speed: f64) {
         <Self as TestInputDevice>::set_accel_speed(self, speed)
     }
@@ -530,4 +618,40 @@ impl<T: TestInputDevice> InputDevice for T {
     fn set_natural_scrolling_enabled(&self, enabled: bool) {
         <Self as TestInputDevice>::set_natural_scrolling_enabled(self, enabled)
     }
+
+    fn set_click_method(&self, method: InputDeviceClickMethod) {
+        <Self as TestInputDevice>::set_click_method(self, method)
+    }
+
+    fn set_middle_button_emulation_enabled(&self, enabled: bool) {
+        <Self as TestInputDevice>::set_middle_button_emulation_enabled(self, enabled)
+    }
+
+    fn set_scroll_method(&self, method: InputDeviceScrollMethod) {
+        <Self as TestInputDevice>::set_scroll_method(self, method)
+    }
+
+    fn set_scroll_button(&self, button: u32) {
+        <Self as TestInputDevice>::set_scroll_button(self, button)
+    }
+
+    fn set_disable_while_typing_enabled(&self, enabled: bool) {
+        <Self as TestInputDevice>::set_disable_while_typing_enabled(self, enabled)
+    }
+
+    fn set_tap_button_map(&self, map: InputDeviceTapButtonMap) {
+        <Self as TestInputDevice>::set_tap_button_map(self, map)
+    }
+
+    fn set_send_events_mode(&self, mode: InputDeviceSendEventsMode) {
+        <Self as TestInputDevice>::set_send_events_mode(self, mode)
+    }
+
+    fn set_rotation(&self, degrees: u32) {
+        <Self as TestInputDevice>::set_rotation(self, degrees)
+    }
+
+    fn set_calibration_matrix(&self, matrix: CalibrationMatrix) {
+        <Self as TestInputDevice>::set_calibration_matrix(self, matrix)
+    }
 }
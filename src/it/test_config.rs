@@ -11,15 +11,25 @@ use {
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage},
+            ipc::{
+                ClientMessage, Response, ServerMessage, StatusBlock, StatusClick, WorkspaceSource,
+            },
             ConfigEntry, VERSION,
         },
+        animation::{Animation, AnimationCurve},
         input::{InputDevice, Seat},
         keyboard::{Keymap, ModifiedKeySym},
+        theme::{sized::Resizable, ColorScheme},
         video::{Connector, Transform},
-        Axis, Direction,
+        Axis, ContainerLayout, Direction, IdleStage, WorkspaceLayout,
+    },
+    std::{
+        cell::Cell,
+        ops::{Deref, Range},
+        ptr,
+        rc::Rc,
+        time::Duration,
     },
-    std::{cell::Cell, ops::Deref, ptr, rc::Rc, time::Duration},
 };
 
 pub static TEST_CONFIG_ENTRY: ConfigEntry = ConfigEntry {
@@ -42,6 +52,8 @@ where
         responses: Default::default(),
         invoked_shortcuts: Default::default(),
         graphics_initialized: Cell::new(false),
+        idle_stages: Default::default(),
+        status_clicks: Default::default(),
     });
     let old = CONFIG.get();
     CONFIG.set(tc.deref());
@@ -121,6 +133,12 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::InterestReady { .. } => {}
         ServerMessage::Features { .. } => {}
         ServerMessage::SwitchEvent { .. } => {}
+        ServerMessage::StatusClicked { click } => tc.status_clicks.push(click),
+        ServerMessage::KeyboardLayoutChanged { .. } => {}
+        ServerMessage::BeforeSleep => {}
+        ServerMessage::IdleStageEntered { name } => tc.idle_stages.push((name, true)),
+        ServerMessage::IdleStageResumed { name } => tc.idle_stages.push((name, false)),
+        ServerMessage::TabletPadEvent { .. } => {}
     }
 }
 
@@ -136,6 +154,8 @@ pub struct TestConfig {
     responses: Stack<Response>,
     pub invoked_shortcuts: CopyHashMap<(SeatId, ModifiedKeySym), ()>,
     pub graphics_initialized: Cell<bool>,
+    pub idle_stages: Stack<(String, bool)>,
+    pub status_clicks: Stack<StatusClick>,
 }
 
 macro_rules! get_response {
@@ -210,6 +230,26 @@ impl TestConfig {
         })
     }
 
+    pub fn set_keyboard_layout(&self, seat: SeatId, layout: u32) -> TestResult {
+        self.send(ClientMessage::SeatSetKeyboardLayout {
+            seat: Seat(seat.raw() as _),
+            layout,
+        })
+    }
+
+    pub fn set_remember_keyboard_layout(&self, seat: SeatId, remember: bool) -> TestResult {
+        self.send(ClientMessage::SetRememberKeyboardLayout {
+            seat: Seat(seat.raw() as _),
+            remember,
+        })
+    }
+
+    pub fn next_keyboard_layout(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::SeatNextKeyboardLayout {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
     pub fn create_split(&self, seat: SeatId, axis: Axis) -> TestResult {
         self.send(ClientMessage::CreateSplit {
             seat: Seat(seat.raw() as _),
@@ -262,6 +302,31 @@ impl TestConfig {
         self.send(ClientMessage::SetIdle { timeout })
     }
 
+    pub fn set_fullscreen_inhibits_idle(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetFullscreenInhibitsIdle { enabled })
+    }
+
+    pub fn lock(&self) -> TestResult {
+        self.send(ClientMessage::Lock)
+    }
+
+    pub fn set_status_blocks(&self, status: &str, blocks: &[Range<usize>]) -> TestResult {
+        self.send(ClientMessage::SetStatusBlocks {
+            status,
+            blocks: blocks
+                .iter()
+                .map(|b| StatusBlock {
+                    start: b.start,
+                    end: b.end,
+                })
+                .collect(),
+        })
+    }
+
+    pub fn set_idle_stages(&self, stages: Vec<IdleStage>) -> TestResult {
+        self.send(ClientMessage::SetIdleStages { stages })
+    }
+
     pub fn set_floating(&self, seat: SeatId, floating: bool) -> TestResult {
         self.send(ClientMessage::SetFloating {
             seat: Seat(seat.raw() as _),
@@ -269,6 +334,130 @@ impl TestConfig {
         })
     }
 
+    pub fn set_color_scheme(&self, scheme: ColorScheme) -> TestResult {
+        self.send(ClientMessage::SetColorScheme { scheme })
+    }
+
+    pub fn set_high_contrast(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetHighContrast { enabled })
+    }
+
+    pub fn set_size(&self, sized: Resizable, size: i32) -> TestResult {
+        self.send(ClientMessage::SetSize { sized, size })
+    }
+
+    pub fn set_smart_gaps(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetSmartGaps { enabled })
+    }
+
+    pub fn set_window_opacity(&self, seat: SeatId, opacity: f64) -> TestResult {
+        self.send(ClientMessage::SetWindowOpacity {
+            seat: Seat(seat.raw() as _),
+            opacity,
+        })
+    }
+
+    pub fn set_blurred_app_ids(&self, app_ids: &[&str]) -> TestResult {
+        self.send(ClientMessage::SetBlurredAppIds {
+            app_ids: app_ids.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    pub fn set_blurred_layer_namespaces(&self, namespaces: &[&str]) -> TestResult {
+        self.send(ClientMessage::SetBlurredLayerNamespaces {
+            namespaces: namespaces.iter().map(|s| s.to_string()).collect(),
+        })
+    }
+
+    pub fn set_window_opacities(&self, opacities: &[(&str, f64)]) -> TestResult {
+        self.send(ClientMessage::SetWindowOpacities {
+            opacities: opacities
+                .iter()
+                .map(|(app_id, opacity)| (app_id.to_string(), *opacity))
+                .collect(),
+        })
+    }
+
+    pub fn set_sticky(&self, seat: SeatId, sticky: bool) -> TestResult {
+        self.send(ClientMessage::SetSticky {
+            seat: Seat(seat.raw() as _),
+            sticky,
+        })
+    }
+
+    pub fn mark(&self, seat: SeatId, mark: &str) -> TestResult {
+        self.send(ClientMessage::Mark {
+            seat: Seat(seat.raw() as _),
+            mark: mark.to_string(),
+        })
+    }
+
+    pub fn unmark(&self, seat: SeatId, mark: Option<&str>) -> TestResult {
+        self.send(ClientMessage::Unmark {
+            seat: Seat(seat.raw() as _),
+            mark: mark.map(|m| m.to_string()),
+        })
+    }
+
+    pub fn focus_mark(&self, seat: SeatId, mark: &str) -> TestResult {
+        self.send(ClientMessage::FocusMark {
+            seat: Seat(seat.raw() as _),
+            mark: mark.to_string(),
+        })
+    }
+
+    pub fn swap_with_mark(&self, seat: SeatId, mark: &str) -> TestResult {
+        self.send(ClientMessage::SwapWithMark {
+            seat: Seat(seat.raw() as _),
+            mark: mark.to_string(),
+        })
+    }
+
+    pub fn set_container_layout(&self, seat: SeatId, layout: ContainerLayout) -> TestResult {
+        self.send(ClientMessage::SetContainerLayout {
+            seat: Seat(seat.raw() as _),
+            layout,
+        })
+    }
+
+    pub fn set_workspace_layout(&self, seat: SeatId, layout: WorkspaceLayout) -> TestResult {
+        self.send(ClientMessage::SetWorkspaceLayout {
+            workspace: WorkspaceSource::Seat(Seat(seat.raw() as _)),
+            layout,
+        })
+    }
+
+    pub fn move_to_scratchpad(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::MoveToScratchpad {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn toggle_scratchpad(&self, seat: SeatId) -> TestResult {
+        self.send(ClientMessage::ToggleScratchpad {
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn set_animations_enabled(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetAnimationsEnabled { enabled })
+    }
+
+    pub fn set_animation_duration(&self, animation: Animation, duration: Duration) -> TestResult {
+        self.send(ClientMessage::SetAnimationDuration {
+            animation,
+            duration,
+        })
+    }
+
+    pub fn set_animation_curve(&self, animation: Animation, curve: AnimationCurve) -> TestResult {
+        self.send(ClientMessage::SetAnimationCurve { animation, curve })
+    }
+
+    pub fn reset_animations(&self) -> TestResult {
+        self.send(ClientMessage::ResetAnimations)
+    }
+
     fn clear(&self) {
         unsafe {
             if let Some(srv) = self.srv.take() {
@@ -24,7 +24,9 @@ pub mod test_input_method;
 pub mod test_input_method_keyboard_grab;
 pub mod test_input_method_manager;
 pub mod test_input_popup_surface;
+pub mod test_jay_appearance;
 pub mod test_jay_compositor;
+pub mod test_jay_global_shortcuts;
 pub mod test_keyboard;
 pub mod test_pointer;
 pub mod test_region;
@@ -45,6 +47,7 @@ pub mod test_text_input;
 pub mod test_text_input_manager;
 pub mod test_toplevel_drag;
 pub mod test_toplevel_drag_manager;
+pub mod test_touch;
 pub mod test_viewport;
 pub mod test_viewporter;
 pub mod test_virtual_keyboard;
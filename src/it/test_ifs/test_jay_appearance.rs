@@ -0,0 +1,68 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_appearance::*, JayAppearanceId},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TestAppearance {
+    pub color_scheme: u32,
+    pub accent_color: [f64; 3],
+    pub contrast: u32,
+}
+
+pub struct TestJayAppearance {
+    pub id: JayAppearanceId,
+    pub tran: Rc<TestTransport>,
+    pub pending: Cell<TestAppearance>,
+    pub done: TEEH<TestAppearance>,
+}
+
+impl TestJayAppearance {
+    fn handle_color_scheme(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = ColorScheme::parse_full(parser)?;
+        let mut pending = self.pending.get();
+        pending.color_scheme = ev.color_scheme;
+        self.pending.set(pending);
+        Ok(())
+    }
+
+    fn handle_accent_color(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = AccentColor::parse_full(parser)?;
+        let mut pending = self.pending.get();
+        pending.accent_color = [ev.r, ev.g, ev.b].map(|c| c.to_f64());
+        self.pending.set(pending);
+        Ok(())
+    }
+
+    fn handle_contrast(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Contrast::parse_full(parser)?;
+        let mut pending = self.pending.get();
+        pending.contrast = ev.contrast;
+        self.pending.set(pending);
+        Ok(())
+    }
+
+    fn handle_done(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let _ev = Done::parse_full(parser)?;
+        self.done.push(self.pending.get());
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayAppearance, JayAppearance;
+
+    COLOR_SCHEME => handle_color_scheme,
+    ACCENT_COLOR => handle_accent_color,
+    CONTRAST => handle_contrast,
+    DONE => handle_done,
+}
+
+impl TestObject for TestJayAppearance {}
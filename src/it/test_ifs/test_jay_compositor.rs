@@ -3,7 +3,11 @@ use {
         client::ClientId,
         it::{
             test_error::{TestError, TestResult},
-            test_ifs::test_screenshot::TestJayScreenshot,
+            test_ifs::{
+                test_jay_appearance::TestJayAppearance,
+                test_jay_global_shortcuts::TestJayGlobalShortcuts,
+                test_screenshot::TestJayScreenshot, test_seat::TestSeat,
+            },
             test_object::TestObject,
             test_transport::TestTransport,
             testrun::ParseFull,
@@ -49,6 +53,11 @@ impl TestJayCompositor {
         Ok(())
     }
 
+    pub fn unlock(&self) -> TestResult {
+        self.tran.send(Unlock { self_id: self.id })?;
+        Ok(())
+    }
+
     pub async fn take_screenshot(&self, include_cursor: bool) -> Result<Dmabuf, TestError> {
         let js = Rc::new(TestJayScreenshot {
             id: self.tran.id(),
@@ -68,6 +77,40 @@ impl TestJayCompositor {
         }
     }
 
+    pub fn create_global_shortcuts(
+        &self,
+        seat: &TestSeat,
+    ) -> Result<Rc<TestJayGlobalShortcuts>, TestError> {
+        let gs = Rc::new(TestJayGlobalShortcuts {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            activated: Default::default(),
+            deactivated: Default::default(),
+        });
+        self.tran.send(CreateGlobalShortcuts {
+            self_id: self.id,
+            id: gs.id,
+            seat: seat.id,
+        })?;
+        self.tran.add_obj(gs.clone())?;
+        Ok(gs)
+    }
+
+    pub fn watch_appearance(&self) -> Result<Rc<TestJayAppearance>, TestError> {
+        let wa = Rc::new(TestJayAppearance {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            pending: Default::default(),
+            done: Default::default(),
+        });
+        self.tran.send(WatchAppearance {
+            self_id: self.id,
+            id: wa.id,
+        })?;
+        self.tran.add_obj(wa.clone())?;
+        Ok(wa)
+    }
+
     fn handle_client_id(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
         let ev = jay_compositor::ClientId::parse_full(parser)?;
         self.client_id.set(Some(ClientId::from_raw(ev.client_id)));
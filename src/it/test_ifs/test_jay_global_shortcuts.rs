@@ -0,0 +1,59 @@
+use {
+    crate::{
+        it::{
+            test_error::TestResult, test_object::TestObject, test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+    },
+    std::rc::Rc,
+};
+
+pub struct TestJayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub tran: Rc<TestTransport>,
+    pub activated: TEEH<u32>,
+    pub deactivated: TEEH<u32>,
+}
+
+impl TestJayGlobalShortcuts {
+    pub fn bind(&self, id: u32, mods: u32, keysym: u32) -> TestResult {
+        self.tran.send(Bind {
+            self_id: self.id,
+            id,
+            mods,
+            keysym,
+        })?;
+        Ok(())
+    }
+
+    pub fn unbind(&self, id: u32) -> TestResult {
+        self.tran.send(Unbind {
+            self_id: self.id,
+            id,
+        })?;
+        Ok(())
+    }
+
+    fn handle_activated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Activated::parse_full(parser)?;
+        self.activated.push(ev.id);
+        Ok(())
+    }
+
+    fn handle_deactivated(&self, parser: MsgParser<'_, '_>) -> TestResult {
+        let ev = Deactivated::parse_full(parser)?;
+        self.deactivated.push(ev.id);
+        Ok(())
+    }
+}
+
+test_object! {
+    TestJayGlobalShortcuts, JayGlobalShortcuts;
+
+    ACTIVATED => handle_activated,
+    DEACTIVATED => handle_deactivated,
+}
+
+impl TestObject for TestJayGlobalShortcuts {}
@@ -74,6 +74,25 @@ mod t0039_alpha_modifier;
 mod t0040_virtual_keyboard;
 mod t0041_input_method;
 mod t0042_toplevel_select;
+mod t0043_global_shortcuts;
+mod t0044_appearance;
+mod t0045_gaps;
+mod t0046_window_effects;
+mod t0047_blur;
+mod t0048_animations;
+mod t0049_scratchpad;
+mod t0050_sticky;
+mod t0051_marks;
+mod t0052_container_layouts;
+mod t0053_workspace_layouts;
+mod t0054_keyboard_layouts;
+mod t0055_remember_keyboard_layout;
+mod t0056_idle_stages;
+mod t0057_fullscreen_inhibits_idle;
+mod t0058_builtin_lock;
+mod t0059_status_click;
+mod t0060_ignore_idle_inhibitors;
+mod t0061_touch;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -135,5 +154,24 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0040_virtual_keyboard,
         t0041_input_method,
         t0042_toplevel_select,
+        t0043_global_shortcuts,
+        t0044_appearance,
+        t0045_gaps,
+        t0046_window_effects,
+        t0047_blur,
+        t0048_animations,
+        t0049_scratchpad,
+        t0050_sticky,
+        t0051_marks,
+        t0052_container_layouts,
+        t0053_workspace_layouts,
+        t0054_keyboard_layouts,
+        t0055_remember_keyboard_layout,
+        t0056_idle_stages,
+        t0057_fullscreen_inhibits_idle,
+        t0058_builtin_lock,
+        t0059_status_click,
+        t0060_ignore_idle_inhibitors,
+        t0061_touch,
     }
 }
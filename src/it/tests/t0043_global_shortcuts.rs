@@ -0,0 +1,48 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::SYM_Escape,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+
+    let gs = client.jc.create_global_shortcuts(&seat.seat)?;
+    gs.bind(1, 0, SYM_Escape.0)?;
+    client.sync().await;
+
+    let key = seat.kb.key.expect()?;
+    let activated = gs.activated.expect()?;
+    let deactivated = gs.deactivated.expect()?;
+
+    let pressed = ds.kb.press(1);
+    client.sync().await;
+    tassert_eq!(activated.next()?, 1);
+    activated.none()?;
+    deactivated.none()?;
+
+    drop(pressed);
+    client.sync().await;
+    tassert_eq!(deactivated.next()?, 1);
+    deactivated.none()?;
+    key.none()?;
+
+    gs.unbind(1)?;
+    client.sync().await;
+
+    ds.kb.press(1);
+    client.sync().await;
+    activated.none()?;
+    deactivated.none()?;
+    tassert_eq!(key.next()?.1.state, 1);
+    tassert_eq!(key.next()?.1.state, 0);
+
+    Ok(())
+}
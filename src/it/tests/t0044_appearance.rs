@@ -0,0 +1,45 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        theme::{
+            COLOR_SCHEME_NO_PREFERENCE, COLOR_SCHEME_PREFER_DARK, COLOR_SCHEME_PREFER_LIGHT,
+            CONTRAST_HIGH, CONTRAST_NO_PREFERENCE,
+        },
+    },
+    jay_config::theme::ColorScheme,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let wa = client.jc.watch_appearance()?;
+    let done = wa.done.expect()?;
+    client.sync().await;
+
+    let appearance = done.next()?;
+    tassert_eq!(appearance.color_scheme, COLOR_SCHEME_PREFER_DARK);
+    tassert_eq!(appearance.contrast, CONTRAST_NO_PREFERENCE);
+    tassert!((appearance.accent_color[0] - 0x28 as f64 / 255.0).abs() < 0.01);
+    tassert!((appearance.accent_color[1] - 0x55 as f64 / 255.0).abs() < 0.01);
+    tassert!((appearance.accent_color[2] - 0x77 as f64 / 255.0).abs() < 0.01);
+    done.none()?;
+
+    run.cfg.set_color_scheme(ColorScheme::Light)?;
+    client.sync().await;
+    tassert_eq!(done.next()?.color_scheme, COLOR_SCHEME_PREFER_LIGHT);
+
+    run.cfg.set_color_scheme(ColorScheme::NoPreference)?;
+    run.cfg.set_high_contrast(true)?;
+    client.sync().await;
+    tassert_eq!(done.next()?.color_scheme, COLOR_SCHEME_NO_PREFERENCE);
+    let appearance = done.next()?;
+    tassert_eq!(appearance.color_scheme, COLOR_SCHEME_NO_PREFERENCE);
+    tassert_eq!(appearance.contrast, CONTRAST_HIGH);
+    done.none()?;
+
+    Ok(())
+}
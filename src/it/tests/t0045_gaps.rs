@@ -0,0 +1,58 @@
+use {
+    crate::{
+        it::{test_error::TestError, testrun::TestRun},
+        rect::Rect,
+        tree::Node,
+    },
+    jay_config::theme::sized::{INNER_GAP, OUTER_GAP},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test inner, outer, and smart gaps
+async fn test(run: Rc<TestRun>) -> Result<(), TestError> {
+    run.backend.install_default()?;
+
+    let client = run.create_client().await?;
+
+    run.cfg.set_size(INNER_GAP, 10)?;
+    run.cfg.set_size(OUTER_GAP, 5)?;
+
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    let bar = run.state.theme.sizes.title_height.get() + 1;
+    let otop = 2 * bar;
+    let bw = run.state.theme.sizes.border_width.get();
+
+    tassert_eq!(
+        window.tl.server.node_absolute_position(),
+        Rect::new_sized(5, otop + 5, 790, 600 - otop - 10).unwrap()
+    );
+
+    run.cfg.set_smart_gaps(true)?;
+    client.sync().await;
+
+    tassert_eq!(
+        window.tl.server.node_absolute_position(),
+        Rect::new_sized(0, otop, 800, 600 - otop).unwrap()
+    );
+
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+
+    let width = (790 - bw - 10) / 2;
+
+    tassert_eq!(
+        window.tl.server.node_absolute_position(),
+        Rect::new_sized(5, otop + 5, width, 600 - otop - 10).unwrap()
+    );
+
+    tassert_eq!(
+        window2.tl.server.node_absolute_position(),
+        Rect::new_sized(5 + width + bw + 10, otop + 5, width, 600 - otop - 10).unwrap()
+    );
+
+    Ok(())
+}
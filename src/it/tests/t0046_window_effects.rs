@@ -0,0 +1,63 @@
+use {
+    crate::{
+        gfx_api::RoundedRect,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    jay_config::theme::sized::{CORNER_RADIUS, SHADOW_SIZE},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test rounded corners, shadows, and window opacity
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let win = client.create_window().await?;
+    win.set_color(255, 0, 0, 255);
+    win.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+
+    run.cfg.set_size(CORNER_RADIUS, 8)?;
+    run.cfg.set_size(SHADOW_SIZE, 16)?;
+    client.sync().await;
+    tassert_eq!(run.state.theme.sizes.corner_radius.get(), 8);
+    tassert_eq!(run.state.theme.sizes.shadow_size.get(), 16);
+
+    let opacity = || win.tl.server.tl_data().opacity.get();
+    tassert_eq!(opacity(), None);
+    run.cfg.set_window_opacities(&[("", 0.5)])?;
+    client.sync().await;
+    tassert_eq!(
+        run.state.theme.window_opacities.borrow().get("").copied(),
+        Some(0.5)
+    );
+    client.compare_screenshot("1", false).await?;
+
+    run.cfg.set_window_opacity(ds.seat.id(), 0.75)?;
+    tassert_eq!(opacity(), Some(0.75));
+    run.cfg.set_window_opacity(ds.seat.id(), 2.0)?;
+    tassert_eq!(opacity(), Some(1.0));
+    run.cfg.set_window_opacity(ds.seat.id(), 0.25)?;
+    tassert_eq!(opacity(), Some(0.25));
+    client.compare_screenshot("2", false).await?;
+
+    let rect = RoundedRect {
+        x1: 0.0,
+        y1: 0.0,
+        x2: 100.0,
+        y2: 100.0,
+        radius: 10.0,
+        blur: 0.0,
+    };
+    tassert_eq!(rect.coverage(50.5, 50.5), 1.0);
+    tassert_eq!(rect.coverage(50.5, 0.5), 1.0);
+    tassert_eq!(rect.coverage(0.5, 0.5), 0.0);
+    tassert_eq!(rect.coverage(99.5, 99.5), 0.0);
+    tassert_eq!(rect.coverage(150.5, 50.5), 0.0);
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use {
+    crate::{
+        gfx_api::{Blur, RoundedRect},
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    jay_config::theme::sized::{BLUR_OFFSET, BLUR_PASSES},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test background blur
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let win = client.create_window().await?;
+    win.set_color(255, 0, 0, 128);
+    win.map2().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+
+    run.cfg.set_size(BLUR_PASSES, 2)?;
+    run.cfg.set_size(BLUR_OFFSET, 4)?;
+    run.cfg.set_blurred_app_ids(&["", "firefox"])?;
+    run.cfg.set_blurred_layer_namespaces(&["waybar"])?;
+    client.sync().await;
+    tassert_eq!(run.state.theme.sizes.blur_passes.get(), 2);
+    tassert_eq!(run.state.theme.sizes.blur_offset.get(), 4);
+    tassert!(run.state.theme.blur_app_ids.borrow().contains("firefox"));
+    tassert!(run
+        .state
+        .theme
+        .blur_layer_namespaces
+        .borrow()
+        .contains("waybar"));
+
+    let cache = win.tl.server.tl_data().blur_cache.clone();
+    client.take_screenshot(false).await?;
+    tassert!(cache.produced.get().is_some());
+    client.take_screenshot(false).await?;
+    tassert!(cache.valid.get());
+
+    // Commits of the blurred window itself do not change the area behind it.
+    win.set_color(0, 255, 0, 128);
+    win.map().await?;
+    client.take_screenshot(false).await?;
+    tassert!(cache.valid.get());
+
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    client.take_screenshot(false).await?;
+    tassert!(!cache.valid.get());
+
+    run.cfg.set_blurred_app_ids(&[])?;
+    tassert!(run.state.theme.blur_app_ids.borrow().is_empty());
+
+    let blur = Blur {
+        rect: RoundedRect {
+            x1: -10.5,
+            y1: 20.5,
+            x2: 99.5,
+            y2: 300.0,
+            radius: 0.0,
+            blur: 0.0,
+        },
+        passes: 8,
+        offset: 3.0,
+        cache: None,
+    };
+    tassert_eq!(blur.bounds(200, 200), Some([0, 20, 100, 200]));
+    tassert_eq!(blur.bounds(0, 0), None);
+    tassert_eq!(blur.effective_passes(100, 180), 7);
+    tassert_eq!(blur.effective_passes(1000, 1000), 8);
+    tassert_eq!(Blur::level_size(100, 180, 3), (12, 22));
+    tassert_eq!(Blur::level_size(100, 180, 8), (1, 1));
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use {
+    crate::{
+        animation::Curve,
+        it::{test_error::TestResult, testrun::TestRun},
+        time::now_usec,
+        tree::ToplevelNodeBase,
+    },
+    jay_config::animation::{AnimationCurve, LAYOUT, WINDOW_OPEN},
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test animations
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let animations = &run.state.animations;
+
+    run.cfg.set_animations_enabled(true)?;
+    run.cfg
+        .set_animation_duration(LAYOUT, Duration::from_secs(60))?;
+    run.cfg
+        .set_animation_duration(WINDOW_OPEN, Duration::from_secs(60))?;
+    run.cfg
+        .set_animation_curve(LAYOUT, AnimationCurve::Linear)?;
+    tassert_eq!(animations.layout.duration_usec.get(), 60_000_000);
+    tassert_eq!(animations.layout.curve.get(), Curve::LINEAR);
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    tassert!(win1.tl.server.tl_data().open_animation.get().is_some());
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    client.sync().await;
+
+    let container = win1.tl.container_parent()?;
+    let child = container.children.iter().next().unwrap();
+    tassert!(child.layout_animation.get().is_some());
+
+    let animation = animations.start(&animations.layout).unwrap();
+    let progress = animation.progress(now_usec()).unwrap();
+    tassert!((0.0..0.1).contains(&progress));
+    tassert!(animation.progress(now_usec() + 60_000_000).is_none());
+
+    run.cfg.set_animations_enabled(false)?;
+    tassert!(animations.start(&animations.layout).is_none());
+    let win3 = client.create_window().await?;
+    win3.map2().await?;
+    tassert!(win3.tl.server.tl_data().open_animation.get().is_none());
+
+    run.cfg.reset_animations()?;
+    tassert!(animations.enabled.get());
+    tassert_eq!(animations.layout.duration_usec.get(), 150_000);
+    tassert_eq!(animations.layout.curve.get(), Curve::EASE_OUT);
+
+    for curve in [
+        Curve::LINEAR,
+        Curve::EASE_IN,
+        Curve::EASE_OUT,
+        Curve::EASE_IN_OUT,
+        Curve::new(0.2, 0.8, 0.2, 1.0),
+    ] {
+        tassert_eq!(curve.apply(0.0), 0.0);
+        tassert_eq!(curve.apply(1.0), 1.0);
+        let mut prev = 0.0;
+        for i in 1..100 {
+            let y = curve.apply(i as f64 / 100.0);
+            tassert!(y >= prev);
+            prev = y;
+        }
+    }
+    tassert!((Curve::LINEAR.apply(0.25) - 0.25).abs() < 1e-6);
+    tassert!(Curve::EASE_IN.apply(0.5) < 0.5);
+    tassert!(Curve::EASE_OUT.apply(0.5) > 0.5);
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test the scratchpad
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let in_scratchpad = |id| {
+        run.state
+            .scratchpad
+            .iter()
+            .map(|tl| tl.node_id())
+            .collect::<Vec<_>>()
+            == id
+    };
+
+    run.cfg.move_to_scratchpad(seat)?;
+    client.sync().await;
+    tassert!(!win2.tl.server.node_visible());
+    tassert!(win2.tl.server.tl_data().parent.get().is_none());
+    tassert!(win1.tl.server.node_visible());
+    tassert!(in_scratchpad(vec![win2.tl.server.node_id()]));
+
+    run.cfg.toggle_scratchpad(seat)?;
+    client.sync().await;
+    tassert!(win2.tl.server.node_visible());
+    tassert!(win2.tl.server.tl_data().is_floating.get());
+    let float = win2.tl.float_parent()?;
+    let output = ds.output.global.pos.get();
+    let pos = float.position.get();
+    tassert!((pos.x1() - output.x1() - (output.x2() - pos.x2())).abs() <= 1);
+    tassert!((pos.y1() - output.y1() - (output.y2() - pos.y2())).abs() <= 1);
+
+    run.cfg.toggle_scratchpad(seat)?;
+    client.sync().await;
+    tassert!(!win2.tl.server.node_visible());
+
+    run.cfg.move_to_scratchpad(seat)?;
+    client.sync().await;
+    tassert!(!win1.tl.server.node_visible());
+    tassert!(in_scratchpad(vec![
+        win2.tl.server.node_id(),
+        win1.tl.server.node_id()
+    ]));
+
+    run.cfg.toggle_scratchpad(seat)?;
+    client.sync().await;
+    tassert!(win2.tl.server.node_visible());
+    tassert!(!win1.tl.server.node_visible());
+
+    run.cfg.toggle_scratchpad(seat)?;
+    run.cfg.toggle_scratchpad(seat)?;
+    client.sync().await;
+    tassert!(!win2.tl.server.node_visible());
+    tassert!(win1.tl.server.node_visible());
+
+    run.cfg.set_floating(seat, false)?;
+    client.sync().await;
+    tassert!(win1.tl.server.node_visible());
+    tassert!(in_scratchpad(vec![win2.tl.server.node_id()]));
+
+    Ok(())
+}
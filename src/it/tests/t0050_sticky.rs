@@ -0,0 +1,43 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that sticky floating windows follow workspace switches
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    run.cfg.set_floating(seat, true)?;
+    run.cfg.set_sticky(seat, true)?;
+    client.sync().await;
+
+    run.cfg.show_workspace(seat, "2")?;
+    client.sync().await;
+    tassert!(!win1.tl.server.node_visible());
+    tassert!(win2.tl.server.node_visible());
+    tassert_eq!(win2.tl.float_parent()?.workspace.get().name.clone(), "2");
+
+    run.cfg.show_workspace(seat, "")?;
+    client.sync().await;
+    tassert!(win1.tl.server.node_visible());
+    tassert!(win2.tl.server.node_visible());
+    tassert!(run.state.workspaces.get("2").is_none());
+
+    run.cfg.set_sticky(seat, false)?;
+    run.cfg.show_workspace(seat, "3")?;
+    client.sync().await;
+    tassert!(!win2.tl.server.node_visible());
+
+    Ok(())
+}
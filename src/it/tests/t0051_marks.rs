@@ -0,0 +1,62 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test window marks
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    run.cfg.mark(seat, "b")?;
+    run.cfg.mark(seat, "c")?;
+    client.sync().await;
+    let data2 = win2.tl.server.tl_data();
+    tassert_eq!(data2.marks.borrow().clone(), ["b", "c"]);
+    tassert_eq!(data2.marked_title("title"), "[b] [c] title");
+    tassert!(run.state.marks.get("b").is_some());
+
+    run.cfg.show_workspace(seat, "2")?;
+    client.sync().await;
+    tassert!(!win2.tl.server.node_visible());
+
+    run.cfg.focus_mark(seat, "b")?;
+    client.sync().await;
+    tassert!(win2.tl.server.node_visible());
+    tassert!(data2.active());
+
+    run.cfg.focus(seat, Direction::Left)?;
+    client.sync().await;
+    tassert!(win1.tl.server.tl_data().active());
+    tassert!(win1.tl.server.tl_data().pos.get().x1() < data2.pos.get().x1());
+    run.cfg.swap_with_mark(seat, "b")?;
+    client.sync().await;
+    tassert!(win1.tl.server.tl_data().pos.get().x1() > data2.pos.get().x1());
+    tassert!(win1.tl.server.tl_data().active());
+
+    run.cfg.mark(seat, "c")?;
+    client.sync().await;
+    tassert_eq!(data2.marks.borrow().clone(), ["b"]);
+    tassert_eq!(win1.tl.server.tl_data().marks.borrow().clone(), ["c"]);
+
+    run.cfg.unmark(seat, Some("b"))?;
+    run.cfg.unmark(seat, None)?;
+    client.sync().await;
+    tassert!(data2.marks.borrow().is_empty());
+    tassert!(win1.tl.server.tl_data().marks.borrow().is_empty());
+    tassert!(run.state.marks.is_empty());
+
+    Ok(())
+}
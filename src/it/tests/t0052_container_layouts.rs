@@ -0,0 +1,77 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{ContainerSplit, Node, ToplevelNodeBase},
+    },
+    jay_config::ContainerLayout,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test tabbed and stacked container layouts
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+
+    let container = match win2.tl.server.tl_data().parent.get() {
+        Some(p) => match p.node_into_container() {
+            Some(p) => p,
+            _ => bail!("Containing node is not a container"),
+        },
+        _ => bail!("Toplevel doesn't have a parent"),
+    };
+    let th = run.state.theme.sizes.title_height.get();
+
+    run.cfg
+        .set_container_layout(seat, ContainerLayout::Stacked)?;
+    client.sync().await;
+    tassert!(container.mono_child.is_some());
+    tassert!(container.stacked.get());
+    tassert!(!win1.tl.server.node_visible());
+    tassert!(win2.tl.server.node_visible());
+    let titles: Vec<_> = container
+        .children
+        .iter()
+        .map(|c| c.title_rect.get())
+        .collect();
+    tassert_eq!(titles[0].y1(), 0);
+    tassert_eq!(titles[1].y1(), th + 1);
+    tassert_eq!(titles[1].width(), container.width.get());
+    tassert_eq!(container.mono_body.get().y1(), 2 * (th + 1));
+
+    let (x, y) = container.node_absolute_position().position();
+    ds.move_to(x + titles[0].x1() + 10, y + titles[0].y1() + 1);
+    ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    tassert!(win1.tl.server.node_visible());
+    tassert!(!win2.tl.server.node_visible());
+    tassert!(win1.tl.server.tl_data().active());
+
+    run.cfg
+        .set_container_layout(seat, ContainerLayout::Tabbed)?;
+    client.sync().await;
+    tassert!(container.mono_child.is_some());
+    tassert!(!container.stacked.get());
+    for child in container.children.iter() {
+        tassert_eq!(child.title_rect.get().y1(), 0);
+    }
+    tassert_eq!(container.mono_body.get().y1(), th + 1);
+
+    run.cfg
+        .set_container_layout(seat, ContainerLayout::SplitVertical)?;
+    client.sync().await;
+    tassert!(container.mono_child.is_none());
+    tassert_eq!(container.split.get(), ContainerSplit::Vertical);
+    tassert!(win1.tl.server.node_visible());
+    tassert!(win2.tl.server.node_visible());
+
+    Ok(())
+}
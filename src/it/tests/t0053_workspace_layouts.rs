@@ -0,0 +1,89 @@
+use {
+    crate::{
+        it::{test_error::TestResult, test_utils::test_window::TestWindow, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    jay_config::{Direction, WorkspaceLayout},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test automatic workspace layouts
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.tl.core.set_title("1")?;
+    win1.map2().await?;
+
+    let ws = match win1.tl.server.tl_data().workspace.get() {
+        Some(ws) => ws,
+        _ => bail!("Window has no workspace"),
+    };
+    let shape = || match ws.container.get() {
+        Some(c) => c.tl_data().title.borrow().clone(),
+        _ => String::new(),
+    };
+
+    run.cfg
+        .set_workspace_layout(seat, WorkspaceLayout::MasterStack)?;
+    let win2 = client.create_window().await?;
+    win2.tl.core.set_title("2")?;
+    win2.map2().await?;
+    let win3 = client.create_window().await?;
+    win3.tl.core.set_title("3")?;
+    win3.map2().await?;
+    client.sync().await;
+    tassert_eq!(shape(), "H[1, V[2, 3]]");
+
+    run.cfg
+        .set_workspace_layout(seat, WorkspaceLayout::Columns)?;
+    client.sync().await;
+    tassert_eq!(shape(), "H[1, 2, 3]");
+
+    run.cfg
+        .set_workspace_layout(seat, WorkspaceLayout::Dwindle)?;
+    client.sync().await;
+    tassert_eq!(shape(), "H[1, V[2, 3]]");
+    let win4 = client.create_window().await?;
+    win4.tl.core.set_title("4")?;
+    win4.map2().await?;
+    client.sync().await;
+    tassert_eq!(shape(), "H[1, V[2, H[3, 4]]]");
+    tassert!(win4.tl.server.tl_data().active());
+
+    run.cfg.focus(seat, Direction::Up)?;
+    run.cfg.set_floating(seat, true)?;
+    client.sync().await;
+    tassert!(win2.tl.server.tl_data().is_floating.get());
+    tassert_eq!(shape(), "H[1, V[3, 4]]");
+
+    run.cfg
+        .set_workspace_layout(seat, WorkspaceLayout::ScrollingColumns)?;
+    client.sync().await;
+    tassert_eq!(shape(), "H[1, 3, 4]");
+    let win5 = client.create_window().await?;
+    win5.tl.core.set_title("5")?;
+    win5.map2().await?;
+    client.sync().await;
+    tassert_eq!(shape(), "H[1, 3, 4, 5]");
+    tassert!(win5.tl.server.tl_data().active());
+    let visible = ws.position.get();
+    let pos = |win: &TestWindow| win.tl.server.node_absolute_position();
+    tassert!(pos(&win1).x1() < visible.x1());
+    tassert!(pos(&win5).x2() <= visible.x2());
+    tassert!(pos(&win5).x1() >= visible.x1());
+
+    for _ in 0..3 {
+        run.cfg.focus(seat, Direction::Left)?;
+    }
+    client.sync().await;
+    tassert!(win1.tl.server.tl_data().active());
+    tassert!(pos(&win1).x1() >= visible.x1());
+    tassert!(pos(&win5).x1() >= visible.x2());
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::keyboard::syms::{SYM_F13, SYM_F14},
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test switching between the layouts of a keymap
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9; # ESC
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        name[Group1] = "one";
+        name[Group2] = "two";
+        key <1> { symbols[Group1] = [ F13 ], symbols[Group2] = [ F14 ] };
+    };
+};
+    "#;
+
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(seat, keymap)?;
+    run.cfg.add_shortcut(seat, SYM_F13)?;
+    run.cfg.add_shortcut(seat, SYM_F14)?;
+    run.sync().await;
+
+    tassert_eq!(ds.seat.keyboard_layout().index, 0);
+    tassert_eq!(ds.seat.keyboard_layout().name, "one");
+
+    run.cfg.set_keyboard_layout(seat, 1)?;
+    run.sync().await;
+    tassert_eq!(ds.seat.keyboard_layout().name, "two");
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert!(run.cfg.invoked_shortcuts.contains(&(seat, SYM_F14.into())));
+    tassert!(!run.cfg.invoked_shortcuts.contains(&(seat, SYM_F13.into())));
+
+    run.cfg.next_keyboard_layout(seat)?;
+    run.sync().await;
+    tassert_eq!(ds.seat.keyboard_layout().index, 0);
+
+    run.cfg.set_keyboard_layout(seat, 2)?;
+    run.sync().await;
+    tassert_eq!(ds.seat.keyboard_layout().index, 0);
+
+    Ok(())
+}
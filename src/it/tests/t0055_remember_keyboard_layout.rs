@@ -0,0 +1,68 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the keyboard layout is remembered per window
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let seat = ds.seat.id();
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9; # ESC
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { symbols[Group1] = [ F13 ], symbols[Group2] = [ F14 ] };
+    };
+};
+    "#;
+
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(seat, keymap)?;
+    run.cfg.set_remember_keyboard_layout(seat, true)?;
+    run.sync().await;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    tassert!(win2.tl.server.tl_data().active());
+
+    run.cfg.set_keyboard_layout(seat, 1)?;
+    client.sync().await;
+    tassert_eq!(ds.seat.keyboard_layout().index, 1);
+
+    // Key presses make the seat keymap the latest keyboard state
+    ds.kb.press(1);
+    run.sync().await;
+
+    run.cfg.focus(seat, Direction::Left)?;
+    client.sync().await;
+    tassert!(win1.tl.server.tl_data().active());
+    tassert_eq!(ds.seat.keyboard_layout().index, 0);
+
+    run.cfg.focus(seat, Direction::Right)?;
+    client.sync().await;
+    tassert!(win2.tl.server.tl_data().active());
+    tassert_eq!(ds.seat.keyboard_layout().index, 1);
+
+    run.cfg.set_remember_keyboard_layout(seat, false)?;
+    run.cfg.focus(seat, Direction::Left)?;
+    client.sync().await;
+    tassert_eq!(ds.seat.keyboard_layout().index, 1);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use {
+    crate::it::{
+        test_error::{TestErrorExt, TestResult},
+        testrun::TestRun,
+    },
+    jay_config::IdleStage,
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test that idle stages are entered in order and resumed on input
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.set_idle_stages(vec![
+        IdleStage {
+            name: "off".to_string(),
+            timeout: Duration::from_micros(200),
+            power_off_outputs: true,
+            suspend: true,
+            ..Default::default()
+        },
+        IdleStage {
+            name: "dim".to_string(),
+            timeout: Duration::from_micros(100),
+            dim_outputs: true,
+            ..Default::default()
+        },
+    ])?;
+
+    let idle = run.backend.idle.expect()?;
+    let suspend = run.backend.suspend.expect()?;
+
+    run.state.wheel.timeout(3).await?;
+
+    tassert!(run.state.idle.dimmed.get());
+    tassert_eq!(idle.next().with_context(|| "idle")?, true);
+    tassert!(idle.next().is_err());
+    suspend.next().with_context(|| "suspend")?;
+    tassert_eq!(
+        run.cfg.idle_stages.take(),
+        vec![("dim".to_string(), true), ("off".to_string(), true)]
+    );
+
+    ds.mouse.rel(1.0, 1.0);
+    run.state.eng.yield_now().await;
+
+    tassert!(!run.state.idle.dimmed.get());
+    tassert_eq!(idle.next().with_context(|| "wake")?, false);
+    tassert_eq!(
+        run.cfg.idle_stages.take(),
+        vec![("off".to_string(), false), ("dim".to_string(), false)]
+    );
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+use {
+    crate::it::{
+        test_error::{TestErrorExt, TestResult},
+        testrun::TestRun,
+    },
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test that a visible fullscreen window inhibits idling if configured
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    run.cfg.set_fullscreen_inhibits_idle(true)?;
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    run.cfg.set_idle(Duration::from_micros(100))?;
+
+    let idle = run.backend.idle.expect()?;
+
+    run.state.wheel.timeout(3).await?;
+    tassert!(idle.next().is_err());
+
+    run.cfg.set_fullscreen(ds.seat.id(), false)?;
+
+    run.state.wheel.timeout(3).await?;
+    tassert_eq!(idle.next().with_context(|| "idle")?, true);
+
+    Ok(())
+}
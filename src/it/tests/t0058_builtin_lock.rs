@@ -0,0 +1,43 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        time::now_usec,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the built-in lock screen captures keyboard input until the screen is unlocked
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    run.cfg.lock()?;
+    run.sync().await;
+    tassert!(run.state.lock.locked.get());
+    tassert!(run.state.lock.builtin.is_some());
+    // The test backend does not present frames
+    tassert!(!run.state.is_fully_locked());
+    ds.output.frame_presented(now_usec());
+    tassert!(!run.state.is_fully_locked());
+    ds.output.frame_submitted();
+    ds.output.frame_presented(now_usec());
+    tassert!(run.state.is_fully_locked());
+
+    let keys = seat.kb.key.expect()?;
+    ds.kb.press(30);
+    client.sync().await;
+    tassert!(keys.next().is_err());
+
+    client.jc.unlock()?;
+    client.sync().await;
+    tassert!(!run.state.lock.locked.get());
+    tassert!(run.state.lock.builtin.is_none());
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use {
+    crate::{
+        ifs::wl_seat::{BTN_LEFT, BTN_RIGHT},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that clicks on the blocks of an i3bar status are reported to the config
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.set_status_blocks("aaaa bbbb", &[0..4, 5..9])?;
+    run.sync().await;
+
+    let blocks = match &ds.output.render_data.borrow().status {
+        Some(status) => status.blocks.clone(),
+        _ => bail!("Output has no status"),
+    };
+    tassert_eq!(blocks.len(), 2);
+    let (x1, x2) = blocks[1];
+    tassert!(blocks[0].1 < x1);
+    tassert!(x1 < x2);
+
+    let pos = ds.output.global.pos.get();
+    let height = run.state.theme.sizes.title_height.get();
+    let x = (x1 + x2) / 2;
+    ds.mouse.abs(&ds.connector, x as f64, 1.0);
+    ds.mouse.click(BTN_RIGHT);
+    run.sync().await;
+    let clicks = run.cfg.status_clicks.take();
+    tassert_eq!(clicks.len(), 1);
+    let click = &clicks[0];
+    tassert_eq!(click.block, 1);
+    tassert_eq!(click.button, 3);
+    tassert_eq!(click.mods.0, 0);
+    tassert_eq!((click.x, click.y), (pos.x1() + x, pos.y1() + 1));
+    tassert_eq!((click.output_x, click.output_y), (x, 1));
+    tassert_eq!((click.relative_x, click.relative_y), (x - x1, 1));
+    tassert_eq!((click.width, click.height), (x2 - x1, height));
+
+    ds.mouse.abs(&ds.connector, blocks[0].0 as f64, 1.0);
+    ds.mouse.scroll(1);
+    run.sync().await;
+    let clicks = run.cfg.status_clicks.take();
+    tassert_eq!(clicks.len(), 1);
+    tassert_eq!(clicks[0].block, 0);
+    tassert_eq!(clicks[0].button, 5);
+    tassert_eq!(clicks[0].relative_x, 0);
+
+    // Between the blocks
+    ds.mouse
+        .abs(&ds.connector, (blocks[0].1 + x1) as f64 / 2.0, 1.0);
+    ds.mouse.click(BTN_LEFT);
+    // Below the bar
+    ds.mouse.abs(&ds.connector, x as f64, (height + 1) as f64);
+    ds.mouse.click(BTN_LEFT);
+    run.sync().await;
+    tassert!(run.cfg.status_clicks.take().is_empty());
+
+    Ok(())
+}
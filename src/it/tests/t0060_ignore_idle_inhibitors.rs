@@ -0,0 +1,38 @@
+use {
+    crate::it::{
+        test_error::{TestErrorExt, TestResult},
+        testrun::TestRun,
+    },
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test that ignored idle inhibitors are respected again after the timeout or on resume
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let idle_state = &run.state.idle;
+
+    idle_state.set_inhibitors_ignored(true, Some(Duration::from_micros(100)));
+    run.state.eng.yield_now().await;
+    tassert!(idle_state.inhibitors_ignored.get());
+    run.state.wheel.timeout(3).await?;
+    tassert!(!idle_state.inhibitors_ignored.get());
+
+    idle_state.set_inhibitors_ignored(true, None);
+    run.cfg.set_idle(Duration::from_micros(100))?;
+
+    let idle = run.backend.idle.expect()?;
+
+    run.state.wheel.timeout(3).await?;
+    tassert_eq!(idle.next().with_context(|| "idle")?, true);
+    tassert!(idle_state.inhibitors_ignored.get());
+
+    ds.mouse.rel(1.0, 1.0);
+    run.state.eng.yield_now().await;
+
+    tassert_eq!(idle.next().with_context(|| "wake")?, false);
+    tassert!(!idle_state.inhibitors_ignored.get());
+
+    Ok(())
+}
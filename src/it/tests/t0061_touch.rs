@@ -0,0 +1,82 @@
+use {
+    crate::{
+        fixed::Fixed,
+        ifs::wl_seat::TOUCH,
+        it::{test_error::TestResult, test_utils::test_rect_ext::TestRectExt, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that touch points are delivered to the surface that received the touch down
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let win1 = client.create_window().await?;
+    win1.map2().await?;
+    let win2 = client.create_window().await?;
+    win2.map2().await?;
+    tassert!(win2.tl.server.tl_data().active());
+
+    let seat = client.get_default_seat().await?;
+    tassert!(seat.seat.caps.get() & TOUCH != 0);
+    let touch = seat.seat.get_touch().await?;
+    let down = touch.down.expect()?;
+    let motion = touch.motion.expect()?;
+    let up = touch.up.expect()?;
+    let frame = touch.frame.expect()?;
+
+    let output = ds.output.global.pos.get();
+    let surface = win1.surface.server.buffer_abs_pos.get();
+    let (x, y) = win1.tl.server.node_absolute_position().center();
+    ds.mouse.touch_down(
+        &ds.connector,
+        3,
+        (x - output.x1()) as f64,
+        (y - output.y1()) as f64,
+    );
+    client.sync().await;
+    let ev = down.next()?;
+    tassert_eq!(ev.surface, win1.surface.id);
+    tassert_eq!(ev.id, 3);
+    tassert_eq!(
+        (ev.x, ev.y),
+        (
+            Fixed::from_int(x - surface.x1()),
+            Fixed::from_int(y - surface.y1())
+        )
+    );
+    tassert!(frame.next().is_ok());
+    tassert!(win1.tl.server.tl_data().active());
+
+    // Motion is delivered to the same surface even if it leaves the surface
+    let (x2, _) = win2.tl.server.node_absolute_position().center();
+    ds.mouse.touch_motion(
+        &ds.connector,
+        3,
+        (x2 - output.x1()) as f64,
+        (y - output.y1()) as f64,
+    );
+    client.sync().await;
+    let ev = motion.next()?;
+    tassert_eq!(ev.id, 3);
+    tassert_eq!(ev.x, Fixed::from_int(x2 - surface.x1()));
+    tassert!(frame.next().is_ok());
+
+    ds.mouse.touch_up(3);
+    client.sync().await;
+    tassert_eq!(up.next()?.id, 3);
+    tassert!(frame.next().is_ok());
+
+    // Unknown touch points are ignored
+    ds.mouse.touch_motion(&ds.connector, 3, 0.0, 0.0);
+    ds.mouse.touch_up(3);
+    client.sync().await;
+    motion.none()?;
+    up.none()?;
+
+    Ok(())
+}
@@ -165,6 +165,15 @@ cenum! {
     LIBINPUT_CONFIG_ACCEL_PROFILE_NONE = 0,
     LIBINPUT_CONFIG_ACCEL_PROFILE_FLAT = 1 << 0,
     LIBINPUT_CONFIG_ACCEL_PROFILE_ADAPTIVE = 1 << 1,
+    LIBINPUT_CONFIG_ACCEL_PROFILE_CUSTOM = 1 << 2,
+}
+
+cenum! {
+    ConfigAccelType, LIBINPUT_CONFIG_ACCEL_TYPE;
+
+    LIBINPUT_ACCEL_TYPE_FALLBACK = 0,
+    LIBINPUT_ACCEL_TYPE_MOTION = 1,
+    LIBINPUT_ACCEL_TYPE_SCROLL = 2,
 }
 
 cenum! {
@@ -187,3 +196,49 @@ cenum! {
     LIBINPUT_CONFIG_DRAG_LOCK_DISABLED = 0,
     LIBINPUT_CONFIG_DRAG_LOCK_ENABLED = 1,
 }
+
+cenum! {
+    ConfigClickMethod, LIBINPUT_CONFIG_CLICK_METHOD;
+
+    LIBINPUT_CONFIG_CLICK_METHOD_NONE = 0,
+    LIBINPUT_CONFIG_CLICK_METHOD_BUTTON_AREAS = 1 << 0,
+    LIBINPUT_CONFIG_CLICK_METHOD_CLICKFINGER = 1 << 1,
+}
+
+cenum! {
+    ConfigMiddleEmulationState, LIBINPUT_CONFIG_MIDDLE_EMULATION_STATE;
+
+    LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED = 0,
+    LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED = 1,
+}
+
+cenum! {
+    ConfigScrollMethod, LIBINPUT_CONFIG_SCROLL_METHOD;
+
+    LIBINPUT_CONFIG_SCROLL_NO_SCROLL = 0,
+    LIBINPUT_CONFIG_SCROLL_2FG = 1 << 0,
+    LIBINPUT_CONFIG_SCROLL_EDGE = 1 << 1,
+    LIBINPUT_CONFIG_SCROLL_ON_BUTTON_DOWN = 1 << 2,
+}
+
+cenum! {
+    ConfigDwtState, LIBINPUT_CONFIG_DWT_STATE;
+
+    LIBINPUT_CONFIG_DWT_DISABLED = 0,
+    LIBINPUT_CONFIG_DWT_ENABLED = 1,
+}
+
+cenum! {
+    ConfigTapButtonMap, LIBINPUT_CONFIG_TAP_BUTTON_MAP;
+
+    LIBINPUT_CONFIG_TAP_MAP_LRM = 0,
+    LIBINPUT_CONFIG_TAP_MAP_LMR = 1,
+}
+
+cenum! {
+    ConfigSendEventsMode, LIBINPUT_CONFIG_SEND_EVENTS_MODE;
+
+    LIBINPUT_CONFIG_SEND_EVENTS_ENABLED = 0,
+    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED = 1 << 0,
+    LIBINPUT_CONFIG_SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE = 1 << 1,
+}
@@ -1,39 +1,59 @@
 use {
     crate::libinput::{
         consts::{
-            AccelProfile, ConfigDragLockState, ConfigDragState, ConfigTapState, DeviceCapability,
+            AccelProfile, ConfigAccelType, ConfigClickMethod, ConfigDragLockState, ConfigDragState,
+            ConfigDwtState, ConfigMiddleEmulationState, ConfigScrollMethod, ConfigSendEventsMode,
+            ConfigStatus, ConfigTapButtonMap, ConfigTapState, DeviceCapability,
             LIBINPUT_CONFIG_DRAG_DISABLED, LIBINPUT_CONFIG_DRAG_ENABLED,
             LIBINPUT_CONFIG_DRAG_LOCK_DISABLED, LIBINPUT_CONFIG_DRAG_LOCK_ENABLED,
+            LIBINPUT_CONFIG_DWT_DISABLED, LIBINPUT_CONFIG_DWT_ENABLED,
+            LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED, LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED,
             LIBINPUT_CONFIG_TAP_DISABLED, LIBINPUT_CONFIG_TAP_ENABLED,
         },
         sys::{
-            libinput_device, libinput_device_config_accel_get_profile,
+            libinput_config_accel, libinput_device, libinput_device_config_accel_get_profile,
             libinput_device_config_accel_get_speed, libinput_device_config_accel_is_available,
             libinput_device_config_accel_set_profile, libinput_device_config_accel_set_speed,
-            libinput_device_config_left_handed_get,
+            libinput_device_config_calibration_get_matrix,
+            libinput_device_config_calibration_has_matrix,
+            libinput_device_config_calibration_set_matrix, libinput_device_config_click_get_method,
+            libinput_device_config_click_get_methods, libinput_device_config_click_set_method,
+            libinput_device_config_dwt_get_enabled, libinput_device_config_dwt_is_available,
+            libinput_device_config_dwt_set_enabled, libinput_device_config_left_handed_get,
             libinput_device_config_left_handed_is_available,
             libinput_device_config_left_handed_set,
+            libinput_device_config_middle_emulation_get_enabled,
+            libinput_device_config_middle_emulation_is_available,
+            libinput_device_config_middle_emulation_set_enabled,
+            libinput_device_config_rotation_get_angle,
+            libinput_device_config_rotation_is_available,
+            libinput_device_config_rotation_set_angle, libinput_device_config_scroll_get_button,
+            libinput_device_config_scroll_get_method, libinput_device_config_scroll_get_methods,
             libinput_device_config_scroll_get_natural_scroll_enabled,
             libinput_device_config_scroll_has_natural_scroll,
+            libinput_device_config_scroll_set_button, libinput_device_config_scroll_set_method,
             libinput_device_config_scroll_set_natural_scroll_enabled,
+            libinput_device_config_send_events_get_mode,
+            libinput_device_config_send_events_set_mode, libinput_device_config_tap_get_button_map,
             libinput_device_config_tap_get_drag_enabled,
             libinput_device_config_tap_get_drag_lock_enabled,
             libinput_device_config_tap_get_enabled, libinput_device_config_tap_get_finger_count,
-            libinput_device_config_tap_set_drag_enabled,
+            libinput_device_config_tap_set_button_map, libinput_device_config_tap_set_drag_enabled,
             libinput_device_config_tap_set_drag_lock_enabled,
             libinput_device_config_tap_set_enabled, libinput_device_get_device_group,
             libinput_device_get_id_product, libinput_device_get_id_vendor,
-            libinput_device_get_name, libinput_device_get_user_data, libinput_device_group,
-            libinput_device_group_get_user_data, libinput_device_group_set_user_data,
-            libinput_device_has_capability, libinput_device_set_user_data,
-            libinput_device_tablet_pad_get_mode_group, libinput_device_tablet_pad_get_num_buttons,
+            libinput_device_get_name, libinput_device_get_size, libinput_device_get_user_data,
+            libinput_device_group, libinput_device_group_get_user_data,
+            libinput_device_group_set_user_data, libinput_device_has_capability,
+            libinput_device_set_user_data, libinput_device_tablet_pad_get_mode_group,
+            libinput_device_tablet_pad_get_num_buttons,
             libinput_device_tablet_pad_get_num_mode_groups,
             libinput_device_tablet_pad_get_num_rings, libinput_device_tablet_pad_get_num_strips,
             libinput_device_unref, libinput_path_remove_device, libinput_tablet_pad_mode_group,
             libinput_tablet_pad_mode_group_get_index, libinput_tablet_pad_mode_group_get_mode,
             libinput_tablet_pad_mode_group_get_num_modes,
             libinput_tablet_pad_mode_group_has_button, libinput_tablet_pad_mode_group_has_ring,
-            libinput_tablet_pad_mode_group_has_strip,
+            libinput_tablet_pad_mode_group_has_strip, LibInputAccelApi, ACCEL_API,
         },
         LibInput,
     },
@@ -56,6 +76,11 @@ pub struct LibInputTabletPadModeGroup<'a> {
     pub(super) _phantom: PhantomData<&'a ()>,
 }
 
+pub struct LibInputAccelConfig {
+    api: &'static LibInputAccelApi,
+    accel: *mut libinput_config_accel,
+}
+
 pub struct RegisteredDevice {
     pub(super) _li: Rc<LibInput>,
     pub(super) dev: *mut libinput_device,
@@ -128,6 +153,15 @@ impl<'a> LibInputDevice<'a> {
         }
     }
 
+    pub fn apply_accel_config(&self, config: &LibInputAccelConfig) -> ConfigStatus {
+        unsafe {
+            ConfigStatus((config.api.libinput_device_config_accel_apply)(
+                self.dev,
+                config.accel,
+            ))
+        }
+    }
+
     pub fn name(&self) -> String {
         unsafe {
             let name = libinput_device_get_name(self.dev);
@@ -212,6 +246,144 @@ impl<'a> LibInputDevice<'a> {
         unsafe { libinput_device_config_scroll_has_natural_scroll(self.dev) != 0 }
     }
 
+    pub fn click_methods(&self) -> u32 {
+        unsafe { libinput_device_config_click_get_methods(self.dev) }
+    }
+
+    pub fn set_click_method(&self, method: ConfigClickMethod) {
+        unsafe {
+            libinput_device_config_click_set_method(self.dev, method.raw() as _);
+        }
+    }
+
+    pub fn click_method(&self) -> ConfigClickMethod {
+        unsafe { ConfigClickMethod(libinput_device_config_click_get_method(self.dev)) }
+    }
+
+    pub fn middle_emulation_available(&self) -> bool {
+        unsafe { libinput_device_config_middle_emulation_is_available(self.dev) != 0 }
+    }
+
+    pub fn set_middle_emulation_enabled(&self, enabled: bool) {
+        let enabled = match enabled {
+            true => LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED,
+            false => LIBINPUT_CONFIG_MIDDLE_EMULATION_DISABLED,
+        };
+        unsafe {
+            libinput_device_config_middle_emulation_set_enabled(self.dev, enabled.raw() as _);
+        }
+    }
+
+    pub fn middle_emulation_enabled(&self) -> bool {
+        let enabled = unsafe {
+            ConfigMiddleEmulationState(libinput_device_config_middle_emulation_get_enabled(
+                self.dev,
+            ))
+        };
+        match enabled {
+            LIBINPUT_CONFIG_MIDDLE_EMULATION_ENABLED => true,
+            _ => false,
+        }
+    }
+
+    pub fn scroll_methods(&self) -> u32 {
+        unsafe { libinput_device_config_scroll_get_methods(self.dev) }
+    }
+
+    pub fn set_scroll_method(&self, method: ConfigScrollMethod) {
+        unsafe {
+            libinput_device_config_scroll_set_method(self.dev, method.raw() as _);
+        }
+    }
+
+    pub fn scroll_method(&self) -> ConfigScrollMethod {
+        unsafe { ConfigScrollMethod(libinput_device_config_scroll_get_method(self.dev)) }
+    }
+
+    pub fn set_scroll_button(&self, button: u32) {
+        unsafe {
+            libinput_device_config_scroll_set_button(self.dev, button);
+        }
+    }
+
+    pub fn scroll_button(&self) -> u32 {
+        unsafe { libinput_device_config_scroll_get_button(self.dev) }
+    }
+
+    pub fn dwt_available(&self) -> bool {
+        unsafe { libinput_device_config_dwt_is_available(self.dev) != 0 }
+    }
+
+    pub fn set_dwt_enabled(&self, enabled: bool) {
+        let enabled = match enabled {
+            true => LIBINPUT_CONFIG_DWT_ENABLED,
+            false => LIBINPUT_CONFIG_DWT_DISABLED,
+        };
+        unsafe {
+            libinput_device_config_dwt_set_enabled(self.dev, enabled.raw() as _);
+        }
+    }
+
+    pub fn dwt_enabled(&self) -> bool {
+        let enabled = unsafe { ConfigDwtState(libinput_device_config_dwt_get_enabled(self.dev)) };
+        match enabled {
+            LIBINPUT_CONFIG_DWT_ENABLED => true,
+            _ => false,
+        }
+    }
+
+    pub fn set_tap_button_map(&self, map: ConfigTapButtonMap) {
+        unsafe {
+            libinput_device_config_tap_set_button_map(self.dev, map.raw() as _);
+        }
+    }
+
+    pub fn tap_button_map(&self) -> ConfigTapButtonMap {
+        unsafe { ConfigTapButtonMap(libinput_device_config_tap_get_button_map(self.dev)) }
+    }
+
+    pub fn set_send_events_mode(&self, mode: ConfigSendEventsMode) {
+        unsafe {
+            libinput_device_config_send_events_set_mode(self.dev, mode.raw() as _);
+        }
+    }
+
+    pub fn send_events_mode(&self) -> ConfigSendEventsMode {
+        unsafe { ConfigSendEventsMode(libinput_device_config_send_events_get_mode(self.dev) as _) }
+    }
+
+    pub fn rotation_available(&self) -> bool {
+        unsafe { libinput_device_config_rotation_is_available(self.dev) != 0 }
+    }
+
+    pub fn set_rotation(&self, degrees: u32) {
+        unsafe {
+            libinput_device_config_rotation_set_angle(self.dev, degrees as _);
+        }
+    }
+
+    pub fn rotation(&self) -> u32 {
+        unsafe { libinput_device_config_rotation_get_angle(self.dev) as u32 }
+    }
+
+    pub fn has_calibration_matrix(&self) -> bool {
+        unsafe { libinput_device_config_calibration_has_matrix(self.dev) != 0 }
+    }
+
+    pub fn set_calibration_matrix(&self, matrix: [f32; 6]) {
+        unsafe {
+            libinput_device_config_calibration_set_matrix(self.dev, &matrix);
+        }
+    }
+
+    pub fn calibration_matrix(&self) -> [f32; 6] {
+        let mut matrix = [0.0; 6];
+        unsafe {
+            libinput_device_config_calibration_get_matrix(self.dev, &mut matrix);
+        }
+        matrix
+    }
+
     pub fn device_group(&self) -> LibInputDeviceGroup<'_> {
         LibInputDeviceGroup {
             group: unsafe { libinput_device_get_device_group(self.dev) },
@@ -227,6 +399,14 @@ impl<'a> LibInputDevice<'a> {
         unsafe { libinput_device_get_id_vendor(self.dev) as u32 }
     }
 
+    /// Returns the physical size of the device in mm.
+    pub fn size(&self) -> Option<(f64, f64)> {
+        let mut width = 0.0;
+        let mut height = 0.0;
+        let res = unsafe { libinput_device_get_size(self.dev, &mut width, &mut height) };
+        (res == 0).then_some((width, height))
+    }
+
     pub fn pad_num_buttons(&self) -> u32 {
         match unsafe { libinput_device_tablet_pad_get_num_buttons(self.dev) } {
             -1 => 0,
@@ -303,6 +483,42 @@ impl<'a> LibInputTabletPadModeGroup<'a> {
     }
 }
 
+impl LibInputAccelConfig {
+    /// Returns whether libinput supports custom acceleration profiles.
+    pub fn available() -> bool {
+        ACCEL_API.is_some()
+    }
+
+    pub fn new(profile: AccelProfile) -> Option<Self> {
+        let api = ACCEL_API.as_ref()?;
+        let accel = unsafe { (api.libinput_config_accel_create)(profile.raw() as _) };
+        if accel.is_null() {
+            return None;
+        }
+        Some(Self { api, accel })
+    }
+
+    pub fn set_points(&self, ty: ConfigAccelType, step: f64, points: &[f64]) -> ConfigStatus {
+        unsafe {
+            ConfigStatus((self.api.libinput_config_accel_set_points)(
+                self.accel,
+                ty.raw() as _,
+                step,
+                points.len(),
+                points.as_ptr(),
+            ))
+        }
+    }
+}
+
+impl Drop for LibInputAccelConfig {
+    fn drop(&mut self) {
+        unsafe {
+            (self.api.libinput_config_accel_destroy)(self.accel);
+        }
+    }
+}
+
 impl RegisteredDevice {
     pub fn device(&self) -> LibInputDevice {
         LibInputDevice {
@@ -1,4 +1,4 @@
-use uapi::c;
+use {once_cell::sync::Lazy, uapi::c};
 
 include!(concat!(env!("OUT_DIR"), "/libinput_tys.rs"));
 
@@ -28,6 +28,8 @@ pub struct libinput_event_tablet_pad(u8);
 pub struct libinput_tablet_pad_mode_group(u8);
 #[repr(transparent)]
 pub struct libinput_tablet_tool(u8);
+#[repr(transparent)]
+pub struct libinput_config_accel(u8);
 // #[repr(transparent)]
 // pub struct libinput_tablet_pad(u8);
 
@@ -111,6 +113,72 @@ extern "C" {
     pub fn libinput_device_config_scroll_has_natural_scroll(
         device: *mut libinput_device,
     ) -> c::c_int;
+    pub fn libinput_device_config_click_get_methods(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_config_click_set_method(
+        device: *mut libinput_device,
+        method: libinput_config_click_method,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_click_get_method(
+        device: *mut libinput_device,
+    ) -> libinput_config_click_method;
+    pub fn libinput_device_config_middle_emulation_is_available(
+        device: *mut libinput_device,
+    ) -> c::c_int;
+    pub fn libinput_device_config_middle_emulation_set_enabled(
+        device: *mut libinput_device,
+        enable: libinput_config_middle_emulation_state,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_middle_emulation_get_enabled(
+        device: *mut libinput_device,
+    ) -> libinput_config_middle_emulation_state;
+    pub fn libinput_device_config_scroll_get_methods(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_config_scroll_set_method(
+        device: *mut libinput_device,
+        method: libinput_config_scroll_method,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_scroll_get_method(
+        device: *mut libinput_device,
+    ) -> libinput_config_scroll_method;
+    pub fn libinput_device_config_scroll_set_button(
+        device: *mut libinput_device,
+        button: u32,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_scroll_get_button(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_config_dwt_is_available(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_config_dwt_set_enabled(
+        device: *mut libinput_device,
+        enable: libinput_config_dwt_state,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_dwt_get_enabled(
+        device: *mut libinput_device,
+    ) -> libinput_config_dwt_state;
+    pub fn libinput_device_config_tap_set_button_map(
+        device: *mut libinput_device,
+        map: libinput_config_tap_button_map,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_tap_get_button_map(
+        device: *mut libinput_device,
+    ) -> libinput_config_tap_button_map;
+    pub fn libinput_device_config_send_events_set_mode(
+        device: *mut libinput_device,
+        mode: u32,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_send_events_get_mode(device: *mut libinput_device) -> u32;
+    pub fn libinput_device_config_rotation_is_available(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_config_rotation_set_angle(
+        device: *mut libinput_device,
+        degrees_cw: c::c_uint,
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_rotation_get_angle(device: *mut libinput_device) -> c::c_uint;
+    pub fn libinput_device_config_calibration_has_matrix(device: *mut libinput_device) -> c::c_int;
+    pub fn libinput_device_config_calibration_set_matrix(
+        device: *mut libinput_device,
+        matrix: *const [f32; 6],
+    ) -> libinput_config_status;
+    pub fn libinput_device_config_calibration_get_matrix(
+        device: *mut libinput_device,
+        matrix: *mut [f32; 6],
+    ) -> c::c_int;
 
     pub fn libinput_event_destroy(event: *mut libinput_event);
     pub fn libinput_event_get_type(event: *mut libinput_event) -> libinput_event_type;
@@ -187,6 +255,11 @@ extern "C" {
 
     pub fn libinput_device_get_id_product(device: *mut libinput_device) -> c::c_uint;
     pub fn libinput_device_get_id_vendor(device: *mut libinput_device) -> c::c_uint;
+    pub fn libinput_device_get_size(
+        device: *mut libinput_device,
+        width: *mut f64,
+        height: *mut f64,
+    ) -> c::c_int;
 
     pub fn libinput_event_get_tablet_tool_event(
         event: *mut libinput_event,
@@ -368,3 +441,49 @@ pub struct libinput_interface {
     ) -> c::c_int,
     pub close_restricted: unsafe extern "C" fn(fd: c::c_int, user_data: *mut c::c_void),
 }
+
+/// The functions of the custom acceleration profile.
+///
+/// These functions were added in libinput 1.23 and are resolved at runtime so that older
+/// versions of libinput can still be used.
+pub struct LibInputAccelApi {
+    pub libinput_device_config_accel_apply: unsafe extern "C" fn(
+        device: *mut libinput_device,
+        accel_config: *mut libinput_config_accel,
+    ) -> libinput_config_status,
+    pub libinput_config_accel_create:
+        unsafe extern "C" fn(profile: libinput_config_accel_profile) -> *mut libinput_config_accel,
+    pub libinput_config_accel_destroy:
+        unsafe extern "C" fn(accel_config: *mut libinput_config_accel),
+    pub libinput_config_accel_set_points: unsafe extern "C" fn(
+        accel_config: *mut libinput_config_accel,
+        accel_type: libinput_config_accel_type,
+        step: f64,
+        npoints: usize,
+        points: *const f64,
+    ) -> libinput_config_status,
+}
+
+pub static ACCEL_API: Lazy<Option<LibInputAccelApi>> = Lazy::new(|| unsafe {
+    let lib = libloading::os::unix::Library::this();
+    macro_rules! get {
+        ($name:ident) => {
+            match lib.get(concat!(stringify!($name), "\0").as_bytes()) {
+                Ok(s) => *s,
+                Err(_) => {
+                    log::info!(
+                        "libinput does not provide {}. Custom acceleration profiles are unavailable.",
+                        stringify!($name),
+                    );
+                    return None;
+                }
+            }
+        };
+    }
+    Some(LibInputAccelApi {
+        libinput_device_config_accel_apply: get!(libinput_device_config_accel_apply),
+        libinput_config_accel_create: get!(libinput_config_accel_create),
+        libinput_config_accel_destroy: get!(libinput_config_accel_destroy),
+        libinput_config_accel_set_points: get!(libinput_config_accel_set_points),
+    })
+});
@@ -5,6 +5,7 @@ use {
         wire_dbus::{
             org,
             org::freedesktop::login1::{
+                manager::{InhibitReply, PrepareForSleep},
                 seat::SwitchToReply,
                 session::{PauseDevice, ResumeDevice, TakeDeviceReply},
             },
@@ -146,6 +147,48 @@ impl Session {
         );
     }
 
+    pub fn suspend(&self) {
+        self.socket.call(
+            LOGIND_NAME,
+            MANAGER_PATH,
+            org::freedesktop::login1::manager::Suspend { interactive: FALSE },
+            |res| {
+                if let Err(e) = res {
+                    log::error!("Could not suspend the system: {}", ErrorFmt(e));
+                }
+            },
+        );
+    }
+
+    pub fn inhibit<F>(&self, what: &str, why: &str, mode: &str, f: F)
+    where
+        F: FnOnce(Result<&InhibitReply, DbusError>) + 'static,
+    {
+        self.socket.call(
+            LOGIND_NAME,
+            MANAGER_PATH,
+            org::freedesktop::login1::manager::Inhibit {
+                what: what.into(),
+                who: "jay".into(),
+                why: why.into(),
+                mode: mode.into(),
+            },
+            f,
+        );
+    }
+
+    pub fn on_prepare_for_sleep<F>(&self, f: F) -> Result<SignalHandler, DbusError>
+    where
+        F: Fn(PrepareForSleep) + 'static,
+    {
+        self.socket
+            .handle_signal::<org::freedesktop::login1::manager::PrepareForSleep, _>(
+                Some(LOGIND_NAME),
+                Some(MANAGER_PATH),
+                f,
+            )
+    }
+
     pub fn switch_to<F>(&self, vtnr: u32, f: F)
     where
         F: FnOnce(Result<&SwitchToReply, DbusError>) + 'static,
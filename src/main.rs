@@ -43,10 +43,12 @@ mod macros;
 #[macro_use]
 mod leaks;
 mod acceptor;
+mod animation;
 mod async_engine;
 mod backend;
 mod backends;
 mod bugs;
+mod builtin_lock;
 mod cli;
 mod client;
 mod clientmem;
@@ -71,6 +73,7 @@ mod libinput;
 mod logger;
 mod logind;
 mod object;
+mod pam;
 mod pango;
 mod pipewire;
 mod portal;
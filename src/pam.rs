@@ -0,0 +1,167 @@
+#![allow(non_camel_case_types)]
+
+use {
+    std::{ffi::CStr, mem, ptr},
+    thiserror::Error,
+    uapi::{c, ustr},
+};
+
+const PAM_SUCCESS: c::c_int = 0;
+const PAM_BUF_ERR: c::c_int = 5;
+const PAM_CONV_ERR: c::c_int = 19;
+
+const PAM_PROMPT_ECHO_OFF: c::c_int = 1;
+const PAM_PROMPT_ECHO_ON: c::c_int = 2;
+
+#[repr(transparent)]
+struct pam_handle_t(u8);
+
+#[repr(C)]
+struct pam_message {
+    msg_style: c::c_int,
+    #[allow(dead_code)]
+    msg: *const c::c_char,
+}
+
+#[repr(C)]
+struct pam_response {
+    resp: *mut c::c_char,
+    #[allow(dead_code)]
+    resp_retcode: c::c_int,
+}
+
+#[repr(C)]
+struct pam_conv {
+    conv: unsafe extern "C" fn(
+        num_msg: c::c_int,
+        msg: *mut *const pam_message,
+        resp: *mut *mut pam_response,
+        appdata_ptr: *mut c::c_void,
+    ) -> c::c_int,
+    appdata_ptr: *mut c::c_void,
+}
+
+#[link(name = "pam")]
+extern "C" {
+    fn pam_start(
+        service_name: *const c::c_char,
+        user: *const c::c_char,
+        pam_conversation: *const pam_conv,
+        pamh: *mut *mut pam_handle_t,
+    ) -> c::c_int;
+    fn pam_authenticate(pamh: *mut pam_handle_t, flags: c::c_int) -> c::c_int;
+    fn pam_acct_mgmt(pamh: *mut pam_handle_t, flags: c::c_int) -> c::c_int;
+    fn pam_end(pamh: *mut pam_handle_t, pam_status: c::c_int) -> c::c_int;
+    fn pam_strerror(pamh: *mut pam_handle_t, errnum: c::c_int) -> *const c::c_char;
+}
+
+#[derive(Debug, Error)]
+pub enum PamError {
+    #[error("Could not determine the name of the current user")]
+    UnknownUser,
+    #[error("The password contains a nul byte")]
+    NulByte,
+    #[error("Could not start a PAM transaction: {0}")]
+    Start(String),
+    #[error("Authentication failed: {0}")]
+    Authenticate(String),
+    #[error("Account validation failed: {0}")]
+    AcctMgmt(String),
+}
+
+unsafe extern "C" fn conversation(
+    num_msg: c::c_int,
+    msg: *mut *const pam_message,
+    resp: *mut *mut pam_response,
+    appdata_ptr: *mut c::c_void,
+) -> c::c_int {
+    if num_msg <= 0 {
+        return PAM_CONV_ERR;
+    }
+    let password = appdata_ptr as *const c::c_char;
+    let responses =
+        c::calloc(num_msg as usize, mem::size_of::<pam_response>()) as *mut pam_response;
+    if responses.is_null() {
+        return PAM_BUF_ERR;
+    }
+    for i in 0..num_msg as usize {
+        let msg = &**msg.add(i);
+        let response = &mut *responses.add(i);
+        match msg.msg_style {
+            PAM_PROMPT_ECHO_OFF | PAM_PROMPT_ECHO_ON => {
+                response.resp = c::strdup(password);
+                if response.resp.is_null() {
+                    for j in 0..i {
+                        c::free((*responses.add(j)).resp as _);
+                    }
+                    c::free(responses as _);
+                    return PAM_BUF_ERR;
+                }
+            }
+            _ => {}
+        }
+    }
+    *resp = responses;
+    PAM_SUCCESS
+}
+
+fn user_name() -> Option<Vec<u8>> {
+    unsafe {
+        let pw = c::getpwuid(c::getuid());
+        if pw.is_null() || (*pw).pw_name.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr((*pw).pw_name).to_bytes().to_vec())
+    }
+}
+
+/// Authenticates the current user with the given password.
+///
+/// This function blocks until PAM has made a decision and should only be called in a
+/// dedicated process.
+pub fn authenticate(password: &[u8]) -> Result<(), PamError> {
+    let mut user = user_name().ok_or(PamError::UnknownUser)?;
+    if password.contains(&0) {
+        return Err(PamError::NulByte);
+    }
+    user.push(0);
+    let mut password = password.to_vec();
+    password.push(0);
+    let res = unsafe { authenticate_(&user, &password) };
+    password.fill(0);
+    res
+}
+
+unsafe fn authenticate_(user: &[u8], password: &[u8]) -> Result<(), PamError> {
+    let conv = pam_conv {
+        conv: conversation,
+        appdata_ptr: password.as_ptr() as _,
+    };
+    let service = ustr!("jay");
+    let mut handle = ptr::null_mut();
+    let res = pam_start(service.as_ptr(), user.as_ptr() as _, &conv, &mut handle);
+    if res != PAM_SUCCESS {
+        return Err(PamError::Start(strerror(handle, res)));
+    }
+    let mut res = pam_authenticate(handle, 0);
+    let mut ret = match res {
+        PAM_SUCCESS => Ok(()),
+        _ => Err(PamError::Authenticate(strerror(handle, res))),
+    };
+    if ret.is_ok() {
+        res = pam_acct_mgmt(handle, 0);
+        if res != PAM_SUCCESS {
+            ret = Err(PamError::AcctMgmt(strerror(handle, res)));
+        }
+    }
+    pam_end(handle, res);
+    ret
+}
+
+unsafe fn strerror(handle: *mut pam_handle_t, res: c::c_int) -> String {
+    let s = pam_strerror(handle, res);
+    if s.is_null() {
+        return format!("error {}", res);
+    }
+    CStr::from_ptr(s).to_string_lossy().into_owned()
+}
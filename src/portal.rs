@@ -1,6 +1,10 @@
 mod ptl_display;
+mod ptl_global_shortcuts;
+mod ptl_remote_desktop;
 mod ptl_render_ctx;
 mod ptl_screencast;
+mod ptl_screenshot;
+mod ptl_settings;
 mod ptr_gui;
 
 use {
@@ -17,9 +21,14 @@ use {
         pipewire::pw_con::{PwCon, PwConHolder, PwConOwner},
         portal::{
             ptl_display::{watch_displays, PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::{add_global_shortcuts_dbus_members, GlobalShortcutsSession},
+            ptl_remote_desktop::add_remote_desktop_dbus_members,
             ptl_render_ctx::PortalRenderCtx,
             ptl_screencast::{add_screencast_dbus_members, ScreencastSession},
+            ptl_screenshot::{add_screenshot_dbus_members, ScreenshotRequest},
+            ptl_settings::add_settings_dbus_members,
         },
+        theme::Appearance,
         utils::{
             clone3::{fork_with_pidfd, Forked},
             copyhashmap::CopyHashMap,
@@ -38,6 +47,7 @@ use {
     },
     log::Level,
     std::{
+        cell::Cell,
         os::unix::process::CommandExt,
         process::Command,
         rc::{Rc, Weak},
@@ -48,7 +58,6 @@ use {
 };
 
 const PORTAL_SUCCESS: u32 = 0;
-#[allow(dead_code)]
 const PORTAL_CANCELLED: u32 = 1;
 #[allow(dead_code)]
 const PORTAL_ENDED: u32 = 2;
@@ -195,6 +204,9 @@ async fn run_async(
         displays: Default::default(),
         dbus,
         screencasts: Default::default(),
+        screenshots: Default::default(),
+        global_shortcuts: Default::default(),
+        appearance: Default::default(),
         next_id: NumCell::new(1),
         render_ctxs: Default::default(),
         dma_buf_ids: Default::default(),
@@ -205,6 +217,10 @@ async fn run_async(
             .add_object("/org/freedesktop/portal/desktop")
             .unwrap();
         add_screencast_dbus_members(&state, &obj);
+        add_screenshot_dbus_members(&state, &obj);
+        add_remote_desktop_dbus_members(&state, &obj);
+        add_global_shortcuts_dbus_members(&state, &obj);
+        add_settings_dbus_members(&state, &obj);
         obj
     };
     state.pw_con.owner.set(Some(state.clone()));
@@ -285,6 +301,9 @@ struct PortalState {
     displays: CopyHashMap<PortalDisplayId, Rc<PortalDisplay>>,
     dbus: Rc<DbusSocket>,
     screencasts: CopyHashMap<String, Rc<ScreencastSession>>,
+    screenshots: CopyHashMap<String, Rc<ScreenshotRequest>>,
+    global_shortcuts: CopyHashMap<String, Rc<GlobalShortcutsSession>>,
+    appearance: Cell<Option<Appearance>>,
     next_id: NumCell<u32>,
     render_ctxs: CopyHashMap<c::dev_t, Weak<PortalRenderCtx>>,
     dma_buf_ids: Rc<DmaBufIds>,
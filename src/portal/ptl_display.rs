@@ -3,9 +3,11 @@ use {
         gfx_apis::create_gfx_context,
         ifs::wl_seat::POINTER,
         portal::{
-            ptl_render_ctx::PortalRenderCtx, ptl_screencast::ScreencastSession,
-            ptr_gui::WindowData, PortalState,
+            ptl_global_shortcuts::GlobalShortcutsSession, ptl_render_ctx::PortalRenderCtx,
+            ptl_screencast::ScreencastSession, ptl_screenshot::ScreenshotRequest,
+            ptl_settings::appearance_changed, ptr_gui::WindowData, PortalState,
         },
+        theme::Appearance,
         utils::{
             bitflags::BitflagsExt, clonecell::CloneCell, copyhashmap::CopyHashMap,
             errorfmt::ErrorFmt, oserror::OsError,
@@ -17,6 +19,7 @@ use {
         },
         wl_usr::{
             usr_ifs::{
+                usr_jay_appearance::UsrJayAppearanceOwner,
                 usr_jay_compositor::UsrJayCompositor,
                 usr_jay_output::{UsrJayOutput, UsrJayOutputOwner},
                 usr_jay_pointer::UsrJayPointer,
@@ -73,6 +76,8 @@ pub struct PortalDisplay {
 
     pub windows: CopyHashMap<WlSurfaceId, Rc<WindowData>>,
     pub screencasts: CopyHashMap<String, Rc<ScreencastSession>>,
+    pub screenshots: CopyHashMap<String, Rc<ScreenshotRequest>>,
+    pub global_shortcuts: CopyHashMap<String, Rc<GlobalShortcutsSession>>,
 }
 
 pub struct PortalOutput {
@@ -188,12 +193,24 @@ impl UsrJayRenderCtxOwner for PortalDisplay {
     }
 }
 
+impl UsrJayAppearanceOwner for PortalDisplay {
+    fn appearance(&self, appearance: Appearance) {
+        appearance_changed(&self.state, appearance);
+    }
+}
+
 impl UsrConOwner for PortalDisplay {
     fn killed(&self) {
         log::info!("Removing display {}", self.id);
         for (_, sc) in self.screencasts.lock().drain() {
             sc.kill();
         }
+        for (_, ss) in self.screenshots.lock().drain() {
+            ss.kill();
+        }
+        for (_, gs) in self.global_shortcuts.lock().drain() {
+            gs.kill();
+        }
         self.windows.clear();
         self.state.displays.remove(&self.id);
     }
@@ -384,6 +401,8 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
         vp,
         windows: Default::default(),
         screencasts: Default::default(),
+        screenshots: Default::default(),
+        global_shortcuts: Default::default(),
     });
 
     dpy.state.displays.set(dpy.id, dpy.clone());
@@ -393,6 +412,16 @@ fn finish_display_connect(dpy: Rc<PortalDisplayPrelude>) {
     let jrc = dpy.jc.get_render_context();
     jrc.owner.set(Some(dpy.clone()));
 
+    dpy.con.sync({
+        let dpy = dpy.clone();
+        move || {
+            if dpy.jc.caps.appearance.get() {
+                let wa = dpy.jc.watch_appearance();
+                wa.owner.set(Some(dpy.clone()));
+            }
+        }
+    });
+
     for (name, version) in outputs {
         add_output(&dpy, name, version);
     }
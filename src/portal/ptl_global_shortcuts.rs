@@ -0,0 +1,476 @@
+mod global_shortcuts_gui;
+
+use {
+    crate::{
+        dbus::{
+            prelude::{ObjectPath, Variant},
+            DbusObject, DictEntry, DynamicType, PendingReply,
+        },
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId},
+            ptl_global_shortcuts::global_shortcuts_gui::GlobalShortcutsGui,
+            PortalState, PORTAL_CANCELLED, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+        },
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::{
+                global_shortcuts::{
+                    Activated, BindShortcuts, BindShortcutsReply, CreateSession,
+                    CreateSessionReply, Deactivated, ListShortcuts, ListShortcutsReply,
+                },
+                session::{CloseReply as SessionCloseReply, Closed},
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_global_shortcuts::{
+            UsrJayGlobalShortcuts, UsrJayGlobalShortcutsOwner,
+        },
+        xkbcommon::{keysym_from_name, keysym_name},
+    },
+    jay_config::keyboard::{
+        mods::{Modifiers, ALT, CTRL, LOGO, SHIFT},
+        syms::KeySym,
+    },
+    std::{borrow::Cow, cell::RefCell, ops::Deref, rc::Rc},
+};
+
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+
+pub struct GlobalShortcutsSession {
+    state: Rc<PortalState>,
+    pub app: String,
+    handle: String,
+    session_obj: DbusObject,
+    pub shortcuts: RefCell<Vec<Shortcut>>,
+    pub phase: CloneCell<GlobalShortcutsPhase>,
+    bound: CloneCell<Option<Rc<BoundShortcuts>>>,
+}
+
+#[derive(Clone)]
+pub enum GlobalShortcutsPhase {
+    Init,
+    Confirming(Rc<ConfirmingShortcuts>),
+    Bound,
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for GlobalShortcutsPhase {}
+
+#[derive(Clone)]
+pub struct Shortcut {
+    pub id: String,
+    pub description: String,
+    pub trigger: Option<Trigger>,
+}
+
+#[derive(Copy, Clone)]
+pub struct Trigger {
+    pub mods: Modifiers,
+    pub sym: KeySym,
+}
+
+pub struct ConfirmingShortcuts {
+    pub session: Rc<GlobalShortcutsSession>,
+    request_obj: CloneCell<Option<Rc<DbusObject>>>,
+    reply: PendingReply<BindShortcutsReply<'static>>,
+    pub shortcuts: Vec<Shortcut>,
+    pub guis: CopyHashMap<PortalDisplayId, Rc<GlobalShortcutsGui>>,
+}
+
+struct BoundShortcuts {
+    session: Rc<GlobalShortcutsSession>,
+    dpy: Rc<PortalDisplay>,
+    seats: Vec<Rc<UsrJayGlobalShortcuts>>,
+}
+
+impl Trigger {
+    /// Parses a trigger in the format of the XDG shortcuts specification, e.g. `CTRL+ALT+t`.
+    fn parse(s: &str) -> Option<Self> {
+        let mut mods = Modifiers::NONE;
+        let mut parts = s.split('+').map(|p| p.trim()).peekable();
+        while let Some(part) = parts.next() {
+            if parts.peek().is_none() {
+                let sym = keysym_from_name(part)?;
+                return Some(Self {
+                    mods,
+                    sym: KeySym(sym),
+                });
+            }
+            mods |= match part.to_ascii_uppercase().as_str() {
+                "SHIFT" => SHIFT,
+                "CTRL" | "CONTROL" => CTRL,
+                "ALT" => ALT,
+                "LOGO" | "SUPER" | "META" => LOGO,
+                _ => return None,
+            };
+        }
+        None
+    }
+
+    fn describe(&self) -> String {
+        let mut res = String::new();
+        for (m, name) in [
+            (LOGO, "Logo"),
+            (CTRL, "Ctrl"),
+            (ALT, "Alt"),
+            (SHIFT, "Shift"),
+        ] {
+            if self.mods.0 & m.0 != 0 {
+                res.push_str(name);
+                res.push('+');
+            }
+        }
+        res.push_str(&keysym_name(self.sym.0));
+        res
+    }
+}
+
+impl Shortcut {
+    pub fn trigger_description(&self) -> String {
+        match &self.trigger {
+            Some(t) => t.describe(),
+            _ => String::new(),
+        }
+    }
+}
+
+impl GlobalShortcutsSession {
+    pub(super) fn kill(&self) {
+        self.session_obj.emit_signal(&Closed);
+        self.state.global_shortcuts.remove(&self.handle);
+        self.unbind();
+        if let GlobalShortcutsPhase::Confirming(c) =
+            self.phase.set(GlobalShortcutsPhase::Terminated)
+        {
+            c.kill_guis();
+            c.request_obj.take();
+            c.reply.err("Session has been closed");
+        }
+    }
+
+    fn unbind(&self) {
+        if let Some(bound) = self.bound.take() {
+            bound.dpy.global_shortcuts.remove(&self.handle);
+            for gs in &bound.seats {
+                bound.dpy.con.remove_obj(gs.deref());
+            }
+        }
+    }
+
+    fn shortcut_id(&self, id: u32) -> Option<String> {
+        self.shortcuts
+            .borrow()
+            .get(id as usize)
+            .map(|s| s.id.clone())
+    }
+}
+
+impl ConfirmingShortcuts {
+    fn kill_guis(&self) {
+        for (_, gui) in self.guis.lock().drain() {
+            gui.kill(false);
+        }
+    }
+
+    fn is_current(&self) -> bool {
+        match self.session.phase.get() {
+            GlobalShortcutsPhase::Confirming(c) => c.request_obj.get().is_some(),
+            _ => false,
+        }
+    }
+
+    pub fn reject(&self) {
+        if !self.is_current() {
+            return;
+        }
+        log::info!("User has rejected the shortcuts");
+        self.kill_guis();
+        self.request_obj.take();
+        let phase = match self.session.bound.get().is_some() {
+            true => GlobalShortcutsPhase::Bound,
+            false => GlobalShortcutsPhase::Init,
+        };
+        self.session.phase.set(phase);
+        self.reply.ok(&BindShortcutsReply {
+            response: PORTAL_CANCELLED,
+            results: Default::default(),
+        });
+    }
+
+    pub fn accept(&self, dpy: &Rc<PortalDisplay>) {
+        if !self.is_current() {
+            return;
+        }
+        log::info!("User has accepted the shortcuts");
+        self.kill_guis();
+        self.request_obj.take();
+        let session = &self.session;
+        session.unbind();
+        *session.shortcuts.borrow_mut() = self.shortcuts.clone();
+        let bound = Rc::new(BoundShortcuts {
+            session: session.clone(),
+            dpy: dpy.clone(),
+            seats: dpy
+                .seats
+                .lock()
+                .values()
+                .map(|seat| dpy.jc.create_global_shortcuts(&seat.wl))
+                .collect(),
+        });
+        for gs in &bound.seats {
+            gs.owner.set(Some(bound.clone()));
+            for (idx, shortcut) in self.shortcuts.iter().enumerate() {
+                if let Some(trigger) = shortcut.trigger {
+                    gs.bind(idx as u32, trigger.mods, trigger.sym);
+                }
+            }
+        }
+        dpy.global_shortcuts
+            .set(session.handle.clone(), session.clone());
+        session.bound.set(Some(bound));
+        session.phase.set(GlobalShortcutsPhase::Bound);
+        self.reply.ok(&BindShortcutsReply {
+            response: PORTAL_SUCCESS,
+            results: Cow::Owned(vec![shortcuts_result(&self.shortcuts)]),
+        });
+    }
+}
+
+impl UsrJayGlobalShortcutsOwner for BoundShortcuts {
+    fn activated(&self, id: u32, time_usec: u64) {
+        let Some(shortcut_id) = self.session.shortcut_id(id) else {
+            return;
+        };
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &Activated {
+                session_handle: ObjectPath(self.session.handle.as_str().into()),
+                shortcut_id: shortcut_id.into(),
+                timestamp: time_usec / 1000,
+                options: Default::default(),
+            },
+        );
+    }
+
+    fn deactivated(&self, id: u32, time_usec: u64) {
+        let Some(shortcut_id) = self.session.shortcut_id(id) else {
+            return;
+        };
+        self.session.state.dbus.emit_signal(
+            PORTAL_PATH,
+            &Deactivated {
+                session_handle: ObjectPath(self.session.handle.as_str().into()),
+                shortcut_id: shortcut_id.into(),
+                timestamp: time_usec / 1000,
+                options: Default::default(),
+            },
+        );
+    }
+}
+
+fn shortcuts_result(shortcuts: &[Shortcut]) -> DictEntry<Cow<'static, str>, Variant<'static>> {
+    let dict_type = DynamicType::DictEntry(
+        Box::new(DynamicType::String),
+        Box::new(DynamicType::Variant),
+    );
+    let shortcut_type = DynamicType::Struct(vec![
+        DynamicType::String,
+        DynamicType::Array(Box::new(dict_type.clone())),
+    ]);
+    let entry = |key: &'static str, value: String| {
+        Variant::DictEntry(
+            Box::new(Variant::String(key.into())),
+            Box::new(Variant::Variant(Box::new(Variant::String(value.into())))),
+        )
+    };
+    let shortcuts = shortcuts
+        .iter()
+        .map(|s| {
+            Variant::Struct(vec![
+                Variant::String(s.id.clone().into()),
+                Variant::Array(
+                    dict_type.clone(),
+                    vec![
+                        entry("description", s.description.clone()),
+                        entry("trigger_description", s.trigger_description()),
+                    ],
+                ),
+            ])
+        })
+        .collect();
+    DictEntry {
+        key: "shortcuts".into(),
+        value: Variant::Array(shortcut_type, shortcuts),
+    }
+}
+
+pub(super) fn add_global_shortcuts_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::global_shortcuts::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<BindShortcuts, _>(move |req, pr| {
+        dbus_bind_shortcuts(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<ListShortcuts, _>(move |req, pr| {
+        dbus_list_shortcuts(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create Global Shortcuts Session {:#?}", req);
+    let handle = req.session_handle.0.to_string();
+    if state.global_shortcuts.contains(&handle) {
+        reply.err("Session already exists");
+        return;
+    }
+    let obj = match state.dbus.add_object(handle.clone()) {
+        Ok(obj) => obj,
+        Err(_) => {
+            reply.err("Session path is not unique");
+            return;
+        }
+    };
+    let session = Rc::new(GlobalShortcutsSession {
+        state: state.clone(),
+        app: req.app_id.to_string(),
+        handle: handle.clone(),
+        session_obj: obj,
+        shortcuts: Default::default(),
+        phase: CloneCell::new(GlobalShortcutsPhase::Init),
+        bound: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::session::*;
+        let ses = session.clone();
+        session.session_obj.add_method::<Close, _>(move |_, pr| {
+            ses.kill();
+            pr.ok(&SessionCloseReply);
+        });
+        session.session_obj.set_property::<version>(Variant::U32(1));
+    }
+    state.global_shortcuts.set(handle, session);
+    reply.ok(&CreateSessionReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+fn dbus_bind_shortcuts(
+    state: &Rc<PortalState>,
+    req: BindShortcuts,
+    reply: PendingReply<BindShortcutsReply<'static>>,
+) {
+    log::info!("Bind Shortcuts {:#?}", req);
+    let Some(session) = get_session(state, &reply, &req.session_handle.0) else {
+        return;
+    };
+    match session.phase.get() {
+        GlobalShortcutsPhase::Init | GlobalShortcutsPhase::Bound => {}
+        _ => {
+            reply.err("Session is not in the correct phase for binding shortcuts");
+            return;
+        }
+    }
+    let mut shortcuts = vec![];
+    for (id, options) in req.shortcuts.iter() {
+        let mut shortcut = Shortcut {
+            id: id.to_string(),
+            description: String::new(),
+            trigger: None,
+        };
+        for opt in options.iter() {
+            let Variant::String(value) = &opt.value else {
+                continue;
+            };
+            match &*opt.key {
+                "description" => shortcut.description = value.to_string(),
+                "preferred_trigger" => {
+                    shortcut.trigger = Trigger::parse(value);
+                    if shortcut.trigger.is_none() {
+                        log::warn!("Could not parse trigger `{}`", value);
+                    }
+                }
+                _ => {}
+            }
+        }
+        shortcuts.push(shortcut);
+    }
+    let request_obj = match state.dbus.add_object(req.handle.0.to_string()) {
+        Ok(r) => r,
+        Err(_) => {
+            reply.err("Request handle is not unique");
+            return;
+        }
+    };
+    let request_obj = Rc::new(request_obj);
+    let confirming = Rc::new(ConfirmingShortcuts {
+        session: session.clone(),
+        request_obj: CloneCell::new(Some(request_obj.clone())),
+        reply,
+        shortcuts,
+        guis: Default::default(),
+    });
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        let c = confirming.clone();
+        request_obj.add_method::<Close, _>(move |_, pr| {
+            c.reject();
+            pr.ok(&CloseReply);
+        });
+    }
+    for dpy in state.displays.lock().values() {
+        if dpy.outputs.len() > 0 && dpy.jc.caps.global_shortcuts.get() {
+            confirming
+                .guis
+                .set(dpy.id, GlobalShortcutsGui::new(&confirming, dpy));
+        }
+    }
+    if confirming.guis.is_empty() {
+        confirming.request_obj.take();
+        confirming.reply.err("There are no running displays");
+        return;
+    }
+    session
+        .phase
+        .set(GlobalShortcutsPhase::Confirming(confirming));
+}
+
+fn dbus_list_shortcuts(
+    state: &Rc<PortalState>,
+    req: ListShortcuts,
+    reply: PendingReply<ListShortcutsReply<'static>>,
+) {
+    let Some(session) = get_session(state, &reply, &req.session_handle.0) else {
+        return;
+    };
+    let shortcuts = shortcuts_result(&session.shortcuts.borrow());
+    reply.ok(&ListShortcutsReply {
+        response: PORTAL_SUCCESS,
+        results: Cow::Owned(vec![shortcuts]),
+    });
+}
+
+fn get_session<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<GlobalShortcutsSession>> {
+    let res = state.global_shortcuts.get(handle);
+    if res.is_none() {
+        let msg = format!("Global shortcuts session `{}` does not exist", handle);
+        reply.err(&msg);
+    }
+    res
+}
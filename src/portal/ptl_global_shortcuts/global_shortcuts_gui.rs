@@ -0,0 +1,166 @@
+use {
+    crate::{
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_global_shortcuts::ConfirmingShortcuts,
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, Label, Orientation, OverlayWindow,
+                OverlayWindowOwner,
+            },
+        },
+        theme::Color,
+        utils::copyhashmap::CopyHashMap,
+    },
+    std::rc::Rc,
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+pub struct GlobalShortcutsGui {
+    confirming: Rc<ConfirmingShortcuts>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<GlobalShortcutsGuiSurface>>,
+}
+
+pub struct GlobalShortcutsGuiSurface {
+    gui: Rc<GlobalShortcutsGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    surface: Rc<GlobalShortcutsGuiSurface>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Allow,
+    Reject,
+}
+
+impl GlobalShortcutsGui {
+    pub fn new(confirming: &Rc<ConfirmingShortcuts>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(GlobalShortcutsGui {
+            confirming: confirming.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let sgs = Rc::new(GlobalShortcutsGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new(output),
+            });
+            let element = create_dialog(&sgs);
+            sgs.overlay.data.content.set(Some(element));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        for (_, surface) in self.surfaces.lock().drain() {
+            surface.overlay.data.kill(false);
+        }
+        self.confirming.guis.remove(&self.dpy.id);
+        if upwards && self.confirming.guis.is_empty() {
+            self.confirming.reject();
+        }
+    }
+}
+
+fn create_dialog(surface: &Rc<GlobalShortcutsGuiSurface>) -> Rc<dyn GuiElement> {
+    let confirming = &surface.gui.confirming;
+    let app = &confirming.session.app;
+    let text = if app.is_empty() {
+        "An application wants to register global shortcuts".to_string()
+    } else {
+        format!("`{}` wants to register global shortcuts", app)
+    };
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label(text)];
+    for shortcut in &confirming.shortcuts {
+        let description = match shortcut.description.is_empty() {
+            true => &shortcut.id,
+            false => &shortcut.description,
+        };
+        let trigger = match shortcut.trigger.is_some() {
+            true => shortcut.trigger_description(),
+            false => "unassigned".to_string(),
+        };
+        elements.push(label(format!("{}: {}", description, trigger)));
+    }
+    let allow_button = static_button(surface, ButtonRole::Allow, "Allow");
+    let reject_button = static_button(surface, ButtonRole::Reject, "Reject");
+    for button in [&allow_button, &reject_button] {
+        button.border_color.set(Color::from_gray(100));
+        button.border.set(2.0);
+        button.padding.set(5.0);
+    }
+    allow_button.bg_color.set(Color::from_rgb(170, 200, 170));
+    allow_button
+        .bg_hover_color
+        .set(Color::from_rgb(170, 255, 170));
+    reject_button.bg_color.set(Color::from_rgb(200, 170, 170));
+    reject_button
+        .bg_hover_color
+        .set(Color::from_rgb(255, 170, 170));
+    elements.push(allow_button);
+    elements.push(reject_button);
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    *flow.elements.borrow_mut() = elements;
+    flow
+}
+
+fn label(text: String) -> Rc<Label> {
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    label
+}
+
+impl OverlayWindowOwner for GlobalShortcutsGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, _seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        let confirming = &self.surface.gui.confirming;
+        match self.role {
+            ButtonRole::Allow => confirming.accept(&self.surface.output.dpy),
+            ButtonRole::Reject => confirming.reject(),
+        }
+    }
+}
+
+fn static_button(
+    surface: &Rc<GlobalShortcutsGuiSurface>,
+    role: ButtonRole,
+    text: &str,
+) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        surface: surface.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button
+}
@@ -0,0 +1,293 @@
+use {
+    crate::{
+        dbus::{prelude::Variant, DbusObject, DictEntry, PendingReply, TRUE},
+        ifs::wl_seat::wl_pointer::{HORIZONTAL_SCROLL, VERTICAL_SCROLL},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screencast::{create_session, get_session, PendingStart, ScreencastPhase},
+            PortalState, PORTAL_SUCCESS,
+        },
+        utils::clonecell::CloneCell,
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::remote_desktop::{
+                CreateSession, CreateSessionReply, SelectDevices, SelectDevicesReply, Start,
+                StartReply,
+            },
+        },
+        wl_usr::usr_ifs::usr_jay_virtual_input::UsrJayVirtualInput,
+    },
+    std::{borrow::Cow, cell::Cell, ops::Deref, rc::Rc},
+};
+
+bitflags! {
+    DeviceTypes: u32;
+
+    KEYBOARD = 1,
+    POINTER = 2,
+    TOUCHSCREEN = 4,
+}
+
+const AVAILABLE_DEVICE_TYPES: DeviceTypes = DeviceTypes(KEYBOARD.0 | POINTER.0 | TOUCHSCREEN.0);
+
+pub struct RemoteDesktop {
+    pub devices: Cell<DeviceTypes>,
+    pub capture: Cell<bool>,
+    pub input: CloneCell<Option<Rc<UsrJayVirtualInput>>>,
+    pub output: CloneCell<Option<Rc<PortalOutput>>>,
+}
+
+impl RemoteDesktop {
+    pub fn connect(&self, dpy: &Rc<PortalDisplay>, seat: &PortalSeat) {
+        self.disconnect();
+        if !dpy.jc.caps.virtual_input.get() {
+            log::warn!("Compositor does not support virtual input devices");
+            return;
+        }
+        self.input.set(Some(dpy.jc.create_virtual_input(&seat.wl)));
+    }
+
+    pub fn disconnect(&self) {
+        self.output.take();
+        if let Some(input) = self.input.take() {
+            input.con.remove_obj(input.deref());
+        }
+    }
+
+    pub fn devices_result(&self) -> DictEntry<Cow<'static, str>, Variant<'static>> {
+        DictEntry {
+            key: "devices".into(),
+            value: Variant::U32(self.devices.get().0),
+        }
+    }
+}
+
+pub(super) fn add_remote_desktop_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::remote_desktop::*;
+    let state = state_.clone();
+    object.add_method::<CreateSession, _>(move |req, pr| {
+        dbus_create_session(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<SelectDevices, _>(move |req, pr| {
+        dbus_select_devices(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<Start, _>(move |req, pr| {
+        dbus_start(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerMotion, _>(move |req, pr| {
+        if let Some(input) = get_input(&state, &pr, &req.session_handle.0, POINTER) {
+            input.motion(req.dx, req.dy);
+            pr.ok(&NotifyPointerMotionReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerMotionAbsolute, _>(move |req, pr| {
+        let handle = &req.session_handle.0;
+        if let Some(input) = get_input(&state, &pr, handle, POINTER) {
+            if let Some(output) = get_output(&state, &pr, handle) {
+                input.motion_absolute(&output.wl, req.x, req.y);
+                pr.ok(&NotifyPointerMotionAbsoluteReply);
+            }
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerButton, _>(move |req, pr| {
+        if let Some(input) = get_input(&state, &pr, &req.session_handle.0, POINTER) {
+            input.button(req.button as u32, req.state);
+            pr.ok(&NotifyPointerButtonReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerAxis, _>(move |req, pr| {
+        if let Some(input) = get_input(&state, &pr, &req.session_handle.0, POINTER) {
+            let finish = req
+                .options
+                .iter()
+                .any(|o| o.key == "finish" && matches!(o.value, Variant::Bool(b) if b == TRUE));
+            if req.dx != 0.0 {
+                input.axis(HORIZONTAL_SCROLL, req.dx);
+            }
+            if req.dy != 0.0 {
+                input.axis(VERTICAL_SCROLL, req.dy);
+            }
+            if finish {
+                input.axis_stop(HORIZONTAL_SCROLL);
+                input.axis_stop(VERTICAL_SCROLL);
+            }
+            input.frame();
+            pr.ok(&NotifyPointerAxisReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyPointerAxisDiscrete, _>(move |req, pr| {
+        if let Some(input) = get_input(&state, &pr, &req.session_handle.0, POINTER) {
+            let axis = match req.axis {
+                0 => VERTICAL_SCROLL,
+                1 => HORIZONTAL_SCROLL,
+                _ => {
+                    pr.err("Unknown axis");
+                    return;
+                }
+            };
+            input.axis_discrete(axis, req.steps);
+            input.frame();
+            pr.ok(&NotifyPointerAxisDiscreteReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyKeyboardKeycode, _>(move |req, pr| {
+        if let Some(input) = get_input(&state, &pr, &req.session_handle.0, KEYBOARD) {
+            input.key(req.keycode as u32, req.state);
+            pr.ok(&NotifyKeyboardKeycodeReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyKeyboardKeysym, _>(move |req, pr| {
+        if let Some(input) = get_input(&state, &pr, &req.session_handle.0, KEYBOARD) {
+            input.keysym(req.keysym as u32, req.state);
+            pr.ok(&NotifyKeyboardKeysymReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyTouchDown, _>(move |req, pr| {
+        let handle = &req.session_handle.0;
+        if let Some((input, output)) = get_touch_input(&state, &pr, handle) {
+            input.touch_down(&output.wl, req.slot as i32, req.x, req.y);
+            input.touch_frame();
+            pr.ok(&NotifyTouchDownReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyTouchMotion, _>(move |req, pr| {
+        let handle = &req.session_handle.0;
+        if let Some((input, output)) = get_touch_input(&state, &pr, handle) {
+            input.touch_motion(&output.wl, req.slot as i32, req.x, req.y);
+            input.touch_frame();
+            pr.ok(&NotifyTouchMotionReply);
+        }
+    });
+    let state = state_.clone();
+    object.add_method::<NotifyTouchUp, _>(move |req, pr| {
+        if let Some(input) = get_input(&state, &pr, &req.session_handle.0, TOUCHSCREEN) {
+            input.touch_up(req.slot as i32);
+            input.touch_frame();
+            pr.ok(&NotifyTouchUpReply);
+        }
+    });
+    object.set_property::<AvailableDeviceTypes>(Variant::U32(AVAILABLE_DEVICE_TYPES.0));
+    object.set_property::<version>(Variant::U32(2));
+}
+
+fn dbus_create_session(
+    state: &Rc<PortalState>,
+    req: CreateSession,
+    reply: PendingReply<CreateSessionReply<'static>>,
+) {
+    log::info!("Create Remote Desktop Session {:#?}", req);
+    let rd = Rc::new(RemoteDesktop {
+        devices: Cell::new(AVAILABLE_DEVICE_TYPES),
+        capture: Cell::new(true),
+        input: Default::default(),
+        output: Default::default(),
+    });
+    match create_session(state, &req.session_handle.0, &req.app_id, Some(rd)) {
+        Ok(()) => reply.ok(&CreateSessionReply {
+            response: PORTAL_SUCCESS,
+            results: Default::default(),
+        }),
+        Err(e) => reply.err(e),
+    }
+}
+
+fn dbus_select_devices(
+    state: &Rc<PortalState>,
+    req: SelectDevices,
+    reply: PendingReply<SelectDevicesReply<'static>>,
+) {
+    let Some(s) = get_session(state, &reply, &req.session_handle.0) else {
+        return;
+    };
+    let Some(rd) = &s.remote_desktop else {
+        reply.err("Session is not a remote desktop session");
+        return;
+    };
+    match s.phase.get() {
+        ScreencastPhase::Init | ScreencastPhase::SourcesSelected => {}
+        _ => {
+            s.kill();
+            reply.err("Devices can only be selected before the session is started");
+            return;
+        }
+    }
+    for opt in req.options.iter() {
+        if opt.key == "types" {
+            if let Variant::U32(types) = opt.value {
+                rd.devices.set(DeviceTypes(types) & AVAILABLE_DEVICE_TYPES);
+            }
+        }
+    }
+    reply.ok(&SelectDevicesReply {
+        response: PORTAL_SUCCESS,
+        results: Default::default(),
+    });
+}
+
+fn dbus_start(state: &Rc<PortalState>, req: Start, reply: PendingReply<StartReply<'static>>) {
+    let Some(s) = get_session(state, &reply, &req.session_handle.0) else {
+        return;
+    };
+    if s.remote_desktop.is_none() {
+        reply.err("Session is not a remote desktop session");
+        return;
+    }
+    s.dbus_start(&req.handle, PendingStart::RemoteDesktop(reply));
+}
+
+fn get_input<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+    device: DeviceTypes,
+) -> Option<Rc<UsrJayVirtualInput>> {
+    let s = get_session(state, reply, handle)?;
+    let input = match s.phase.get() {
+        ScreencastPhase::Started(_) | ScreencastPhase::RemoteControl(_) => s
+            .remote_desktop
+            .as_ref()
+            .filter(|rd| rd.devices.get().contains(device))
+            .and_then(|rd| rd.input.get()),
+        _ => None,
+    };
+    if input.is_none() {
+        reply.err("Session cannot send events of this type");
+    }
+    input
+}
+
+fn get_output<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<Rc<PortalOutput>> {
+    let output = state
+        .screencasts
+        .get(handle)
+        .and_then(|s| s.remote_desktop.as_ref().and_then(|rd| rd.output.get()));
+    if output.is_none() {
+        reply.err("Session does not have a stream that supports absolute positions");
+    }
+    output
+}
+
+fn get_touch_input<T>(
+    state: &Rc<PortalState>,
+    reply: &PendingReply<T>,
+    handle: &str,
+) -> Option<(Rc<UsrJayVirtualInput>, Rc<PortalOutput>)> {
+    let input = get_input(state, reply, handle, TOUCHSCREEN)?;
+    let output = get_output(state, reply, handle)?;
+    Some((input, output))
+}
@@ -15,6 +15,7 @@ use {
         },
         portal::{
             ptl_display::{PortalDisplay, PortalDisplayId, PortalOutput},
+            ptl_remote_desktop::RemoteDesktop,
             ptl_screencast::screencast_gui::SelectionGui,
             PortalState, PORTAL_SUCCESS,
         },
@@ -27,6 +28,7 @@ use {
         wire_dbus::{
             org,
             org::freedesktop::impl_::portal::{
+                remote_desktop::StartReply as RemoteDesktopStartReply,
                 screen_cast::{
                     CreateSession, CreateSessionReply, SelectSources, SelectSourcesReply, Start,
                     StartReply,
@@ -58,6 +60,7 @@ pub struct ScreencastSession {
     pub app: String,
     session_obj: DbusObject,
     pub phase: CloneCell<ScreencastPhase>,
+    pub remote_desktop: Option<Rc<RemoteDesktop>>,
 }
 
 #[derive(Clone)]
@@ -69,16 +72,45 @@ pub enum ScreencastPhase {
     SelectingWorkspace(Rc<SelectingWorkspaceScreencast>),
     Starting(Rc<StartingScreencast>),
     Started(Rc<StartedScreencast>),
+    RemoteControl(Rc<PortalDisplay>),
     Terminated,
 }
 
 unsafe impl UnsafeCellCloneSafe for ScreencastPhase {}
 
+pub enum PendingStart {
+    ScreenCast(PendingReply<StartReply<'static>>),
+    RemoteDesktop(PendingReply<RemoteDesktopStartReply<'static>>),
+}
+
+impl PendingStart {
+    pub fn ok(&self, results: &[DictEntry<Cow<'static, str>, Variant<'static>>]) {
+        let results = Cow::Borrowed(results);
+        match self {
+            PendingStart::ScreenCast(r) => r.ok(&StartReply {
+                response: PORTAL_SUCCESS,
+                results,
+            }),
+            PendingStart::RemoteDesktop(r) => r.ok(&RemoteDesktopStartReply {
+                response: PORTAL_SUCCESS,
+                results,
+            }),
+        }
+    }
+
+    pub fn err(&self, msg: &str) {
+        match self {
+            PendingStart::ScreenCast(r) => r.err(msg),
+            PendingStart::RemoteDesktop(r) => r.err(msg),
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SelectingScreencastCore {
     pub session: Rc<ScreencastSession>,
     pub request_obj: Rc<DbusObject>,
-    pub reply: Rc<PendingReply<StartReply<'static>>>,
+    pub reply: Rc<PendingStart>,
 }
 
 pub struct SelectingScreencast {
@@ -101,7 +133,7 @@ pub struct SelectingWorkspaceScreencast {
 pub struct StartingScreencast {
     pub session: Rc<ScreencastSession>,
     pub request_obj: Rc<DbusObject>,
-    pub reply: Rc<PendingReply<StartReply<'static>>>,
+    pub reply: Rc<PendingStart>,
     pub node: Rc<PwClientNode>,
     pub dpy: Rc<PortalDisplay>,
     pub target: ScreencastTarget,
@@ -149,17 +181,17 @@ impl PwClientNodeOwner for StartingScreencast {
                 DynamicType::U32,
                 DynamicType::Array(Box::new(inner_type.clone())),
             ]);
-            let variants = &[DictEntry {
+            let mut variants = vec![DictEntry {
                 key: "streams".into(),
                 value: Variant::Array(
                     kt,
                     vec![Variant::U32(node_id), Variant::Array(inner_type, vec![])],
                 ),
             }];
-            self.reply.ok(&StartReply {
-                response: PORTAL_SUCCESS,
-                results: Cow::Borrowed(variants),
-            });
+            if let Some(rd) = &self.session.remote_desktop {
+                variants.push(rd.devices_result());
+            }
+            self.reply.ok(&variants);
         }
         let port = self.node.create_port(true);
         port.can_alloc_buffers.set(true);
@@ -238,6 +270,14 @@ impl SelectingScreencastCore {
             ("node.name".to_string(), "jay-desktop-portal".to_string()),
             ("node.driver".to_string(), "true".to_string()),
         ]);
+        if let Some(rd) = &self.session.remote_desktop {
+            match &target {
+                ScreencastTarget::Output(o) | ScreencastTarget::Workspace(o, _) => {
+                    rd.output.set(Some(o.clone()));
+                }
+                ScreencastTarget::Toplevel(_) => {}
+            }
+        }
         let starting = Rc::new(StartingScreencast {
             session: self.session.clone(),
             request_obj: self.request_obj.clone(),
@@ -255,12 +295,28 @@ impl SelectingScreencastCore {
             self.session.clone(),
         );
     }
+
+    pub fn remote_control(&self, dpy: &Rc<PortalDisplay>) {
+        if let Some(rd) = &self.session.remote_desktop {
+            self.reply.ok(&[rd.devices_result()]);
+        }
+        self.session
+            .phase
+            .set(ScreencastPhase::RemoteControl(dpy.clone()));
+        dpy.screencasts.set(
+            self.session.session_obj.path().to_owned(),
+            self.session.clone(),
+        );
+    }
 }
 
 impl ScreencastSession {
     pub(super) fn kill(&self) {
         self.session_obj.emit_signal(&Closed);
         self.state.screencasts.remove(self.session_obj.path());
+        if let Some(rd) = &self.remote_desktop {
+            rd.disconnect();
+        }
         match self.phase.set(ScreencastPhase::Terminated) {
             ScreencastPhase::Init => {}
             ScreencastPhase::SourcesSelected => {}
@@ -298,6 +354,16 @@ impl ScreencastSession {
                 s.node.con.destroy_obj(s.node.deref());
                 s.dpy.screencasts.remove(self.session_obj.path());
             }
+            ScreencastPhase::RemoteControl(dpy) => {
+                dpy.screencasts.remove(self.session_obj.path());
+            }
+        }
+    }
+
+    pub fn captures_screen(&self) -> bool {
+        match &self.remote_desktop {
+            Some(rd) => rd.capture.get(),
+            None => true,
         }
     }
 
@@ -321,16 +387,17 @@ impl ScreencastSession {
         });
     }
 
-    fn dbus_start(self: &Rc<Self>, req: Start<'_>, reply: PendingReply<StartReply<'static>>) {
-        match self.phase.get() {
-            ScreencastPhase::SourcesSelected => {}
+    pub(super) fn dbus_start(self: &Rc<Self>, handle: &str, reply: PendingStart) {
+        match (self.phase.get(), &self.remote_desktop) {
+            (ScreencastPhase::SourcesSelected, _) => {}
+            (ScreencastPhase::Init, Some(rd)) => rd.capture.set(false),
             _ => {
                 self.kill();
                 reply.err("Session is not in the correct phase for starting");
                 return;
             }
         }
-        let request_obj = match self.state.dbus.add_object(req.handle.to_string()) {
+        let request_obj = match self.state.dbus.add_object(handle.to_string()) {
             Ok(r) => r,
             Err(_) => {
                 self.kill();
@@ -487,23 +554,35 @@ fn dbus_create_session(
     reply: PendingReply<CreateSessionReply<'static>>,
 ) {
     log::info!("Create Session {:#?}", req);
-    if state.screencasts.contains(req.session_handle.0.deref()) {
-        reply.err("Session already exists");
-        return;
+    match create_session(state, &req.session_handle.0, &req.app_id, None) {
+        Ok(()) => reply.ok(&CreateSessionReply {
+            response: PORTAL_SUCCESS,
+            results: Default::default(),
+        }),
+        Err(e) => reply.err(e),
     }
-    let obj = match state.dbus.add_object(req.session_handle.0.to_string()) {
+}
+
+pub(super) fn create_session(
+    state: &Rc<PortalState>,
+    handle: &str,
+    app: &str,
+    remote_desktop: Option<Rc<RemoteDesktop>>,
+) -> Result<(), &'static str> {
+    if state.screencasts.contains(handle) {
+        return Err("Session already exists");
+    }
+    let obj = match state.dbus.add_object(handle.to_string()) {
         Ok(obj) => obj,
-        Err(_) => {
-            reply.err("Session path is not unique");
-            return;
-        }
+        Err(_) => return Err("Session path is not unique"),
     };
     let session = Rc::new(ScreencastSession {
         _id: state.id(),
         state: state.clone(),
-        app: req.app_id.to_string(),
+        app: app.to_string(),
         session_obj: obj,
         phase: CloneCell::new(ScreencastPhase::Init),
+        remote_desktop,
     });
     {
         use org::freedesktop::impl_::portal::session::*;
@@ -514,13 +593,8 @@ fn dbus_create_session(
         });
         session.session_obj.set_property::<version>(Variant::U32(4));
     }
-    state
-        .screencasts
-        .set(req.session_handle.0.to_string(), session);
-    reply.ok(&CreateSessionReply {
-        response: PORTAL_SUCCESS,
-        results: Default::default(),
-    });
+    state.screencasts.set(handle.to_string(), session);
+    Ok(())
 }
 
 fn dbus_select_sources(
@@ -535,11 +609,11 @@ fn dbus_select_sources(
 
 fn dbus_start(state: &Rc<PortalState>, req: Start, reply: PendingReply<StartReply<'static>>) {
     if let Some(s) = get_session(state, &reply, &req.session_handle.0) {
-        s.dbus_start(req, reply);
+        s.dbus_start(&req.handle, PendingStart::ScreenCast(reply));
     }
 }
 
-fn get_session<T>(
+pub(super) fn get_session<T>(
     state: &Rc<PortalState>,
     reply: &PendingReply<T>,
     handle: &str,
@@ -66,49 +66,65 @@ impl SelectionGui {
 }
 
 fn create_accept_gui(surface: &Rc<SelectionGuiSurface>) -> Rc<dyn GuiElement> {
-    let app = &surface.gui.screencast_session.app;
+    let session = &surface.gui.screencast_session;
+    let app = &session.app;
+    let action = match (session.remote_desktop.is_some(), session.captures_screen()) {
+        (false, _) => "capture the screen",
+        (true, true) => "capture the screen and control the keyboard and pointer",
+        (true, false) => "control the keyboard and pointer",
+    };
     let text = if app.is_empty() {
-        format!("An application wants to capture the screen")
+        format!("An application wants to {}", action)
     } else {
-        format!("`{}` wants to capture the screen", app)
+        format!("`{}` wants to {}", app, action)
     };
     let label = Rc::new(Label::default());
     *label.text.borrow_mut() = text;
+    if !session.captures_screen() {
+        let accept_button = static_button(surface, ButtonRole::Accept, "Allow");
+        let reject_button = static_button(surface, ButtonRole::Reject, "Reject");
+        style_buttons(&[&accept_button], &reject_button);
+        return create_flow(vec![label, accept_button, reject_button]);
+    }
     let accept_button = static_button(surface, ButtonRole::Accept, "Share This Output");
     let workspace_button = static_button(surface, ButtonRole::SelectWorkspace, "Share A Workspcae");
     let window_button = static_button(surface, ButtonRole::SelectWindow, "Share A Window");
     let reject_button = static_button(surface, ButtonRole::Reject, "Reject");
-    for button in [
-        &accept_button,
-        &workspace_button,
-        &window_button,
+    style_buttons(
+        &[&accept_button, &workspace_button, &window_button],
         &reject_button,
-    ] {
+    );
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label, accept_button];
+    if surface.gui.dpy.jc.caps.select_workspace.get() {
+        elements.push(workspace_button);
+    }
+    if surface.gui.dpy.jc.caps.window_capture.get() {
+        elements.push(window_button);
+    }
+    elements.push(reject_button);
+    create_flow(elements)
+}
+
+fn style_buttons(accept: &[&Rc<Button>], reject: &Rc<Button>) {
+    for button in accept.iter().copied().chain([reject]) {
         button.border_color.set(Color::from_gray(100));
         button.border.set(2.0);
         button.padding.set(5.0);
     }
-    for button in [&accept_button, &workspace_button, &window_button] {
+    for button in accept {
         button.bg_color.set(Color::from_rgb(170, 200, 170));
         button.bg_hover_color.set(Color::from_rgb(170, 255, 170));
     }
-    reject_button.bg_color.set(Color::from_rgb(200, 170, 170));
-    reject_button
-        .bg_hover_color
-        .set(Color::from_rgb(255, 170, 170));
+    reject.bg_color.set(Color::from_rgb(200, 170, 170));
+    reject.bg_hover_color.set(Color::from_rgb(255, 170, 170));
+}
+
+fn create_flow(elements: Vec<Rc<dyn GuiElement>>) -> Rc<dyn GuiElement> {
     let flow = Rc::new(Flow::default());
     flow.orientation.set(Orientation::Vertical);
     flow.cross_align.set(Align::Center);
     flow.in_margin.set(V_MARGIN);
     flow.cross_margin.set(H_MARGIN);
-    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label, accept_button];
-    if surface.gui.dpy.jc.caps.select_workspace.get() {
-        elements.push(workspace_button);
-    }
-    if surface.gui.dpy.jc.caps.window_capture.get() {
-        elements.push(window_button);
-    }
-    elements.push(reject_button);
     *flow.elements.borrow_mut() = elements;
     flow
 }
@@ -163,7 +179,13 @@ impl ButtonOwner for StaticButton {
                     gui.kill(false);
                 }
                 let dpy = &self.surface.output.dpy;
-                if self.role == ButtonRole::Accept {
+                let session = &self.surface.gui.screencast_session;
+                if let Some(rd) = &session.remote_desktop {
+                    rd.connect(dpy, seat);
+                }
+                if !session.captures_screen() {
+                    selecting.core.remote_control(dpy);
+                } else if self.role == ButtonRole::Accept {
                     selecting
                         .core
                         .starting(dpy, ScreencastTarget::Output(self.surface.output.clone()));
@@ -0,0 +1,616 @@
+mod screenshot_gui;
+
+use {
+    crate::{
+        cli::screenshot::xrgb8888_encode_png,
+        dbus::{prelude::Variant, DbusObject, DictEntry, PendingReply, TRUE},
+        format::XRGB8888,
+        portal::{
+            ptl_display::{PortalDisplay, PortalDisplayId, PortalOutput},
+            ptl_screenshot::screenshot_gui::{ColorPickerGui, ScreenshotGui},
+            PortalState, PORTAL_CANCELLED, PORTAL_SUCCESS,
+        },
+        utils::{
+            clonecell::{CloneCell, UnsafeCellCloneSafe},
+            copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
+        },
+        video::{
+            dmabuf::{DmaBuf, DmaBufPlane, PlaneVec},
+            gbm::GBM_BO_USE_LINEAR,
+        },
+        wire::{jay_screencast::Ready, jay_screenshot::Dmabuf},
+        wire_dbus::{
+            org,
+            org::freedesktop::impl_::portal::screenshot::{
+                PickColor, PickColorReply, Screenshot, ScreenshotReply,
+            },
+        },
+        wl_usr::usr_ifs::{
+            usr_jay_screencast::{UsrJayScreencast, UsrJayScreencastOwner},
+            usr_jay_screenshot::{UsrJayScreenshot, UsrJayScreenshotOwner},
+            usr_jay_select_toplevel::UsrJaySelectToplevel,
+            usr_jay_toplevel::UsrJayToplevel,
+        },
+    },
+    chrono::Local,
+    std::{
+        borrow::Cow,
+        cell::{Cell, RefCell},
+        fmt::Write as _,
+        fs::OpenOptions,
+        io::{ErrorKind, Write},
+        ops::Deref,
+        path::PathBuf,
+        rc::Rc,
+    },
+};
+
+pub struct ScreenshotRequest {
+    state: Rc<PortalState>,
+    pub app: String,
+    handle: String,
+    request_obj: CloneCell<Option<Rc<DbusObject>>>,
+    reply: ScreenshotRequestReply,
+    pub phase: CloneCell<ScreenshotPhase>,
+}
+
+enum ScreenshotRequestReply {
+    Screenshot(PendingReply<ScreenshotReply<'static>>),
+    PickColor(PendingReply<PickColorReply<'static>>),
+}
+
+#[derive(Clone)]
+pub enum ScreenshotPhase {
+    Init,
+    Selecting(Rc<SelectingScreenshot>),
+    SelectingWindow(Rc<SelectingWindowScreenshot>),
+    PickingColor(Rc<PickingColor>),
+    Capturing(Rc<CapturingScreenshot>),
+    Terminated,
+}
+
+unsafe impl UnsafeCellCloneSafe for ScreenshotPhase {}
+
+pub struct SelectingScreenshot {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ScreenshotGui>>,
+}
+
+pub struct SelectingWindowScreenshot {
+    pub request: Rc<ScreenshotRequest>,
+    pub dpy: Rc<PortalDisplay>,
+    pub selector: Rc<UsrJaySelectToplevel>,
+}
+
+pub struct PickingColor {
+    pub guis: CopyHashMap<PortalDisplayId, Rc<ColorPickerGui>>,
+}
+
+pub struct CapturingScreenshot {
+    parts: Vec<Rc<FrameCapture>>,
+    frames: RefCell<Vec<Option<Frame>>>,
+}
+
+struct CapturePart {
+    request: Rc<ScreenshotRequest>,
+    idx: usize,
+}
+
+pub enum CaptureTarget {
+    Screen,
+    Output(Rc<PortalOutput>),
+    Toplevel(Rc<UsrJayToplevel>),
+}
+
+enum CaptureSource {
+    Screenshot(Rc<UsrJayScreenshot>),
+    Screencast(Rc<UsrJayScreencast>),
+}
+
+pub struct FrameCapture {
+    dpy: Rc<PortalDisplay>,
+    source: CaptureSource,
+    detached: Cell<bool>,
+    buffers: RefCell<PlaneVec<DmaBuf>>,
+    pub owner: CloneCell<Option<Rc<dyn FrameCaptureOwner>>>,
+}
+
+pub trait FrameCaptureOwner {
+    fn done(&self, frame: Option<Frame>);
+}
+
+pub struct Frame {
+    pub width: u32,
+    pub height: u32,
+    pub stride: u32,
+    pub data: Vec<u8>,
+}
+
+impl Frame {
+    /// Returns the color of the pixel at the given position relative to the frame size.
+    pub fn sample(&self, rel_x: f32, rel_y: f32) -> [f64; 3] {
+        let x = ((rel_x * self.width as f32) as u32).min(self.width.saturating_sub(1));
+        let y = ((rel_y * self.height as f32) as u32).min(self.height.saturating_sub(1));
+        let offset = (y * self.stride + x * 4) as usize;
+        let px = &self.data[offset..offset + 4];
+        [
+            px[2] as f64 / 255.0,
+            px[1] as f64 / 255.0,
+            px[0] as f64 / 255.0,
+        ]
+    }
+}
+
+impl ScreenshotRequestReply {
+    fn send(&self, response: u32, results: &[DictEntry<Cow<'static, str>, Variant<'static>>]) {
+        let results = Cow::Borrowed(results);
+        match self {
+            ScreenshotRequestReply::Screenshot(r) => r.ok(&ScreenshotReply { response, results }),
+            ScreenshotRequestReply::PickColor(r) => r.ok(&PickColorReply { response, results }),
+        }
+    }
+}
+
+impl ScreenshotRequest {
+    pub(super) fn kill(&self) {
+        self.complete(PORTAL_CANCELLED, &[]);
+    }
+
+    fn complete(&self, response: u32, results: &[DictEntry<Cow<'static, str>, Variant<'static>>]) {
+        self.state.screenshots.remove(&self.handle);
+        match self.phase.set(ScreenshotPhase::Terminated) {
+            ScreenshotPhase::Terminated => return,
+            ScreenshotPhase::Init => {}
+            ScreenshotPhase::Selecting(s) => {
+                for (_, gui) in s.guis.lock().drain() {
+                    gui.kill(false);
+                }
+            }
+            ScreenshotPhase::SelectingWindow(s) => {
+                s.dpy.con.remove_obj(&*s.selector);
+            }
+            ScreenshotPhase::PickingColor(s) => {
+                for (_, gui) in s.guis.lock().drain() {
+                    gui.kill(false);
+                }
+            }
+            ScreenshotPhase::Capturing(c) => {
+                for part in &c.parts {
+                    part.kill();
+                    part.dpy.screenshots.remove(&self.handle);
+                }
+            }
+        }
+        self.reply.send(response, results);
+        self.request_obj.take();
+    }
+
+    pub fn capture(self: &Rc<Self>, dpy: &Rc<PortalDisplay>, target: CaptureTarget) {
+        self.capture_all(vec![(dpy.clone(), target)]);
+    }
+
+    /// Captures all targets and combines them side by side into a single screenshot.
+    fn capture_all(self: &Rc<Self>, targets: Vec<(Rc<PortalDisplay>, CaptureTarget)>) {
+        let mut parts = vec![];
+        for (idx, (dpy, target)) in targets.into_iter().enumerate() {
+            let capture = FrameCapture::new(&dpy, target);
+            capture.owner.set(Some(Rc::new(CapturePart {
+                request: self.clone(),
+                idx,
+            })));
+            dpy.screenshots.set(self.handle.clone(), self.clone());
+            parts.push(capture);
+        }
+        let frames = RefCell::new(parts.iter().map(|_| None).collect());
+        self.phase
+            .set(ScreenshotPhase::Capturing(Rc::new(CapturingScreenshot {
+                parts,
+                frames,
+            })));
+    }
+
+    fn save(&self, frame: Frame) {
+        let path = match save_frame(&frame) {
+            Ok(p) => p,
+            Err(e) => {
+                log::error!("Could not save the screenshot: {}", e);
+                self.kill();
+                return;
+            }
+        };
+        log::info!("Saved screenshot to {}", path.display());
+        let uri = file_uri(&path);
+        self.complete(
+            PORTAL_SUCCESS,
+            &[DictEntry {
+                key: "uri".into(),
+                value: Variant::String(uri.into()),
+            }],
+        );
+    }
+}
+
+impl FrameCaptureOwner for CapturePart {
+    fn done(&self, frame: Option<Frame>) {
+        let ScreenshotPhase::Capturing(c) = self.request.phase.get() else {
+            return;
+        };
+        c.parts[self.idx]
+            .dpy
+            .screenshots
+            .remove(&self.request.handle);
+        let Some(frame) = frame else {
+            self.request.kill();
+            return;
+        };
+        let frames = {
+            let mut frames = c.frames.borrow_mut();
+            frames[self.idx] = Some(frame);
+            if frames.iter().any(|f| f.is_none()) {
+                return;
+            }
+            frames.drain(..).flatten().collect()
+        };
+        self.request.save(combine_frames(frames));
+    }
+}
+
+impl ScreenshotRequest {
+    pub fn color_picked(&self, color: [f64; 3]) {
+        log::info!("User has picked the color {:?}", color);
+        self.complete(
+            PORTAL_SUCCESS,
+            &[DictEntry {
+                key: "color".into(),
+                value: Variant::Struct(color.iter().map(|c| Variant::F64(*c)).collect()),
+            }],
+        );
+    }
+}
+
+impl FrameCapture {
+    fn new(dpy: &Rc<PortalDisplay>, target: CaptureTarget) -> Rc<Self> {
+        let source = match &target {
+            CaptureTarget::Screen => CaptureSource::Screenshot(dpy.jc.take_screenshot(false)),
+            CaptureTarget::Output(_) | CaptureTarget::Toplevel(_) => {
+                CaptureSource::Screencast(dpy.jc.create_screencast())
+            }
+        };
+        let slf = Rc::new(Self {
+            dpy: dpy.clone(),
+            source,
+            detached: Cell::new(false),
+            buffers: Default::default(),
+            owner: Default::default(),
+        });
+        match &slf.source {
+            CaptureSource::Screenshot(s) => {
+                s.owner.set(Some(slf.clone()));
+            }
+            CaptureSource::Screencast(jsc) => {
+                jsc.owner.set(Some(slf.clone()));
+                match &target {
+                    CaptureTarget::Screen => {}
+                    CaptureTarget::Output(o) => {
+                        jsc.set_output(&o.jay);
+                        jsc.set_allow_all_workspaces(true);
+                    }
+                    CaptureTarget::Toplevel(t) => jsc.set_toplevel(t),
+                }
+                jsc.set_use_linear_buffers(true);
+                jsc.set_running(true);
+                jsc.configure();
+                if let CaptureTarget::Toplevel(t) = &target {
+                    dpy.con.remove_obj(&**t);
+                }
+            }
+        }
+        slf
+    }
+
+    pub fn kill(&self) {
+        self.owner.take();
+        self.detach();
+    }
+
+    fn detach(&self) {
+        if self.detached.replace(true) {
+            return;
+        }
+        match &self.source {
+            CaptureSource::Screenshot(s) => {
+                s.owner.take();
+            }
+            CaptureSource::Screencast(jsc) => {
+                jsc.con.remove_obj(jsc.deref());
+            }
+        }
+    }
+
+    fn finish(&self, frame: Option<Frame>) {
+        self.detach();
+        if let Some(owner) = self.owner.take() {
+            owner.done(frame);
+        }
+    }
+
+    fn map(&self, dmabuf: &DmaBuf) -> Option<Frame> {
+        let Some(ctx) = self.dpy.render_ctx.get() else {
+            log::error!("Display has no render context");
+            return None;
+        };
+        let bo = match ctx.ctx.gbm().import_dmabuf(dmabuf, GBM_BO_USE_LINEAR) {
+            Ok(bo) => Rc::new(bo),
+            Err(e) => {
+                log::error!("Could not import dmabuf: {}", ErrorFmt(e));
+                return None;
+            }
+        };
+        let map = match bo.map() {
+            Ok(map) => map,
+            Err(e) => {
+                log::error!("Could not map dmabuf: {}", ErrorFmt(e));
+                return None;
+            }
+        };
+        Some(Frame {
+            width: dmabuf.width as _,
+            height: dmabuf.height as _,
+            stride: map.stride() as _,
+            data: unsafe { map.data() }.to_vec(),
+        })
+    }
+}
+
+impl UsrJayScreenshotOwner for FrameCapture {
+    fn dmabuf(&self, ev: &Dmabuf) {
+        let mut planes = PlaneVec::new();
+        planes.push(DmaBufPlane {
+            offset: ev.offset,
+            stride: ev.stride,
+            fd: ev.fd.clone(),
+        });
+        let dmabuf = DmaBuf {
+            id: self.dpy.state.dma_buf_ids.next(),
+            width: ev.width as _,
+            height: ev.height as _,
+            format: XRGB8888,
+            modifier: (ev.modifier_hi as u64) << 32 | (ev.modifier_lo as u64),
+            planes,
+        };
+        let frame = self.map(&dmabuf);
+        self.finish(frame);
+    }
+
+    fn error(&self, msg: &str) {
+        log::error!("Could not take a screenshot: {}", msg);
+        self.finish(None);
+    }
+}
+
+impl UsrJayScreencastOwner for FrameCapture {
+    fn buffers(&self, buffers: PlaneVec<DmaBuf>) {
+        *self.buffers.borrow_mut() = buffers;
+    }
+
+    fn ready(&self, ev: &Ready) {
+        let frame = match self.buffers.borrow().get(ev.idx as usize) {
+            Some(buf) => self.map(buf),
+            _ => return,
+        };
+        self.finish(frame);
+    }
+
+    fn destroyed(&self) {
+        log::warn!("Capture source has been destroyed");
+        self.finish(None);
+    }
+}
+
+fn combine_frames(mut frames: Vec<Frame>) -> Frame {
+    if frames.len() == 1 {
+        return frames.pop().unwrap();
+    }
+    let width: u32 = frames.iter().map(|f| f.width).sum();
+    let height = frames.iter().map(|f| f.height).max().unwrap_or(0);
+    let stride = width * 4;
+    let mut data = vec![0; (stride * height) as usize];
+    let mut x = 0;
+    for frame in &frames {
+        let row_len = (frame.width * 4) as usize;
+        for y in 0..frame.height as usize {
+            let src = &frame.data[y * frame.stride as usize..][..row_len];
+            data[y * stride as usize + x..][..row_len].copy_from_slice(src);
+        }
+        x += row_len;
+    }
+    Frame {
+        width,
+        height,
+        stride,
+        data,
+    }
+}
+
+fn save_frame(frame: &Frame) -> Result<PathBuf, String> {
+    let dir = match dirs::picture_dir().or_else(dirs::home_dir) {
+        Some(d) => d,
+        _ => return Err("Could not determine the pictures directory".to_string()),
+    };
+    if let Err(e) = std::fs::create_dir_all(&dir) {
+        return Err(format!(
+            "Could not create `{}`: {}",
+            dir.display(),
+            ErrorFmt(e)
+        ));
+    }
+    let name = Local::now()
+        .format("Screenshot_%Y-%m-%d-%H%M%S")
+        .to_string();
+    let data = xrgb8888_encode_png(&frame.data, frame.width, frame.height, frame.stride);
+    for n in 0.. {
+        let path = match n {
+            0 => dir.join(format!("{name}.png")),
+            _ => dir.join(format!("{name}_{n}.png")),
+        };
+        let file = OpenOptions::new().write(true).create_new(true).open(&path);
+        let res = match file {
+            Ok(mut f) => f.write_all(&data),
+            Err(e) if e.kind() == ErrorKind::AlreadyExists => continue,
+            Err(e) => Err(e),
+        };
+        return match res {
+            Ok(()) => Ok(path),
+            Err(e) => Err(format!(
+                "Could not write `{}`: {}",
+                path.display(),
+                ErrorFmt(e)
+            )),
+        };
+    }
+    unreachable!()
+}
+
+fn file_uri(path: &std::path::Path) -> String {
+    use std::os::unix::ffi::OsStrExt;
+    let mut uri = "file://".to_string();
+    for &b in path.as_os_str().as_bytes() {
+        match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'/' | b'-' | b'_' | b'.' | b'~' => {
+                uri.push(b as char)
+            }
+            _ => {
+                let _ = write!(uri, "%{:02X}", b);
+            }
+        }
+    }
+    uri
+}
+
+pub(super) fn add_screenshot_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::screenshot::*;
+    let state = state_.clone();
+    object.add_method::<Screenshot, _>(move |req, pr| {
+        dbus_screenshot(&state, req, pr);
+    });
+    let state = state_.clone();
+    object.add_method::<PickColor, _>(move |req, pr| {
+        dbus_pick_color(&state, req, pr);
+    });
+    object.set_property::<version>(Variant::U32(2));
+}
+
+fn create_request(
+    state: &Rc<PortalState>,
+    handle: &str,
+    app: &str,
+    reply: ScreenshotRequestReply,
+) -> Option<Rc<ScreenshotRequest>> {
+    let request_obj = match state.dbus.add_object(handle.to_string()) {
+        Ok(r) => r,
+        Err(_) => {
+            match reply {
+                ScreenshotRequestReply::Screenshot(r) => r.err("Request handle is not unique"),
+                ScreenshotRequestReply::PickColor(r) => r.err("Request handle is not unique"),
+            }
+            return None;
+        }
+    };
+    let request = Rc::new(ScreenshotRequest {
+        state: state.clone(),
+        app: app.to_string(),
+        handle: handle.to_string(),
+        request_obj: Default::default(),
+        reply,
+        phase: CloneCell::new(ScreenshotPhase::Init),
+    });
+    {
+        use org::freedesktop::impl_::portal::request::*;
+        request_obj.add_method::<Close, _>({
+            let slf = request.clone();
+            move |_, pr| {
+                slf.kill();
+                pr.ok(&CloseReply);
+            }
+        });
+    }
+    request.request_obj.set(Some(Rc::new(request_obj)));
+    state.screenshots.set(handle.to_string(), request.clone());
+    Some(request)
+}
+
+fn dbus_screenshot(
+    state: &Rc<PortalState>,
+    req: Screenshot,
+    reply: PendingReply<ScreenshotReply<'static>>,
+) {
+    log::info!("Screenshot {:#?}", req);
+    let interactive = req
+        .options
+        .iter()
+        .any(|o| o.key == "interactive" && matches!(o.value, Variant::Bool(b) if b == TRUE));
+    let reply = ScreenshotRequestReply::Screenshot(reply);
+    let Some(request) = create_request(state, &req.handle.0, &req.app_id, reply) else {
+        return;
+    };
+    if !interactive {
+        let targets: Vec<_> = state
+            .displays
+            .lock()
+            .values()
+            .filter(|dpy| dpy.outputs.len() > 0)
+            .map(|dpy| (dpy.clone(), CaptureTarget::Screen))
+            .collect();
+        if targets.is_empty() {
+            log::warn!("There are no running displays");
+            request.kill();
+        } else {
+            request.capture_all(targets);
+        }
+        return;
+    }
+    let guis = CopyHashMap::new();
+    for dpy in state.displays.lock().values() {
+        if dpy.outputs.len() > 0 {
+            guis.set(dpy.id, ScreenshotGui::new(&request, dpy));
+        }
+    }
+    if guis.is_empty() {
+        log::warn!("There are no running displays");
+        request.kill();
+        return;
+    }
+    request
+        .phase
+        .set(ScreenshotPhase::Selecting(Rc::new(SelectingScreenshot {
+            guis,
+        })));
+}
+
+fn dbus_pick_color(
+    state: &Rc<PortalState>,
+    req: PickColor,
+    reply: PendingReply<PickColorReply<'static>>,
+) {
+    log::info!("Pick Color {:#?}", req);
+    let reply = ScreenshotRequestReply::PickColor(reply);
+    let Some(request) = create_request(state, &req.handle.0, &req.app_id, reply) else {
+        return;
+    };
+    let guis = CopyHashMap::new();
+    for dpy in state.displays.lock().values() {
+        if dpy.outputs.len() > 0 {
+            guis.set(dpy.id, ColorPickerGui::new(&request, dpy));
+        }
+    }
+    if guis.is_empty() {
+        log::warn!("There are no running displays");
+        request.kill();
+        return;
+    }
+    request
+        .phase
+        .set(ScreenshotPhase::PickingColor(Rc::new(PickingColor {
+            guis,
+        })));
+}
@@ -0,0 +1,368 @@
+use {
+    crate::{
+        cursor::KnownCursor,
+        gfx_api::GfxContext,
+        ifs::wl_seat::{wl_pointer::PRESSED, BTN_LEFT, BTN_RIGHT},
+        portal::{
+            ptl_display::{PortalDisplay, PortalOutput, PortalSeat},
+            ptl_screenshot::{
+                CaptureTarget, Frame, FrameCapture, FrameCaptureOwner, ScreenshotPhase,
+                ScreenshotRequest, SelectingWindowScreenshot,
+            },
+            ptr_gui::{
+                Align, Button, ButtonOwner, Flow, GuiElement, GuiElementData, Label, Orientation,
+                OverlayWindow, OverlayWindowOwner,
+            },
+        },
+        renderer::renderer_base::RendererBase,
+        theme::Color,
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap},
+        wl_usr::usr_ifs::{
+            usr_jay_select_toplevel::UsrJaySelectToplevelOwner, usr_jay_toplevel::UsrJayToplevel,
+        },
+    },
+    std::{cell::RefCell, rc::Rc},
+};
+
+const H_MARGIN: f32 = 30.0;
+const V_MARGIN: f32 = 20.0;
+
+pub struct ScreenshotGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<ScreenshotGuiSurface>>,
+}
+
+pub struct ScreenshotGuiSurface {
+    gui: Rc<ScreenshotGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+}
+
+struct StaticButton {
+    surface: Rc<ScreenshotGuiSurface>,
+    role: ButtonRole,
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+enum ButtonRole {
+    Screen,
+    Output,
+    Window,
+    Cancel,
+}
+
+impl ScreenshotGui {
+    pub fn new(request: &Rc<ScreenshotRequest>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(ScreenshotGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let sgs = Rc::new(ScreenshotGuiSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new(output),
+            });
+            let element = create_dialog(&sgs);
+            sgs.overlay.data.content.set(Some(element));
+            gui.dpy
+                .windows
+                .set(sgs.overlay.data.surface.id, sgs.overlay.data.clone());
+            gui.surfaces.set(output.global_id, sgs);
+        }
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        for (_, surface) in self.surfaces.lock().drain() {
+            surface.overlay.data.kill(false);
+        }
+        if let ScreenshotPhase::Selecting(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.kill();
+            }
+        }
+    }
+}
+
+fn create_dialog(surface: &Rc<ScreenshotGuiSurface>) -> Rc<dyn GuiElement> {
+    let app = &surface.gui.request.app;
+    let text = if app.is_empty() {
+        "An application wants to take a screenshot".to_string()
+    } else {
+        format!("`{}` wants to take a screenshot", app)
+    };
+    let label = Rc::new(Label::default());
+    *label.text.borrow_mut() = text;
+    let screen_button = static_button(surface, ButtonRole::Screen, "Capture The Screen");
+    let output_button = static_button(surface, ButtonRole::Output, "Capture This Output");
+    let window_button = static_button(surface, ButtonRole::Window, "Capture A Window");
+    let cancel_button = static_button(surface, ButtonRole::Cancel, "Cancel");
+    for button in [
+        &screen_button,
+        &output_button,
+        &window_button,
+        &cancel_button,
+    ] {
+        button.border_color.set(Color::from_gray(100));
+        button.border.set(2.0);
+        button.padding.set(5.0);
+    }
+    for button in [&screen_button, &output_button, &window_button] {
+        button.bg_color.set(Color::from_rgb(170, 200, 170));
+        button.bg_hover_color.set(Color::from_rgb(170, 255, 170));
+    }
+    cancel_button.bg_color.set(Color::from_rgb(200, 170, 170));
+    cancel_button
+        .bg_hover_color
+        .set(Color::from_rgb(255, 170, 170));
+    let flow = Rc::new(Flow::default());
+    flow.orientation.set(Orientation::Vertical);
+    flow.cross_align.set(Align::Center);
+    flow.in_margin.set(V_MARGIN);
+    flow.cross_margin.set(H_MARGIN);
+    let mut elements: Vec<Rc<dyn GuiElement>> = vec![label, screen_button, output_button];
+    if surface.gui.dpy.jc.caps.window_capture.get() {
+        elements.push(window_button);
+    }
+    elements.push(cancel_button);
+    *flow.elements.borrow_mut() = elements;
+    flow
+}
+
+impl OverlayWindowOwner for ScreenshotGuiSurface {
+    fn kill(&self, upwards: bool) {
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl ButtonOwner for StaticButton {
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if button != BTN_LEFT || state != PRESSED {
+            return;
+        }
+        let request = &self.surface.gui.request;
+        if self.role == ButtonRole::Cancel {
+            log::info!("User has cancelled the screenshot request");
+            request.kill();
+            return;
+        }
+        log::info!("User has accepted the request");
+        let selecting = match request.phase.get() {
+            ScreenshotPhase::Selecting(selecting) => selecting,
+            _ => return,
+        };
+        for (_, gui) in selecting.guis.lock().drain() {
+            gui.kill(false);
+        }
+        let dpy = &self.surface.output.dpy;
+        match self.role {
+            ButtonRole::Screen => request.capture(dpy, CaptureTarget::Screen),
+            ButtonRole::Output => {
+                request.capture(dpy, CaptureTarget::Output(self.surface.output.clone()))
+            }
+            ButtonRole::Window => {
+                let selector = dpy.jc.select_toplevel(&seat.wl);
+                let selecting = Rc::new(SelectingWindowScreenshot {
+                    request: request.clone(),
+                    dpy: dpy.clone(),
+                    selector: selector.clone(),
+                });
+                selector.owner.set(Some(selecting.clone()));
+                request
+                    .phase
+                    .set(ScreenshotPhase::SelectingWindow(selecting));
+            }
+            ButtonRole::Cancel => unreachable!(),
+        }
+    }
+}
+
+impl UsrJaySelectToplevelOwner for SelectingWindowScreenshot {
+    fn done(&self, tl: Option<Rc<UsrJayToplevel>>) {
+        let Some(tl) = tl else {
+            log::info!("User has aborted the selection");
+            self.request.kill();
+            return;
+        };
+        match self.request.phase.get() {
+            ScreenshotPhase::SelectingWindow(s) => {
+                self.dpy.con.remove_obj(&*s.selector);
+            }
+            _ => {
+                self.dpy.con.remove_obj(&*tl);
+                return;
+            }
+        }
+        log::info!("User has selected a window");
+        self.request.capture(&self.dpy, CaptureTarget::Toplevel(tl));
+    }
+}
+
+fn static_button(surface: &Rc<ScreenshotGuiSurface>, role: ButtonRole, text: &str) -> Rc<Button> {
+    let button = Rc::new(Button::default());
+    let slf = Rc::new(StaticButton {
+        surface: surface.clone(),
+        role,
+    });
+    button.owner.set(Some(slf));
+    *button.text.borrow_mut() = text.to_string();
+    button
+}
+
+pub struct ColorPickerGui {
+    request: Rc<ScreenshotRequest>,
+    dpy: Rc<PortalDisplay>,
+    surfaces: CopyHashMap<u32, Rc<ColorPickerSurface>>,
+}
+
+pub struct ColorPickerSurface {
+    gui: Rc<ColorPickerGui>,
+    output: Rc<PortalOutput>,
+    overlay: Rc<OverlayWindow>,
+    capture: Rc<FrameCapture>,
+    frame: RefCell<Option<Frame>>,
+}
+
+struct ColorPickerArea {
+    data: GuiElementData,
+    surface: CloneCell<Option<Rc<ColorPickerSurface>>>,
+}
+
+impl ColorPickerGui {
+    pub fn new(request: &Rc<ScreenshotRequest>, dpy: &Rc<PortalDisplay>) -> Rc<Self> {
+        let gui = Rc::new(ColorPickerGui {
+            request: request.clone(),
+            dpy: dpy.clone(),
+            surfaces: Default::default(),
+        });
+        for output in dpy.outputs.lock().values() {
+            let cps = Rc::new(ColorPickerSurface {
+                gui: gui.clone(),
+                output: output.clone(),
+                overlay: OverlayWindow::new_fullscreen(output),
+                capture: FrameCapture::new(dpy, CaptureTarget::Output(output.clone())),
+                frame: Default::default(),
+            });
+            cps.capture.owner.set(Some(cps.clone()));
+            let area = Rc::new(ColorPickerArea {
+                data: Default::default(),
+                surface: CloneCell::new(Some(cps.clone())),
+            });
+            cps.overlay.data.content.set(Some(area));
+            gui.dpy
+                .windows
+                .set(cps.overlay.data.surface.id, cps.overlay.data.clone());
+            gui.surfaces.set(output.global_id, cps);
+        }
+        gui
+    }
+
+    pub fn kill(&self, upwards: bool) {
+        for (_, surface) in self.surfaces.lock().drain() {
+            surface.capture.kill();
+            surface.overlay.data.kill(false);
+        }
+        if let ScreenshotPhase::PickingColor(s) = self.request.phase.get() {
+            s.guis.remove(&self.dpy.id);
+            if upwards && s.guis.is_empty() {
+                self.request.kill();
+            }
+        }
+    }
+}
+
+impl OverlayWindowOwner for ColorPickerSurface {
+    fn kill(&self, upwards: bool) {
+        self.capture.kill();
+        self.gui.dpy.windows.remove(&self.overlay.data.surface.id);
+        self.gui.surfaces.remove(&self.output.global_id);
+        if upwards && self.gui.surfaces.is_empty() {
+            self.gui.kill(true);
+        }
+    }
+}
+
+impl FrameCaptureOwner for ColorPickerSurface {
+    fn done(&self, frame: Option<Frame>) {
+        if frame.is_none() {
+            log::warn!("Could not capture the contents of an output");
+        }
+        *self.frame.borrow_mut() = frame;
+    }
+}
+
+impl ColorPickerSurface {
+    fn pick(&self, seat: &PortalSeat) {
+        let data = &self.overlay.data;
+        let Some(pos) = data.seats.get(&seat.global_id) else {
+            return;
+        };
+        let rel_x = pos.x.get() / data.width.get().max(1) as f32;
+        let rel_y = pos.y.get() / data.height.get().max(1) as f32;
+        let color = match &*self.frame.borrow() {
+            Some(frame) => frame.sample(rel_x, rel_y),
+            _ => {
+                log::warn!("The contents of the output have not been captured yet");
+                return;
+            }
+        };
+        self.gui.request.color_picked(color);
+    }
+}
+
+impl GuiElement for ColorPickerArea {
+    fn data(&self) -> &GuiElementData {
+        &self.data
+    }
+
+    fn layout(
+        &self,
+        _ctx: &Rc<dyn GfxContext>,
+        _scale: f32,
+        max_width: f32,
+        max_height: f32,
+    ) -> (f32, f32) {
+        (max_width, max_height)
+    }
+
+    fn render_at(&self, _r: &mut RendererBase, _x: f32, _y: f32) {
+        // nothing
+    }
+
+    fn child_at(&self, _x: f32, _y: f32) -> Option<Rc<dyn GuiElement>> {
+        None
+    }
+
+    fn hover_cursor(&self) -> KnownCursor {
+        KnownCursor::Crosshair
+    }
+
+    fn button(&self, seat: &PortalSeat, button: u32, state: u32) {
+        if state != PRESSED {
+            return;
+        }
+        let Some(surface) = self.surface.get() else {
+            return;
+        };
+        match button {
+            BTN_LEFT => surface.pick(seat),
+            BTN_RIGHT => {
+                log::info!("User has cancelled the color selection");
+                surface.gui.request.kill();
+            }
+            _ => {}
+        }
+    }
+
+    fn destroy(&self) {
+        self.surface.take();
+    }
+}
@@ -0,0 +1,102 @@
+use {
+    crate::{
+        dbus::{prelude::Variant, DbusObject, DictEntry},
+        portal::PortalState,
+        theme::Appearance,
+        wire_dbus::{org, org::freedesktop::impl_::portal::settings::SettingChanged},
+    },
+    std::rc::Rc,
+};
+
+const PORTAL_PATH: &str = "/org/freedesktop/portal/desktop";
+const APPEARANCE_NAMESPACE: &str = "org.freedesktop.appearance";
+
+const COLOR_SCHEME: &str = "color-scheme";
+const ACCENT_COLOR: &str = "accent-color";
+const CONTRAST: &str = "contrast";
+
+pub(super) fn add_settings_dbus_members(state_: &Rc<PortalState>, object: &DbusObject) {
+    use org::freedesktop::impl_::portal::settings::*;
+    let state = state_.clone();
+    object.add_method::<ReadAll, _>(move |req, pr| {
+        let mut value = vec![];
+        let requested = req.namespaces.is_empty()
+            || req
+                .namespaces
+                .iter()
+                .any(|ns| namespace_matches(ns, APPEARANCE_NAMESPACE));
+        if requested {
+            if let Some(appearance) = state.appearance.get() {
+                let settings = appearance_settings(&appearance)
+                    .into_iter()
+                    .map(|(key, value)| DictEntry {
+                        key: key.into(),
+                        value,
+                    })
+                    .collect();
+                value.push(DictEntry {
+                    key: APPEARANCE_NAMESPACE.into(),
+                    value: settings,
+                });
+            }
+        }
+        pr.ok(&ReadAllReply {
+            value: value.into(),
+        });
+    });
+    let state = state_.clone();
+    object.add_method::<Read, _>(move |req, pr| {
+        let value = match state.appearance.get() {
+            Some(a) if req.namespace == APPEARANCE_NAMESPACE => appearance_settings(&a)
+                .into_iter()
+                .find(|(key, _)| req.key == *key)
+                .map(|(_, value)| value),
+            _ => None,
+        };
+        match value {
+            Some(value) => pr.ok(&ReadReply { value }),
+            None => pr.err("Requested setting not found"),
+        }
+    });
+    object.set_property::<version>(Variant::U32(1));
+}
+
+pub(super) fn appearance_changed(state: &PortalState, appearance: Appearance) {
+    let old = state.appearance.replace(Some(appearance));
+    let changed = match old {
+        Some(old) => [
+            old.color_scheme != appearance.color_scheme,
+            old.accent_color != appearance.accent_color,
+            old.contrast != appearance.contrast,
+        ],
+        None => [true; 3],
+    };
+    for (changed, (key, value)) in changed.into_iter().zip(appearance_settings(&appearance)) {
+        if changed {
+            state.dbus.emit_signal(
+                PORTAL_PATH,
+                &SettingChanged {
+                    namespace: APPEARANCE_NAMESPACE.into(),
+                    key: key.into(),
+                    value,
+                },
+            );
+        }
+    }
+}
+
+fn appearance_settings(appearance: &Appearance) -> [(&'static str, Variant<'static>); 3] {
+    let [r, g, b] = appearance.accent_color.map(|c| Variant::F64(c as f64));
+    [
+        (COLOR_SCHEME, Variant::U32(appearance.color_scheme)),
+        (ACCENT_COLOR, Variant::Struct(vec![r, g, b])),
+        (CONTRAST, Variant::U32(appearance.contrast)),
+    ]
+}
+
+fn namespace_matches(pattern: &str, namespace: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => namespace.starts_with(prefix),
+        None => pattern == namespace,
+    }
+}
@@ -495,6 +495,8 @@ pub struct WindowData {
     pending_bufs: CopyHashMap<ZwpLinuxBufferParamsV1Id, Rc<GuiBufferPending>>,
     pub width: Cell<i32>,
     pub height: Cell<i32>,
+    pub fixed_size: Cell<Option<(i32, i32)>>,
+    pub clear_color: Cell<Color>,
     pub owner: CloneCell<Option<Rc<dyn WindowDataOwner>>>,
     pub seats: CopyHashMap<u32, Rc<GuiWindowSeatState>>,
 }
@@ -514,8 +516,10 @@ pub trait WindowDataOwner {
 
 impl WindowDataOwner for OverlayWindow {
     fn post_layout(&self) {
-        self.layer_surface
-            .set_size(self.data.width.get(), self.data.height.get());
+        if self.data.fixed_size.get().is_none() {
+            self.layer_surface
+                .set_size(self.data.width.get(), self.data.height.get());
+        }
         self.data.surface.commit();
     }
 
@@ -531,6 +535,11 @@ impl WindowDataOwner for OverlayWindow {
 
 const NUM_BUFFERS: usize = 2;
 
+const ANCHOR_TOP: u32 = 1;
+const ANCHOR_BOTTOM: u32 = 2;
+const ANCHOR_LEFT: u32 = 4;
+const ANCHOR_RIGHT: u32 = 8;
+
 impl OverlayWindow {
     pub fn new(output: &Rc<PortalOutput>) -> Rc<Self> {
         let data = WindowData::new(&output.dpy);
@@ -549,6 +558,31 @@ impl OverlayWindow {
         slf.data.surface.commit();
         slf
     }
+
+    /// Creates an overlay that covers the whole output.
+    ///
+    /// The window is transparent and its content is laid out with the size of the output.
+    pub fn new_fullscreen(output: &Rc<PortalOutput>) -> Rc<Self> {
+        let data = WindowData::new(&output.dpy);
+        data.fixed_size.set(Some((1, 1)));
+        data.clear_color.set(Color::TRANSPARENT);
+        let layer_surface = output
+            .dpy
+            .ls
+            .get_layer_surface(&data.surface, &output.wl, OVERLAY);
+        layer_surface.set_anchor(ANCHOR_TOP | ANCHOR_BOTTOM | ANCHOR_LEFT | ANCHOR_RIGHT);
+        layer_surface.set_exclusive_zone(-1);
+        layer_surface.set_size(0, 0);
+        let slf = Rc::new(Self {
+            layer_surface,
+            data,
+            owner: Default::default(),
+        });
+        slf.data.owner.set(Some(slf.clone()));
+        slf.layer_surface.owner.set(Some(slf.clone()));
+        slf.data.surface.commit();
+        slf
+    }
 }
 
 impl WindowData {
@@ -569,6 +603,8 @@ impl WindowData {
             pending_bufs: Default::default(),
             width: Cell::new(0),
             height: Cell::new(0),
+            fixed_size: Cell::new(None),
+            clear_color: Cell::new(Color::from_gray(0)),
             owner: Default::default(),
             render_trigger: Default::default(),
             render_task: Cell::new(None),
@@ -599,7 +635,14 @@ impl WindowData {
             Some(c) => c,
             _ => return,
         };
-        let (mut width, mut height) = content.layout(&ctx.ctx, scale, f32::INFINITY, f32::INFINITY);
+        let (mut width, mut height) = match self.fixed_size.get() {
+            Some((width, height)) => {
+                let (width, height) = (width as f32, height as f32);
+                content.layout(&ctx.ctx, scale, width, height);
+                (width, height)
+            }
+            _ => content.layout(&ctx.ctx, scale, f32::INFINITY, f32::INFINITY),
+        };
         content.data().width.set(width);
         content.data().height.set(height);
         width = width.max(1.0);
@@ -637,7 +680,7 @@ impl WindowData {
 
         let res = buf
             .fb
-            .render_custom(self.scale.get(), Some(&Color::from_gray(0)), &mut |r| {
+            .render_custom(self.scale.get(), Some(&self.clear_color.get()), &mut |r| {
                 if let Some(content) = self.content.get() {
                     content.render_at(r, 0.0, 0.0)
                 }
@@ -876,7 +919,16 @@ impl UsrWpFractionalScaleOwner for WindowData {
 }
 
 impl UsrWlrLayerSurfaceOwner for OverlayWindow {
-    fn configure(&self, _ev: &Configure) {
+    fn configure(&self, ev: &Configure) {
+        if let Some(size) = self.data.fixed_size.get() {
+            let new_size = (ev.width as i32, ev.height as i32);
+            if new_size.0 > 0 && new_size.1 > 0 && new_size != size {
+                self.data.fixed_size.set(Some(new_size));
+                self.data.layout();
+                self.data.allocate_buffers();
+                return;
+            }
+        }
         self.data.schedule_render();
     }
 
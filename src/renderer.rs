@@ -1,6 +1,7 @@
 use {
     crate::{
-        gfx_api::{AcquireSync, GfxApiOpt, ReleaseSync, SampleRect},
+        animation::{lerp, lerp_rect, Animation, WINDOW_ZOOM},
+        gfx_api::{AcquireSync, BlurCache, BlurCacheFrame, GfxApiOpt, ReleaseSync, SampleRect},
         ifs::{
             wl_callback::WlCallback,
             wl_surface::{
@@ -18,17 +19,21 @@ use {
         theme::Color,
         tree::{
             ContainerNode, DisplayNode, FloatNode, OutputNode, PlaceholderNode, ToplevelData,
-            ToplevelNodeBase, WorkspaceNode,
+            ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
+        wire::WlSurfaceId,
     },
+    jay_config::animation::WorkspaceAnimationStyle,
     std::{
         fmt::{Debug, Formatter},
+        mem,
         ops::Deref,
         rc::Rc,
         slice,
     },
 };
 
+pub mod damage_log;
 pub mod renderer_base;
 
 #[derive(Default)]
@@ -64,7 +69,14 @@ pub struct Renderer<'a> {
     pub state: &'a State,
     pub result: Option<&'a mut RenderResult>,
     pub logical_extents: Rect,
+    /// The global extents of the rendered node.
+    pub scene: Rect,
     pub pixel_extents: Rect,
+    pub opacity: f32,
+    /// The time at which the rendered frame will be presented.
+    pub animation_time_usec: u64,
+    /// Whether an animation is still running at `animation_time_usec`.
+    pub animating: bool,
 }
 
 impl Renderer<'_> {
@@ -91,11 +103,59 @@ impl Renderer<'_> {
     }
 
     pub fn render_output(&mut self, output: &OutputNode, x: i32, y: i32) {
+        self.animation_time_usec = output.next_frame_time_usec();
+        self.animating = false;
+        self.render_output_contents(output, x, y);
+        if self.state.idle.dimmed.get() {
+            let pos = output.global.pos.get();
+            let bounds = Rect::new_sized(x, y, pos.width(), pos.height()).unwrap();
+            let color = Color::from_rgba_straight(0, 0, 0, 128);
+            self.base.fill_boxes(&[bounds], &color);
+        }
+        if self.animating {
+            self.state.damage_log.damage_all();
+        }
+        if self.result.is_some() {
+            output.animation_frame_pending.set(self.animating);
+        }
+    }
+
+    fn render_builtin_lock(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let pos = output.global.pos.get();
+        let c = self.state.theme.colors.background.get();
+        self.base.fill_boxes2(
+            slice::from_ref(&Rect::new_sized(0, 0, pos.width(), pos.height()).unwrap()),
+            &c,
+            x,
+            y,
+        );
+        let rd = output.render_data.borrow_mut();
+        if let Some(prompt) = &rd.lock_prompt {
+            let (x, y) = self.base.scale_point(x + prompt.tex_x, y + prompt.tex_y);
+            self.base.render_texture(
+                &prompt.tex.texture,
+                None,
+                x,
+                y,
+                None,
+                None,
+                output.global.persistent.scale.get(),
+                None,
+                None,
+                AcquireSync::None,
+                ReleaseSync::None,
+            );
+        }
+    }
+
+    fn render_output_contents(&mut self, output: &OutputNode, x: i32, y: i32) {
         if self.state.lock.locked.get() {
             if let Some(surface) = output.lock_surface.get() {
                 if surface.surface.buffer.is_some() {
                     self.render_surface(&surface.surface, x, y, None);
                 }
+            } else if self.state.lock.builtin.is_some() {
+                self.render_builtin_lock(output, x, y);
             }
             return;
         }
@@ -117,6 +177,7 @@ impl Renderer<'_> {
         if let Some(ws) = output.workspace.get() {
             fullscreen = ws.fullscreen.get();
         }
+        let mut transition_ws = None;
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         if let Some(fs) = fullscreen {
@@ -186,10 +247,21 @@ impl Renderer<'_> {
                 }
             }
             if let Some(ws) = output.workspace.get() {
-                self.render_workspace(&ws, x, y + th + 1);
+                match self.workspace_transition(output) {
+                    Some(p) => {
+                        transition_ws = Some(ws.clone());
+                        self.render_workspace_transition(output, &ws, p, x, y + th + 1);
+                    }
+                    _ => self.render_workspace(&ws, x, y + th + 1),
+                }
             }
         }
         for stacked in self.state.root.stacked.iter() {
+            if let Some(ws) = &transition_ws {
+                if ws.stacked.iter().any(|s| s.node_id() == stacked.node_id()) {
+                    continue;
+                }
+            }
             if stacked.node_visible() {
                 self.base.ops.push(GfxApiOpt::Sync);
                 let pos = stacked.node_absolute_position();
@@ -199,6 +271,7 @@ impl Renderer<'_> {
                 }
             }
         }
+        self.render_closing_windows(output, x, y);
         render_layer!(output.layers[2]);
         render_layer!(output.layers[3]);
         if let Some(ws) = output.workspace.get() {
@@ -210,9 +283,149 @@ impl Renderer<'_> {
         }
     }
 
+    /// Returns the progress of the workspace transition of the output.
+    fn workspace_transition(&mut self, output: &OutputNode) -> Option<f64> {
+        let mut transition = output.workspace_transition.borrow_mut();
+        let t = transition.as_ref()?;
+        let valid = !t.old.visible.get() && t.old.output.get().id == output.id;
+        let progress = match valid {
+            true => self.animation_progress(Some(t.animation)),
+            false => None,
+        };
+        if progress.is_none() {
+            *transition = None;
+        }
+        progress
+    }
+
+    fn render_workspace_transition(
+        &mut self,
+        output: &OutputNode,
+        ws: &WorkspaceNode,
+        p: f64,
+        x: i32,
+        y: i32,
+    ) {
+        let Some((old, forward)) = output
+            .workspace_transition
+            .borrow()
+            .as_ref()
+            .map(|t| (t.old.clone(), t.forward))
+        else {
+            return;
+        };
+        let workspaces = match self.state.animations.workspace_style.get() {
+            WorkspaceAnimationStyle::Slide => {
+                let width = ws.position.get().width();
+                let width = if forward { width } else { -width };
+                [
+                    (&*old, lerp(0, -width, p), 1.0),
+                    (ws, lerp(width, 0, p), 1.0),
+                ]
+            }
+            WorkspaceAnimationStyle::Fade => [(&*old, 0, 1.0 - p), (ws, 0, p)],
+        };
+        let opos = output.global.pos.get();
+        let alpha = self.base.alpha;
+        for (ws, dx, a) in workspaces {
+            self.base.alpha = alpha * a as f32;
+            self.render_workspace(ws, x + dx, y);
+            for stacked in ws.stacked.iter() {
+                self.base.ops.push(GfxApiOpt::Sync);
+                let pos = stacked.node_absolute_position();
+                let (sx, sy) = opos.translate(pos.x1(), pos.y1());
+                stacked.node_render(self, sx + dx, sy, None);
+            }
+        }
+        self.base.alpha = alpha;
+    }
+
+    fn render_closing_windows(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let opos = output.global.pos.get();
+        let mut windows = output.closing_windows.borrow_mut();
+        let time = self.animation_time_usec;
+        windows.retain(|w| w.animation.progress(time).is_some());
+        for w in windows.iter() {
+            let Some(p) = self.animation_progress(Some(w.animation)) else {
+                continue;
+            };
+            let rect = w.rect.move_(x - opos.x1(), y - opos.y1());
+            let alpha = self.base.alpha;
+            self.base.alpha = alpha * (1.0 - p) as f32;
+            self.render_zoomed(rect, 1.0 - (1.0 - WINDOW_ZOOM) * p, |slf, dx, dy| {
+                let (x1, y1) = slf.base.scale_point(rect.x1() + dx, rect.y1() + dy);
+                let (x2, y2) = slf.base.scale_point(rect.x2() + dx, rect.y2() + dy);
+                slf.base.render_texture(
+                    &w.texture,
+                    None,
+                    x1,
+                    y1,
+                    Some(w.source),
+                    Some((x2 - x1, y2 - y1)),
+                    slf.base.scale,
+                    None,
+                    None,
+                    AcquireSync::None,
+                    ReleaseSync::None,
+                );
+            });
+            self.base.alpha = alpha;
+        }
+    }
+
+    /// Returns the progress of the animation and records whether it is still running.
+    fn animation_progress(&mut self, animation: Option<Animation>) -> Option<f64> {
+        let p = animation?.progress(self.animation_time_usec);
+        self.animating |= p.is_some();
+        p
+    }
+
+    /// Scales all operations of `f` by `factor` around the center of `rect`.
+    ///
+    /// `f` receives the offset that must be added to all logical coordinates.
+    fn render_zoomed<F: FnOnce(&mut Self, i32, i32)>(&mut self, rect: Rect, factor: f64, f: F) {
+        if factor == 1.0 {
+            f(self, 0, 0);
+            return;
+        }
+        let cx = (rect.x1() + rect.x2()) as f64 / 2.0;
+        let cy = (rect.y1() + rect.y2()) as f64 / 2.0;
+        let dx = (cx / factor - cx).round() as i32;
+        let dy = (cy / factor - cy).round() as i32;
+        let scaled = mem::replace(&mut self.base.scaled, true);
+        let scalef = self.base.scalef;
+        self.base.scalef = scalef * factor;
+        f(self, dx, dy);
+        self.base.scalef = scalef;
+        self.base.scaled = scaled;
+    }
+
+    /// Applies the open animation of a toplevel occupying `rect` to the operations of `f`.
+    fn render_opening<F: FnOnce(&mut Self, i32, i32)>(
+        &mut self,
+        tl_data: &ToplevelData,
+        rect: Rect,
+        f: F,
+    ) {
+        let animation = tl_data.open_animation.get();
+        let Some(p) = self.animation_progress(animation) else {
+            if animation.is_some() {
+                tl_data.open_animation.set(None);
+            }
+            f(self, 0, 0);
+            return;
+        };
+        let alpha = self.base.alpha;
+        self.base.alpha = alpha * p as f32;
+        self.render_zoomed(rect, WINDOW_ZOOM + (1.0 - WINDOW_ZOOM) * p, f);
+        self.base.alpha = alpha;
+    }
+
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
         if let Some(node) = workspace.container.get() {
-            self.render_container(&node, x, y)
+            let pos = workspace.position.get();
+            let rect = workspace.container_rect();
+            self.render_container(&node, x + rect.x1() - pos.x1(), y + rect.y1() - pos.y1())
         }
     }
 
@@ -290,37 +503,74 @@ impl Renderer<'_> {
                 }
             }
         }
+        let rounded = self.base.rounded;
         if let Some(child) = container.mono_child.get() {
-            let body = container.mono_body.get().move_(x, y);
-            let body = self.base.scale_rect(body);
+            let body = container.mono_body.get();
             let content = container.mono_content.get();
-            child
-                .node
-                .node_render(self, x + content.x1(), y + content.y1(), Some(&body));
+            self.render_container_child(&child.node, body, content, x, y);
         } else {
             for child in container.children.iter() {
-                let body = child.body.get();
+                let mut body = child.body.get();
                 if body.x1() >= container.width.get() || body.y1() >= container.height.get() {
                     break;
                 }
-                let body = body.move_(x, y);
-                let body = self.base.scale_rect(body);
-                let content = child.content.get();
-                child
-                    .node
-                    .node_render(self, x + content.x1(), y + content.y1(), Some(&body));
+                let mut content = child.content.get();
+                let animation = child.layout_animation.get();
+                if let Some(p) = self.animation_progress(animation.map(|a| a.1)) {
+                    let from = animation.unwrap().0;
+                    let animated = lerp_rect(from, body, p);
+                    content = content.move_(animated.x1() - body.x1(), animated.y1() - body.y1());
+                    body = animated;
+                }
+                self.render_container_child(&child.node, body, content, x, y);
             }
         }
+        self.base.set_rounded(rounded);
         self.render_tl_aux(container.tl_data(), None, false);
     }
 
+    fn render_container_child(
+        &mut self,
+        node: &Rc<dyn ToplevelNode>,
+        body: Rect,
+        content: Rect,
+        x: i32,
+        y: i32,
+    ) {
+        let radius = self.state.theme.sizes.corner_radius.get();
+        let body = body.move_(x, y);
+        self.render_opening(node.tl_data(), body, |slf, dx, dy| {
+            let body = body.move_(dx, dy);
+            if slf.wants_blur(node.tl_data()) {
+                slf.render_tl_blur(&**node, body, radius);
+            }
+            if radius > 0 {
+                slf.base
+                    .set_rounded(Some(slf.base.rounded_rect(body, radius, 0)));
+            }
+            let body = slf.base.scale_rect(body);
+            node.node_render(
+                slf,
+                x + dx + content.x1(),
+                y + dy + content.y1(),
+                Some(&body),
+            );
+        });
+    }
+
     pub fn render_xwindow(&mut self, tl: &Xwindow, x: i32, y: i32, bounds: Option<&Rect>) {
+        let opacity = self.window_opacity(tl.tl_data());
+        let opacity = mem::replace(&mut self.opacity, opacity);
         self.render_surface(&tl.x.surface, x, y, bounds);
+        self.opacity = opacity;
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
     pub fn render_xdg_toplevel(&mut self, tl: &XdgToplevel, x: i32, y: i32, bounds: Option<&Rect>) {
+        let opacity = self.window_opacity(tl.tl_data());
+        let opacity = mem::replace(&mut self.opacity, opacity);
         self.render_xdg_surface(&tl.xdg, x, y, bounds);
+        self.opacity = opacity;
         self.render_tl_aux(tl.tl_data(), bounds, true);
     }
 
@@ -398,7 +648,10 @@ impl Renderer<'_> {
         } else {
             size = self.base.scale_point(size.0, size.1);
         }
-        let alpha = surface.alpha();
+        let mut alpha = surface.alpha();
+        if self.opacity < 1.0 {
+            alpha = Some(alpha.unwrap_or(1.0) * self.opacity);
+        }
         if let Some(children) = children.deref() {
             macro_rules! render {
                 ($children:expr) => {
@@ -486,6 +739,20 @@ impl Renderer<'_> {
             Some(c) => c,
             _ => return,
         };
+        let pos = floating.position.get();
+        let frame = Rect::new_sized(x, y, pos.width(), pos.height()).unwrap();
+        self.render_opening(child.tl_data(), frame, |slf, dx, dy| {
+            slf.render_floating_frame(floating, &child, x + dx, y + dy);
+        });
+    }
+
+    fn render_floating_frame(
+        &mut self,
+        floating: &FloatNode,
+        child: &Rc<dyn ToplevelNode>,
+        x: i32,
+        y: i32,
+    ) {
         let pos = floating.position.get();
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
@@ -499,6 +766,20 @@ impl Renderer<'_> {
             theme.colors.unfocused_title_background.get()
         };
         let uc = theme.colors.separator.get();
+        let radius = theme.sizes.corner_radius.get();
+        let shadow = theme.sizes.shadow_size.get();
+        let frame = Rect::new_sized(x, y, pos.width(), pos.height()).unwrap();
+        if shadow > 0 {
+            self.render_shadow(frame, radius, shadow);
+        }
+        if self.wants_blur(child.tl_data()) {
+            self.render_tl_blur(&**child, frame, radius);
+        }
+        let rounded = self.base.rounded;
+        if radius > 0 {
+            self.base
+                .set_rounded(Some(self.base.rounded_rect(frame, radius, 0)));
+        }
         let borders = [
             Rect::new_sized(x, y, pos.width(), bw).unwrap(),
             Rect::new_sized(x, y + bw, bw, pos.height() - bw).unwrap(),
@@ -534,12 +815,102 @@ impl Renderer<'_> {
             pos.height() - 2 * bw - th - 1,
         )
         .unwrap();
+        if radius > 0 {
+            // The top corners of this rectangle are hidden behind the title so that only
+            // the bottom corners of the body are rounded.
+            let inner = Rect::new(body.x1(), y + bw, body.x2(), body.y2()).unwrap();
+            self.base
+                .set_rounded(Some(self.base.rounded_rect(inner, radius - bw, 0)));
+        }
         let scissor_body = self.base.scale_rect(body);
         child.node_render(self, body.x1(), body.y1(), Some(&scissor_body));
+        self.base.set_rounded(rounded);
+    }
+
+    fn render_shadow(&mut self, rect: Rect, radius: i32, size: i32) {
+        let color = self.state.theme.colors.shadow.get();
+        let grow = |rect: Rect, n: i32| {
+            Rect::new(rect.x1() - n, rect.y1() - n, rect.x2() + n, rect.y2() + n).unwrap()
+        };
+        let edge = grow(rect, size / 2);
+        let shadow = self.base.rounded_rect(edge, radius + size / 2, size);
+        let rounded = self.base.set_rounded(Some(shadow));
+        self.base.fill_boxes(&[grow(rect, size)], &color);
+        self.base.set_rounded(rounded);
+        self.base.ops.push(GfxApiOpt::Sync);
+    }
+
+    fn wants_blur(&self, tl_data: &ToplevelData) -> bool {
+        let app_ids = self.state.theme.blur_app_ids.borrow();
+        !app_ids.is_empty() && app_ids.contains(tl_data.app_id.borrow().as_str())
+    }
+
+    fn window_opacity(&self, tl_data: &ToplevelData) -> f32 {
+        if let Some(opacity) = tl_data.opacity.get() {
+            return opacity;
+        }
+        let opacities = self.state.theme.window_opacities.borrow();
+        if opacities.is_empty() {
+            return 1.0;
+        }
+        opacities
+            .get(tl_data.app_id.borrow().as_str())
+            .copied()
+            .unwrap_or(1.0)
+    }
+
+    fn render_tl_blur(&mut self, tl: &dyn ToplevelNode, rect: Rect, radius: i32) {
+        let origin = tl.tl_scanout_surface().map(|s| s.id);
+        self.render_blur(rect, radius, &tl.tl_data().blur_cache, origin);
+    }
+
+    /// Blurs the area behind `rect`.
+    ///
+    /// The previous result is reused if nothing except the surfaces rooted at `origin`
+    /// has changed in this area.
+    fn render_blur(
+        &mut self,
+        rect: Rect,
+        radius: i32,
+        cache: &Rc<BlurCache>,
+        origin: Option<WlSurfaceId>,
+    ) {
+        let log = &self.state.damage_log;
+        let frame = BlurCacheFrame {
+            serial: log.serial(),
+            scene: self.scene,
+            rect,
+        };
+        let valid = cache.produced.get().is_some_and(|p| {
+            let global = rect.move_(self.scene.x1(), self.scene.y1());
+            p.scene == frame.scene
+                && p.rect == frame.rect
+                && log.unchanged_since(p.serial, global, origin)
+        });
+        cache.valid.set(valid);
+        cache.pending.set(Some(frame));
+        let sizes = &self.state.theme.sizes;
+        let passes = sizes.blur_passes.get() as u32;
+        self.base.blur(
+            rect,
+            radius,
+            passes,
+            sizes.blur_offset.get(),
+            Some(cache.clone()),
+        );
     }
 
     pub fn render_layer_surface(&mut self, surface: &ZwlrLayerSurfaceV1, x: i32, y: i32) {
         let body = surface.position().at_point(x, y);
+        if self
+            .state
+            .theme
+            .blur_layer_namespaces
+            .borrow()
+            .contains(&surface.namespace)
+        {
+            self.render_blur(body, 0, &surface.blur_cache, Some(surface.surface.id));
+        }
         let body = self.base.scale_rect(body);
         self.render_surface(&surface.surface, x, y, Some(&body));
     }
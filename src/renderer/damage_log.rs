@@ -0,0 +1,87 @@
+use {
+    crate::{rect::Rect, utils::numcell::NumCell, wire::WlSurfaceId},
+    std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+    },
+};
+
+#[cfg(test)]
+mod tests;
+
+/// The maximum number of damaged rectangles that are remembered.
+///
+/// Once this number is exceeded, the oldest rectangle is turned into damage of the
+/// entire scene.
+const MAX_ENTRIES: usize = 64;
+
+/// Records which parts of the scene have changed.
+///
+/// Cached blurs use this to determine whether the contents behind them have changed
+/// since they were produced.
+#[derive(Default)]
+pub struct DamageLog {
+    serial: NumCell<u64>,
+    /// Everything up to and including this serial damaged the entire scene.
+    full: Cell<u64>,
+    entries: RefCell<VecDeque<DamageEntry>>,
+}
+
+struct DamageEntry {
+    serial: u64,
+    rect: Rect,
+    origin: WlSurfaceId,
+}
+
+impl DamageLog {
+    /// Returns the serial of the most recent damage.
+    pub fn serial(&self) -> u64 {
+        self.serial.get()
+    }
+
+    /// Marks the entire scene as damaged.
+    pub fn damage_all(&self) {
+        self.full.set(self.next_serial());
+        self.entries.borrow_mut().clear();
+    }
+
+    /// Marks a rectangle in global coordinates as damaged by the surface tree rooted at
+    /// `origin`.
+    pub fn damage(&self, rect: Rect, origin: WlSurfaceId) {
+        if rect.is_empty() {
+            return;
+        }
+        let serial = self.next_serial();
+        let entries = &mut *self.entries.borrow_mut();
+        if entries.len() == MAX_ENTRIES {
+            if let Some(entry) = entries.pop_front() {
+                self.full.set(entry.serial);
+            }
+        }
+        entries.push_back(DamageEntry {
+            serial,
+            rect,
+            origin,
+        });
+    }
+
+    /// Returns whether `rect` has not been damaged since `serial`.
+    ///
+    /// Damage caused by the surface tree rooted at `origin` is ignored since it is drawn
+    /// on top of the area behind it.
+    pub fn unchanged_since(&self, serial: u64, rect: Rect, origin: Option<WlSurfaceId>) -> bool {
+        if serial < self.full.get() {
+            return false;
+        }
+        self.entries
+            .borrow()
+            .iter()
+            .rev()
+            .take_while(|e| e.serial > serial)
+            .all(|e| Some(e.origin) == origin || !e.rect.intersects(&rect))
+    }
+
+    fn next_serial(&self) -> u64 {
+        self.serial.fetch_add(1) + 1
+    }
+}
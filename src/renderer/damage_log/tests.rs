@@ -0,0 +1,53 @@
+use crate::{
+    rect::Rect,
+    renderer::damage_log::{DamageLog, MAX_ENTRIES},
+    wire::WlSurfaceId,
+};
+
+const A: WlSurfaceId = WlSurfaceId::from_raw(10);
+const B: WlSurfaceId = WlSurfaceId::from_raw(11);
+
+fn rect(x1: i32, y1: i32, x2: i32, y2: i32) -> Rect {
+    Rect::new(x1, y1, x2, y2).unwrap()
+}
+
+#[test]
+fn unrelated_damage() {
+    let log = DamageLog::default();
+    let serial = log.serial();
+    log.damage(rect(100, 100, 200, 200), B);
+    assert!(log.unchanged_since(serial, rect(0, 0, 50, 50), Some(A)));
+    assert!(!log.unchanged_since(serial, rect(0, 0, 150, 150), Some(A)));
+    assert!(log.unchanged_since(log.serial(), rect(0, 0, 150, 150), Some(A)));
+}
+
+#[test]
+fn own_damage() {
+    let log = DamageLog::default();
+    let serial = log.serial();
+    log.damage(rect(0, 0, 100, 100), A);
+    assert!(log.unchanged_since(serial, rect(0, 0, 100, 100), Some(A)));
+    assert!(!log.unchanged_since(serial, rect(0, 0, 100, 100), Some(B)));
+    assert!(!log.unchanged_since(serial, rect(0, 0, 100, 100), None));
+}
+
+#[test]
+fn full_damage() {
+    let log = DamageLog::default();
+    let serial = log.serial();
+    log.damage_all();
+    assert!(!log.unchanged_since(serial, rect(0, 0, 1, 1), None));
+    assert!(log.unchanged_since(log.serial(), rect(0, 0, 1, 1), None));
+}
+
+#[test]
+fn overflow() {
+    let log = DamageLog::default();
+    let serial = log.serial();
+    for _ in 0..MAX_ENTRIES {
+        log.damage(rect(100, 100, 200, 200), B);
+    }
+    assert!(log.unchanged_since(serial, rect(0, 0, 50, 50), None));
+    log.damage(rect(100, 100, 200, 200), B);
+    assert!(!log.unchanged_since(serial, rect(0, 0, 50, 50), None));
+}
@@ -1,8 +1,8 @@
 use {
     crate::{
         gfx_api::{
-            AcquireSync, BufferResv, CopyTexture, FillRect, FramebufferRect, GfxApiOpt, GfxTexture,
-            ReleaseSync, SampleRect,
+            AcquireSync, Blur, BlurCache, BufferResv, CopyTexture, FillRect, FramebufferRect,
+            GfxApiOpt, GfxTexture, ReleaseSync, RoundedRect, SampleRect,
         },
         rect::Rect,
         scale::Scale,
@@ -10,7 +10,7 @@ use {
         utils::transform_ext::TransformExt,
     },
     jay_config::video::Transform,
-    std::rc::Rc,
+    std::{mem, rc::Rc},
 };
 
 pub struct RendererBase<'a> {
@@ -21,6 +21,9 @@ pub struct RendererBase<'a> {
     pub transform: Transform,
     pub fb_width: f32,
     pub fb_height: f32,
+    pub rounded: Option<RoundedRect>,
+    /// Multiplies the alpha of all fills and textures.
+    pub alpha: f32,
 }
 
 impl RendererBase<'_> {
@@ -66,6 +69,80 @@ impl RendererBase<'_> {
         rect
     }
 
+    /// Creates a rounded rectangle from a rectangle in logical coordinates.
+    ///
+    /// The radius is limited to half of the smaller side of the rectangle.
+    pub fn rounded_rect(&self, rect: Rect, radius: i32, blur: i32) -> RoundedRect {
+        let rect = self.scale_rect(rect);
+        let [x1, y1, x2, y2] = [rect.x1(), rect.y1(), rect.x2(), rect.y2()].map(|v| v as f32);
+        let points = FramebufferRect::new(
+            x1,
+            y1,
+            x2,
+            y2,
+            self.transform,
+            self.fb_width,
+            self.fb_height,
+        )
+        .to_points();
+        let (width, height) = self.transform.maybe_swap((self.fb_width, self.fb_height));
+        let [mut x1, mut y1, mut x2, mut y2] = [f32::MAX, f32::MAX, f32::MIN, f32::MIN];
+        for [x, y] in points {
+            x1 = x1.min(x);
+            y1 = y1.min(y);
+            x2 = x2.max(x);
+            y2 = y2.max(y);
+        }
+        let x1 = (x1 + 1.0) * width / 2.0;
+        let x2 = (x2 + 1.0) * width / 2.0;
+        let y1 = (y1 + 1.0) * height / 2.0;
+        let y2 = (y2 + 1.0) * height / 2.0;
+        let radius = (radius as f64 * self.scalef) as f32;
+        let blur = (blur as f64 * self.scalef) as f32;
+        RoundedRect {
+            x1,
+            y1,
+            x2,
+            y2,
+            radius: radius.min((x2 - x1).min(y2 - y1) / 2.0).max(0.0),
+            blur,
+        }
+    }
+
+    /// Sets the rounded rectangle that limits all subsequent operations.
+    ///
+    /// Returns the previous rectangle.
+    pub fn set_rounded(&mut self, rounded: Option<RoundedRect>) -> Option<RoundedRect> {
+        mem::replace(&mut self.rounded, rounded)
+    }
+
+    /// Blurs the framebuffer contents behind a rectangle in logical coordinates.
+    pub fn blur(
+        &mut self,
+        rect: Rect,
+        radius: i32,
+        passes: u32,
+        offset: i32,
+        cache: Option<Rc<BlurCache>>,
+    ) {
+        if passes == 0 {
+            return;
+        }
+        self.ops.push(GfxApiOpt::Blur(Blur {
+            rect: self.rounded_rect(rect, radius, 0),
+            passes,
+            offset: (offset as f64 * self.scalef) as f32,
+            cache,
+        }));
+    }
+
+    fn apply_alpha(&self, color: Color) -> Color {
+        match self.alpha < 1.0 {
+            true => color * self.alpha,
+            false => color,
+        }
+    }
+
     pub fn fill_boxes(&mut self, boxes: &[Rect], color: &Color) {
         self.fill_boxes2(boxes, color, 0, 0);
     }
@@ -75,6 +152,7 @@ impl RendererBase<'_> {
             return;
         }
         let (dx, dy) = self.scale_point(dx, dy);
+        let color = &self.apply_alpha(*color);
         for bx in boxes {
             let bx = self.scale_rect(*bx);
             self.ops.push(GfxApiOpt::FillRect(FillRect {
@@ -88,6 +166,7 @@ impl RendererBase<'_> {
                     self.fb_height,
                 ),
                 color: *color,
+                rounded: self.rounded,
             }));
         }
     }
@@ -107,6 +186,7 @@ impl RendererBase<'_> {
             return;
         }
         let (dx, dy) = self.scale_point_f(dx, dy);
+        let color = &self.apply_alpha(*color);
         for bx in boxes {
             let (x1, y1, x2, y2) = self.scale_rect_f(*bx);
             self.ops.push(GfxApiOpt::FillRect(FillRect {
@@ -120,6 +200,7 @@ impl RendererBase<'_> {
                     self.fb_height,
                 ),
                 color: *color,
+                rounded: self.rounded,
             }));
         }
     }
@@ -144,8 +225,8 @@ impl RendererBase<'_> {
             size
         } else {
             let (mut w, mut h) = texcoord.buffer_transform.maybe_swap(texture.size());
-            if tscale != self.scale {
-                let tscale = tscale.to_f64();
+            let tscale = tscale.to_f64();
+            if tscale != self.scalef {
                 w = (w as f64 * self.scalef / tscale).round() as _;
                 h = (h as f64 * self.scalef / tscale).round() as _;
             }
@@ -171,11 +252,16 @@ impl RendererBase<'_> {
             self.fb_height,
         );
 
+        let alpha = match self.alpha < 1.0 {
+            true => Some(alpha.unwrap_or(1.0) * self.alpha),
+            false => alpha,
+        };
         self.ops.push(GfxApiOpt::CopyTexture(CopyTexture {
             tex: texture.clone(),
             source: texcoord,
             target,
             alpha,
+            rounded: self.rounded,
             buffer_resv,
             acquire_sync,
             release_sync,
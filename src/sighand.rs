@@ -21,18 +21,23 @@ pub fn install(
     eng: &Rc<AsyncEngine>,
     ring: &Rc<IoUring>,
 ) -> Result<SpawnedFuture<()>, SighandError> {
+    let fd = block(&[c::SIGINT, c::SIGTERM, c::SIGPIPE])?;
+    Ok(eng.spawn(handle_signals(fd, ring.clone())))
+}
+
+/// Blocks the signals and returns a signalfd that can be used to read them.
+pub fn block(signals: &[c::c_int]) -> Result<Rc<OwnedFd>, SighandError> {
     let mut set: c::sigset_t = uapi::pod_zeroed();
-    uapi::sigaddset(&mut set, c::SIGINT).unwrap();
-    uapi::sigaddset(&mut set, c::SIGTERM).unwrap();
-    uapi::sigaddset(&mut set, c::SIGPIPE).unwrap();
+    for &signal in signals {
+        uapi::sigaddset(&mut set, signal).unwrap();
+    }
     if let Err(e) = uapi::pthread_sigmask(c::SIG_BLOCK, Some(&set), None) {
         return Err(SighandError::BlockFailed(e.into()));
     }
-    let fd = match uapi::signalfd_new(&set, c::SFD_CLOEXEC) {
-        Ok(fd) => Rc::new(fd),
-        Err(e) => return Err(SighandError::CreateFailed(e.into())),
-    };
-    Ok(eng.spawn(handle_signals(fd, ring.clone())))
+    match uapi::signalfd_new(&set, c::SFD_CLOEXEC) {
+        Ok(fd) => Ok(Rc::new(fd)),
+        Err(e) => Err(SighandError::CreateFailed(e.into())),
+    }
 }
 
 async fn handle_signals(fd: Rc<OwnedFd>, ring: Rc<IoUring>) {
@@ -1,6 +1,7 @@
 use {
     crate::{
         acceptor::Acceptor,
+        animation::Animations,
         async_engine::{AsyncEngine, SpawnedFuture},
         backend::{
             Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorId, ConnectorIds,
@@ -8,6 +9,7 @@ use {
             InputDeviceIds, MonitorInfo,
         },
         backends::dummy::DummyBackend,
+        builtin_lock::BuiltinLock,
         cli::RunArgs,
         client::{Client, ClientId, Clients, SerialRange, NUM_CACHED_SERIAL_RANGES},
         clientmem::ClientMemOffset,
@@ -29,6 +31,7 @@ use {
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
             ext_session_lock_v1::ExtSessionLockV1,
             ipc::{x_data_device::XIpcDeviceIds, DataOfferIds, DataSourceIds},
+            jay_appearance::JayAppearance,
             jay_render_ctx::JayRenderCtx,
             jay_screencast::JayScreencast,
             jay_seat_events::JaySeatEvents,
@@ -36,7 +39,10 @@ use {
             wl_drm::WlDrmGlobal,
             wl_output::{OutputGlobalOpt, OutputId, PersistentOutputState},
             wl_seat::{
-                tablet::{TabletIds, TabletInit, TabletPadIds, TabletPadInit, TabletToolIds},
+                tablet::{
+                    PressureCurve, TabletArea, TabletIds, TabletInit, TabletPadIds, TabletPadInit,
+                    TabletToolIds,
+                },
                 SeatIds, WlSeatGlobal,
             },
             wl_surface::{
@@ -56,14 +62,15 @@ use {
         leaks::Tracker,
         logger::Logger,
         rect::Rect,
-        renderer::{RenderResult, Renderer},
+        renderer::{damage_log::DamageLog, RenderResult, Renderer},
         scale::Scale,
         security_context_acceptor::SecurityContextAcceptors,
         theme::{Color, Theme},
+        time::now_usec,
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeIds,
-            NodeVisitorBase, OutputNode, PlaceholderNode, ToplevelNode, ToplevelNodeBase,
-            WorkspaceNode,
+            place_tiled, ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node,
+            NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode, ToplevelNode, ToplevelNodeBase,
+            WorkspaceLayout, WorkspaceNode,
         },
         utils::{
             activation_token::ActivationToken, asyncevent::AsyncEvent, bindings::Bindings,
@@ -81,24 +88,26 @@ use {
         },
         wheel::Wheel,
         wire::{
-            ExtForeignToplevelListV1Id, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
-            ZwpLinuxDmabufFeedbackV1Id,
+            ExtForeignToplevelListV1Id, JayAppearanceId, JayRenderCtxId, JaySeatEventsId,
+            JayWorkspaceWatcherId, WlSurfaceId, ZwpLinuxDmabufFeedbackV1Id,
         },
         xkbcommon::{KeyboardStateIds, XkbContext, XkbKeymap, XkbState},
         xwayland::{self, XWaylandEvent},
     },
-    ahash::AHashMap,
+    ahash::{AHashMap, AHashSet},
     bstr::ByteSlice,
+    futures_util::{select, FutureExt},
     jay_config::{
+        input::TabletPadControl,
         video::{GfxApi, Transform},
-        PciId,
+        IdleStage, PciId,
     },
     std::{
         cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
         mem,
         num::Wrapping,
-        ops::DerefMut,
+        ops::{DerefMut, Range},
         rc::Rc,
         sync::Arc,
         time::Duration,
@@ -106,6 +115,8 @@ use {
     thiserror::Error,
 };
 
+const LOCK_BEFORE_SLEEP_TIMEOUT_MS: u64 = 3000;
+
 pub struct State {
     pub xkb_ctx: XkbContext,
     pub backend: CloneCell<Rc<dyn Backend>>,
@@ -134,11 +145,15 @@ pub struct State {
     pub backend_events: AsyncQueue<BackendEvent>,
     pub input_device_handlers: RefCell<AHashMap<InputDeviceId, InputDeviceData>>,
     pub seat_queue: LinkedList<Rc<WlSeatGlobal>>,
+    pub scratchpad: LinkedList<Rc<dyn ToplevelNode>>,
+    pub marks: CopyHashMap<String, Rc<dyn ToplevelNode>>,
     pub slow_clients: AsyncQueue<Rc<Client>>,
     pub none_surface_ext: Rc<NoneSurfaceExt>,
     pub tree_changed_sent: Cell<bool>,
     pub config: CloneCell<Option<Rc<ConfigProxy>>>,
     pub theme: Theme,
+    pub animations: Animations,
+    pub damage_log: DamageLog,
     pub pending_container_layout: AsyncQueue<Rc<ContainerNode>>,
     pub pending_container_render_data: AsyncQueue<Rc<ContainerNode>>,
     pub pending_output_render_data: AsyncQueue<Rc<OutputNode>>,
@@ -154,6 +169,7 @@ pub struct State {
     pub outputs: CopyHashMap<ConnectorId, Rc<OutputData>>,
     pub drm_devs: CopyHashMap<DrmDeviceId, Rc<DrmDevData>>,
     pub status: CloneCell<Rc<String>>,
+    pub status_blocks: CloneCell<Rc<Vec<Range<usize>>>>,
     pub idle: IdleState,
     pub run_args: RunArgs,
     pub xwayland: XWaylandState,
@@ -173,7 +189,9 @@ pub struct State {
     pub testers: RefCell<AHashMap<(ClientId, JaySeatEventsId), Rc<JaySeatEvents>>>,
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
+    pub appearance_watchers: CopyHashMap<(ClientId, JayAppearanceId), Rc<JayAppearance>>,
     pub default_workspace_capture: Cell<bool>,
+    pub default_workspace_layout: Cell<WorkspaceLayout>,
     pub default_gfx_api: Cell<GfxApi>,
     pub activation_tokens: CopyHashMap<ActivationToken, ()>,
     pub toplevel_lists:
@@ -215,6 +233,8 @@ impl Debug for State {
 pub struct ScreenlockState {
     pub locked: Cell<bool>,
     pub lock: CloneCell<Option<Rc<ExtSessionLockV1>>>,
+    pub builtin: CloneCell<Option<Rc<BuiltinLock>>>,
+    pub changed: AsyncEvent,
 }
 
 pub struct XWaylandState {
@@ -231,7 +251,17 @@ pub struct IdleState {
     pub timeout_changed: Cell<bool>,
     pub inhibitors: CopyHashMap<IdleInhibitorId, Rc<ZwpIdleInhibitorV1>>,
     pub inhibitors_changed: Cell<bool>,
+    pub registered_inhibitors: CopyHashMap<IdleInhibitorId, Rc<ZwpIdleInhibitorV1>>,
+    pub inhibitors_ignored: Cell<bool>,
+    /// How long inhibitors are ignored. `None` ignores them until the system resumes from
+    /// idle.
+    pub inhibitors_ignored_timeout: Cell<Option<Duration>>,
+    pub inhibitors_ignored_changed: Cell<bool>,
+    pub fullscreen_inhibits: Cell<bool>,
     pub backend_idle: Cell<bool>,
+    pub stages: RefCell<Vec<IdleStage>>,
+    pub stages_changed: Cell<bool>,
+    pub dimmed: Cell<bool>,
 }
 
 impl IdleState {
@@ -241,6 +271,34 @@ impl IdleState {
         self.change.trigger();
     }
 
+    pub fn set_stages(&self, stages: Vec<IdleStage>) {
+        *self.stages.borrow_mut() = stages;
+        self.stages_changed.set(true);
+        self.change.trigger();
+    }
+
+    /// Ignores or respects inhibitors.
+    ///
+    /// Ignored inhibitors are respected again once `timeout` has elapsed or the system
+    /// resumes from idle.
+    pub fn set_inhibitors_ignored(&self, ignored: bool, timeout: Option<Duration>) {
+        self.inhibitors_ignored.set(ignored);
+        self.inhibitors_ignored_timeout.set(timeout);
+        self.inhibitors_ignored_changed.set(true);
+        self.inhibitors_changed.set(true);
+        self.change.trigger();
+    }
+
+    pub fn set_fullscreen_inhibits(&self, inhibits: bool) {
+        self.fullscreen_inhibits.set(inhibits);
+        self.inhibitors_changed.set(true);
+        self.change.trigger();
+    }
+
+    pub fn is_inhibited(&self) -> bool {
+        self.inhibitors.is_not_empty() && !self.inhibitors_ignored.get()
+    }
+
     pub fn add_inhibitor(&self, inhibitor: &Rc<ZwpIdleInhibitorV1>) {
         self.inhibitors.set(inhibitor.inhibit_id, inhibitor.clone());
         self.inhibitors_changed.set(true);
@@ -270,8 +328,16 @@ pub struct DeviceHandlerData {
     pub keymap: CloneCell<Option<Rc<XkbKeymap>>>,
     pub xkb_state: CloneCell<Option<Rc<RefCell<XkbState>>>>,
     pub output: CloneCell<Option<Rc<OutputGlobalOpt>>>,
+    pub region: Cell<Option<Rect>>,
     pub tablet_init: Option<Box<TabletInit>>,
     pub tablet_pad_init: Option<Box<TabletPadInit>>,
+    pub tablet_area: Cell<TabletArea>,
+    pub tablet_keep_aspect_ratio: Cell<bool>,
+    pub tablet_pressure_curve: Cell<Option<PressureCurve>>,
+    /// The pad controls bound by the config and the last position of rings and strips.
+    pub tablet_pad_bindings: RefCell<AHashMap<(TabletPadControl, u32), Option<f64>>>,
+    /// The pad buttons whose press was forwarded to clients.
+    pub tablet_pad_forwarded_buttons: RefCell<AHashSet<u32>>,
 }
 
 pub struct ConnectorData {
@@ -567,6 +633,10 @@ impl State {
 
     pub fn map_tiled_on(self: &Rc<Self>, node: Rc<dyn ToplevelNode>, ws: &Rc<WorkspaceNode>) {
         if let Some(c) = ws.container.get() {
+            if ws.layout.get() != WorkspaceLayout::Manual {
+                place_tiled(self, ws, &c, node);
+                return;
+            }
             let la = c.clone().tl_last_active_child();
             let lap = la
                 .tl_data()
@@ -670,6 +740,12 @@ impl State {
     }
 
     pub fn set_status(&self, status: &str) {
+        self.set_status_blocks(status, vec![]);
+    }
+
+    /// Sets the status and the byte ranges of its i3bar components.
+    pub fn set_status_blocks(&self, status: &str, blocks: Vec<Range<usize>>) {
+        self.status_blocks.set(Rc::new(blocks));
         let status = Rc::new(status.to_owned());
         self.status.set(status.clone());
         let outputs = self.root.outputs.lock();
@@ -679,6 +755,13 @@ impl State {
         self.damage();
     }
 
+    pub fn appearance_changed(&self) {
+        let appearance = self.theme.appearance();
+        for watcher in self.appearance_watchers.lock().values() {
+            watcher.send_appearance(&appearance);
+        }
+    }
+
     pub fn input_occurred(&self) {
         if !self.idle.input.replace(true) {
             self.idle.change.trigger();
@@ -719,6 +802,17 @@ impl State {
     }
 
     pub fn damage(&self) {
+        self.damage_log.damage_all();
+        self.damage_connectors();
+    }
+
+    /// Damages the area of a surface tree that changed because of a commit.
+    pub fn damage_surface(&self, rect: Rect, root: WlSurfaceId) {
+        self.damage_log.damage(rect, root);
+        self.damage_connectors();
+    }
+
+    fn damage_connectors(&self) {
         for connector in self.connectors.lock().values() {
             if connector.connected.get() {
                 connector.connector.damage();
@@ -728,6 +822,9 @@ impl State {
 
     pub fn clear(&self) {
         self.lock.lock.take();
+        if let Some(builtin) = self.lock.builtin.take() {
+            builtin.clear();
+        }
         self.xwayland.handler.borrow_mut().take();
         self.clients.clear();
         if let Some(config) = self.config.set(None) {
@@ -742,6 +839,7 @@ impl State {
         self.xwayland.handler.borrow_mut().take();
         self.xwayland.queue.clear();
         self.idle.inhibitors.clear();
+        self.idle.registered_inhibitors.clear();
         self.idle.change.clear();
         for (_, drm_dev) in self.drm_devs.lock().drain() {
             drm_dev.handler.take();
@@ -766,6 +864,7 @@ impl State {
         self.pending_toplevel_screencast_reallocs.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
+        self.appearance_watchers.clear();
         self.toplevel_lists.clear();
         self.security_context_acceptors.clear();
         self.slow_clients.clear();
@@ -774,6 +873,10 @@ impl State {
         }
         self.backend_events.clear();
         self.workspaces.clear();
+        self.marks.clear();
+        for tl in self.scratchpad.iter() {
+            tl.tl_data().scratchpad_link.take();
+        }
         {
             let seats = mem::take(self.globals.seats.lock().deref_mut());
             for seat in seats.values() {
@@ -834,6 +937,9 @@ impl State {
         )?;
         output.perform_screencopies(tex, !render_hw_cursor, 0, 0, None);
         rr.dispatch_frame_requests();
+        if output.animation_frame_pending.get() {
+            output.global.connector.connector.damage();
+        }
         Ok(sync_file)
     }
 
@@ -854,10 +960,14 @@ impl State {
             state: self,
             result: None,
             logical_extents: position.at_point(0, 0),
+            scene: position,
             pixel_extents: {
                 let (width, height) = target.logical_size(Transform::None);
                 Rect::new_sized(0, 0, width, height).unwrap()
             },
+            opacity: 1.0,
+            animation_time_usec: now_usec(),
+            animating: false,
         };
         let mut sample_rect = SampleRect::identity();
         sample_rect.buffer_transform = transform;
@@ -993,6 +1103,115 @@ impl State {
         }
     }
 
+    /// Locks the screen with the built-in lock screen.
+    ///
+    /// This has no effect if a lock client has already locked the screen.
+    pub fn lock_builtin(self: &Rc<Self>) {
+        if self.lock.builtin.is_some() || self.lock.lock.is_some() {
+            return;
+        }
+        if !self.lock.locked.replace(true) {
+            log::info!("Locking the screen with the built-in lock screen");
+            for seat in self.globals.seats.lock().values() {
+                seat.prepare_for_lock();
+            }
+        }
+        self.lock.builtin.set(Some(BuiltinLock::new(self)));
+        self.reset_lock_presented();
+        for output in self.root.outputs.lock().values() {
+            output.update_visible();
+        }
+        self.lock.changed.trigger();
+        self.tree_changed();
+        self.damage();
+    }
+
+    pub fn unlock(&self) {
+        if !self.lock.locked.replace(false) {
+            return;
+        }
+        if let Some(lock) = self.lock.lock.take() {
+            lock.finish();
+        }
+        if let Some(builtin) = self.lock.builtin.take() {
+            builtin.clear();
+        }
+        for output in self.root.outputs.lock().values() {
+            if let Some(surface) = output.set_lock_surface(None) {
+                surface.destroy_node();
+            }
+        }
+        self.lock.changed.trigger();
+        self.tree_changed();
+        self.damage();
+    }
+
+    /// Returns whether the lock screen is rendered on the output.
+    pub fn lock_visible_on(&self, output: &OutputNode) -> bool {
+        if !self.lock.locked.get() {
+            return false;
+        }
+        if self.lock.builtin.is_some() {
+            return true;
+        }
+        matches!(output.lock_surface.get(), Some(ls) if ls.surface.buffer.is_some())
+    }
+
+    /// Returns whether the screen is locked and all outputs have presented a frame that
+    /// shows the lock screen.
+    ///
+    /// Outputs are ignored while they are powered off since they cannot present frames.
+    pub fn is_fully_locked(&self) -> bool {
+        if !self.lock.locked.get() {
+            return false;
+        }
+        for output in self.root.outputs.lock().values() {
+            if !self.lock_visible_on(output) {
+                return false;
+            }
+            if !output.lock_presented.get() && self.root_visible() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Forgets which outputs have presented the lock screen.
+    ///
+    /// This must be called when the lock screen is replaced since frames that were
+    /// presented before do not show the new lock screen.
+    pub fn reset_lock_presented(&self) {
+        for output in self.root.outputs.lock().values() {
+            output.lock_frame_pending.set(false);
+            output.lock_presented.set(false);
+        }
+    }
+
+    /// Gives the config a chance to lock the screen before the system goes to sleep.
+    ///
+    /// Returns once the screen is fully locked or after a timeout.
+    pub async fn prepare_for_sleep(&self) {
+        let mut wait = self.lock.locked.get();
+        if !wait {
+            if let Some(config) = self.config.get() {
+                wait = config.before_sleep();
+            }
+        }
+        if !wait {
+            return;
+        }
+        let mut timeout = self.wheel.timeout(LOCK_BEFORE_SLEEP_TIMEOUT_MS).fuse();
+        while !self.is_fully_locked() {
+            select! {
+                _ = timeout => {
+                    log::warn!("Screen was not locked before the system went to sleep");
+                    return;
+                },
+                _ = self.lock.changed.triggered().fuse() => {},
+            }
+        }
+    }
+
     pub fn set_backend_idle(&self, idle: bool) {
         if self.idle.backend_idle.replace(idle) != idle {
             self.root.update_visible(self);
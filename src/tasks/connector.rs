@@ -155,6 +155,7 @@ impl ConnectorHandler {
                 captured_inactive_workspaces: Default::default(),
                 titles: Default::default(),
                 status: None,
+                lock_prompt: None,
             }),
             state: self.state.clone(),
             is_dummy: false,
@@ -168,6 +169,12 @@ impl ConnectorHandler {
             update_render_data_scheduled: Cell::new(false),
             hardware_cursor_needs_render: Cell::new(false),
             screencopies: Default::default(),
+            workspace_transition: Default::default(),
+            closing_windows: Default::default(),
+            last_presentation_usec: Cell::new(0),
+            animation_frame_pending: Cell::new(false),
+            lock_frame_pending: Cell::new(false),
+            lock_presented: Cell::new(false),
         });
         self.state
             .add_output_scale(on.global.persistent.scale.get());
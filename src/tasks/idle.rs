@@ -20,13 +20,23 @@ pub async fn idle(state: Rc<State>, backend: Rc<dyn Backend>) {
             return;
         }
     };
+    let ignore_timer = match TimerFd::new(c::CLOCK_MONOTONIC) {
+        Ok(t) => t,
+        Err(e) => {
+            log::error!("Could not create idle inhibitor timer: {}", ErrorFmt(e));
+            return;
+        }
+    };
     state.idle.change.trigger();
     state.idle.timeout_changed.set(true);
     let mut idle = Idle {
         state,
         backend,
         timer,
-        idle: false,
+        ignore_timer,
+        stages: vec![],
+        entered: 0,
+        powered_off: false,
         dead: false,
         is_inhibited: false,
         last_input: now(),
@@ -38,17 +48,31 @@ struct Idle {
     state: Rc<State>,
     backend: Rc<dyn Backend>,
     timer: TimerFd,
-    idle: bool,
+    /// Expires when ignored inhibitors should be respected again.
+    ignore_timer: TimerFd,
+    stages: Vec<Stage>,
+    entered: usize,
+    powered_off: bool,
     dead: bool,
     is_inhibited: bool,
     last_input: c::timespec,
 }
 
+struct Stage {
+    /// The name of the stage or `None` for the timeout configured via `set_idle`.
+    name: Option<String>,
+    timeout: Duration,
+    dim_outputs: bool,
+    power_off_outputs: bool,
+    suspend: bool,
+}
+
 impl Idle {
     async fn run(&mut self) {
         while !self.dead {
             select! {
                 res = self.timer.expired(&self.state.ring).fuse() => self.handle_expired(res),
+                res = self.ignore_timer.expired(&self.state.ring).fuse() => self.handle_ignore_expired(res),
                 _ = self.state.idle.change.triggered().fuse() => self.handle_idle_changes(),
             }
         }
@@ -61,46 +85,164 @@ impl Idle {
             self.dead = true;
             return;
         }
-        let timeout = self.state.idle.timeout.get();
+        if self.is_inhibited {
+            return;
+        }
+        if self.state.idle.fullscreen_inhibits.get() && self.fullscreen_visible() {
+            self.last_input = now();
+            self.program_timer();
+            return;
+        }
         let since = duration_since(self.last_input);
-        if since >= timeout {
-            if !timeout.is_zero() && !self.is_inhibited {
-                if let Some(config) = self.state.config.get() {
-                    config.idle();
+        while let Some(stage) = self.stages.get(self.entered) {
+            if stage.timeout > since {
+                self.program_timer2(stage.timeout - since);
+                return;
+            }
+            self.entered += 1;
+            self.enter_stage(self.entered - 1);
+        }
+    }
+
+    fn handle_ignore_expired(&mut self, res: Result<u64, TimerError>) {
+        if let Err(e) = res {
+            log::error!(
+                "Could not wait for idle inhibitor timer to expire: {}",
+                ErrorFmt(e)
+            );
+            self.dead = true;
+            return;
+        }
+        self.state.idle.set_inhibitors_ignored(false, None);
+    }
+
+    fn fullscreen_visible(&self) -> bool {
+        for output in self.state.root.outputs.lock().values() {
+            if let Some(ws) = output.workspace.get() {
+                if ws.fullscreen.is_some() {
+                    return true;
                 }
-                self.backend.set_idle(true);
-                self.idle = true;
             }
-        } else {
-            self.program_timer2(timeout - since);
+        }
+        false
+    }
+
+    fn enter_stage(&mut self, idx: usize) {
+        let stage = &self.stages[idx];
+        if let Some(config) = self.state.config.get() {
+            match &stage.name {
+                Some(name) => config.idle_stage_entered(name),
+                None => config.idle(),
+            }
+        }
+        if stage.dim_outputs && !self.state.idle.dimmed.replace(true) {
+            self.state.damage();
+        }
+        if stage.power_off_outputs && !self.powered_off {
+            self.backend.set_idle(true);
+            self.powered_off = true;
+        }
+        if stage.suspend {
+            self.backend.suspend();
+        }
+    }
+
+    fn resume(&mut self) {
+        if let Some(config) = self.state.config.get() {
+            for stage in self.stages[..self.entered].iter().rev() {
+                if let Some(name) = &stage.name {
+                    config.idle_stage_resumed(name);
+                }
+            }
+        }
+        self.entered = 0;
+        if self.state.idle.dimmed.replace(false) {
+            self.state.damage();
+        }
+        if self.powered_off {
+            self.backend.set_idle(false);
+            self.powered_off = false;
         }
     }
 
     fn handle_idle_changes(&mut self) {
+        if self.state.idle.inhibitors_ignored_changed.replace(false) {
+            let mut timeout = None;
+            if self.state.idle.inhibitors_ignored.get() {
+                timeout = self.state.idle.inhibitors_ignored_timeout.get();
+            }
+            if let Err(e) = self.ignore_timer.program(timeout, None) {
+                log::error!("Could not program idle inhibitor timer: {}", ErrorFmt(e));
+                self.dead = true;
+            }
+        }
         if self.state.idle.inhibitors_changed.replace(false) {
-            let is_inhibited = self.state.idle.inhibitors.len() > 0;
+            let is_inhibited = self.state.idle.is_inhibited();
             if self.is_inhibited != is_inhibited {
                 self.is_inhibited = is_inhibited;
                 if !self.is_inhibited {
+                    self.last_input = now();
                     self.program_timer();
                 }
             }
         }
-        if self.state.idle.timeout_changed.replace(false) {
+        let timeout_changed = self.state.idle.timeout_changed.replace(false);
+        let stages_changed = self.state.idle.stages_changed.replace(false);
+        if timeout_changed || stages_changed {
+            self.resume();
+            self.update_stages();
             self.program_timer();
         }
         if self.state.idle.input.replace(false) {
             self.last_input = now();
-            if self.idle {
-                self.backend.set_idle(false);
-                self.idle = false;
+            if self.entered > 0 {
+                self.resume();
                 self.program_timer();
+                if self.state.idle.inhibitors_ignored.get() {
+                    self.state.idle.set_inhibitors_ignored(false, None);
+                }
+            }
+        }
+    }
+
+    fn update_stages(&mut self) {
+        self.stages.clear();
+        let timeout = self.state.idle.timeout.get();
+        if !timeout.is_zero() {
+            self.stages.push(Stage {
+                name: None,
+                timeout,
+                dim_outputs: false,
+                power_off_outputs: true,
+                suspend: false,
+            });
+        }
+        for stage in self.state.idle.stages.borrow().iter() {
+            if stage.timeout.is_zero() {
+                log::warn!("Ignoring idle stage {} with zero timeout", stage.name);
+                continue;
             }
+            self.stages.push(Stage {
+                name: Some(stage.name.clone()),
+                timeout: stage.timeout,
+                dim_outputs: stage.dim_outputs,
+                power_off_outputs: stage.power_off_outputs,
+                suspend: stage.suspend,
+            });
         }
+        self.stages.sort_by_key(|s| s.timeout);
     }
 
     fn program_timer(&mut self) {
-        self.program_timer2(self.state.idle.timeout.get());
+        let mut timeout = Duration::ZERO;
+        if let Some(stage) = self.stages.get(self.entered) {
+            // a zero timeout would disarm the timer
+            timeout = stage
+                .timeout
+                .saturating_sub(duration_since(self.last_input))
+                .max(Duration::from_nanos(1));
+        }
+        self.program_timer2(timeout);
     }
 
     fn program_timer2(&mut self, timeout: Duration) {
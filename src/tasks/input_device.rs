@@ -24,8 +24,14 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn InputDevice>) {
         keymap: Default::default(),
         xkb_state: Default::default(),
         output: Default::default(),
+        region: Default::default(),
         tablet_init: dev.tablet_info(),
         tablet_pad_init: dev.tablet_pad_info(),
+        tablet_area: Default::default(),
+        tablet_keep_aspect_ratio: Cell::new(false),
+        tablet_pressure_curve: Default::default(),
+        tablet_pad_bindings: Default::default(),
+        tablet_pad_forwarded_buttons: Default::default(),
     });
     let ae = Rc::new(AsyncEvent::default());
     let oh = DeviceHandler {
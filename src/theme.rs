@@ -1,7 +1,11 @@
-use std::{
-    cell::{Cell, RefCell},
-    cmp::Ordering,
-    ops::Mul,
+use {
+    ahash::{AHashMap, AHashSet},
+    jay_config::theme::ColorScheme,
+    std::{
+        cell::{Cell, RefCell},
+        cmp::Ordering,
+        ops::Mul,
+    },
 };
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -187,6 +191,7 @@ colors! {
     bar_text = (0xff, 0xff, 0xff),
     attention_requested_background = (0x23, 0x09, 0x2c),
     highlight = (0x9d, 0x28, 0xc6, 0x7f),
+    shadow = (0x00, 0x00, 0x00, 0x7f),
 }
 
 macro_rules! sizes {
@@ -264,6 +269,12 @@ macro_rules! sizes {
 sizes! {
     title_height = (1, 1000, 17),
     border_width = (1, 1000, 4),
+    inner_gap = (0, 1000, 0),
+    outer_gap = (0, 1000, 0),
+    corner_radius = (0, 1000, 0),
+    shadow_size = (0, 1000, 0),
+    blur_passes = (0, 8, 3),
+    blur_offset = (1, 100, 3),
 }
 
 pub const DEFAULT_FONT: &str = "monospace 8";
@@ -272,6 +283,12 @@ pub struct Theme {
     pub colors: ThemeColors,
     pub sizes: ThemeSizes,
     pub font: RefCell<String>,
+    pub color_scheme: Cell<ColorScheme>,
+    pub high_contrast: Cell<bool>,
+    pub smart_gaps: Cell<bool>,
+    pub blur_app_ids: RefCell<AHashSet<String>>,
+    pub blur_layer_namespaces: RefCell<AHashSet<String>>,
+    pub window_opacities: RefCell<AHashMap<String, f32>>,
 }
 
 impl Default for Theme {
@@ -280,6 +297,62 @@ impl Default for Theme {
             colors: Default::default(),
             sizes: Default::default(),
             font: RefCell::new(DEFAULT_FONT.to_string()),
+            color_scheme: Cell::new(ColorScheme::Automatic),
+            high_contrast: Cell::new(false),
+            smart_gaps: Cell::new(false),
+            blur_app_ids: Default::default(),
+            blur_layer_namespaces: Default::default(),
+            window_opacities: Default::default(),
         }
     }
 }
+
+pub const COLOR_SCHEME_NO_PREFERENCE: u32 = 0;
+pub const COLOR_SCHEME_PREFER_DARK: u32 = 1;
+pub const COLOR_SCHEME_PREFER_LIGHT: u32 = 2;
+
+pub const CONTRAST_NO_PREFERENCE: u32 = 0;
+pub const CONTRAST_HIGH: u32 = 1;
+
+/// The appearance preferences communicated to applications.
+///
+/// The values use the encoding of the `org.freedesktop.appearance` settings namespace.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Appearance {
+    pub color_scheme: u32,
+    pub accent_color: [f32; 3],
+    pub contrast: u32,
+}
+
+impl Theme {
+    pub fn appearance(&self) -> Appearance {
+        let color_scheme = match self.color_scheme.get() {
+            ColorScheme::Automatic => {
+                let [r, g, b] = straight_rgb(self.colors.background.get());
+                match 0.2126 * r + 0.7152 * g + 0.0722 * b < 0.5 {
+                    true => COLOR_SCHEME_PREFER_DARK,
+                    false => COLOR_SCHEME_PREFER_LIGHT,
+                }
+            }
+            ColorScheme::NoPreference => COLOR_SCHEME_NO_PREFERENCE,
+            ColorScheme::Dark => COLOR_SCHEME_PREFER_DARK,
+            ColorScheme::Light => COLOR_SCHEME_PREFER_LIGHT,
+        };
+        let contrast = match self.high_contrast.get() {
+            true => CONTRAST_HIGH,
+            false => CONTRAST_NO_PREFERENCE,
+        };
+        Appearance {
+            color_scheme,
+            accent_color: straight_rgb(self.colors.focused_title_background.get()),
+            contrast,
+        }
+    }
+}
+
+fn straight_rgb(c: Color) -> [f32; 3] {
+    if c.a == 0.0 {
+        return [0.0, 0.0, 0.0];
+    }
+    [c.r / c.a, c.g / c.a, c.b / c.a]
+}
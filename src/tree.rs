@@ -28,7 +28,7 @@ use {
 };
 pub use {
     container::*, containing::*, display::*, float::*, output::*, placeholder::*, stacked::*,
-    toplevel::*, walker::*, workspace::*,
+    tiling::*, toplevel::*, walker::*, workspace::*,
 };
 
 mod container;
@@ -38,6 +38,7 @@ mod float;
 mod output;
 mod placeholder;
 mod stacked;
+mod tiling;
 mod toplevel;
 mod walker;
 mod workspace;
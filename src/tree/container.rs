@@ -1,5 +1,6 @@
 use {
     crate::{
+        animation::{lerp_rect, Animation},
         backend::KeyState,
         cursor::KnownCursor,
         cursor_user::CursorUser,
@@ -15,6 +16,7 @@ use {
         scale::Scale,
         state::State,
         text::{self, TextTexture},
+        time::now_usec,
         tree::{
             walker::NodeVisitor, ContainingNode, Direction, FindTreeResult, FindTreeUsecase,
             FoundNode, Node, NodeId, ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
@@ -99,6 +101,8 @@ pub struct ContainerNode {
     pub id: ContainerNodeId,
     pub split: Cell<ContainerSplit>,
     pub mono_child: CloneCell<Option<NodeRef<ContainerChild>>>,
+    /// Whether the titles of a mono container are stacked vertically instead of tabbed.
+    pub stacked: Cell<bool>,
     pub mono_body: Cell<Rect>,
     pub mono_content: Cell<Rect>,
     pub abs_x1: Cell<i32>,
@@ -142,6 +146,8 @@ pub struct ContainerChild {
     pub body: Cell<Rect>,
     pub content: Cell<Rect>,
     factor: Cell<f64>,
+    /// The body at the start of the current layout transition.
+    pub layout_animation: Cell<Option<(Rect, Animation)>>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -174,6 +180,18 @@ impl ContainerChild {
         // log::debug!("content: {:?}", content);
         self.content.set(content);
     }
+
+    /// Returns the body as it is displayed at the given time.
+    fn animated_body(&self, time_usec: u64) -> Rect {
+        let body = self.body.get();
+        if let Some((from, animation)) = self.layout_animation.get() {
+            match animation.progress(time_usec) {
+                Some(p) => return lerp_rect(from, body, p),
+                None => self.layout_animation.set(None),
+            }
+        }
+        body
+    }
 }
 
 impl ContainerNode {
@@ -195,6 +213,7 @@ impl ContainerNode {
             title_rect: Default::default(),
             focus_history: Default::default(),
             attention_requested: Cell::new(false),
+            layout_animation: Default::default(),
         });
         let child_node_ref = child_node.clone();
         let mut child_nodes = AHashMap::new();
@@ -203,6 +222,7 @@ impl ContainerNode {
             id: state.node_ids.next(),
             split: Cell::new(split),
             mono_child: CloneCell::new(None),
+            stacked: Cell::new(false),
             mono_body: Cell::new(Default::default()),
             mono_content: Cell::new(Default::default()),
             abs_x1: Cell::new(0),
@@ -306,6 +326,7 @@ impl ContainerNode {
                 title_rect: Default::default(),
                 focus_history: Default::default(),
                 attention_requested: Default::default(),
+                layout_animation: Default::default(),
             });
             let r = link.to_ref();
             links.insert(new.node_id(), link);
@@ -336,6 +357,21 @@ impl ContainerNode {
         self.cancel_seat_ops();
     }
 
+    /// Returns the horizontal span of the most recently focused child as fractions of the
+    /// width of the container.
+    pub fn focused_child_span(&self) -> Option<(f64, f64)> {
+        let focused = self.focus_history.last()?.node.node_id();
+        let mut start = 0.0;
+        for child in self.children.iter() {
+            let factor = child.factor.get() / self.sum_factors.get();
+            if child.node.node_id() == focused {
+                return Some((start, start + factor));
+            }
+            start += factor;
+        }
+        None
+    }
+
     fn cancel_seat_ops(&self) {
         let mut seats = self.cursors.borrow_mut();
         for seat in seats.values_mut() {
@@ -365,6 +401,7 @@ impl ContainerNode {
             return;
         }
         self.layout_scheduled.set(false);
+        self.update_workspace_gaps();
         if let Some(child) = self.mono_child.get() {
             self.perform_mono_layout(&child);
         } else {
@@ -375,6 +412,16 @@ impl ContainerNode {
         self.schedule_compute_render_data();
     }
 
+    /// Re-evaluates the smart gaps of the workspace if this is its root container.
+    fn update_workspace_gaps(&self) {
+        let ws = self.workspace.get();
+        if let Some(c) = ws.container.get() {
+            if c.id == self.id {
+                ws.update_container_extents();
+            }
+        }
+    }
+
     fn perform_mono_layout(self: &Rc<Self>, child: &ContainerChild) {
         let mb = self.mono_body.get();
         child
@@ -385,6 +432,16 @@ impl ContainerNode {
             .set(child.content.get().at_point(mb.x1(), mb.y1()));
 
         let th = self.state.theme.sizes.title_height.get();
+        if self.stacked.get() {
+            let width = self.width.get();
+            for (i, child) in self.children.iter().enumerate() {
+                let y = i as i32 * (th + 1);
+                child
+                    .title_rect
+                    .set(Rect::new_sized(0, y, width, th).unwrap());
+            }
+            return;
+        }
         let bw = self.state.theme.sizes.border_width.get();
         let num_children = self.num_children.get() as i32;
         let content_width = self.width.get().sub(bw * (num_children - 1)).max(0);
@@ -408,6 +465,7 @@ impl ContainerNode {
         let sum_factors = self.sum_factors.get();
         let border_width = self.state.theme.sizes.border_width.get();
         let title_height = self.state.theme.sizes.title_height.get();
+        let spacing = border_width + self.state.theme.sizes.inner_gap.get();
         let split = self.split.get();
         let (content_size, other_content_size) = match split {
             ContainerSplit::Horizontal => (self.content_width.get(), self.content_height.get()),
@@ -417,6 +475,9 @@ impl ContainerNode {
         if num_children == 0 {
             return;
         }
+        let now = now_usec();
+        let prev_bodies: SmallVec<[Rect; 8]> =
+            self.children.iter().map(|c| c.animated_body(now)).collect();
         let mut pos = 0;
         let mut remaining_content_size = content_size;
         for child in self.children.iter() {
@@ -433,7 +494,7 @@ impl ContainerNode {
             };
             let body = Rect::new_sized(x1, y1, width, height).unwrap();
             child.body.set(body);
-            pos += body_size + border_width;
+            pos += body_size + spacing;
             if split == ContainerSplit::Vertical {
                 pos += title_height + 1;
             }
@@ -467,15 +528,25 @@ impl ContainerNode {
                 };
                 body = Rect::new_sized(x1, y1, width, height).unwrap();
                 child.body.set(body);
-                pos += size + border_width;
+                pos += size + spacing;
                 if split == ContainerSplit::Vertical {
                     pos += title_height + 1;
                 }
             }
         }
         self.sum_factors.set(1.0);
-        for child in self.children.iter() {
+        let animation = match self.is_resizing() {
+            true => None,
+            false => {
+                let animations = &self.state.animations;
+                animations.start(&animations.layout)
+            }
+        };
+        for (child, prev) in self.children.iter().zip(prev_bodies) {
             let body = child.body.get();
+            if prev != body && !prev.is_empty() {
+                child.layout_animation.set(animation.map(|a| (prev, a)));
+            }
             child.title_rect.set(
                 Rect::new_sized(
                     body.x1(),
@@ -491,13 +562,19 @@ impl ContainerNode {
         }
     }
 
+    /// Returns whether a seat is interactively resizing children of this container.
+    fn is_resizing(&self) -> bool {
+        self.cursors.borrow().values().any(|c| c.op.is_some())
+    }
+
     fn update_content_size(&self) {
-        let border_width = self.state.theme.sizes.border_width.get();
+        let spacing =
+            self.state.theme.sizes.border_width.get() + self.state.theme.sizes.inner_gap.get();
         let title_height = self.state.theme.sizes.title_height.get();
         let nc = self.num_children.get();
         match self.split.get() {
             ContainerSplit::Horizontal => {
-                let new_content_size = self.width.get().sub((nc - 1) as i32 * border_width).max(0);
+                let new_content_size = self.width.get().sub((nc - 1) as i32 * spacing).max(0);
                 self.content_width.set(new_content_size);
                 self.content_height
                     .set(self.height.get().sub(title_height + 1).max(0));
@@ -506,18 +583,22 @@ impl ContainerNode {
                 let new_content_size = self
                     .height
                     .get()
-                    .sub(title_height + 1 + (nc - 1) as i32 * (border_width + title_height + 1))
+                    .sub(title_height + 1 + (nc - 1) as i32 * (spacing + title_height + 1))
                     .max(0);
                 self.content_height.set(new_content_size);
                 self.content_width.set(self.width.get());
             }
         }
+        let header_height = match self.stacked.get() {
+            true => nc as i32 * (title_height + 1),
+            false => title_height + 1,
+        };
         self.mono_body.set(
             Rect::new_sized(
                 0,
-                title_height + 1,
+                header_height,
                 self.width.get(),
-                self.height.get().sub(title_height + 1).max(0),
+                self.height.get().sub(header_height).max(0),
             )
             .unwrap(),
         );
@@ -628,6 +709,7 @@ impl ContainerNode {
         let mut title = self.toplevel_data.title.borrow_mut();
         title.clear();
         let split = match (self.mono_child.is_some(), self.split.get()) {
+            (true, _) if self.stacked.get() => "S",
             (true, _) => "T",
             (_, ContainerSplit::Horizontal) => "H",
             (_, ContainerSplit::Vertical) => "V",
@@ -658,6 +740,9 @@ impl ContainerNode {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
+        // Borders are centered in the inner gaps between children.
+        let gap = theme.sizes.inner_gap.get();
+        let gap_after = gap - gap / 2;
         let font = theme.font.borrow_mut();
         let cwidth = self.width.get();
         let cheight = self.height.get();
@@ -673,18 +758,19 @@ impl ContainerNode {
         rd.last_active_rect.take();
         let last_active = self.focus_history.last().map(|v| v.node.node_id());
         let mono = self.mono_child.is_some();
+        let stacked = mono && self.stacked.get();
         let split = self.split.get();
         let have_active = self.children.iter().any(|c| c.active.get());
         let scales = self.state.scales.lock();
         for (i, child) in self.children.iter().enumerate() {
             let rect = child.title_rect.get();
-            if i > 0 {
+            if i > 0 && !stacked {
                 let rect = if mono {
                     Rect::new_sized(rect.x1() - bw, 0, bw, th)
                 } else if split == ContainerSplit::Horizontal {
-                    Rect::new_sized(rect.x1() - bw, 0, bw, cheight)
+                    Rect::new_sized(rect.x1() - bw - gap_after, 0, bw, cheight)
                 } else {
-                    Rect::new_sized(0, rect.y1() - bw, cwidth, bw)
+                    Rect::new_sized(0, rect.y1() - bw - gap_after, cwidth, bw)
                 };
                 rd.border_rects.push(rect.unwrap());
             }
@@ -701,11 +787,12 @@ impl ContainerNode {
                 rd.title_rects.push(rect);
                 theme.colors.unfocused_title_text.get()
             };
-            if !mono {
+            if !mono || stacked {
                 let rect = Rect::new_sized(rect.x1(), rect.y2(), rect.width(), 1).unwrap();
                 rd.underline_rects.push(rect);
             }
             let title = child.title.borrow_mut();
+            let title = child.node.tl_data().marked_title(&title);
             for (scale, _) in scales.iter() {
                 let old_tex = child.title_tex.remove(scale);
                 let titles = rd.titles.get_or_default_mut(*scale);
@@ -749,13 +836,25 @@ impl ContainerNode {
                 }
             }
         }
-        if mono {
+        if mono && !stacked {
             rd.underline_rects
                 .push(Rect::new_sized(0, th, cwidth, 1).unwrap());
         }
         rd.titles.remove_if(|_, v| v.is_empty());
     }
 
+    /// Makes the child visible if this is a mono container.
+    pub fn activate_node(self: &Rc<Self>, node: &dyn Node) {
+        let child = self
+            .child_nodes
+            .borrow()
+            .get(&node.node_id())
+            .map(|c| c.to_ref());
+        if let Some(child) = child {
+            self.activate_child(&child);
+        }
+    }
+
     fn activate_child(self: &Rc<Self>, child: &NodeRef<ContainerChild>) {
         self.activate_child2(child, false);
     }
@@ -831,6 +930,14 @@ impl ContainerNode {
         self.update_title();
     }
 
+    pub fn set_stacked(self: &Rc<Self>, stacked: bool) {
+        if self.stacked.replace(stacked) != stacked {
+            self.update_content_size();
+            self.schedule_layout();
+            self.update_title();
+        }
+    }
+
     pub fn set_split(self: &Rc<Self>, split: ContainerSplit) {
         if self.split.replace(split) != split {
             self.update_content_size();
@@ -1297,7 +1404,7 @@ impl Node for ContainerNode {
             Some(s) => s,
             _ => return,
         };
-        if seat_data.y > self.state.theme.sizes.title_height.get() {
+        if seat_data.y >= self.mono_body.get().y1() {
             return;
         }
         let cur_mc = match self.mono_child.get() {
@@ -1443,6 +1550,7 @@ impl ContainingNode for ContainerNode {
             title_rect: Cell::new(node.title_rect.get()),
             focus_history: Cell::new(None),
             attention_requested: Cell::new(false),
+            layout_animation: Cell::new(node.layout_animation.get()),
         });
         if let Some(fh) = node.focus_history.take() {
             link.focus_history.set(Some(fh.append(link.to_ref())));
@@ -1466,6 +1574,7 @@ impl ContainingNode for ContainerNode {
             let body = body.move_(self.abs_x1.get(), self.abs_y1.get());
             new.clone().tl_change_extents(&body);
         }
+        self.update_workspace_gaps();
     }
 
     fn cnode_remove_child2(self: Rc<Self>, child: &dyn Node, preserve_focus: bool) {
@@ -1473,6 +1582,7 @@ impl ContainingNode for ContainerNode {
             Some(c) => c,
             None => return,
         };
+        self.workspace.get().schedule_retile();
         node.focus_history.set(None);
         self.discard_child_properties(&node);
         if let Some(mono) = self.mono_child.get() {
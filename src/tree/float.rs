@@ -24,6 +24,7 @@ use {
     },
     ahash::AHashMap,
     std::{
+        borrow::Cow,
         cell::{Cell, RefCell},
         fmt::{Debug, Formatter},
         mem,
@@ -138,6 +139,13 @@ impl FloatNode {
         floater
     }
 
+    pub fn is_sticky(&self) -> bool {
+        match self.child.get() {
+            Some(c) => c.tl_data().sticky.get(),
+            _ => false,
+        }
+    }
+
     pub fn on_spaces_changed(self: &Rc<Self>) {
         self.schedule_layout();
     }
@@ -189,7 +197,12 @@ impl FloatNode {
         };
         let bw = theme.sizes.border_width.get();
         let font = theme.font.borrow_mut();
+        let child = self.child.get();
         let title = self.title.borrow_mut();
+        let title = match &child {
+            Some(c) => c.tl_data().marked_title(&title),
+            _ => Cow::Borrowed(title.as_str()),
+        };
         let pos = self.position.get();
         if pos.width() <= 2 * bw || title.is_empty() {
             return;
@@ -359,7 +372,7 @@ impl FloatNode {
         }
     }
 
-    fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
+    pub fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
         if let Some(c) = self.child.get() {
             c.tl_set_workspace(ws);
         }
@@ -1,5 +1,6 @@
 use {
     crate::{
+        animation::{ClosingWindow, WorkspaceTransition},
         backend::{HardwareCursor, KeyState, Mode},
         client::ClientId,
         cursor::KnownCursor,
@@ -14,7 +15,8 @@ use {
                 collect_kb_foci2,
                 tablet::{TabletTool, TabletToolChanges, TabletToolId},
                 wl_pointer::PendingScroll,
-                NodeSeatState, SeatId, WlSeatGlobal, BTN_LEFT,
+                NodeSeatState, SeatId, WlSeatGlobal, BTN_EXTRA, BTN_LEFT, BTN_MIDDLE, BTN_RIGHT,
+                BTN_SIDE,
             },
             wl_surface::{
                 ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
@@ -29,7 +31,7 @@ use {
         scale::Scale,
         state::State,
         text::{self, TextTexture},
-        time::Time,
+        time::{now_usec, Time},
         tree::{
             walker::NodeVisitor, Direction, FindTreeResult, FindTreeUsecase, FoundNode, Node,
             NodeId, WorkspaceNode,
@@ -41,7 +43,7 @@ use {
         wire::{JayOutputId, JayScreencastId, ZwlrScreencopyFrameV1Id},
     },
     ahash::AHashMap,
-    jay_config::video::Transform,
+    jay_config::{_private::ipc::StatusClick, video::Transform},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -72,6 +74,14 @@ pub struct OutputNode {
     pub update_render_data_scheduled: Cell<bool>,
     pub screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
     pub screencopies: CopyHashMap<(ClientId, ZwlrScreencopyFrameV1Id), Rc<ZwlrScreencopyFrameV1>>,
+    pub workspace_transition: RefCell<Option<WorkspaceTransition>>,
+    pub closing_windows: RefCell<Vec<ClosingWindow>>,
+    pub last_presentation_usec: Cell<u64>,
+    pub animation_frame_pending: Cell<bool>,
+    /// Whether the most recently submitted frame shows the lock screen.
+    pub lock_frame_pending: Cell<bool>,
+    /// Whether the most recently presented frame shows the lock screen.
+    pub lock_presented: Cell<bool>,
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
@@ -360,7 +370,7 @@ impl OutputNode {
                 break 'set_status;
             }
             let tc = self.state.theme.colors.bar_text.get();
-            let title = match text::render_fitting(
+            let (title, measurement) = match text::render_fitting2(
                 &ctx,
                 old_tex,
                 Some(texture_height),
@@ -369,6 +379,7 @@ impl OutputNode {
                 tc,
                 true,
                 scale,
+                false,
             ) {
                 Ok(t) => t,
                 Err(e) => {
@@ -377,14 +388,64 @@ impl OutputNode {
                 }
             };
             let (mut width, _) = title.texture.size();
-            if let Some(scale) = scale {
-                width = (width as f64 / scale).round() as _;
-            }
+            let to_logical = |v: i32| match scale {
+                Some(scale) => (v as f64 / scale).round() as i32,
+                _ => v,
+            };
+            width = to_logical(width);
             let pos = output_width - width - 1;
+            let offset = |idx: usize| {
+                if idx == 0 {
+                    return Some(0);
+                }
+                let prefix = status.get(..idx)?;
+                let m = text::measure(&font, prefix, true, scale, true).ok()?;
+                Some(to_logical(m.logical_rect.x2() - measurement.ink_rect.x1()))
+            };
+            let mut blocks = vec![];
+            for block in self.state.status_blocks.get().iter() {
+                let range = match (offset(block.start), offset(block.end)) {
+                    (Some(x1), Some(x2)) => (pos + x1, pos + x2.min(width)),
+                    _ => (0, 0),
+                };
+                blocks.push(range);
+            }
             rd.status = Some(OutputStatus {
                 tex_x: pos,
                 tex_y: 0,
                 tex: title,
+                blocks,
+            });
+        }
+        'set_lock_prompt: {
+            let old_tex = rd.lock_prompt.take().map(|p| p.tex);
+            let Some(lock) = self.state.lock.builtin.get() else {
+                break 'set_lock_prompt;
+            };
+            let ctx = match self.state.render_ctx.get() {
+                Some(ctx) => ctx,
+                _ => break 'set_lock_prompt,
+            };
+            let prompt = lock.prompt();
+            let tc = self.state.theme.colors.bar_text.get();
+            let tex =
+                match text::render_fitting(&ctx, old_tex, None, &font, &prompt, tc, false, scale) {
+                    Ok(t) => t,
+                    Err(e) => {
+                        log::error!("Could not render the lock prompt: {}", ErrorFmt(e));
+                        break 'set_lock_prompt;
+                    }
+                };
+            let (mut width, mut height) = tex.texture.size();
+            if let Some(scale) = scale {
+                width = (width as f64 / scale).round() as _;
+                height = (height as f64 / scale).round() as _;
+            }
+            let pos = self.global.pos.get();
+            rd.lock_prompt = Some(OutputLockPrompt {
+                tex_x: (pos.width() - width) / 2,
+                tex_y: (pos.height() - height) / 2,
+                tex,
             });
         }
         self.state.damage();
@@ -408,10 +469,13 @@ impl OutputNode {
 
     pub fn show_workspace(&self, ws: &Rc<WorkspaceNode>) -> bool {
         let mut seats = SmallVec::new();
+        let mut sticky = vec![];
         if let Some(old) = self.workspace.set(Some(ws.clone())) {
             if old.id == ws.id {
                 return false;
             }
+            self.start_workspace_transition(&old, ws);
+            sticky = old.take_sticky_floats();
             collect_kb_foci2(old.clone(), &mut seats);
             if old.is_empty() {
                 for jw in old.jay_workspaces.lock().values() {
@@ -426,6 +490,9 @@ impl OutputNode {
             }
         }
         self.update_visible();
+        for float in sticky {
+            float.set_workspace(ws);
+        }
         if let Some(fs) = ws.fullscreen.get() {
             fs.tl_change_extents(&self.global.pos.get());
         }
@@ -436,6 +503,59 @@ impl OutputNode {
         true
     }
 
+    fn start_workspace_transition(&self, old: &Rc<WorkspaceNode>, new: &WorkspaceNode) {
+        let animations = &self.state.animations;
+        let Some(animation) = animations.start(&animations.workspace_switch) else {
+            self.workspace_transition.take();
+            return;
+        };
+        let mut forward = true;
+        for ws in self.workspaces.iter() {
+            if ws.id == old.id {
+                break;
+            }
+            if ws.id == new.id {
+                forward = false;
+                break;
+            }
+        }
+        *self.workspace_transition.borrow_mut() = Some(WorkspaceTransition {
+            old: old.clone(),
+            animation,
+            forward,
+        });
+    }
+
+    /// Must be called when a new frame has been submitted to the backend.
+    pub fn frame_submitted(&self) {
+        self.lock_frame_pending
+            .set(self.state.lock_visible_on(self));
+    }
+
+    /// Must be called when the most recently submitted frame has been presented.
+    pub fn frame_presented(&self, time_usec: u64) {
+        self.last_presentation_usec.set(time_usec);
+        let lock = self.lock_frame_pending.get();
+        if self.lock_presented.replace(lock) != lock && lock {
+            self.state.lock.changed.trigger();
+        }
+    }
+
+    /// Returns the time at which the next frame will most likely be presented.
+    ///
+    /// This is the first vblank after the current time, extrapolated from the last
+    /// presentation reported by the backend.
+    pub fn next_frame_time_usec(&self) -> u64 {
+        let now = now_usec();
+        let last = self.last_presentation_usec.get();
+        let refresh_millihz = self.global.mode.get().refresh_rate_millihz as u64;
+        if last == 0 || last > now || refresh_millihz == 0 {
+            return now;
+        }
+        let period = 1_000_000_000 / refresh_millihz;
+        last + ((now - last) / period + 1) * period
+    }
+
     pub fn create_workspace(self: &Rc<Self>, name: &str) -> Rc<WorkspaceNode> {
         let ws = Rc::new(WorkspaceNode {
             id: self.state.node_ids.next(),
@@ -457,6 +577,9 @@ impl OutputNode {
             title_texture: Default::default(),
             attention_requests: Default::default(),
             render_highlight: Default::default(),
+            layout: self.state.default_workspace_layout.clone(),
+            retile_scheduled: Cell::new(false),
+            scroll: Default::default(),
         });
         ws.update_has_captures();
         *ws.output_link.borrow_mut() = Some(self.workspaces.add_last(ws.clone()));
@@ -673,6 +796,46 @@ impl OutputNode {
         self.schedule_update_render_data();
         self.state.tree_changed();
     }
+
+    fn status_click(&self, id: PointerType, seat: &WlSeatGlobal, button: u32) -> bool {
+        let Some((x, y)) = self.pointer_positions.get(&id) else {
+            return false;
+        };
+        let height = self.state.theme.sizes.title_height.get();
+        if y >= height {
+            return false;
+        }
+        let Some(config) = self.state.config.get() else {
+            return false;
+        };
+        let block = {
+            let rd = self.render_data.borrow();
+            let Some(status) = &rd.status else {
+                return false;
+            };
+            let block = status.blocks.iter().position(|&(x1, x2)| x >= x1 && x < x2);
+            match block {
+                Some(idx) => (idx, status.blocks[idx]),
+                _ => return false,
+            }
+        };
+        let (idx, (x1, x2)) = block;
+        let pos = self.global.pos.get();
+        config.status_clicked(StatusClick {
+            block: idx,
+            button,
+            mods: seat.modifiers(),
+            x: pos.x1() + x,
+            y: pos.y1() + y,
+            relative_x: x - x1,
+            relative_y: y,
+            output_x: x,
+            output_y: y,
+            width: x2 - x1,
+            height,
+        });
+        true
+    }
 }
 
 pub struct OutputTitle {
@@ -688,6 +851,14 @@ pub struct OutputStatus {
     pub tex_x: i32,
     pub tex_y: i32,
     pub tex: TextTexture,
+    /// The output-local x ranges of the components of an i3bar status.
+    pub blocks: Vec<(i32, i32)>,
+}
+
+pub struct OutputLockPrompt {
+    pub tex_x: i32,
+    pub tex_y: i32,
+    pub tex: TextTexture,
 }
 
 #[derive(Copy, Clone)]
@@ -705,6 +876,7 @@ pub struct OutputRenderData {
     pub captured_inactive_workspaces: Vec<Rect>,
     pub titles: Vec<OutputTitle>,
     pub status: Option<OutputStatus>,
+    pub lock_prompt: Option<OutputLockPrompt>,
 }
 
 impl Debug for OutputNode {
@@ -873,10 +1045,25 @@ impl Node for OutputNode {
         state: KeyState,
         _serial: u32,
     ) {
-        if state != KeyState::Pressed || button != BTN_LEFT {
+        if state != KeyState::Pressed {
             return;
         }
-        self.button(PointerType::Seat(seat.id()));
+        let id = PointerType::Seat(seat.id());
+        let i3bar_button = match button {
+            BTN_LEFT => 1,
+            BTN_MIDDLE => 2,
+            BTN_RIGHT => 3,
+            BTN_SIDE => 8,
+            BTN_EXTRA => 9,
+            _ => 0,
+        };
+        if i3bar_button != 0 && self.status_click(id, seat, i3bar_button) {
+            return;
+        }
+        if button != BTN_LEFT {
+            return;
+        }
+        self.button(id);
     }
 
     fn node_on_axis_event(self: Rc<Self>, seat: &Rc<WlSeatGlobal>, event: &PendingScroll) {
@@ -887,6 +1074,14 @@ impl Node for OutputNode {
         if steps == 0 {
             return;
         }
+        let id = PointerType::Seat(seat.id());
+        let i3bar_button = if steps < 0 { 4 } else { 5 };
+        if self.status_click(id, seat, i3bar_button) {
+            for _ in 1..steps.abs() {
+                self.status_click(id, seat, i3bar_button);
+            }
+            return;
+        }
         let ws = match self.workspace.get() {
             Some(ws) => ws,
             _ => return,
@@ -0,0 +1,216 @@
+use {
+    crate::{
+        ifs::wl_seat::collect_kb_foci2,
+        state::State,
+        tree::{ContainerNode, ContainerSplit, Direction, ToplevelNode, WorkspaceNode},
+    },
+    jay_config::WorkspaceLayout as JayWorkspaceLayout,
+    smallvec::SmallVec,
+    std::rc::Rc,
+};
+
+/// The automatic tiling layout of a workspace.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum WorkspaceLayout {
+    /// New windows are placed next to the last active window.
+    #[default]
+    Manual,
+    /// The first window occupies the left half and all other windows are stacked on the right.
+    MasterStack,
+    /// Each new window splits the space of the previous window, alternating the axis.
+    Dwindle,
+    /// All windows are placed in equally sized columns.
+    Columns,
+    /// All windows are placed in columns that are half as wide as the workspace. The
+    /// workspace scrolls horizontally to show the focused column.
+    ScrollingColumns,
+}
+
+impl From<JayWorkspaceLayout> for WorkspaceLayout {
+    fn from(l: JayWorkspaceLayout) -> Self {
+        match l {
+            JayWorkspaceLayout::Manual => Self::Manual,
+            JayWorkspaceLayout::MasterStack => Self::MasterStack,
+            JayWorkspaceLayout::Dwindle => Self::Dwindle,
+            JayWorkspaceLayout::Columns => Self::Columns,
+            JayWorkspaceLayout::ScrollingColumns => Self::ScrollingColumns,
+        }
+    }
+}
+
+impl Into<JayWorkspaceLayout> for WorkspaceLayout {
+    fn into(self) -> JayWorkspaceLayout {
+        match self {
+            Self::Manual => JayWorkspaceLayout::Manual,
+            Self::MasterStack => JayWorkspaceLayout::MasterStack,
+            Self::Dwindle => JayWorkspaceLayout::Dwindle,
+            Self::Columns => JayWorkspaceLayout::Columns,
+            Self::ScrollingColumns => JayWorkspaceLayout::ScrollingColumns,
+        }
+    }
+}
+
+fn is_leaf(node: &Rc<dyn ToplevelNode>) -> bool {
+    !node.tl_as_node().node_is_container()
+}
+
+fn as_container(node: &Rc<dyn ToplevelNode>) -> Option<Rc<ContainerNode>> {
+    node.clone().tl_into_node().node_into_container()
+}
+
+/// Inserts a new tiled window into the root container of a workspace with an automatic layout.
+pub fn place_tiled(
+    state: &Rc<State>,
+    ws: &Rc<WorkspaceNode>,
+    root: &Rc<ContainerNode>,
+    node: Rc<dyn ToplevelNode>,
+) {
+    match ws.layout.get() {
+        WorkspaceLayout::Manual | WorkspaceLayout::Columns => {
+            root.append_child(node);
+        }
+        WorkspaceLayout::ScrollingColumns => {
+            root.append_child(node);
+            ws.update_container_extents();
+        }
+        WorkspaceLayout::MasterStack => {
+            let last = root.children.last().map(|c| c.node.clone());
+            match last {
+                Some(last) if root.children.iter().count() > 1 => {
+                    let stack = match as_container(&last) {
+                        Some(c) if c.split.get() == ContainerSplit::Vertical => c,
+                        _ => wrap(state, ws, &last, ContainerSplit::Vertical),
+                    };
+                    stack.append_child(node);
+                }
+                _ => root.append_child(node),
+            }
+        }
+        WorkspaceLayout::Dwindle => {
+            let mut parent = root.clone();
+            while let Some(c) = parent.children.last().and_then(|c| as_container(&c.node)) {
+                parent = c;
+            }
+            let leaf = parent.children.last().map(|c| c.node.clone());
+            match leaf {
+                Some(leaf) if parent.children.iter().count() > 1 => {
+                    let split = match parent.split.get() {
+                        ContainerSplit::Horizontal => ContainerSplit::Vertical,
+                        ContainerSplit::Vertical => ContainerSplit::Horizontal,
+                    };
+                    wrap(state, ws, &leaf, split).append_child(node);
+                }
+                _ => parent.append_child(node),
+            }
+        }
+    }
+}
+
+/// Replaces `node` by a new container with the given split that contains `node`.
+fn wrap(
+    state: &Rc<State>,
+    ws: &Rc<WorkspaceNode>,
+    node: &Rc<dyn ToplevelNode>,
+    split: ContainerSplit,
+) -> Rc<ContainerNode> {
+    let parent = node.tl_data().parent.get();
+    let container = ContainerNode::new(state, ws, node.clone(), split);
+    if let Some(parent) = parent {
+        parent.cnode_replace_child(node.tl_as_node(), container.clone());
+    }
+    container
+}
+
+/// Returns whether the tree below `root` has the shape produced by `place_tiled`.
+pub fn is_canonical(layout: WorkspaceLayout, root: &ContainerNode) -> bool {
+    let is_split =
+        |c: &ContainerNode, split: ContainerSplit| c.mono_child.is_none() && c.split.get() == split;
+    if !is_split(root, ContainerSplit::Horizontal) {
+        return layout == WorkspaceLayout::Manual;
+    }
+    let children: SmallVec<[_; 4]> = root.children.iter().map(|c| c.node.clone()).collect();
+    match layout {
+        WorkspaceLayout::Manual => true,
+        WorkspaceLayout::Columns | WorkspaceLayout::ScrollingColumns => {
+            children.iter().all(is_leaf)
+        }
+        WorkspaceLayout::MasterStack => match &*children {
+            [master] => is_leaf(master),
+            [master, stack] => {
+                is_leaf(master)
+                    && (is_leaf(stack)
+                        || as_container(stack).is_some_and(|s| {
+                            is_split(&s, ContainerSplit::Vertical)
+                                && s.children.iter().count() > 1
+                                && s.children.iter().all(|c| is_leaf(&c.node))
+                        }))
+            }
+            _ => false,
+        },
+        WorkspaceLayout::Dwindle => {
+            let mut children = children;
+            let mut split = ContainerSplit::Horizontal;
+            loop {
+                let [first, second] = &*children else {
+                    return children.len() == 1 && is_leaf(&children[0]);
+                };
+                if !is_leaf(first) {
+                    return false;
+                }
+                if is_leaf(second) {
+                    return true;
+                }
+                let Some(c) = as_container(second) else {
+                    return false;
+                };
+                split = match split {
+                    ContainerSplit::Horizontal => ContainerSplit::Vertical,
+                    ContainerSplit::Vertical => ContainerSplit::Horizontal,
+                };
+                if !is_split(&c, split) {
+                    return false;
+                }
+                children = c.children.iter().map(|c| c.node.clone()).collect();
+                if children.len() != 2 {
+                    return false;
+                }
+            }
+        }
+    }
+}
+
+/// Removes all tiled windows from the workspace and inserts them again in their previous order.
+pub fn retile(state: &Rc<State>, ws: &Rc<WorkspaceNode>, root: &Rc<ContainerNode>) {
+    let mut leaves = vec![];
+    collect_leaves(root, &mut leaves);
+    let mut foci = vec![];
+    for leaf in &leaves {
+        let mut seats = SmallVec::new();
+        collect_kb_foci2(leaf.clone().tl_into_node(), &mut seats);
+        for seat in seats {
+            foci.push((seat, leaf.clone()));
+        }
+    }
+    for leaf in &leaves {
+        if let Some(parent) = leaf.tl_data().parent.get() {
+            parent.cnode_remove_child2(leaf.tl_as_node(), true);
+        }
+    }
+    for leaf in leaves {
+        state.map_tiled_on(leaf, ws);
+    }
+    for (seat, leaf) in foci {
+        if leaf.node_visible() {
+            leaf.node_do_focus(&seat, Direction::Unspecified);
+        }
+    }
+}
+
+fn collect_leaves(container: &ContainerNode, leaves: &mut Vec<Rc<dyn ToplevelNode>>) {
+    for child in container.children.iter() {
+        match as_container(&child.node) {
+            Some(c) => collect_leaves(&c, leaves),
+            _ => leaves.push(child.node.clone()),
+        }
+    }
+}
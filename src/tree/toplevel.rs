@@ -1,6 +1,8 @@
 use {
     crate::{
+        animation::{Animation, ClosingWindow},
         client::{Client, ClientId},
+        gfx_api::BlurCache,
         ifs::{
             ext_foreign_toplevel_handle_v1::ExtForeignToplevelHandleV1,
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
@@ -15,6 +17,7 @@ use {
         utils::{
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
+            linkedlist::LinkedNode,
             numcell::NumCell,
             smallmap::SmallMap,
             threshold_counter::ThresholdCounter,
@@ -23,7 +26,9 @@ use {
         wire::{ExtForeignToplevelHandleV1Id, JayScreencastId, JayToplevelId},
     },
     std::{
+        borrow::Cow,
         cell::{Cell, RefCell},
+        fmt::Write,
         ops::Deref,
         rc::Rc,
     },
@@ -136,6 +141,11 @@ impl<T: ToplevelNodeBase> ToplevelNode for T {
     }
 
     fn tl_destroy(&self) {
+        if self.node_visible() {
+            if let Some(surface) = self.tl_scanout_surface() {
+                self.tl_data().animate_close(&surface);
+            }
+        }
         self.tl_data().destroy_node(self);
         self.tl_destroy_impl();
     }
@@ -222,6 +232,13 @@ pub struct ToplevelData {
     pub render_highlight: NumCell<u32>,
     pub jay_toplevels: CopyHashMap<(ClientId, JayToplevelId), Rc<JayToplevel>>,
     pub jay_screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
+    pub opacity: Cell<Option<f32>>,
+    pub blur_cache: Rc<BlurCache>,
+    pub open_animation: Cell<Option<Animation>>,
+    pub scratchpad_link: Cell<Option<LinkedNode<Rc<dyn ToplevelNode>>>>,
+    pub sticky: Cell<bool>,
+    pub marks: RefCell<Vec<String>>,
+    pub keyboard_layouts: SmallMap<SeatId, u32, 1>,
 }
 
 impl ToplevelData {
@@ -252,7 +269,73 @@ impl ToplevelData {
             render_highlight: Default::default(),
             jay_toplevels: Default::default(),
             jay_screencasts: Default::default(),
+            opacity: Default::default(),
+            blur_cache: Default::default(),
+            open_animation: Default::default(),
+            scratchpad_link: Default::default(),
+            sticky: Cell::new(false),
+            marks: Default::default(),
+            keyboard_layouts: Default::default(),
+        }
+    }
+
+    /// Starts the animation that is shown when the toplevel is mapped.
+    pub fn animate_open(&self) {
+        let animations = &self.state.animations;
+        self.open_animation
+            .set(animations.start(&animations.window_open));
+    }
+
+    /// Keeps the last contents of the surface on screen while the toplevel disappears.
+    fn animate_close(&self, surface: &WlSurface) {
+        let Some(ws) = self.workspace.get() else {
+            return;
+        };
+        let animations = &self.state.animations;
+        let Some(animation) = animations.start(&animations.window_close) else {
+            return;
+        };
+        let Some(buffer) = surface.buffer.get() else {
+            return;
+        };
+        let Some(texture) = buffer.buffer.texture.get() else {
+            return;
+        };
+        let output = ws.output.get();
+        output.closing_windows.borrow_mut().push(ClosingWindow {
+            texture,
+            source: *surface.buffer_points_norm.borrow(),
+            rect: surface.buffer_abs_pos.get(),
+            animation,
+        });
+        self.state.damage_log.damage_all();
+        output.global.connector.connector.damage();
+    }
+
+    /// Returns the title prefixed by the marks of the toplevel.
+    pub fn marked_title<'a>(&self, title: &'a str) -> Cow<'a, str> {
+        let marks = self.marks.borrow();
+        if marks.is_empty() {
+            return Cow::Borrowed(title);
+        }
+        let mut res = String::new();
+        for mark in marks.iter() {
+            let _ = write!(res, "[{}] ", mark);
         }
+        res.push_str(title);
+        Cow::Owned(res)
+    }
+
+    /// Re-renders the title of the toplevel after its marks have changed.
+    pub fn marks_changed(&self) {
+        if let Some(parent) = self.parent.get() {
+            if let Some(container) = parent.clone().node_into_container() {
+                container.schedule_compute_render_data();
+            } else if let Some(float) = parent.node_into_float() {
+                float.schedule_render_titles();
+            }
+        }
+        self.state.damage();
     }
 
     pub fn active(&self) -> bool {
@@ -313,6 +396,10 @@ impl ToplevelData {
             parent.cnode_remove_child(node);
         }
         self.workspace.take();
+        self.scratchpad_link.take();
+        for mark in self.marks.take() {
+            self.state.marks.remove(&mark);
+        }
         self.seat_state.destroy_node(node);
         self.focus_node.clear();
     }
@@ -13,9 +13,12 @@ use {
         renderer::Renderer,
         text::TextTexture,
         tree::{
-            container::ContainerNode, walker::NodeVisitor, ContainingNode, Direction,
-            FindTreeResult, FindTreeUsecase, FoundNode, Node, NodeId, NodeVisitorBase, OutputNode,
-            StackedNode, ToplevelNode,
+            container::ContainerNode,
+            tiling::{self, WorkspaceLayout},
+            toplevel::ToplevelNodeBase,
+            walker::NodeVisitor,
+            ContainingNode, Direction, FindTreeResult, FindTreeUsecase, FloatNode, FoundNode, Node,
+            NodeId, NodeVisitorBase, OutputNode, StackedNode, ToplevelNode,
         },
         utils::{
             clonecell::CloneCell,
@@ -56,6 +59,10 @@ pub struct WorkspaceNode {
     pub title_texture: Cell<Option<TextTexture>>,
     pub attention_requests: ThresholdCounter,
     pub render_highlight: NumCell<u32>,
+    pub layout: Cell<WorkspaceLayout>,
+    pub retile_scheduled: Cell<bool>,
+    /// The horizontal scroll position of the container in the scrolling-columns layout.
+    pub scroll: Cell<i32>,
 }
 
 impl WorkspaceNode {
@@ -112,11 +119,56 @@ impl WorkspaceNode {
             self.discard_child_properties(&*prev);
         }
         self.pull_child_properties(&**container);
-        let pos = self.position.get();
+        self.container.set(Some(container.clone()));
+        let pos = self.container_rect();
         container.clone().tl_change_extents(&pos);
         container.tl_set_parent(self.clone());
         container.tl_set_visible(self.container_visible());
-        self.container.set(Some(container.clone()));
+    }
+
+    pub fn set_layout(self: &Rc<Self>, layout: WorkspaceLayout) {
+        if self.layout.replace(layout) != layout {
+            self.retile();
+        }
+    }
+
+    /// Restores the shape of the automatic layout after a tiled window has been removed.
+    pub fn schedule_retile(self: &Rc<Self>) {
+        if self.layout.get() == WorkspaceLayout::Manual || self.retile_scheduled.replace(true) {
+            return;
+        }
+        let slf = self.clone();
+        self.output
+            .get()
+            .state
+            .run_toplevel
+            .schedule(move || slf.retile());
+    }
+
+    fn retile(self: &Rc<Self>) {
+        self.retile_scheduled.set(true);
+        if let Some(root) = self.container.get() {
+            if !tiling::is_canonical(self.layout.get(), &root) {
+                tiling::retile(&self.output.get().state, self, &root);
+            }
+        }
+        self.retile_scheduled.set(false);
+        self.scroll_to_active();
+        self.update_container_extents();
+    }
+
+    /// Detaches the sticky floating windows from this workspace.
+    pub fn take_sticky_floats(&self) -> Vec<Rc<FloatNode>> {
+        let floats: Vec<_> = self
+            .stacked
+            .iter()
+            .filter_map(|s| s.deref().clone().stacked_into_node().node_into_float())
+            .filter(|f| f.is_sticky())
+            .collect();
+        for float in &floats {
+            float.workspace_link.take();
+        }
+        floats
     }
 
     pub fn is_empty(&self) -> bool {
@@ -129,8 +181,89 @@ impl WorkspaceNode {
 
     pub fn change_extents(&self, rect: &Rect) {
         self.position.set(*rect);
+        self.update_container_extents();
+    }
+
+    /// Returns the area of the workspace that is occupied by the container.
+    ///
+    /// This is the position of the workspace shrunk by the outer gaps. In the
+    /// scrolling-columns layout, the container can be wider than this area and is shifted
+    /// by the scroll position.
+    pub fn container_rect(&self) -> Rect {
+        let visible = self.visible_container_rect();
+        if self.layout.get() != WorkspaceLayout::ScrollingColumns {
+            return visible;
+        }
+        let width = self.scrolling_width(&visible);
+        let x1 = visible.x1() - self.scroll.get().clamp(0, width - visible.width());
+        Rect::new_sized(x1, visible.y1(), width, visible.height()).unwrap()
+    }
+
+    fn visible_container_rect(&self) -> Rect {
+        let pos = self.position.get();
+        let theme = &self.output.get().state.theme;
+        let mut gap = theme.sizes.outer_gap.get();
+        if theme.smart_gaps.get() && self.has_single_window() {
+            gap = 0;
+        }
+        let x1 = pos.x1() + gap;
+        let y1 = pos.y1() + gap;
+        Rect::new(x1, y1, (pos.x2() - gap).max(x1), (pos.y2() - gap).max(y1)).unwrap()
+    }
+
+    /// Returns the width of the container in the scrolling-columns layout.
+    ///
+    /// Each column occupies half of the visible area unless there are fewer than two columns.
+    fn scrolling_width(&self, visible: &Rect) -> i32 {
+        let columns = match self.container.get() {
+            Some(c) => c.children.iter().count() as i32,
+            _ => 0,
+        };
+        visible.width().max(columns * (visible.width() / 2))
+    }
+
+    /// Scrolls the container such that the most recently focused column is visible.
+    pub fn scroll_to_active(&self) {
+        if self.layout.get() != WorkspaceLayout::ScrollingColumns {
+            return;
+        }
+        let Some(container) = self.container.get() else {
+            return;
+        };
+        let Some((start, end)) = container.focused_child_span() else {
+            return;
+        };
+        let visible = self.visible_container_rect().width();
+        let width = self.scrolling_width(&self.visible_container_rect());
+        let x1 = (start * width as f64).round() as i32;
+        let x2 = (end * width as f64).round() as i32;
+        let mut scroll = self.scroll.get().clamp(0, width - visible);
+        if x1 < scroll {
+            scroll = x1;
+        } else if x2 > scroll + visible {
+            scroll = x2 - visible;
+        }
+        if self.scroll.replace(scroll) != scroll {
+            self.update_container_extents();
+        }
+    }
+
+    fn has_single_window(&self) -> bool {
+        match self.container.get() {
+            Some(c) => match c.children.first() {
+                Some(child) => child.next().is_none() && !child.node.node_is_container(),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    pub fn update_container_extents(&self) {
         if let Some(c) = self.container.get() {
-            c.tl_change_extents(rect);
+            let rect = self.container_rect();
+            if c.tl_data().pos.get() != rect {
+                c.tl_change_extents(&rect);
+            }
         }
     }
 
@@ -243,6 +376,12 @@ impl Node for WorkspaceNode {
         self.position.get()
     }
 
+    fn node_child_active_changed(self: Rc<Self>, _child: &dyn Node, active: bool, _depth: u32) {
+        if active {
+            self.scroll_to_active();
+        }
+    }
+
     fn node_do_focus(self: Rc<Self>, seat: &Rc<WlSeatGlobal>, direction: Direction) {
         if let Some(fs) = self.fullscreen.get() {
             fs.tl_into_node().node_do_focus(seat, direction);
@@ -259,12 +398,18 @@ impl Node for WorkspaceNode {
         usecase: FindTreeUsecase,
     ) -> FindTreeResult {
         if let Some(n) = self.container.get() {
-            tree.push(FoundNode {
-                node: n.clone(),
-                x,
-                y,
-            });
-            n.node_find_tree_at(x, y, tree, usecase);
+            let rect = self
+                .container_rect()
+                .move_(-self.position.get().x1(), -self.position.get().y1());
+            if rect.contains(x, y) {
+                let (x, y) = rect.translate(x, y);
+                tree.push(FoundNode {
+                    node: n.clone(),
+                    x,
+                    y,
+                });
+                n.node_find_tree_at(x, y, tree, usecase);
+            }
         }
         FindTreeResult::AcceptsInput
     }
@@ -11,6 +11,7 @@ impl GfxApiExt for GfxApi {
         match self {
             GfxApi::OpenGl => "OpenGl",
             GfxApi::Vulkan => "Vulkan",
+            GfxApi::Software => "Software",
             _ => "unknown",
         }
     }
@@ -19,6 +20,7 @@ impl GfxApiExt for GfxApi {
         match &*s.to_ascii_lowercase() {
             "opengl" => Some(Self::OpenGl),
             "vulkan" => Some(Self::Vulkan),
+            "software" => Some(Self::Software),
             _ => None,
         }
     }
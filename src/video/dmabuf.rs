@@ -59,6 +59,12 @@ impl DmaBuf {
 
 const DMA_BUF_BASE: u64 = b'b' as _;
 
+#[allow(non_camel_case_types)]
+#[repr(C)]
+struct dma_buf_sync {
+    flags: u64,
+}
+
 #[allow(non_camel_case_types)]
 #[repr(C)]
 struct dma_buf_export_sync_file {
@@ -75,10 +81,24 @@ struct dma_buf_import_sync_file {
 
 pub const DMA_BUF_SYNC_READ: u32 = 1 << 0;
 pub const DMA_BUF_SYNC_WRITE: u32 = 1 << 1;
+pub const DMA_BUF_SYNC_START: u32 = 0 << 2;
+pub const DMA_BUF_SYNC_END: u32 = 1 << 2;
+
+const DMA_BUF_IOCTL_SYNC: u64 = _IOW::<dma_buf_sync>(DMA_BUF_BASE, 0);
 
 const DMA_BUF_IOCTL_EXPORT_SYNC_FILE: u64 = _IOWR::<dma_buf_export_sync_file>(DMA_BUF_BASE, 2);
 const DMA_BUF_IOCTL_IMPORT_SYNC_FILE: u64 = _IOW::<dma_buf_import_sync_file>(DMA_BUF_BASE, 3);
 
+pub fn dma_buf_sync(dmabuf: &OwnedFd, flags: u32) -> Result<(), OsError> {
+    let mut data = dma_buf_sync { flags: flags as _ };
+    let res = unsafe { ioctl(dmabuf.raw(), DMA_BUF_IOCTL_SYNC, &mut data) };
+    if res != 0 {
+        Err(OsError::default())
+    } else {
+        Ok(())
+    }
+}
+
 pub fn dma_buf_export_sync_file(dmabuf: &OwnedFd, flags: u32) -> Result<OwnedFd, OsError> {
     let mut data = dma_buf_export_sync_file { flags, fd: -1 };
     let res = unsafe { ioctl(dmabuf.raw(), DMA_BUF_IOCTL_EXPORT_SYNC_FILE, &mut data) };
@@ -141,6 +141,7 @@ pub struct GbmBo {
 pub struct GbmBoMap {
     bo: Rc<GbmBo>,
     data: *mut [u8],
+    stride: i32,
     opaque: *mut u8,
 }
 
@@ -148,6 +149,10 @@ impl GbmBoMap {
     pub unsafe fn data(&self) -> &[u8] {
         &*self.data
     }
+
+    pub fn stride(&self) -> i32 {
+        self.stride
+    }
 }
 
 unsafe fn export_bo(dmabuf_ids: &DmaBufIds, bo: *mut Bo) -> Result<DmaBuf, GbmError> {
@@ -305,6 +310,7 @@ impl GbmBo {
             Ok(GbmBoMap {
                 bo: self.clone(),
                 data: map,
+                stride: stride as _,
                 opaque: map_data,
             })
         }
@@ -1,11 +1,15 @@
+pub mod usr_jay_appearance;
 pub mod usr_jay_compositor;
+pub mod usr_jay_global_shortcuts;
 pub mod usr_jay_output;
 pub mod usr_jay_pointer;
 pub mod usr_jay_render_ctx;
 pub mod usr_jay_screencast;
+pub mod usr_jay_screenshot;
 pub mod usr_jay_select_toplevel;
 pub mod usr_jay_select_workspace;
 pub mod usr_jay_toplevel;
+pub mod usr_jay_virtual_input;
 pub mod usr_jay_workspace;
 pub mod usr_jay_workspace_watcher;
 pub mod usr_linux_buffer_params;
@@ -0,0 +1,76 @@
+use {
+    crate::{
+        theme::Appearance,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{jay_appearance::*, JayAppearanceId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct UsrJayAppearance {
+    pub id: JayAppearanceId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayAppearanceOwner>>>,
+    pub pending: Cell<Appearance>,
+}
+
+pub trait UsrJayAppearanceOwner {
+    fn appearance(&self, appearance: Appearance);
+}
+
+impl UsrJayAppearance {
+    fn color_scheme(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: ColorScheme = self.con.parse(self, parser)?;
+        let mut pending = self.pending.get();
+        pending.color_scheme = ev.color_scheme;
+        self.pending.set(pending);
+        Ok(())
+    }
+
+    fn accent_color(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: AccentColor = self.con.parse(self, parser)?;
+        let mut pending = self.pending.get();
+        pending.accent_color = [ev.r, ev.g, ev.b].map(|c| c.to_f64() as f32);
+        self.pending.set(pending);
+        Ok(())
+    }
+
+    fn contrast(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: Contrast = self.con.parse(self, parser)?;
+        let mut pending = self.pending.get();
+        pending.contrast = ev.contrast;
+        self.pending.set(pending);
+        Ok(())
+    }
+
+    fn done(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let _ev: Done = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.appearance(self.pending.get());
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    UsrJayAppearance, JayAppearance;
+
+    COLOR_SCHEME => color_scheme,
+    ACCENT_COLOR => accent_color,
+    CONTRAST => contrast,
+    DONE => done,
+}
+
+impl UsrObject for UsrJayAppearance {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
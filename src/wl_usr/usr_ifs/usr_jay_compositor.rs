@@ -8,10 +8,13 @@ use {
         wire::{jay_compositor::*, JayCompositorId},
         wl_usr::{
             usr_ifs::{
-                usr_jay_output::UsrJayOutput, usr_jay_pointer::UsrJayPointer,
-                usr_jay_render_ctx::UsrJayRenderCtx, usr_jay_screencast::UsrJayScreencast,
+                usr_jay_appearance::UsrJayAppearance,
+                usr_jay_global_shortcuts::UsrJayGlobalShortcuts, usr_jay_output::UsrJayOutput,
+                usr_jay_pointer::UsrJayPointer, usr_jay_render_ctx::UsrJayRenderCtx,
+                usr_jay_screencast::UsrJayScreencast, usr_jay_screenshot::UsrJayScreenshot,
                 usr_jay_select_toplevel::UsrJaySelectToplevel,
                 usr_jay_select_workspace::UsrJaySelectWorkspace,
+                usr_jay_virtual_input::UsrJayVirtualInput,
                 usr_jay_workspace_watcher::UsrJayWorkspaceWatcher, usr_wl_output::UsrWlOutput,
                 usr_wl_seat::UsrWlSeat,
             },
@@ -33,6 +36,9 @@ pub struct UsrJayCompositor {
 pub struct UsrJayCompositorCaps {
     pub window_capture: Cell<bool>,
     pub select_workspace: Cell<bool>,
+    pub virtual_input: Cell<bool>,
+    pub global_shortcuts: Cell<bool>,
+    pub appearance: Cell<bool>,
 }
 
 pub trait UsrJayCompositorOwner {
@@ -77,6 +83,21 @@ impl UsrJayCompositor {
         sc
     }
 
+    pub fn take_screenshot(&self, include_cursor: bool) -> Rc<UsrJayScreenshot> {
+        let ss = Rc::new(UsrJayScreenshot {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+        });
+        self.con.request(TakeScreenshot2 {
+            self_id: self.id,
+            id: ss.id,
+            include_cursor: include_cursor as _,
+        });
+        self.con.add_object(ss.clone());
+        ss
+    }
+
     pub fn get_output(&self, output: &UsrWlOutput) -> Rc<UsrJayOutput> {
         let jo = Rc::new(UsrJayOutput {
             id: self.con.id(),
@@ -107,6 +128,21 @@ impl UsrJayCompositor {
         ww
     }
 
+    pub fn watch_appearance(&self) -> Rc<UsrJayAppearance> {
+        let wa = Rc::new(UsrJayAppearance {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+            pending: Default::default(),
+        });
+        self.con.request(WatchAppearance {
+            self_id: self.id,
+            id: wa.id,
+        });
+        self.con.add_object(wa.clone());
+        wa
+    }
+
     pub fn get_pointer(&self, seat: &UsrWlSeat) -> Rc<UsrJayPointer> {
         let jp = Rc::new(UsrJayPointer {
             id: self.con.id(),
@@ -151,6 +187,35 @@ impl UsrJayCompositor {
         sc
     }
 
+    pub fn create_virtual_input(&self, seat: &UsrWlSeat) -> Rc<UsrJayVirtualInput> {
+        let vi = Rc::new(UsrJayVirtualInput {
+            id: self.con.id(),
+            con: self.con.clone(),
+        });
+        self.con.request(CreateVirtualInput {
+            self_id: self.id,
+            id: vi.id,
+            seat: seat.id,
+        });
+        self.con.add_object(vi.clone());
+        vi
+    }
+
+    pub fn create_global_shortcuts(&self, seat: &UsrWlSeat) -> Rc<UsrJayGlobalShortcuts> {
+        let gs = Rc::new(UsrJayGlobalShortcuts {
+            id: self.con.id(),
+            con: self.con.clone(),
+            owner: Default::default(),
+        });
+        self.con.request(CreateGlobalShortcuts {
+            self_id: self.id,
+            id: gs.id,
+            seat: seat.id,
+        });
+        self.con.add_object(gs.clone());
+        gs
+    }
+
     fn client_id(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
         let ev: ClientId = self.con.parse(self, parser)?;
         if let Some(owner) = self.owner.get() {
@@ -174,6 +239,9 @@ impl UsrJayCompositor {
                 Cap::NONE => {}
                 Cap::WINDOW_CAPTURE => self.caps.window_capture.set(true),
                 Cap::SELECT_WORKSPACE => self.caps.select_workspace.set(true),
+                Cap::VIRTUAL_INPUT => self.caps.virtual_input.set(true),
+                Cap::GLOBAL_SHORTCUTS => self.caps.global_shortcuts.set(true),
+                Cap::APPEARANCE => self.caps.appearance.set(true),
                 _ => {}
             }
         }
@@ -0,0 +1,74 @@
+use {
+    crate::{
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{jay_global_shortcuts::*, JayGlobalShortcutsId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    jay_config::keyboard::{mods::Modifiers, syms::KeySym},
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct UsrJayGlobalShortcuts {
+    pub id: JayGlobalShortcutsId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayGlobalShortcutsOwner>>>,
+}
+
+pub trait UsrJayGlobalShortcutsOwner {
+    fn activated(&self, id: u32, time_usec: u64);
+    fn deactivated(&self, id: u32, time_usec: u64);
+}
+
+impl UsrJayGlobalShortcuts {
+    pub fn bind(&self, id: u32, mods: Modifiers, sym: KeySym) {
+        self.con.request(Bind {
+            self_id: self.id,
+            id,
+            mods: mods.0,
+            keysym: sym.0,
+        });
+    }
+
+    fn activated(&self, parser: MsgParser<'_, '_>) -> Result<(), UsrJayGlobalShortcutsError> {
+        let ev: Activated = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.activated(ev.id, ev.time_usec);
+        }
+        Ok(())
+    }
+
+    fn deactivated(&self, parser: MsgParser<'_, '_>) -> Result<(), UsrJayGlobalShortcutsError> {
+        let ev: Deactivated = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.deactivated(ev.id, ev.time_usec);
+        }
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    UsrJayGlobalShortcuts, JayGlobalShortcuts;
+
+    ACTIVATED => activated,
+    DEACTIVATED => deactivated,
+}
+
+impl UsrObject for UsrJayGlobalShortcuts {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum UsrJayGlobalShortcutsError {
+    #[error("Parsing failed")]
+    MsgParserError(#[from] MsgParserError),
+}
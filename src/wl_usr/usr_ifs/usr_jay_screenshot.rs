@@ -0,0 +1,59 @@
+use {
+    crate::{
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            clonecell::CloneCell,
+        },
+        wire::{jay_screenshot::*, JayScreenshotId},
+        wl_usr::{usr_object::UsrObject, UsrCon},
+    },
+    std::rc::Rc,
+};
+
+pub struct UsrJayScreenshot {
+    pub id: JayScreenshotId,
+    pub con: Rc<UsrCon>,
+    pub owner: CloneCell<Option<Rc<dyn UsrJayScreenshotOwner>>>,
+}
+
+pub trait UsrJayScreenshotOwner {
+    fn dmabuf(&self, ev: &Dmabuf);
+    fn error(&self, msg: &str);
+}
+
+impl UsrJayScreenshot {
+    fn dmabuf(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: Dmabuf = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.dmabuf(&ev);
+        }
+        self.con.remove_obj(self);
+        Ok(())
+    }
+
+    fn error(&self, parser: MsgParser<'_, '_>) -> Result<(), MsgParserError> {
+        let ev: Error = self.con.parse(self, parser)?;
+        if let Some(owner) = self.owner.get() {
+            owner.error(ev.msg);
+        }
+        self.con.remove_obj(self);
+        Ok(())
+    }
+}
+
+usr_object_base! {
+    UsrJayScreenshot, JayScreenshot;
+
+    DMABUF => dmabuf,
+    ERROR => error,
+}
+
+impl UsrObject for UsrJayScreenshot {
+    fn destroy(&self) {
+        // nothing
+    }
+
+    fn break_loops(&self) {
+        self.owner.take();
+    }
+}
@@ -0,0 +1,124 @@
+use {
+    crate::{
+        fixed::Fixed,
+        wire::{jay_virtual_input::*, JayVirtualInputId},
+        wl_usr::{usr_ifs::usr_wl_output::UsrWlOutput, usr_object::UsrObject, UsrCon},
+    },
+    std::rc::Rc,
+};
+
+pub struct UsrJayVirtualInput {
+    pub id: JayVirtualInputId,
+    pub con: Rc<UsrCon>,
+}
+
+impl UsrJayVirtualInput {
+    pub fn motion(&self, dx: f64, dy: f64) {
+        self.con.request(Motion {
+            self_id: self.id,
+            dx: Fixed::from_f64(dx),
+            dy: Fixed::from_f64(dy),
+        });
+    }
+
+    pub fn motion_absolute(&self, output: &UsrWlOutput, x: f64, y: f64) {
+        self.con.request(MotionAbsolute {
+            self_id: self.id,
+            output: output.id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+    }
+
+    pub fn button(&self, button: u32, state: u32) {
+        self.con.request(Button {
+            self_id: self.id,
+            button,
+            state,
+        });
+    }
+
+    pub fn axis(&self, axis: u32, value: f64) {
+        self.con.request(Axis {
+            self_id: self.id,
+            axis,
+            value: Fixed::from_f64(value),
+        });
+    }
+
+    pub fn axis_discrete(&self, axis: u32, steps: i32) {
+        self.con.request(AxisDiscrete {
+            self_id: self.id,
+            axis,
+            steps,
+        });
+    }
+
+    pub fn axis_stop(&self, axis: u32) {
+        self.con.request(AxisStop {
+            self_id: self.id,
+            axis,
+        });
+    }
+
+    pub fn frame(&self) {
+        self.con.request(Frame { self_id: self.id });
+    }
+
+    pub fn key(&self, key: u32, state: u32) {
+        self.con.request(Key {
+            self_id: self.id,
+            key,
+            state,
+        });
+    }
+
+    pub fn keysym(&self, keysym: u32, state: u32) {
+        self.con.request(Keysym {
+            self_id: self.id,
+            keysym,
+            state,
+        });
+    }
+
+    pub fn touch_down(&self, output: &UsrWlOutput, id: i32, x: f64, y: f64) {
+        self.con.request(TouchDown {
+            self_id: self.id,
+            output: output.id,
+            id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+    }
+
+    pub fn touch_motion(&self, output: &UsrWlOutput, id: i32, x: f64, y: f64) {
+        self.con.request(TouchMotion {
+            self_id: self.id,
+            output: output.id,
+            id,
+            x: Fixed::from_f64(x),
+            y: Fixed::from_f64(y),
+        });
+    }
+
+    pub fn touch_up(&self, id: i32) {
+        self.con.request(TouchUp {
+            self_id: self.id,
+            id,
+        });
+    }
+
+    pub fn touch_frame(&self) {
+        self.con.request(TouchFrame { self_id: self.id });
+    }
+}
+
+usr_object_base! {
+    UsrJayVirtualInput, JayVirtualInput;
+}
+
+impl UsrObject for UsrJayVirtualInput {
+    fn destroy(&self) {
+        self.con.request(Destroy { self_id: self.id });
+    }
+}
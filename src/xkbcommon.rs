@@ -13,7 +13,7 @@ use {
     isnt::std_1::primitive::IsntConstPtrExt,
     std::{
         cell::{Ref, RefCell},
-        ffi::CStr,
+        ffi::{CStr, CString},
         io::Write,
         ops::Deref,
         ptr,
@@ -31,6 +31,10 @@ pub enum XkbCommonError {
     CreateState,
     #[error("Could not create keymap from buffer")]
     KeymapFromBuffer,
+    #[error("Could not create keymap from names")]
+    KeymapFromNames,
+    #[error("A keymap name contains a nul byte")]
+    NulInName,
     #[error("Could not convert the keymap to a string")]
     AsStr,
     #[error("Could not create a keymap memfd")]
@@ -83,11 +87,23 @@ extern "C" {
         format: xkb_keymap_format,
         flags: xkb_keymap_compile_flags,
     ) -> *mut xkb_keymap;
+    fn xkb_keymap_new_from_names(
+        context: *mut xkb_context,
+        names: *const xkb_rule_names,
+        flags: xkb_keymap_compile_flags,
+    ) -> *mut xkb_keymap;
+    fn xkb_keymap_num_layouts(keymap: *mut xkb_keymap) -> xkb_layout_index_t;
+    fn xkb_keymap_layout_get_name(
+        keymap: *mut xkb_keymap,
+        idx: xkb_layout_index_t,
+    ) -> *const c::c_char;
     fn xkb_keymap_get_as_string(
         keymap: *mut xkb_keymap,
         format: xkb_keymap_format,
     ) -> *mut c::c_char;
     fn xkb_keymap_unref(keymap: *mut xkb_keymap);
+    fn xkb_keymap_min_keycode(keymap: *mut xkb_keymap) -> xkb_keycode_t;
+    fn xkb_keymap_max_keycode(keymap: *mut xkb_keymap) -> xkb_keycode_t;
     // fn xkb_keymap_ref(keymap: *mut xkb_keymap) -> *mut xkb_keymap;
     fn xkb_keymap_key_get_syms_by_level(
         keymap: *mut xkb_keymap,
@@ -117,6 +133,37 @@ extern "C" {
         latched_layout: xkb_layout_index_t,
         locked_layout: xkb_layout_index_t,
     ) -> xkb_state_component;
+    fn xkb_state_key_get_one_sym(state: *mut xkb_state, key: xkb_keycode_t) -> xkb_keysym_t;
+    fn xkb_state_key_get_utf8(
+        state: *mut xkb_state,
+        key: xkb_keycode_t,
+        buffer: *mut c::c_char,
+        size: usize,
+    ) -> c::c_int;
+    fn xkb_keysym_from_name(name: *const c::c_char, flags: xkb_keysym_flags) -> xkb_keysym_t;
+    fn xkb_keysym_get_name(keysym: xkb_keysym_t, buffer: *mut c::c_char, size: usize) -> c::c_int;
+}
+
+/// Looks up a keysym by its name, ignoring case if there is no exact match.
+pub fn keysym_from_name(name: &str) -> Option<u32> {
+    let name = CString::new(name).ok()?;
+    for flags in [XKB_KEYSYM_NO_FLAGS, XKB_KEYSYM_CASE_INSENSITIVE] {
+        let sym = unsafe { xkb_keysym_from_name(name.as_ptr(), flags.raw() as _) };
+        if sym != 0 {
+            return Some(sym);
+        }
+    }
+    None
+}
+
+pub fn keysym_name(sym: u32) -> String {
+    let mut buf = [0u8; 64];
+    let len = unsafe { xkb_keysym_get_name(sym, buf.as_mut_ptr().cast(), buf.len()) };
+    if len < 0 {
+        return format!("{:#x}", sym);
+    }
+    let len = (len as usize).min(buf.len() - 1);
+    String::from_utf8_lossy(&buf[..len]).into_owned()
 }
 
 pub struct XkbContext {
@@ -194,6 +241,43 @@ impl XkbContext {
             self.raw_to_map(keymap)
         }
     }
+
+    /// Compiles a keymap from RMLVO names. Unset names use the libxkbcommon defaults.
+    pub fn keymap_from_names(
+        &self,
+        rules: Option<&str>,
+        model: Option<&str>,
+        layout: Option<&str>,
+        variant: Option<&str>,
+        options: Option<&str>,
+    ) -> Result<Rc<XkbKeymap>, XkbCommonError> {
+        let cstr = |s: Option<&str>| match s {
+            Some(s) => CString::new(s)
+                .map(Some)
+                .map_err(|_| XkbCommonError::NulInName),
+            _ => Ok(None),
+        };
+        let rules = cstr(rules)?;
+        let model = cstr(model)?;
+        let layout = cstr(layout)?;
+        let variant = cstr(variant)?;
+        let options = cstr(options)?;
+        let ptr = |s: &Option<CString>| s.as_ref().map(|s| s.as_ptr()).unwrap_or(ptr::null());
+        let names = xkb_rule_names {
+            rules: ptr(&rules),
+            model: ptr(&model),
+            layout: ptr(&layout),
+            variant: ptr(&variant),
+            options: ptr(&options),
+        };
+        unsafe {
+            let keymap = xkb_keymap_new_from_names(self.context, &names, 0);
+            if keymap.is_null() {
+                return Err(XkbCommonError::KeymapFromNames);
+            }
+            self.raw_to_map(keymap)
+        }
+    }
 }
 
 impl Drop for XkbContext {
@@ -231,6 +315,20 @@ impl XkbKeymap {
     }
 }
 
+impl XkbKeymap {
+    pub fn num_layouts(&self) -> u32 {
+        unsafe { xkb_keymap_num_layouts(self.keymap) }
+    }
+
+    pub fn layout_name(&self, idx: u32) -> String {
+        let name = unsafe { xkb_keymap_layout_get_name(self.keymap, idx) };
+        if name.is_null() {
+            return String::new();
+        }
+        unsafe { CStr::from_ptr(name).to_string_lossy().into_owned() }
+    }
+}
+
 impl Drop for XkbKeymap {
     fn drop(&mut self) {
         unsafe {
@@ -323,6 +421,22 @@ impl XkbState {
         self.kb_state.mods
     }
 
+    pub fn keymap(&self) -> &Rc<XkbKeymap> {
+        &self.map
+    }
+
+    /// Locks the given layout. Returns whether the effective layout changed.
+    pub fn set_layout(&mut self, layout: u32) -> bool {
+        let mods = self.kb_state.mods;
+        self.set(
+            mods.mods_depressed,
+            mods.mods_latched,
+            mods.mods_locked,
+            layout,
+        );
+        self.kb_state.mods.group != mods.group
+    }
+
     fn fetch(&mut self, changes: xkb_state_component) -> bool {
         unsafe {
             if changes != 0 {
@@ -362,7 +476,6 @@ impl XkbState {
         *self = new_state;
     }
 
-    #[allow(dead_code)]
     pub fn set(
         &mut self,
         mods_depressed: u32,
@@ -401,6 +514,46 @@ impl XkbState {
             }
         }
     }
+
+    /// Returns a key of the current layout that produces the keysym and whether shift
+    /// must be held to produce it.
+    pub fn find_keysym(&self, sym: xkb_keysym_t) -> Option<(u32, bool)> {
+        unsafe {
+            let min = xkb_keymap_min_keycode(self.map.keymap).max(8);
+            let max = xkb_keymap_max_keycode(self.map.keymap);
+            for level in 0..2 {
+                for key in min..=max {
+                    let mut res = ptr::null();
+                    let num = xkb_keymap_key_get_syms_by_level(
+                        self.map.keymap,
+                        key,
+                        self.kb_state.mods.group,
+                        level,
+                        &mut res,
+                    );
+                    if num == 1 && *res == sym {
+                        return Some((key - 8, level == 1));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the keysym produced by the key in the current state.
+    pub fn keysym(&self, key: u32) -> xkb_keysym_t {
+        unsafe { xkb_state_key_get_one_sym(self.state, key + 8) }
+    }
+
+    /// Returns the text produced by the key in the current state.
+    pub fn utf8(&self, key: u32) -> String {
+        let mut buf = [0u8; 64];
+        let len = unsafe {
+            xkb_state_key_get_utf8(self.state, key + 8, buf.as_mut_ptr() as _, buf.len())
+        };
+        let len = (len.max(0) as usize).min(buf.len() - 1);
+        String::from_utf8_lossy(&buf[..len]).into_owned()
+    }
 }
 
 impl Drop for XkbState {
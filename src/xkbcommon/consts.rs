@@ -56,3 +56,10 @@ cenum! {
     XKB_KEY_UP = 0,
     XKB_KEY_DOWN = 1,
 }
+
+cenum! {
+    XkbKeysymFlags, XKB_KEYSYM_FLAGS;
+
+    XKB_KEYSYM_NO_FLAGS = 0,
+    XKB_KEYSYM_CASE_INSENSITIVE = 1 << 0,
+}
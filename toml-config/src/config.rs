@@ -17,13 +17,21 @@ use {
     },
     ahash::AHashMap,
     jay_config::{
-        input::{acceleration::AccelProfile, SwitchEvent},
+        animation::{AnimationCurve, WorkspaceAnimationStyle},
+        input::{
+            acceleration::{AccelProfile, AccelType},
+            click_method::ClickMethod,
+            scroll_method::ScrollMethod,
+            send_events::SendEventsMode,
+            tap_button_map::TapButtonMap,
+            SwitchEvent, TabletPadControl,
+        },
         keyboard::{mods::Modifiers, Keymap, ModifiedKeySym},
         logging::LogLevel,
         status::MessageFormat,
-        theme::Color,
+        theme::{Color, ColorScheme},
         video::{GfxApi, Transform},
-        Axis, Direction, Workspace,
+        Axis, ContainerLayout, Direction, Workspace, WorkspaceLayout,
     },
     std::{
         error::Error,
@@ -51,6 +59,12 @@ pub enum SimpleCommand {
     ToggleMono,
     ToggleSplit,
     Forward(bool),
+    MoveToScratchpad,
+    ToggleScratchpad,
+    ToggleSticky,
+    SetContainerLayout(ContainerLayout),
+    NextKeyboardLayout,
+    Lock,
 }
 
 #[derive(Debug, Clone)]
@@ -122,6 +136,27 @@ pub enum Action {
     SetRepeatRate {
         rate: RepeatRate,
     },
+    SetWindowOpacity {
+        opacity: f64,
+    },
+    Mark {
+        mark: String,
+    },
+    Unmark {
+        mark: Option<String>,
+    },
+    FocusMark {
+        mark: String,
+    },
+    SwapWithMark {
+        mark: String,
+    },
+    SetWorkspaceLayout {
+        layout: WorkspaceLayout,
+    },
+    SetKeyboardLayout {
+        layout: u32,
+    },
 }
 
 #[derive(Debug, Clone, Default)]
@@ -144,6 +179,19 @@ pub struct Theme {
     pub border_width: Option<i32>,
     pub title_height: Option<i32>,
     pub font: Option<String>,
+    pub color_scheme: Option<ColorScheme>,
+    pub high_contrast: Option<bool>,
+    pub inner_gap: Option<i32>,
+    pub outer_gap: Option<i32>,
+    pub smart_gaps: Option<bool>,
+    pub corner_radius: Option<i32>,
+    pub shadow_size: Option<i32>,
+    pub shadow_color: Option<Color>,
+    pub blur_passes: Option<i32>,
+    pub blur_offset: Option<i32>,
+    pub blurred_app_ids: Option<Vec<String>>,
+    pub blurred_layer_namespaces: Option<Vec<String>>,
+    pub window_opacities: Option<Vec<(String, f64)>>,
 }
 
 #[derive(Debug, Clone)]
@@ -247,6 +295,52 @@ pub struct Input {
     pub keymap: Option<ConfigKeymap>,
     pub switch_actions: AHashMap<SwitchEvent, Action>,
     pub output: Option<Option<OutputMatch>>,
+    pub click_method: Option<ClickMethod>,
+    pub middle_button_emulation: Option<bool>,
+    pub scroll_method: Option<ScrollMethod>,
+    pub scroll_button: Option<u32>,
+    pub disable_while_typing: Option<bool>,
+    pub tap_button_map: Option<TapButtonMap>,
+    pub send_events: Option<SendEventsMode>,
+    pub rotation: Option<u32>,
+    pub calibration_matrix: Option<[[f32; 3]; 2]>,
+    pub custom_accel: Option<Vec<CustomAccelFunction>>,
+    pub region: Option<InputRegion>,
+    pub tablet_area: Option<TabletArea>,
+    pub keep_aspect_ratio: Option<bool>,
+    pub pressure_curve: Option<[f64; 4]>,
+    pub tablet_pad_actions: Vec<TabletPadAction>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct InputRegion {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TabletArea {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TabletPadAction {
+    pub control: TabletPadControl,
+    pub index: u32,
+    pub negative: bool,
+    pub action: Action,
+}
+
+#[derive(Debug, Clone)]
+pub struct CustomAccelFunction {
+    pub ty: AccelType,
+    pub step: f64,
+    pub points: Vec<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -292,6 +386,33 @@ pub struct Shortcut {
     pub latch: Option<Action>,
 }
 
+#[derive(Debug, Clone)]
+pub struct AnimationSettings {
+    pub duration: Option<Duration>,
+    pub curve: Option<AnimationCurve>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Animations {
+    pub enabled: Option<bool>,
+    pub workspace_style: Option<WorkspaceAnimationStyle>,
+    pub workspace_switch: Option<AnimationSettings>,
+    pub window_open: Option<AnimationSettings>,
+    pub window_close: Option<AnimationSettings>,
+    pub layout: Option<AnimationSettings>,
+}
+
+#[derive(Debug, Clone)]
+pub struct IdleStage {
+    pub name: String,
+    pub timeout: Duration,
+    pub dim_outputs: bool,
+    pub power_off_outputs: bool,
+    pub suspend: bool,
+    pub on_idle: Option<Action>,
+    pub on_resume: Option<Action>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub keymap: Option<ConfigKeymap>,
@@ -299,6 +420,7 @@ pub struct Config {
     pub shortcuts: Vec<Shortcut>,
     pub on_graphics_initialized: Option<Action>,
     pub on_idle: Option<Action>,
+    pub on_before_sleep: Option<Action>,
     pub status: Option<Status>,
     pub connectors: Vec<ConfigConnector>,
     pub outputs: Vec<Output>,
@@ -314,8 +436,13 @@ pub struct Config {
     pub render_device: Option<DrmDeviceMatch>,
     pub inputs: Vec<Input>,
     pub idle: Option<Duration>,
+    pub idle_stages: Vec<IdleStage>,
+    pub fullscreen_inhibits_idle: bool,
     pub explicit_sync_enabled: Option<bool>,
     pub focus_follows_mouse: bool,
+    pub animations: Option<Animations>,
+    pub workspace_layout: Option<WorkspaceLayout>,
+    pub remember_keyboard_layout: bool,
 }
 
 #[derive(Debug, Error)]
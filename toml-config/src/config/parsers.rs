@@ -7,16 +7,20 @@ use {
 };
 
 pub mod action;
+mod animations;
 mod color;
+mod color_scheme;
 pub mod config;
 mod connector;
 mod connector_match;
+mod custom_accel;
 mod drm_device;
 mod drm_device_match;
 mod env;
 pub mod exec;
 mod gfx_api;
 mod idle;
+mod idle_stage;
 mod input;
 mod input_match;
 pub mod keymap;
@@ -28,7 +32,10 @@ mod output_match;
 mod repeat_rate;
 pub mod shortcuts;
 mod status;
+mod tablet;
 mod theme;
+mod window_opacities;
+mod workspace_layout;
 
 #[derive(Debug, Error)]
 pub enum StringParserError {
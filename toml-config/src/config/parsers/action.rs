@@ -2,7 +2,7 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{arr, bol, n32, opt, str, val, Extractor, ExtractorError},
+            extractor::{arr, bol, fltorint, n32, opt, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 connector::{ConnectorParser, ConnectorParserError},
@@ -20,6 +20,7 @@ use {
                 repeat_rate::{RepeatRateParser, RepeatRateParserError},
                 status::{StatusParser, StatusParserError},
                 theme::{ThemeParser, ThemeParserError},
+                workspace_layout::{WorkspaceLayoutParser, WorkspaceLayoutParserError},
                 StringParser, StringParserError,
             },
             spanned::SpannedErrorExt,
@@ -34,6 +35,7 @@ use {
     jay_config::{
         get_workspace,
         Axis::{Horizontal, Vertical},
+        ContainerLayout,
     },
     thiserror::Error,
 };
@@ -80,6 +82,8 @@ pub enum ActionParserError {
     MoveToOutput(#[source] OutputMatchParserError),
     #[error("Could not parse a set-repeat-rate action")]
     RepeatRate(#[source] RepeatRateParserError),
+    #[error("Could not parse a set-workspace-layout action")]
+    WorkspaceLayout(#[source] WorkspaceLayoutParserError),
 }
 
 pub struct ActionParser<'a>(pub &'a Context<'a>);
@@ -111,6 +115,15 @@ impl ActionParser<'_> {
             "none" => None,
             "forward" => Forward(true),
             "consume" => Forward(false),
+            "move-to-scratchpad" => MoveToScratchpad,
+            "toggle-scratchpad" => ToggleScratchpad,
+            "toggle-sticky" => ToggleSticky,
+            "layout-splith" => SetContainerLayout(ContainerLayout::SplitHorizontal),
+            "layout-splitv" => SetContainerLayout(ContainerLayout::SplitVertical),
+            "layout-tabbed" => SetContainerLayout(ContainerLayout::Tabbed),
+            "layout-stacking" => SetContainerLayout(ContainerLayout::Stacked),
+            "next-layout" => NextKeyboardLayout,
+            "lock" => Lock,
             _ => {
                 return Err(ActionParserError::UnknownSimpleAction(string.to_string()).spanned(span))
             }
@@ -139,6 +152,47 @@ impl ActionParser<'_> {
         Ok(Action::SwitchToVt { num })
     }
 
+    fn parse_set_window_opacity(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let opacity = ext.extract(fltorint("opacity"))?.value;
+        Ok(Action::SetWindowOpacity { opacity })
+    }
+
+    fn parse_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext.extract(str("mark"))?.value.to_string();
+        Ok(Action::Mark { mark })
+    }
+
+    fn parse_unmark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext
+            .extract(opt(str("mark")))?
+            .despan()
+            .map(|m| m.to_string());
+        Ok(Action::Unmark { mark })
+    }
+
+    fn parse_focus_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext.extract(str("mark"))?.value.to_string();
+        Ok(Action::FocusMark { mark })
+    }
+
+    fn parse_swap_with_mark(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let mark = ext.extract(str("mark"))?.value.to_string();
+        Ok(Action::SwapWithMark { mark })
+    }
+
+    fn parse_set_workspace_layout(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let layout = ext
+            .extract(val("layout"))?
+            .parse_map(&mut WorkspaceLayoutParser)
+            .map_spanned_err(ActionParserError::WorkspaceLayout)?;
+        Ok(Action::SetWorkspaceLayout { layout })
+    }
+
+    fn parse_set_keyboard_layout(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
+        let layout = ext.extract(n32("layout"))?.value;
+        Ok(Action::SetKeyboardLayout { layout })
+    }
+
     fn parse_show_workspace(&mut self, ext: &mut Extractor<'_>) -> ParseResult<Self> {
         let name = ext.extract(str("name"))?.value.to_string();
         Ok(Action::ShowWorkspace { name })
@@ -359,6 +413,13 @@ impl<'a> Parser for ActionParser<'a> {
             "configure-idle" => self.parse_configure_idle(&mut ext),
             "move-to-output" => self.parse_move_to_output(&mut ext),
             "set-repeat-rate" => self.parse_set_repeat_rate(&mut ext),
+            "set-window-opacity" => self.parse_set_window_opacity(&mut ext),
+            "mark" => self.parse_mark(&mut ext),
+            "unmark" => self.parse_unmark(&mut ext),
+            "focus-mark" => self.parse_focus_mark(&mut ext),
+            "swap-with-mark" => self.parse_swap_with_mark(&mut ext),
+            "set-workspace-layout" => self.parse_set_workspace_layout(&mut ext),
+            "set-layout" => self.parse_set_keyboard_layout(&mut ext),
             v => {
                 ext.ignore_unused();
                 return Err(ActionParserError::UnknownType(v.to_string()).spanned(ty.span));
@@ -0,0 +1,154 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, n64, opt, recover, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            AnimationSettings, Animations,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::animation::{AnimationCurve, WorkspaceAnimationStyle},
+    std::time::Duration,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum AnimationsParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Unknown curve {0}")]
+    UnknownCurve(String),
+    #[error("A cubic bézier curve must have exactly 4 control values")]
+    FourValues,
+    #[error("Control values must be floats")]
+    Float,
+}
+
+pub struct AnimationsParser<'a>(pub &'a Context<'a>);
+
+impl Parser for AnimationsParser<'_> {
+    type Value = Animations;
+    type Error = AnimationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (enabled, workspace_style, workspace_switch, window_open, window_close, layout) =
+            ext.extract((
+                recover(opt(bol("enabled"))),
+                recover(opt(str("workspace-style"))),
+                opt(val("workspace-switch")),
+                opt(val("window-open")),
+                opt(val("window-close")),
+                opt(val("layout")),
+            ))?;
+        let workspace_style = match workspace_style {
+            None => None,
+            Some(s) => match s.value.to_ascii_lowercase().as_str() {
+                "slide" => Some(WorkspaceAnimationStyle::Slide),
+                "fade" => Some(WorkspaceAnimationStyle::Fade),
+                v => {
+                    log::warn!("Unknown workspace-style {v}: {}", self.0.error3(s.span));
+                    None
+                }
+            },
+        };
+        let settings = |name: &str, value: Option<Spanned<&Value>>| {
+            let value = value?;
+            match value.parse(&mut AnimationSettingsParser(self.0)) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the {name} animation: {}", self.0.error(e));
+                    None
+                }
+            }
+        };
+        Ok(Animations {
+            enabled: enabled.despan(),
+            workspace_style,
+            workspace_switch: settings("workspace-switch", workspace_switch),
+            window_open: settings("window-open", window_open),
+            window_close: settings("window-close", window_close),
+            layout: settings("layout", layout),
+        })
+    }
+}
+
+struct AnimationSettingsParser<'a>(&'a Context<'a>);
+
+impl Parser for AnimationSettingsParser<'_> {
+    type Value = AnimationSettings;
+    type Error = AnimationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (duration_ms, curve) =
+            ext.extract((recover(opt(n64("duration-ms"))), opt(val("curve"))))?;
+        let curve = match curve {
+            None => None,
+            Some(curve) => match curve.parse(&mut CurveParser) {
+                Ok(c) => Some(c),
+                Err(e) => {
+                    log::warn!("Could not parse the curve: {}", self.0.error(e));
+                    None
+                }
+            },
+        };
+        Ok(AnimationSettings {
+            duration: duration_ms.despan().map(Duration::from_millis),
+            curve,
+        })
+    }
+}
+
+struct CurveParser;
+
+impl Parser for CurveParser {
+    type Value = AnimationCurve;
+    type Error = AnimationsParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::String, DataType::Array];
+
+    fn parse_string(&mut self, span: Span, string: &str) -> ParseResult<Self> {
+        let curve = match string.to_ascii_lowercase().as_str() {
+            "linear" => AnimationCurve::Linear,
+            "ease-in" => AnimationCurve::EaseIn,
+            "ease-out" => AnimationCurve::EaseOut,
+            "ease-in-out" => AnimationCurve::EaseInOut,
+            _ => return Err(AnimationsParserError::UnknownCurve(string.to_string()).spanned(span)),
+        };
+        Ok(curve)
+    }
+
+    fn parse_array(&mut self, span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let [x1, y1, x2, y2] = array else {
+            return Err(AnimationsParserError::FourValues.spanned(span));
+        };
+        let extract = |v: &Spanned<Value>| match v.value {
+            Value::Float(f) => Ok(f),
+            Value::Integer(f) => Ok(f as _),
+            _ => Err(AnimationsParserError::Float.spanned(v.span)),
+        };
+        Ok(AnimationCurve::CubicBezier {
+            x1: extract(x1)?,
+            y1: extract(y1)?,
+            x2: extract(x2)?,
+            y2: extract(y2)?,
+        })
+    }
+}
@@ -6,12 +6,14 @@ use {
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
+                animations::AnimationsParser,
                 connector::ConnectorsParser,
                 drm_device::DrmDevicesParser,
                 drm_device_match::DrmDeviceMatchParser,
                 env::EnvParser,
                 gfx_api::GfxApiParser,
                 idle::IdleParser,
+                idle_stage::IdleStagesParser,
                 input::InputsParser,
                 keymap::KeymapParser,
                 log_level::LogLevelParser,
@@ -20,6 +22,7 @@ use {
                 shortcuts::{ComplexShortcutsParser, ShortcutsParser, ShortcutsParserError},
                 status::StatusParser,
                 theme::ThemeParser,
+                workspace_layout::WorkspaceLayoutParser,
             },
             spanned::SpannedErrorExt,
             Action, Config, Theme,
@@ -97,7 +100,18 @@ impl Parser for ConfigParser<'_> {
                 _,
                 idle_val,
             ),
-            (explicit_sync, repeat_rate_val, complex_shortcuts_val, focus_follows_mouse),
+            (
+                explicit_sync,
+                repeat_rate_val,
+                complex_shortcuts_val,
+                focus_follows_mouse,
+                animations_val,
+                workspace_layout_val,
+                remember_keyboard_layout,
+                idle_stages_val,
+                fullscreen_inhibits_idle,
+                on_before_sleep_val,
+            ),
         ) = ext.extract((
             (
                 opt(val("keymap")),
@@ -128,6 +142,12 @@ impl Parser for ConfigParser<'_> {
                 opt(val("repeat-rate")),
                 opt(val("complex-shortcuts")),
                 recover(opt(bol("focus-follows-mouse"))),
+                opt(val("animations")),
+                opt(val("workspace-layout")),
+                recover(opt(bol("remember-keyboard-layout"))),
+                opt(val("idle-stages")),
+                recover(opt(bol("fullscreen-inhibits-idle"))),
+                opt(val("on-before-sleep")),
             ),
         ))?;
         let mut keymap = None;
@@ -168,6 +188,7 @@ impl Parser for ConfigParser<'_> {
         let on_graphics_initialized =
             self.parse_action("on-graphics-initialized", on_graphics_init_val);
         let on_idle = self.parse_action("on-idle", on_idle_val);
+        let on_before_sleep = self.parse_action("on-before-sleep", on_before_sleep_val);
         let on_startup = self.parse_action("on-startup", on_startup_val);
         let mut status = None;
         if let Some(value) = status_val {
@@ -277,6 +298,15 @@ impl Parser for ConfigParser<'_> {
                 }
             }
         }
+        let mut idle_stages = vec![];
+        if let Some(value) = idle_stages_val {
+            match value.parse(&mut IdleStagesParser(self.0)) {
+                Ok(v) => idle_stages = v,
+                Err(e) => {
+                    log::warn!("Could not parse the idle stages: {}", self.0.error(e));
+                }
+            }
+        }
         let mut repeat_rate = None;
         if let Some(value) = repeat_rate_val {
             match value.parse(&mut RepeatRateParser(self.0)) {
@@ -286,12 +316,31 @@ impl Parser for ConfigParser<'_> {
                 }
             }
         }
+        let mut animations = None;
+        if let Some(value) = animations_val {
+            match value.parse(&mut AnimationsParser(self.0)) {
+                Ok(v) => animations = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the animations: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut workspace_layout = None;
+        if let Some(value) = workspace_layout_val {
+            match value.parse(&mut WorkspaceLayoutParser) {
+                Ok(v) => workspace_layout = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the workspace layout: {}", self.0.error(e));
+                }
+            }
+        }
         Ok(Config {
             keymap,
             repeat_rate,
             shortcuts,
             on_graphics_initialized,
             on_idle,
+            on_before_sleep,
             status,
             outputs,
             connectors,
@@ -308,7 +357,12 @@ impl Parser for ConfigParser<'_> {
             render_device,
             inputs,
             idle,
+            idle_stages,
+            fullscreen_inhibits_idle: fullscreen_inhibits_idle.despan().unwrap_or(false),
             focus_follows_mouse: focus_follows_mouse.despan().unwrap_or(true),
+            animations,
+            workspace_layout,
+            remember_keyboard_layout: remember_keyboard_layout.despan().unwrap_or(false),
         })
     }
 }
@@ -0,0 +1,94 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{arr, fltorint, opt, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            CustomAccelFunction,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::input::acceleration::{
+        AccelType, ACCEL_TYPE_FALLBACK, ACCEL_TYPE_MOTION, ACCEL_TYPE_SCROLL,
+    },
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum CustomAccelParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("The step must be positive")]
+    NonPositiveStep,
+    #[error("Points must be floats")]
+    Float,
+}
+
+pub struct CustomAccelParser<'a>(pub &'a Context<'a>);
+
+impl Parser for CustomAccelParser<'_> {
+    type Value = Vec<CustomAccelFunction>;
+    type Error = CustomAccelParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (fallback, motion, scroll) =
+            ext.extract((opt(val("fallback")), opt(val("motion")), opt(val("scroll"))))?;
+        let mut res = vec![];
+        for (ty, val) in [
+            (ACCEL_TYPE_FALLBACK, fallback),
+            (ACCEL_TYPE_MOTION, motion),
+            (ACCEL_TYPE_SCROLL, scroll),
+        ] {
+            if let Some(val) = val {
+                res.push(val.parse(&mut CustomAccelFunctionParser(self.0, ty))?);
+            }
+        }
+        Ok(res)
+    }
+}
+
+struct CustomAccelFunctionParser<'a>(&'a Context<'a>, AccelType);
+
+impl Parser for CustomAccelFunctionParser<'_> {
+    type Value = CustomAccelFunction;
+    type Error = CustomAccelParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (step, points_val) = ext.extract((fltorint("step"), arr("points")))?;
+        if step.value <= 0.0 {
+            return Err(CustomAccelParserError::NonPositiveStep.spanned(step.span));
+        }
+        let mut points = vec![];
+        for v in points_val.value {
+            let p = match v.value {
+                Value::Float(f) => f,
+                Value::Integer(i) => i as _,
+                _ => return Err(CustomAccelParserError::Float.spanned(v.span)),
+            };
+            points.push(p);
+        }
+        Ok(CustomAccelFunction {
+            ty: self.1,
+            step: step.value,
+            points,
+        })
+    }
+}
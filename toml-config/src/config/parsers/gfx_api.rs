@@ -27,6 +27,7 @@ impl Parser for GfxApiParser {
         let api = match string.to_ascii_lowercase().as_str() {
             "opengl" => OpenGl,
             "vulkan" => Vulkan,
+            "software" => Software,
             _ => return Err(GfxApiParserError::Unknown(string.to_string()).spanned(span)),
         };
         Ok(api)
@@ -0,0 +1,109 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{bol, opt, recover, str, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::{
+                action::ActionParser,
+                idle::{IdleParser, IdleParserError},
+            },
+            spanned::SpannedErrorExt,
+            IdleStage,
+        },
+        toml::{
+            toml_span::{DespanExt, Span, Spanned},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum IdleStageParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error("Could not parse the timeout")]
+    Timeout(#[source] IdleParserError),
+}
+
+pub struct IdleStageParser<'a>(pub &'a Context<'a>);
+
+impl Parser for IdleStageParser<'_> {
+    type Value = IdleStage;
+    type Error = IdleStageParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (
+            name,
+            timeout_val,
+            dim_outputs,
+            power_off_outputs,
+            suspend,
+            on_idle_val,
+            on_resume_val,
+        ) = ext.extract((
+            str("name"),
+            val("timeout"),
+            recover(opt(bol("dim-outputs"))),
+            recover(opt(bol("power-off-outputs"))),
+            recover(opt(bol("suspend"))),
+            opt(val("on-idle")),
+            opt(val("on-resume")),
+        ))?;
+        let timeout = timeout_val
+            .parse(&mut IdleParser(self.0))
+            .map_spanned_err(IdleStageParserError::Timeout)?;
+        let parse_action = |name: &str, val: Option<Spanned<&Value>>| {
+            let val = val?;
+            match val.parse(&mut ActionParser(self.0)) {
+                Ok(a) => Some(a),
+                Err(e) => {
+                    log::warn!("Could not parse the {name} action: {}", self.0.error(e));
+                    None
+                }
+            }
+        };
+        let on_idle = parse_action("on-idle", on_idle_val);
+        let on_resume = parse_action("on-resume", on_resume_val);
+        Ok(IdleStage {
+            name: name.value.to_string(),
+            timeout,
+            dim_outputs: dim_outputs.despan().unwrap_or(false),
+            power_off_outputs: power_off_outputs.despan().unwrap_or(false),
+            suspend: suspend.despan().unwrap_or(false),
+            on_idle,
+            on_resume,
+        })
+    }
+}
+
+pub struct IdleStagesParser<'a>(pub &'a Context<'a>);
+
+impl Parser for IdleStagesParser<'_> {
+    type Value = Vec<IdleStage>;
+    type Error = IdleStageParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, _span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        let mut res = vec![];
+        for el in array {
+            match el.parse(&mut IdleStageParser(self.0)) {
+                Ok(o) => res.push(o),
+                Err(e) => {
+                    log::warn!("Could not parse idle stage: {}", self.0.error(e));
+                }
+            }
+        }
+        Ok(res)
+    }
+}
@@ -2,13 +2,18 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{bol, fltorint, opt, recover, str, val, Extractor, ExtractorError},
+            extractor::{bol, fltorint, n32, opt, recover, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
             parsers::{
                 action::ActionParser,
+                custom_accel::CustomAccelParser,
                 input_match::{InputMatchParser, InputMatchParserError},
                 keymap::KeymapParser,
                 output_match::OutputMatchParser,
+                tablet::{
+                    InputRegionParser, PressureCurveParser, TabletAreaParser,
+                    TabletPadActionsParser,
+                },
             },
             Input,
         },
@@ -20,8 +25,17 @@ use {
     ahash::AHashMap,
     indexmap::IndexMap,
     jay_config::input::{
-        acceleration::{ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_FLAT},
-        SwitchEvent,
+        acceleration::{ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_CUSTOM, ACCEL_PROFILE_FLAT},
+        click_method::{CLICK_METHOD_BUTTON_AREAS, CLICK_METHOD_CLICKFINGER, CLICK_METHOD_NONE},
+        scroll_method::{
+            SCROLL_METHOD_EDGE, SCROLL_METHOD_NONE, SCROLL_METHOD_ON_BUTTON_DOWN,
+            SCROLL_METHOD_TWO_FINGER,
+        },
+        send_events::{
+            SEND_EVENTS_DISABLED, SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE, SEND_EVENTS_ENABLED,
+        },
+        tap_button_map::{TAP_BUTTON_MAP_LMR, TAP_BUTTON_MAP_LRM},
+        SwitchEvent, TabletPadControl,
     },
     thiserror::Error,
 };
@@ -40,6 +54,12 @@ pub enum InputParserError {
     TwoColumns,
     #[error("Transform matrix entries must be floats")]
     Float,
+    #[error("Calibration matrix must have exactly two rows")]
+    CalibrationTwoRows,
+    #[error("Calibration matrix must have exactly three columns")]
+    CalibrationThreeColumns,
+    #[error("Calibration matrix entries must be floats")]
+    CalibrationFloat,
 }
 
 pub struct InputParser<'a> {
@@ -81,6 +101,27 @@ impl<'a> Parser for InputParser<'a> {
                 output_val,
                 remove_mapping,
             ),
+            (
+                click_method,
+                middle_button_emulation,
+                scroll_method,
+                scroll_button,
+                disable_while_typing,
+                tap_button_map,
+                send_events,
+                rotation,
+                calibration_matrix,
+                custom_accel_val,
+            ),
+            (
+                region_val,
+                tablet_area_val,
+                keep_aspect_ratio,
+                pressure_curve_val,
+                pad_buttons_val,
+                pad_rings_val,
+                pad_strips_val,
+            ),
         ) = ext.extract((
             (
                 opt(str("tag")),
@@ -104,18 +145,98 @@ impl<'a> Parser for InputParser<'a> {
                 opt(val("output")),
                 recover(opt(bol("remove-mapping"))),
             ),
+            (
+                recover(opt(str("click-method"))),
+                recover(opt(bol("middle-button-emulation"))),
+                recover(opt(str("scroll-method"))),
+                recover(opt(n32("scroll-button"))),
+                recover(opt(bol("disable-while-typing"))),
+                recover(opt(str("tap-button-map"))),
+                recover(opt(str("send-events"))),
+                recover(opt(n32("rotation"))),
+                recover(opt(val("calibration-matrix"))),
+                opt(val("custom-accel")),
+            ),
+            (
+                opt(val("region")),
+                opt(val("tablet-area")),
+                recover(opt(bol("keep-aspect-ratio"))),
+                opt(val("pressure-curve")),
+                opt(val("pad-buttons")),
+                opt(val("pad-rings")),
+                opt(val("pad-strips")),
+            ),
         ))?;
         let accel_profile = match accel_profile {
             None => None,
             Some(p) => match p.value.to_ascii_lowercase().as_str() {
                 "flat" => Some(ACCEL_PROFILE_FLAT),
                 "adaptive" => Some(ACCEL_PROFILE_ADAPTIVE),
+                "custom" => Some(ACCEL_PROFILE_CUSTOM),
                 v => {
                     log::warn!("Unknown accel-profile {v}: {}", self.cx.error3(p.span));
                     None
                 }
             },
         };
+        let click_method = match click_method {
+            None => None,
+            Some(p) => match p.value.to_ascii_lowercase().as_str() {
+                "none" => Some(CLICK_METHOD_NONE),
+                "button-areas" => Some(CLICK_METHOD_BUTTON_AREAS),
+                "clickfinger" => Some(CLICK_METHOD_CLICKFINGER),
+                v => {
+                    log::warn!("Unknown click-method {v}: {}", self.cx.error3(p.span));
+                    None
+                }
+            },
+        };
+        let scroll_method = match scroll_method {
+            None => None,
+            Some(p) => match p.value.to_ascii_lowercase().as_str() {
+                "none" => Some(SCROLL_METHOD_NONE),
+                "two-finger" => Some(SCROLL_METHOD_TWO_FINGER),
+                "edge" => Some(SCROLL_METHOD_EDGE),
+                "on-button-down" => Some(SCROLL_METHOD_ON_BUTTON_DOWN),
+                v => {
+                    log::warn!("Unknown scroll-method {v}: {}", self.cx.error3(p.span));
+                    None
+                }
+            },
+        };
+        let tap_button_map = match tap_button_map {
+            None => None,
+            Some(p) => match p.value.to_ascii_lowercase().as_str() {
+                "lrm" => Some(TAP_BUTTON_MAP_LRM),
+                "lmr" => Some(TAP_BUTTON_MAP_LMR),
+                v => {
+                    log::warn!("Unknown tap-button-map {v}: {}", self.cx.error3(p.span));
+                    None
+                }
+            },
+        };
+        let send_events = match send_events {
+            None => None,
+            Some(p) => match p.value.to_ascii_lowercase().as_str() {
+                "enabled" => Some(SEND_EVENTS_ENABLED),
+                "disabled" => Some(SEND_EVENTS_DISABLED),
+                "disabled-on-external-mouse" => Some(SEND_EVENTS_DISABLED_ON_EXTERNAL_MOUSE),
+                v => {
+                    log::warn!("Unknown send-events mode {v}: {}", self.cx.error3(p.span));
+                    None
+                }
+            },
+        };
+        let calibration_matrix = match calibration_matrix {
+            None => None,
+            Some(matrix) => match matrix.parse(&mut CalibrationMatrixParser) {
+                Ok(v) => Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse calibration matrix: {}", self.cx.error(e));
+                    None
+                }
+            },
+        };
         let transform_matrix = match transform_matrix {
             None => None,
             Some(matrix) => match matrix.parse(&mut TransformMatrixParser) {
@@ -214,6 +335,84 @@ impl<'a> Parser for InputParser<'a> {
                 output = Some(None);
             }
         }
+        let mut custom_accel = None;
+        if let Some(val) = custom_accel_val {
+            match val.parse(&mut CustomAccelParser(self.cx)) {
+                Ok(v) => custom_accel = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse custom-accel: {}", self.cx.error(e));
+                }
+            }
+        }
+        let mut region = None;
+        if let Some(val) = region_val {
+            match val.parse(&mut InputRegionParser(self.cx)) {
+                Ok(v) => region = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse region: {}", self.cx.error(e));
+                }
+            }
+        }
+        let mut tablet_area = None;
+        if let Some(val) = tablet_area_val {
+            match val.parse(&mut TabletAreaParser(self.cx)) {
+                Ok(v) => tablet_area = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse tablet-area: {}", self.cx.error(e));
+                }
+            }
+        }
+        let mut pressure_curve = None;
+        if let Some(val) = pressure_curve_val {
+            match val.parse(&mut PressureCurveParser) {
+                Ok(v) => pressure_curve = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse pressure-curve: {}", self.cx.error(e));
+                }
+            }
+        }
+        let mut tablet_pad_actions = vec![];
+        for (val, name, control, directions) in [
+            (
+                pad_buttons_val,
+                "pad-buttons",
+                TabletPadControl::Button,
+                ["", ""],
+            ),
+            (
+                pad_rings_val,
+                "pad-rings",
+                TabletPadControl::Ring,
+                ["clockwise", "counter-clockwise"],
+            ),
+            (
+                pad_strips_val,
+                "pad-strips",
+                TabletPadControl::Strip,
+                ["down", "up"],
+            ),
+        ] {
+            let Some(val) = val else {
+                continue;
+            };
+            if !self.is_inputs_array {
+                log::warn!(
+                    "{name} has no effect in this position: {}",
+                    self.cx.error3(val.span)
+                );
+                continue;
+            }
+            match val.parse(&mut TabletPadActionsParser {
+                cx: self.cx,
+                control,
+                directions,
+            }) {
+                Ok(v) => tablet_pad_actions.extend(v),
+                Err(e) => {
+                    log::warn!("Could not parse {name}: {}", self.cx.error(e));
+                }
+            }
+        }
         Ok(Input {
             tag: tag.despan_into(),
             match_: match_val.parse_map(&mut InputMatchParser(self.cx))?,
@@ -229,6 +428,21 @@ impl<'a> Parser for InputParser<'a> {
             keymap,
             switch_actions,
             output,
+            click_method,
+            middle_button_emulation: middle_button_emulation.despan(),
+            scroll_method,
+            scroll_button: scroll_button.despan(),
+            disable_while_typing: disable_while_typing.despan(),
+            tap_button_map,
+            send_events,
+            rotation: rotation.despan(),
+            calibration_matrix,
+            custom_accel,
+            region,
+            tablet_area,
+            keep_aspect_ratio: keep_aspect_ratio.despan(),
+            pressure_curve,
+            tablet_pad_actions,
         })
     }
 }
@@ -311,3 +525,45 @@ impl Parser for TransformMatrixRowParser {
         Ok([extract(&array[0])?, extract(&array[1])?])
     }
 }
+
+struct CalibrationMatrixParser;
+
+impl Parser for CalibrationMatrixParser {
+    type Value = [[f32; 3]; 2];
+    type Error = InputParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        if array.len() != 2 {
+            return Err(InputParserError::CalibrationTwoRows.spanned(span));
+        }
+        Ok([
+            array[0].parse(&mut CalibrationMatrixRowParser)?,
+            array[1].parse(&mut CalibrationMatrixRowParser)?,
+        ])
+    }
+}
+
+struct CalibrationMatrixRowParser;
+
+impl Parser for CalibrationMatrixRowParser {
+    type Value = [f32; 3];
+    type Error = InputParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        if array.len() != 3 {
+            return Err(InputParserError::CalibrationThreeColumns.spanned(span));
+        }
+        let extract = |v: &Spanned<Value>| match v.value {
+            Value::Float(f) => Ok(f as _),
+            Value::Integer(f) => Ok(f as _),
+            _ => Err(InputParserError::CalibrationFloat.spanned(v.span)),
+        };
+        Ok([
+            extract(&array[0])?,
+            extract(&array[1])?,
+            extract(&array[2])?,
+        ])
+    }
+}
@@ -14,7 +14,7 @@ use {
     indexmap::IndexMap,
     jay_config::{
         config_dir,
-        keyboard::{parse_keymap, Keymap},
+        keyboard::{keymap_from_names, parse_keymap, Keymap, KeymapNames},
     },
     std::{io, path::PathBuf},
     thiserror::Error,
@@ -28,7 +28,7 @@ pub enum KeymapParserError {
     Extractor(#[from] ExtractorError),
     #[error("The keymap is invalid")]
     Invalid,
-    #[error("Keymap table must contain at least one of `name`, `map`")]
+    #[error("Keymap table must contain at least one of `name`, `map`, `path`, or an RMLVO field")]
     MissingField,
     #[error("Keymap must have both `name` and `map` fields in this context")]
     DefinitionRequired,
@@ -58,6 +58,13 @@ impl Parser for KeymapParser<'_> {
         let mut ext = Extractor::new(self.cx, span, table);
         let (mut name_val, mut map_val, mut path) =
             ext.extract((opt(str("name")), opt(str("map")), opt(str("path"))))?;
+        let (rules, model, layout, variant, options) = ext.extract((
+            opt(str("rules")),
+            opt(str("model")),
+            opt(str("layout")),
+            opt(str("variant")),
+            opt(str("options")),
+        ))?;
         if map_val.is_some() && path.is_some() {
             log::warn!(
                 "Both `name` and `path` are specified. Ignoring `path`: {}",
@@ -78,10 +85,31 @@ impl Parser for KeymapParser<'_> {
             };
             map_val = Some(file_content.as_str().spanned(path.span));
         }
-        if self.definition && (name_val.is_none() || map_val.is_none()) {
+        let mut names = None;
+        let rmlvo = [rules, model, layout, variant, options];
+        if rmlvo.iter().any(|n| n.is_some()) {
+            if map_val.is_some() {
+                log::warn!(
+                    "Both `map` and RMLVO fields are specified. Ignoring the RMLVO fields: {}",
+                    self.cx.error3(span)
+                );
+            } else {
+                let [rules, model, layout, variant, options] =
+                    rmlvo.map(|n| n.map(|n| n.value.to_string()));
+                names = Some(KeymapNames {
+                    rules,
+                    model,
+                    layout,
+                    variant,
+                    options,
+                });
+            }
+        }
+        let have_map = map_val.is_some() || names.is_some();
+        if self.definition && (name_val.is_none() || !have_map) {
             return Err(KeymapParserError::DefinitionRequired.spanned(span));
         }
-        if !self.definition && map_val.is_some() {
+        if !self.definition && have_map {
             if let Some(val) = name_val {
                 log::warn!(
                     "Cannot use both `name` and `map` in this position. Ignoring `name`: {}",
@@ -101,13 +129,18 @@ impl Parser for KeymapParser<'_> {
                 self.cx.used.borrow_mut().keymaps.push(name.into());
             }
         }
-        let res = match (name_val, map_val) {
-            (Some(name_val), Some(map_val)) => ConfigKeymap::Defined {
+        let map = match (map_val, names) {
+            (Some(map_val), _) => Some(parse(map_val.span, map_val.value)?),
+            (_, Some(names)) => Some(from_names(span, &names)?),
+            _ => None,
+        };
+        let res = match (name_val, map) {
+            (Some(name_val), Some(map)) => ConfigKeymap::Defined {
                 name: name_val.value.to_string(),
-                map: parse(map_val.span, map_val.value)?,
+                map,
             },
             (Some(name_val), None) => ConfigKeymap::Named(name_val.value.to_string()),
-            (None, Some(map_val)) => ConfigKeymap::Literal(parse(map_val.span, map_val.value)?),
+            (None, Some(map)) => ConfigKeymap::Literal(map),
             (None, None) => return Err(KeymapParserError::MissingField.spanned(span)),
         };
         Ok(res)
@@ -121,3 +154,11 @@ fn parse(span: Span, string: &str) -> Result<Keymap, Spanned<KeymapParserError>>
         false => Err(KeymapParserError::Invalid.spanned(span)),
     }
 }
+
+fn from_names(span: Span, names: &KeymapNames) -> Result<Keymap, Spanned<KeymapParserError>> {
+    let map = keymap_from_names(names);
+    match map.is_valid() {
+        true => Ok(map),
+        false => Err(KeymapParserError::Invalid.spanned(span)),
+    }
+}
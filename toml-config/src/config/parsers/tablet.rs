@@ -0,0 +1,213 @@
+use {
+    crate::{
+        config::{
+            context::Context,
+            extractor::{fltorint, opt, s32, val, Extractor, ExtractorError},
+            parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+            parsers::action::{ActionParser, ActionParserError},
+            InputRegion, TabletArea, TabletPadAction,
+        },
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    jay_config::input::TabletPadControl,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum TabletParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error(transparent)]
+    Extract(#[from] ExtractorError),
+    #[error(transparent)]
+    Action(#[from] ActionParserError),
+    #[error("The region must have a positive size")]
+    EmptyRegion,
+    #[error("The area must be a non-empty part of the range [0, 1]")]
+    InvalidArea,
+    #[error("The pressure curve must have exactly four entries")]
+    FourEntries,
+    #[error("The pressure curve entries must be floats in the range [0, 1]")]
+    CurveEntry,
+    #[error("Pad controls must be identified by their index")]
+    Index,
+}
+
+pub struct InputRegionParser<'a>(pub &'a Context<'a>);
+
+impl Parser for InputRegionParser<'_> {
+    type Value = InputRegion;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (x, y, width, height) =
+            ext.extract((s32("x"), s32("y"), s32("width"), s32("height")))?;
+        if width.value <= 0 || height.value <= 0 {
+            return Err(TabletParserError::EmptyRegion.spanned(span));
+        }
+        Ok(InputRegion {
+            x: x.value,
+            y: y.value,
+            width: width.value,
+            height: height.value,
+        })
+    }
+}
+
+pub struct TabletAreaParser<'a>(pub &'a Context<'a>);
+
+impl Parser for TabletAreaParser<'_> {
+    type Value = TabletArea;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.0, span, table);
+        let (x, y, width, height) = ext.extract((
+            opt(fltorint("x")),
+            opt(fltorint("y")),
+            opt(fltorint("width")),
+            opt(fltorint("height")),
+        ))?;
+        let x = x.map(|v| v.value).unwrap_or(0.0);
+        let y = y.map(|v| v.value).unwrap_or(0.0);
+        let width = width.map(|v| v.value).unwrap_or(1.0 - x);
+        let height = height.map(|v| v.value).unwrap_or(1.0 - y);
+        let range = 0.0..=1.0;
+        if !range.contains(&x)
+            || !range.contains(&y)
+            || width <= 0.0
+            || height <= 0.0
+            || !range.contains(&(x + width))
+            || !range.contains(&(y + height))
+        {
+            return Err(TabletParserError::InvalidArea.spanned(span));
+        }
+        Ok(TabletArea {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+pub struct PressureCurveParser;
+
+impl Parser for PressureCurveParser {
+    type Value = [f64; 4];
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Array];
+
+    fn parse_array(&mut self, span: Span, array: &[Spanned<Value>]) -> ParseResult<Self> {
+        if array.len() != 4 {
+            return Err(TabletParserError::FourEntries.spanned(span));
+        }
+        let mut res = [0.0; 4];
+        for (res, v) in res.iter_mut().zip(array) {
+            *res = match v.value {
+                Value::Float(f) => f,
+                Value::Integer(i) => i as _,
+                _ => return Err(TabletParserError::CurveEntry.spanned(v.span)),
+            };
+            if !(0.0..=1.0).contains(res) {
+                return Err(TabletParserError::CurveEntry.spanned(v.span));
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// Parses a table that maps the indices of pad controls to actions.
+///
+/// For buttons, the values are actions. Otherwise the values are tables containing one
+/// action per direction. The first direction is the positive one.
+pub struct TabletPadActionsParser<'a> {
+    pub cx: &'a Context<'a>,
+    pub control: TabletPadControl,
+    pub directions: [&'static str; 2],
+}
+
+impl Parser for TabletPadActionsParser<'_> {
+    type Value = Vec<TabletPadAction>;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut res = vec![];
+        for (k, v) in table {
+            let Ok(index) = k.value.parse::<u32>() else {
+                return Err(TabletParserError::Index.spanned(k.span));
+            };
+            if self.control == TabletPadControl::Button {
+                res.push(TabletPadAction {
+                    control: self.control,
+                    index,
+                    negative: false,
+                    action: v.parse_map(&mut ActionParser(self.cx))?,
+                });
+                continue;
+            }
+            res.extend(v.parse(&mut TabletPadDirectionsParser {
+                cx: self.cx,
+                control: self.control,
+                index,
+                directions: self.directions,
+            })?);
+        }
+        Ok(res)
+    }
+}
+
+struct TabletPadDirectionsParser<'a> {
+    cx: &'a Context<'a>,
+    control: TabletPadControl,
+    index: u32,
+    directions: [&'static str; 2],
+}
+
+impl Parser for TabletPadDirectionsParser<'_> {
+    type Value = Vec<TabletPadAction>;
+    type Error = TabletParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut ext = Extractor::new(self.cx, span, table);
+        let [positive, negative] = self.directions;
+        let (positive, negative) = ext.extract((opt(val(positive)), opt(val(negative))))?;
+        let mut res = vec![];
+        for (action, negative) in [(positive, false), (negative, true)] {
+            if let Some(action) = action {
+                res.push(TabletPadAction {
+                    control: self.control,
+                    index: self.index,
+                    negative,
+                    action: action.parse_map(&mut ActionParser(self.cx))?,
+                });
+            }
+        }
+        Ok(res)
+    }
+}
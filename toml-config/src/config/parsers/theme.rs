@@ -2,9 +2,12 @@ use {
     crate::{
         config::{
             context::Context,
-            extractor::{opt, recover, s32, str, val, Extractor, ExtractorError},
+            extractor::{arr, bol, opt, recover, s32, str, val, Extractor, ExtractorError},
             parser::{DataType, ParseResult, Parser, UnexpectedDataType},
-            parsers::color::ColorParser,
+            parsers::{
+                color::ColorParser, color_scheme::ColorSchemeParser,
+                window_opacities::WindowOpacitiesParser, StringParser,
+            },
             Theme,
         },
         toml::{
@@ -59,7 +62,22 @@ impl Parser for ThemeParser<'_> {
                 border_width,
                 title_height,
                 font,
+                color_scheme_val,
+                high_contrast,
             ),
+            (
+                inner_gap,
+                outer_gap,
+                smart_gaps,
+                corner_radius,
+                shadow_size,
+                shadow_color,
+                blur_passes,
+                blur_offset,
+                blurred_app_ids_val,
+                blurred_layer_namespaces_val,
+            ),
+            (window_opacities_val,),
         ) = ext.extract((
             (
                 opt(val("attention-requested-bg-color")),
@@ -82,7 +100,22 @@ impl Parser for ThemeParser<'_> {
                 recover(opt(s32("border-width"))),
                 recover(opt(s32("title-height"))),
                 recover(opt(str("font"))),
+                opt(val("color-scheme")),
+                recover(opt(bol("high-contrast"))),
+            ),
+            (
+                recover(opt(s32("inner-gap"))),
+                recover(opt(s32("outer-gap"))),
+                recover(opt(bol("smart-gaps"))),
+                recover(opt(s32("corner-radius"))),
+                recover(opt(s32("shadow-size"))),
+                opt(val("shadow-color")),
+                recover(opt(s32("blur-passes"))),
+                recover(opt(s32("blur-offset"))),
+                recover(opt(arr("blurred-app-ids"))),
+                recover(opt(arr("blurred-layer-namespaces"))),
             ),
+            (opt(val("window-opacities")),),
         ))?;
         macro_rules! color {
             ($e:expr) => {
@@ -98,6 +131,38 @@ impl Parser for ThemeParser<'_> {
                 }
             };
         }
+        let mut color_scheme = None;
+        if let Some(value) = color_scheme_val {
+            match value.parse(&mut ColorSchemeParser) {
+                Ok(v) => color_scheme = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the color scheme: {}", self.0.error(e));
+                }
+            }
+        }
+        let mut window_opacities = None;
+        if let Some(value) = window_opacities_val {
+            match value.parse(&mut WindowOpacitiesParser) {
+                Ok(v) => window_opacities = Some(v),
+                Err(e) => {
+                    log::warn!("Could not parse the window opacities: {}", self.0.error(e));
+                }
+            }
+        }
+        macro_rules! strings {
+            ($e:expr) => {
+                $e.map(|array| {
+                    let mut res = vec![];
+                    for v in array.value {
+                        match v.parse(&mut StringParser) {
+                            Ok(v) => res.push(v),
+                            Err(e) => log::warn!("Could not parse a string: {}", self.0.error(e)),
+                        }
+                    }
+                    res
+                })
+            };
+        }
         Ok(Theme {
             attention_requested_bg_color: color!(attention_requested_bg_color),
             bg_color: color!(bg_color),
@@ -117,6 +182,19 @@ impl Parser for ThemeParser<'_> {
             border_width: border_width.despan(),
             title_height: title_height.despan(),
             font: font.map(|f| f.value.to_string()),
+            color_scheme,
+            high_contrast: high_contrast.despan(),
+            inner_gap: inner_gap.despan(),
+            outer_gap: outer_gap.despan(),
+            smart_gaps: smart_gaps.despan(),
+            corner_radius: corner_radius.despan(),
+            shadow_size: shadow_size.despan(),
+            shadow_color: color!(shadow_color),
+            blur_passes: blur_passes.despan(),
+            blur_offset: blur_offset.despan(),
+            blurred_app_ids: strings!(blurred_app_ids_val),
+            blurred_layer_namespaces: strings!(blurred_layer_namespaces_val),
+            window_opacities,
         })
     }
 }
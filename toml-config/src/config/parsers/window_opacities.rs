@@ -0,0 +1,58 @@
+use {
+    crate::{
+        config::parser::{DataType, ParseResult, Parser, UnexpectedDataType},
+        toml::{
+            toml_span::{Span, Spanned, SpannedExt},
+            toml_value::Value,
+        },
+    },
+    indexmap::IndexMap,
+    thiserror::Error,
+};
+
+#[derive(Debug, Error)]
+pub enum WindowOpacitiesParserError {
+    #[error(transparent)]
+    Expected(#[from] UnexpectedDataType),
+    #[error("Opacities must be between 0 and 1")]
+    Range,
+}
+
+pub struct WindowOpacitiesParser;
+
+impl Parser for WindowOpacitiesParser {
+    type Value = Vec<(String, f64)>;
+    type Error = WindowOpacitiesParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Table];
+
+    fn parse_table(
+        &mut self,
+        _span: Span,
+        table: &IndexMap<Spanned<String>, Spanned<Value>>,
+    ) -> ParseResult<Self> {
+        let mut opacities = vec![];
+        for (k, v) in table {
+            opacities.push((k.value.to_string(), v.parse_map(&mut OpacityParser)?));
+        }
+        Ok(opacities)
+    }
+}
+
+struct OpacityParser;
+
+impl Parser for OpacityParser {
+    type Value = f64;
+    type Error = WindowOpacitiesParserError;
+    const EXPECTED: &'static [DataType] = &[DataType::Float, DataType::Integer];
+
+    fn parse_integer(&mut self, span: Span, integer: i64) -> ParseResult<Self> {
+        self.parse_float(span, integer as f64)
+    }
+
+    fn parse_float(&mut self, span: Span, float: f64) -> ParseResult<Self> {
+        if !(0.0..=1.0).contains(&float) {
+            return Err(WindowOpacitiesParserError::Range.spanned(span));
+        }
+        Ok(float)
+    }
+}
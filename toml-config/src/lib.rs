@@ -5,33 +5,45 @@ mod toml;
 
 use {
     crate::config::{
-        parse_config, Action, Config, ConfigConnector, ConfigDrmDevice, ConfigKeymap,
-        ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch, Output, OutputMatch, Shortcut,
-        SimpleCommand, Status, Theme,
+        parse_config, Action, AnimationSettings, Animations, Config, ConfigConnector,
+        ConfigDrmDevice, ConfigKeymap, ConnectorMatch, DrmDeviceMatch, Exec, Input, InputMatch,
+        Output, OutputMatch, Shortcut, SimpleCommand, Status, Theme,
     },
     ahash::{AHashMap, AHashSet},
     error_reporter::Report,
     jay_config::{
+        animation::{
+            reset_animations, set_animations_enabled, set_workspace_animation_style, Animation,
+            LAYOUT, WINDOW_CLOSE, WINDOW_OPEN, WORKSPACE_SWITCH,
+        },
         config, config_dir,
         exec::{set_env, unset_env, Command},
         get_workspace,
         input::{
-            capability::CAP_SWITCH, get_seat, input_devices, on_input_device_removed,
-            on_new_input_device, FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent,
+            capability::{CAP_SWITCH, CAP_TABLET_PAD},
+            get_seat, input_devices, on_input_device_removed, on_new_input_device,
+            FocusFollowsMouseMode, InputDevice, Seat, SwitchEvent, TabletPadControl,
         },
         is_reload,
         keyboard::{Keymap, ModifiedKeySym},
+        lock,
         logging::set_log_level,
-        on_devices_enumerated, on_idle, quit, reload, set_default_workspace_capture,
-        set_explicit_sync_enabled, set_idle,
+        on_before_sleep, on_devices_enumerated, on_idle, on_idle_stage, on_idle_stage_resumed,
+        quit, reload, set_default_workspace_capture, set_default_workspace_layout,
+        set_explicit_sync_enabled, set_fullscreen_inhibits_idle, set_idle, set_idle_stages,
         status::{set_i3bar_separator, set_status, set_status_command, unset_status_command},
         switch_to_vt,
-        theme::{reset_colors, reset_font, reset_sizes, set_font},
+        theme::{
+            reset_colors, reset_font, reset_sizes, set_blurred_app_ids,
+            set_blurred_layer_namespaces, set_color_scheme, set_font, set_high_contrast,
+            set_smart_gaps, set_window_opacities, ColorScheme,
+        },
         video::{
             connectors, drm_devices, on_connector_connected, on_connector_disconnected,
             on_graphics_initialized, on_new_connector, on_new_drm_device,
             set_direct_scanout_enabled, set_gfx_api, Connector, DrmDevice,
         },
+        WorkspaceLayout,
     },
     std::{cell::RefCell, io::ErrorKind, path::PathBuf, rc::Rc},
 };
@@ -89,6 +101,12 @@ impl Action {
                 SimpleCommand::ReloadConfigSo => B::new(reload),
                 SimpleCommand::None => B::new(|| ()),
                 SimpleCommand::Forward(bool) => B::new(move || s.set_forward(bool)),
+                SimpleCommand::MoveToScratchpad => B::new(move || s.move_to_scratchpad()),
+                SimpleCommand::ToggleScratchpad => B::new(move || s.toggle_scratchpad()),
+                SimpleCommand::ToggleSticky => B::new(move || s.toggle_sticky()),
+                SimpleCommand::SetContainerLayout(l) => B::new(move || s.set_container_layout(l)),
+                SimpleCommand::NextKeyboardLayout => B::new(move || s.next_keyboard_layout()),
+                SimpleCommand::Lock => B::new(lock),
             },
             Action::Multi { actions } => {
                 let actions: Vec<_> = actions.into_iter().map(|a| a.into_fn(state)).collect();
@@ -100,6 +118,13 @@ impl Action {
             }
             Action::Exec { exec } => B::new(move || create_command(&exec).spawn()),
             Action::SwitchToVt { num } => B::new(move || switch_to_vt(num)),
+            Action::SetWindowOpacity { opacity } => B::new(move || s.set_window_opacity(opacity)),
+            Action::Mark { mark } => B::new(move || s.mark(&mark)),
+            Action::Unmark { mark } => B::new(move || s.unmark(mark.as_deref())),
+            Action::FocusMark { mark } => B::new(move || s.focus_mark(&mark)),
+            Action::SwapWithMark { mark } => B::new(move || s.swap_with_mark(&mark)),
+            Action::SetWorkspaceLayout { layout } => B::new(move || s.set_workspace_layout(layout)),
+            Action::SetKeyboardLayout { layout } => B::new(move || s.set_keyboard_layout(layout)),
             Action::ShowWorkspace { name } => {
                 let workspace = get_workspace(&name);
                 B::new(move || s.show_workspace(workspace))
@@ -390,6 +415,11 @@ impl InputMatch {
 
 impl Input {
     fn apply(&self, c: InputDevice, state: &State) {
+        if let Some(v) = &self.custom_accel {
+            for f in v {
+                c.set_custom_accel_function(f.ty, f.step, &f.points);
+            }
+        }
         if let Some(v) = self.accel_profile {
             c.set_accel_profile(v);
         }
@@ -417,6 +447,33 @@ impl Input {
         if let Some(v) = self.transform_matrix {
             c.set_transform_matrix(v);
         }
+        if let Some(v) = self.click_method {
+            c.set_click_method(v);
+        }
+        if let Some(v) = self.middle_button_emulation {
+            c.set_middle_button_emulation_enabled(v);
+        }
+        if let Some(v) = self.scroll_method {
+            c.set_scroll_method(v);
+        }
+        if let Some(v) = self.scroll_button {
+            c.set_scroll_button(v);
+        }
+        if let Some(v) = self.disable_while_typing {
+            c.set_disable_while_typing_enabled(v);
+        }
+        if let Some(v) = self.tap_button_map {
+            c.set_tap_button_map(v);
+        }
+        if let Some(v) = self.send_events {
+            c.set_send_events_mode(v);
+        }
+        if let Some(v) = self.rotation {
+            c.set_rotation(v);
+        }
+        if let Some(v) = self.calibration_matrix {
+            c.set_calibration_matrix(v);
+        }
         if let Some(v) = &self.keymap {
             if let Some(km) = state.get_keymap(v) {
                 c.set_keymap(km);
@@ -433,6 +490,18 @@ impl Input {
                 c.remove_mapping();
             }
         }
+        if let Some(r) = self.region {
+            c.set_region(r.x, r.y, r.width, r.height);
+        }
+        if let Some(a) = self.tablet_area {
+            c.set_tablet_area(a.x, a.y, a.width, a.height);
+        }
+        if let Some(v) = self.keep_aspect_ratio {
+            c.set_tablet_keep_aspect_ratio(v);
+        }
+        if let Some([x1, y1, x2, y2]) = self.pressure_curve {
+            c.set_tablet_pressure_curve(x1, y1, x2, y2);
+        }
     }
 }
 
@@ -555,6 +624,39 @@ impl Output {
     }
 }
 
+impl AnimationSettings {
+    fn apply(&self, animation: Animation) {
+        if let Some(duration) = self.duration {
+            animation.set_duration(duration);
+        }
+        if let Some(curve) = self.curve {
+            animation.set_curve(curve);
+        }
+    }
+}
+
+impl Animations {
+    fn apply(&self) {
+        if let Some(enabled) = self.enabled {
+            set_animations_enabled(enabled);
+        }
+        if let Some(style) = self.workspace_style {
+            set_workspace_animation_style(style);
+        }
+        let settings = [
+            (WORKSPACE_SWITCH, &self.workspace_switch),
+            (WINDOW_OPEN, &self.window_open),
+            (WINDOW_CLOSE, &self.window_close),
+            (LAYOUT, &self.layout),
+        ];
+        for (animation, settings) in settings {
+            if let Some(settings) = settings {
+                settings.apply(animation);
+            }
+        }
+    }
+}
+
 struct State {
     outputs: AHashMap<String, OutputMatch>,
     drm_devices: AHashMap<String, DrmDeviceMatch>,
@@ -577,6 +679,8 @@ impl Drop for State {
 
 type SwitchActions = Vec<(InputMatch, AHashMap<SwitchEvent, Box<dyn Fn()>>)>;
 
+type TabletPadActions = Vec<(InputMatch, Vec<(TabletPadControl, u32, bool, Rc<dyn Fn()>)>)>;
+
 impl State {
     fn unbind_all(&self) {
         let mut binds = self.persistent.binds.borrow_mut();
@@ -686,6 +790,7 @@ impl State {
         color!(UNFOCUSED_TITLE_BACKGROUND_COLOR, unfocused_title_bg_color);
         color!(UNFOCUSED_TITLE_TEXT_COLOR, unfocused_title_text_color);
         color!(HIGHLIGHT_COLOR, highlight_color);
+        color!(SHADOW_COLOR, shadow_color);
         macro_rules! size {
             ($sized:ident, $field:ident) => {
                 if let Some(size) = theme.$field {
@@ -695,9 +800,33 @@ impl State {
         }
         size!(BORDER_WIDTH, border_width);
         size!(TITLE_HEIGHT, title_height);
+        size!(INNER_GAP, inner_gap);
+        size!(OUTER_GAP, outer_gap);
+        size!(CORNER_RADIUS, corner_radius);
+        size!(SHADOW_SIZE, shadow_size);
+        size!(BLUR_PASSES, blur_passes);
+        size!(BLUR_OFFSET, blur_offset);
+        if let Some(app_ids) = &theme.blurred_app_ids {
+            set_blurred_app_ids(app_ids);
+        }
+        if let Some(namespaces) = &theme.blurred_layer_namespaces {
+            set_blurred_layer_namespaces(namespaces);
+        }
+        if let Some(opacities) = &theme.window_opacities {
+            set_window_opacities(opacities.iter().map(|(a, o)| (a, *o)));
+        }
         if let Some(font) = &theme.font {
             set_font(font);
         }
+        if let Some(scheme) = theme.color_scheme {
+            set_color_scheme(scheme);
+        }
+        if let Some(enabled) = theme.high_contrast {
+            set_high_contrast(enabled);
+        }
+        if let Some(enabled) = theme.smart_gaps {
+            set_smart_gaps(enabled);
+        }
     }
 
     fn handle_switch_device(self: &Rc<Self>, dev: InputDevice, actions: &Rc<SwitchActions>) {
@@ -717,6 +846,41 @@ impl State {
         });
     }
 
+    fn handle_tablet_pad_device(self: &Rc<Self>, dev: InputDevice, actions: &TabletPadActions) {
+        if !dev.has_capability(CAP_TABLET_PAD) {
+            return;
+        }
+        type Actions = (Vec<Rc<dyn Fn()>>, Vec<Rc<dyn Fn()>>);
+        let mut controls = AHashMap::<_, Actions>::new();
+        for (match_, actions) in actions {
+            if match_.matches(dev, self) {
+                for (control, index, negative, action) in actions {
+                    let entry = controls.entry((*control, *index)).or_default();
+                    match negative {
+                        false => entry.0.push(action.clone()),
+                        true => entry.1.push(action.clone()),
+                    }
+                }
+            }
+        }
+        for ((control, index), (positive, negative)) in controls {
+            let run = move |delta: f64| {
+                let actions = match delta < 0.0 {
+                    true => &negative,
+                    false => &positive,
+                };
+                for action in actions {
+                    action();
+                }
+            };
+            match control {
+                TabletPadControl::Button => dev.on_tablet_pad_button(index, move || run(0.0)),
+                TabletPadControl::Ring => dev.on_tablet_pad_ring(index, run),
+                TabletPadControl::Strip => dev.on_tablet_pad_strip(index, run),
+            }
+        }
+    }
+
     fn add_io_output(&self, c: Connector) {
         let mappings: Vec<_> = self
             .io_maps
@@ -875,6 +1039,18 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         }
     }
     let switch_actions = Rc::new(switch_actions);
+    let mut tablet_pad_actions = vec![];
+    for input in &mut config.inputs {
+        let actions: Vec<_> = input
+            .tablet_pad_actions
+            .drain(..)
+            .map(|a| (a.control, a.index, a.negative, a.action.into_rc_fn(&state)))
+            .collect();
+        if actions.len() > 0 {
+            tablet_pad_actions.push((input.match_.clone(), actions));
+        }
+    }
+    let tablet_pad_actions = Rc::new(tablet_pad_actions);
     match config.on_graphics_initialized {
         None => on_graphics_initialized(|| ()),
         Some(a) => on_graphics_initialized(a.into_fn(&state)),
@@ -883,6 +1059,41 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         None => on_idle(|| ()),
         Some(a) => on_idle(a.into_fn(&state)),
     }
+    if let Some(a) = config.on_before_sleep {
+        on_before_sleep(a.into_fn(&state));
+    }
+    {
+        let mut stages = vec![];
+        let mut on_enter = AHashMap::new();
+        let mut on_resume = AHashMap::new();
+        for stage in config.idle_stages {
+            if let Some(a) = stage.on_idle {
+                on_enter.insert(stage.name.clone(), a.into_fn(&state));
+            }
+            if let Some(a) = stage.on_resume {
+                on_resume.insert(stage.name.clone(), a.into_fn(&state));
+            }
+            stages.push(jay_config::IdleStage {
+                name: stage.name,
+                timeout: stage.timeout,
+                dim_outputs: stage.dim_outputs,
+                power_off_outputs: stage.power_off_outputs,
+                suspend: stage.suspend,
+            });
+        }
+        on_idle_stage(move |name| {
+            if let Some(f) = on_enter.get(name) {
+                f();
+            }
+        });
+        on_idle_stage_resumed(move |name| {
+            if let Some(f) = on_resume.get(name) {
+                f();
+            }
+        });
+        set_idle_stages(stages);
+    }
+    set_fullscreen_inhibits_idle(config.fullscreen_inhibits_idle);
     state.unbind_all();
     state.apply_shortcuts(config.shortcuts);
     if let Some(keymap) = config.keymap {
@@ -926,6 +1137,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         }
     });
     set_default_workspace_capture(config.workspace_capture);
+    set_default_workspace_layout(config.workspace_layout.unwrap_or(WorkspaceLayout::Manual));
     for (k, v) in config.env {
         set_env(&k, &v);
     }
@@ -956,7 +1168,17 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     reset_colors();
     reset_font();
     reset_sizes();
+    set_color_scheme(ColorScheme::Automatic);
+    set_high_contrast(false);
+    set_smart_gaps(false);
+    set_blurred_app_ids::<&str>([]);
+    set_blurred_layer_namespaces::<&str>([]);
+    set_window_opacities::<&str>([]);
     state.apply_theme(&config.theme);
+    reset_animations();
+    if let Some(animations) = &config.animations {
+        animations.apply();
+    }
     if let Some(api) = config.gfx_api {
         set_gfx_api(api);
     }
@@ -979,6 +1201,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
     on_new_input_device({
         let state = state.clone();
         let switch_actions = switch_actions.clone();
+        let tablet_pad_actions = tablet_pad_actions.clone();
         move |c| {
             state.add_io_input(c);
             for input in &config.inputs {
@@ -987,6 +1210,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
                 }
             }
             state.handle_switch_device(c, &switch_actions);
+            state.handle_tablet_pad_device(c, &tablet_pad_actions);
         }
     });
     on_input_device_removed({
@@ -1002,6 +1226,7 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
         state.add_io_input(c);
         state.map_input_to_output(c);
         state.handle_switch_device(c, &switch_actions);
+        state.handle_tablet_pad_device(c, &tablet_pad_actions);
     }
     persistent
         .seat
@@ -1009,6 +1234,9 @@ fn load_config(initial_load: bool, persistent: &Rc<PersistentState>) {
             true => FocusFollowsMouseMode::True,
             false => FocusFollowsMouseMode::False,
         });
+    persistent
+        .seat
+        .set_remember_keyboard_layout(config.remember_keyboard_layout);
 }
 
 fn create_command(exec: &Exec) -> Command {